@@ -1,5 +1,7 @@
 const constants = require('../../../specs/constants.json');
-const { computePrizePool, computePayout } = require('./protocol_primitives.cjs');
+const { computePrizePool, computePayout, claimWindowEnd } = require('./protocol_primitives.cjs');
+const { MARKET_TYPE_PODIUM } = require('./create_market_instruction.cjs');
+const { finalizeResolution } = require('./resolve_market_instruction.cjs');
 
 function validateClaimResolvedInput(input) {
   // CLR-ADV-001: missing position PDA is expected to fail at account resolution layer.
@@ -8,14 +10,18 @@ function validateClaimResolvedInput(input) {
 
   // CLR-ORD-001: evaluate market lifecycle status before any vault/outcome math checks.
   // This guarantees deterministic error precedence when multiple inputs are invalid.
-  if (input.marketState.status !== 'Resolved') return 'MarketNotResolved';
+  // PendingResolution is final (and claimable) once its dispute deadline has passed.
+  const status = input.marketState.status;
+  if (status !== 'Resolved' && status !== 'PendingResolution') return 'MarketNotResolved';
+  if (status === 'PendingResolution' && input.nowTs <= input.marketState.disputeDeadline) return 'DisputeWindowActive';
 
   // CLR-REJ-002: claim is single-use; once claimed, all future attempts must fail.
   if (input.positionState.claimed) return 'AlreadyClaimed';
 
-  // CLR-REJ-003: claim window is inclusive at resolutionTimestamp + claimWindowSecs.
+  // CLR-REJ-003: claim window is inclusive at resolutionTimestamp + claimWindowSecs,
+  // counted from the dispute deadline when one applied.
   // Expiry only begins strictly after the inclusive end boundary.
-  const claimEnd = input.resolutionTimestamp + input.claimWindowSecs;
+  const claimEnd = claimWindowEnd(input.resolutionTimestamp, input.marketState.disputeDeadline, input.claimWindowSecs);
   if (input.nowTs > claimEnd) return 'ClaimWindowExpired';
 
   // CLR-ADV-002: transfers are locked to canonical SPL Token Program id.
@@ -40,17 +46,23 @@ function executeClaimResolved(input) {
 
   // Winners are paid proportionally from the net prize pool.
   // Losers still transition to claimed, but receive payout=0.
-  const isWinner = input.outcomeId === input.winningOutcomeId;
+  // Podium markets: every outcome in winningOutcomes wins and divides by the combined
+  // winnerPool snapshotted at resolve; Winner markets divide by the single winning pool.
+  const isPodium = input.marketState.marketType === MARKET_TYPE_PODIUM;
+  const isWinner = isPodium
+    ? input.marketState.winningOutcomes.includes(input.outcomeId)
+    : input.outcomeId === input.winningOutcomeId;
+  const winnerPool = isPodium ? input.marketState.winnerPool : input.outcomePoolState.poolAmount;
 
   // Prize pool is total pool minus protocol fee (as defined in locked math primitives).
   const prizePool = computePrizePool(input.marketState.totalPool, input.feeBps);
 
   let payout = 0;
   if (isWinner) {
-    // Winner payout: floor(position.amount * prizePool / winningOutcomePool.amount)
+    // Winner payout: floor(position.amount * prizePool / winnerPool)
     // with deterministic error mapping for harness conformance.
     try {
-      payout = computePayout(input.positionState.amount, prizePool, input.outcomePoolState.poolAmount);
+      payout = computePayout(input.positionState.amount, prizePool, winnerPool);
     } catch (e) {
       if (e && e.message === 'DivisionByZero') return { ok: false, error: 'DivisionByZero' };
       return { ok: false, error: 'Overflow' };
@@ -78,9 +90,22 @@ function executeClaimResolved(input) {
     outcome_id: input.outcomeId,
     payout,
     claimed_at: input.nowTs,
+    initiator: input.user,
   };
 
-  return { ok: true, position, vaultAmount, userUsdcAmount, event };
+  // The first claim past the dispute deadline records the pending result as Resolved.
+  const finalized = finalizeResolution(input.market, input.marketState, input.nowTs);
+  const market = finalized ? finalized.market : input.marketState;
+
+  return {
+    ok: true,
+    market,
+    position,
+    vaultAmount,
+    userUsdcAmount,
+    event,
+    resolutionFinalizedEvent: finalized ? finalized.event : null,
+  };
 }
 
 module.exports = { validateClaimResolvedInput, executeClaimResolved };
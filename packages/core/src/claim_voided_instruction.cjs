@@ -40,6 +40,7 @@ function executeClaimVoided(input) {
     outcome_id: input.outcomeId,
     payout,
     claimed_at: input.nowTs,
+    initiator: input.user,
   };
 
   return { ok: true, position, vaultAmount, userUsdcAmount, event };
@@ -4,6 +4,11 @@ const constants = require('../../../specs/constants.json');
 const MAX_OUTCOMES = 100;
 const SUPPORTED_MARKET_TYPE = 0;
 const SUPPORTED_RULES_VERSION = 1;
+const MARKET_TYPE_WINNER = SUPPORTED_MARKET_TYPE;
+const MARKET_TYPE_PODIUM = 1;
+const PODIUM_SIZE = 3;
+// market_type -> the one rules_version it is created with.
+const RULES_VERSION_BY_MARKET_TYPE = { [MARKET_TYPE_WINNER]: 1, [MARKET_TYPE_PODIUM]: 2 };
 
 function validateCreateMarketInput(input) {
   // CRM-REJ-001: only config authority can create markets.
@@ -16,18 +21,50 @@ function validateCreateMarketInput(input) {
   if (input.maxOutcomes === 0) return 'ZeroOutcomes';
   // CRM-REJ-004b: cap outcomes to deterministic MAX_OUTCOMES bound.
   if (input.maxOutcomes > MAX_OUTCOMES) return 'TooManyOutcomes';
-  // CRM-REJ-005a: MVP currently supports market_type=Winner(0) only.
-  if (input.marketType !== SUPPORTED_MARKET_TYPE) return 'UnsupportedMarketType';
-  // CRM-REJ-005b: MVP currently supports rules_version=1 only.
-  if (input.rulesVersion !== SUPPORTED_RULES_VERSION) return 'UnsupportedRulesVersion';
+  // CRM-REJ-005a: market_type must be Winner(0) or Podium(1).
+  const rulesVersion = RULES_VERSION_BY_MARKET_TYPE[input.marketType];
+  if (rulesVersion === undefined) return 'UnsupportedMarketType';
+  // CRM-REJ-005b: rules_version must match the market_type (Winner=1, Podium=2).
+  if (input.rulesVersion !== rulesVersion) return 'UnsupportedRulesVersion';
 
   // CRM-REJ-006: recompute on-chain-equivalent market_id to prevent canonicalization drift.
   const recomputed = computeMarketIdHex(input.eventIdHex, input.marketType, input.rulesVersion);
   if (recomputed !== input.marketIdHex) return 'InvalidMarketId';
+  // CRM-REJ-010: a closed market_id stays retired (its tombstone PDA exists).
+  if (input.marketTombstoneExists) return 'MarketIdRetired';
+  // CRM-REJ-008: collateral must be config.usdc_mint or an allowlisted mint.
+  if (collateralTreasury(input) === null) return 'CollateralMintNotAllowed';
+  // CRM-REJ-009: overrides may only tighten the config caps, and the resulting pair
+  // must still be a valid cap pair (positive, per-user <= market).
+  if (!capOverridesValid(input)) return 'InvalidCap';
 
   return null;
 }
 
+// Absent overrides (null/undefined) fall back to the config caps.
+function capOverridesValid(input) {
+  const hasTotal = input.maxTotalPoolPerMarket != null;
+  const hasPerUser = input.maxBetPerUserPerMarket != null;
+  if (!hasTotal && !hasPerUser) return true;
+  const maxTotal = hasTotal ? input.maxTotalPoolPerMarket : input.configMaxTotalPoolPerMarket;
+  const maxPerUser = hasPerUser ? input.maxBetPerUserPerMarket : input.configMaxBetPerUserPerMarket;
+  return (
+    Number.isInteger(maxTotal) && maxTotal > 0 &&
+    Number.isInteger(maxPerUser) && maxPerUser > 0 &&
+    maxTotal <= input.configMaxTotalPoolPerMarket &&
+    maxPerUser <= input.configMaxBetPerUserPerMarket &&
+    maxPerUser <= maxTotal
+  );
+}
+
+// Treasury paired with the market's collateral: config treasury for config.usdc_mint,
+// otherwise the allowlist entry's treasury (null = not allowed).
+function collateralTreasury(input) {
+  if (input.collateralMint === input.configUsdcMint) return input.configTreasury;
+  const entry = (input.collateralAllowlist || []).find((e) => e.mint === input.collateralMint);
+  return entry ? entry.treasury : null;
+}
+
 function executeCreateMarket(input) {
   const err = validateCreateMarketInput(input);
   if (err) return { ok: false, error: err };
@@ -42,11 +79,17 @@ function executeCreateMarket(input) {
     // create_market always initializes market lifecycle at Seeding.
     status: 'Seeding',
     resolvedOutcome: null,
+    winningOutcomes: [],
+    winnerPool: 0,
     resolutionPayloadHash: '0'.repeat(64),
     resolutionTimestamp: 0,
     marketType: input.marketType,
     rulesVersion: input.rulesVersion,
     vault: input.vault,
+    collateralMint: input.collateralMint,
+    treasury: collateralTreasury(input),
+    maxTotalPoolPerMarket: input.maxTotalPoolPerMarket ?? null,
+    maxBetPerUserPerMarket: input.maxBetPerUserPerMarket ?? null,
   };
 
   // Must emit MarketCreated only after successful state/vault initialization.
@@ -59,6 +102,9 @@ function executeCreateMarket(input) {
     max_outcomes: input.maxOutcomes,
     market_type: input.marketType,
     rules_version: input.rulesVersion,
+    collateral_mint: input.collateralMint,
+    max_total_pool_per_market: input.maxTotalPoolPerMarket ?? null,
+    max_bet_per_user_per_market: input.maxBetPerUserPerMarket ?? null,
     timestamp: input.nowTs,
   };
 
@@ -69,6 +115,10 @@ module.exports = {
   MAX_OUTCOMES,
   SUPPORTED_MARKET_TYPE,
   SUPPORTED_RULES_VERSION,
+  MARKET_TYPE_WINNER,
+  MARKET_TYPE_PODIUM,
+  PODIUM_SIZE,
+  RULES_VERSION_BY_MARKET_TYPE,
   validateCreateMarketInput,
   executeCreateMarket,
 };
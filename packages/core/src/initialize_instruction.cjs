@@ -34,8 +34,7 @@ function executeInitialize(input) {
   const timestamp = input.nowTs;
   const config = {
     authority: input.authority,
-    // MVP default: authority is the initial oracle until admin update flows exist.
-    oracle: input.authority,
+    oracle: input.oracle,
     usdcMint: input.usdcMint,
     treasury: input.treasury,
     treasuryAuthority: input.treasuryAuthority,
@@ -46,13 +45,13 @@ function executeInitialize(input) {
     maxBetPerUserPerMarket: input.maxPerUser,
     claimWindowSecs: input.claimWindowSecs,
     tokenProgram: constants.REQUIRED_TOKEN_PROGRAM,
+    pendingAuthority: null,
   };
 
   const event = {
     name: 'ConfigInitialized',
     authority: input.authority,
-    // MVP default: authority is the initial oracle until admin update flows exist.
-    oracle: input.authority,
+    oracle: input.oracle,
     usdc_mint: input.usdcMint,
     treasury: input.treasury,
     fee_bps: 0,
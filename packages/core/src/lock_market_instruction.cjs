@@ -1,5 +1,5 @@
 function validateLockMarketInput(input) {
-  if (input.authority !== input.configAuthority) return 'Unauthorized'; // LKM-REJ-001
+  // LKM-REJ-001 retired: any signer may lock once lock_timestamp has passed.
   if (input.marketState.status !== 'Open') return 'MarketNotOpen'; // LKM-REJ-002
   if (input.nowTs < input.marketState.lockTimestamp) return 'TooEarlyToLock'; // LKM-REJ-003
   return null;
@@ -9,10 +9,12 @@ function executeLockMarket(input) {
   const err = validateLockMarketInput(input);
   if (err) return { ok: false, error: err };
 
-  const market = { ...input.marketState, status: 'Locked' };
+  const market = { ...input.marketState, status: 'Locked', lockedAt: input.nowTs };
   const event = {
     name: 'MarketLocked',
     market: input.market,
+    locked_by: input.caller,
+    lock_timestamp: input.marketState.lockTimestamp,
     timestamp: input.nowTs,
   };
 
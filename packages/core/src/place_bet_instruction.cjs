@@ -1,4 +1,5 @@
 const constants = require('../../../specs/constants.json');
+const { computePrizePool, computePayout } = require('./protocol_primitives.cjs');
 
 function validatePlaceBetInput(input) {
   if (input.configPaused) return 'ProtocolPaused'; // PBT-REJ-001
@@ -20,9 +21,24 @@ function validatePlaceBetInput(input) {
   const nextMarketTotal = input.marketState.totalPool + input.amount;
   if (nextMarketTotal > input.maxTotalPoolPerMarket) return 'MarketCapExceeded'; // PBT-REJ-007
 
-  const nextUserPosition = input.positionState.amount + input.amount;
-  if (nextUserPosition > input.maxBetPerUserPerMarket) return 'UserBetCapExceeded'; // PBT-REJ-008
+  // The per-user cap covers the user's stake on every outcome of the market.
+  const nextUserTotal = input.userMarketStatsState.totalStaked + input.amount;
+  if (nextUserTotal > input.maxBetPerUserPerMarket) return 'UserBetCapExceeded'; // PBT-REJ-008
 
+  return validateSlippageGuards(input, nextMarketTotal); // PBT-REJ-012
+}
+
+// Optional quote guards, checked against the post-bet pools.
+function validateSlippageGuards(input, nextMarketTotal) {
+  const nextOutcomePool = input.outcomePoolState.poolAmount + input.amount;
+  if (input.maxOutcomeShareBps != null) {
+    const share = BigInt(nextOutcomePool) * 10_000n;
+    if (share > BigInt(input.maxOutcomeShareBps) * BigInt(nextMarketTotal)) return 'SlippageExceeded';
+  }
+  if (input.minImpliedPayout != null) {
+    const prizePool = computePrizePool(nextMarketTotal, input.marketState.feeBps ?? 0);
+    if (computePayout(input.amount, prizePool, nextOutcomePool) < input.minImpliedPayout) return 'SlippageExceeded';
+  }
   return null;
 }
 
@@ -37,6 +53,14 @@ function executePlaceBet(input) {
   const market = { ...input.marketState, totalPool: marketTotalPool };
   const outcomePool = { ...input.outcomePoolState, poolAmount: outcomePoolAmount };
   const position = { ...input.positionState, amount: positionAmount };
+  const outcomeIds = input.userMarketStatsState.outcomeIds || [];
+  const userMarketStats = {
+    ...input.userMarketStatsState,
+    totalStaked: input.userMarketStatsState.totalStaked + input.amount,
+    outcomeIds: outcomeIds.includes(input.outcomeId)
+      ? outcomeIds
+      : [...outcomeIds, input.outcomeId].sort((a, b) => a - b),
+  };
   const vaultAmount = input.vaultAmount + input.amount;
 
   const event = {
@@ -50,7 +74,7 @@ function executePlaceBet(input) {
     timestamp: input.nowTs,
   };
 
-  return { ok: true, market, outcomePool, position, vaultAmount, event };
+  return { ok: true, market, outcomePool, position, userMarketStats, vaultAmount, event };
 }
 
 module.exports = { validatePlaceBetInput, executePlaceBet };
@@ -2,7 +2,7 @@ const crypto = require('crypto');
 
 function canonicalJson(descriptor) {
   const keys = Object.keys(descriptor).sort();
-  return `{${keys.map((k) => `"${k}":"${descriptor[k]}"`).join(',')}}`;
+  return `{${keys.map((k) => `${JSON.stringify(k)}:${JSON.stringify(String(descriptor[k]))}`).join(',')}}`;
 }
 
 function sha256Hex(buf) {
@@ -13,6 +13,19 @@ function computeEventIdHex(descriptor) {
   return sha256Hex(Buffer.from(canonicalJson(descriptor), 'utf8'));
 }
 
+// Result payloads use the same canonicalization as descriptors (SPEC_CANONICAL.md).
+const PAYLOAD_EVENT_ID_KEY = 'event_id';
+// Winner set in finishing order: decimal outcome ids, comma-separated ("1", "3,0,2").
+const PAYLOAD_WINNING_OUTCOMES_KEY = 'winning_outcomes';
+
+function winningOutcomesField(winningOutcomes) {
+  return winningOutcomes.map(String).join(',');
+}
+
+function computePayloadHashHex(payload) {
+  return computeEventIdHex(payload);
+}
+
 function computeMarketIdHex(eventIdHex, marketTypeByte, rulesVersion) {
   if (!/^[0-9a-fA-F]{64}$/.test(eventIdHex)) throw new Error('InvalidEventIdHex');
   if (!Number.isInteger(marketTypeByte) || marketTypeByte < 0 || marketTypeByte > 255) {
@@ -36,10 +49,32 @@ function validateTimestampSeconds(ts) {
   return true;
 }
 
+const U64_MAX = (1n << 64n) - 1n;
+const BPS_DENOMINATOR = 10_000n;
+
+// Mirrors programs/pitstop/src/math.rs: products are exact (BigInt, like the on-chain u128),
+// divisions floor. Number inputs return Numbers; BigInt inputs return BigInts so
+// pools beyond 2^53 stay exact.
+function toU64(value, errName) {
+  if (typeof value === 'bigint') {
+    if (value < 0n || value > U64_MAX) throw new Error(errName);
+    return value;
+  }
+  if (!Number.isInteger(value) || value < 0) throw new Error(errName);
+  return BigInt(value);
+}
+
+function fromU64(value, wantBigInt) {
+  if (value > U64_MAX) throw new Error('Overflow');
+  if (wantBigInt) return value;
+  if (value > BigInt(Number.MAX_SAFE_INTEGER)) throw new Error('UnsafeInteger');
+  return Number(value);
+}
+
 function computeFee(totalPool, feeBps) {
-  if (!Number.isInteger(totalPool) || totalPool < 0) throw new Error('InvalidTotalPool');
+  const total = toU64(totalPool, 'InvalidTotalPool');
   if (!Number.isInteger(feeBps) || feeBps < 0 || feeBps > 10_000) throw new Error('FeeBpsOutOfRange');
-  return Math.floor((totalPool * feeBps) / 10_000);
+  return fromU64((total * BigInt(feeBps)) / BPS_DENOMINATOR, typeof totalPool === 'bigint');
 }
 
 function computePrizePool(totalPool, feeBps) {
@@ -47,19 +82,55 @@ function computePrizePool(totalPool, feeBps) {
 }
 
 function computePayout(positionAmount, prizePool, winnerPool) {
-  if (!Number.isInteger(positionAmount) || positionAmount < 0) throw new Error('InvalidPositionAmount');
-  if (!Number.isInteger(prizePool) || prizePool < 0) throw new Error('InvalidPrizePool');
-  if (!Number.isInteger(winnerPool) || winnerPool < 0) throw new Error('InvalidWinnerPool');
-  if (winnerPool === 0) throw new Error('DivisionByZero');
-  return Math.floor((positionAmount * prizePool) / winnerPool);
+  const position = toU64(positionAmount, 'InvalidPositionAmount');
+  const prize = toU64(prizePool, 'InvalidPrizePool');
+  const winners = toU64(winnerPool, 'InvalidWinnerPool');
+  if (winners === 0n) throw new Error('DivisionByZero');
+  const wantBigInt = [positionAmount, prizePool, winnerPool].some((v) => typeof v === 'bigint');
+  return fromU64((position * prize) / winners, wantBigInt);
+}
+
+function computeDust(prizePool, totalPaid) {
+  const prize = toU64(prizePool, 'InvalidPrizePool');
+  const paid = toU64(totalPaid, 'InvalidTotalPaid');
+  if (paid > prize) throw new Error('Underflow');
+  return fromU64(prize - paid, typeof prizePool === 'bigint' || typeof totalPaid === 'bigint');
+}
+
+// Oracle committee quorum (mirrors submit_resolution_vote::quorum_reached): distinct
+// current members whose vote is exactly `decision` ({ void: true } or
+// { winningOutcomes }) with the same payload hash.
+function committeeQuorumReached(committee, votes, decision, payloadHash) {
+  const sameDecision = (vote) =>
+    Boolean(vote.void) === Boolean(decision.void) &&
+    (decision.void || (vote.winningOutcomes || []).join(',') === decision.winningOutcomes.join(','));
+  const voters = new Set(
+    (votes || [])
+      .filter((vote) => committee.members.includes(vote.member) && vote.payloadHash === payloadHash && sameDecision(vote))
+      .map((vote) => vote.member)
+  );
+  return committee.threshold > 0 && voters.size >= committee.threshold;
+}
+
+// Inclusive claim window end (mirrors claim_resolved::claim_window_end): the window
+// opens when the result is final, i.e. after any dispute window.
+function claimWindowEnd(resolutionTimestamp, disputeDeadline, claimWindowSecs) {
+  return Math.max(resolutionTimestamp, disputeDeadline || 0) + claimWindowSecs;
 }
 
 module.exports = {
   canonicalJson,
   computeEventIdHex,
   computeMarketIdHex,
+  PAYLOAD_EVENT_ID_KEY,
+  PAYLOAD_WINNING_OUTCOMES_KEY,
+  winningOutcomesField,
+  computePayloadHashHex,
+  committeeQuorumReached,
+  claimWindowEnd,
   validateTimestampSeconds,
   computeFee,
   computePrizePool,
   computePayout,
+  computeDust,
 };
@@ -1,6 +1,32 @@
+const { MARKET_TYPE_PODIUM, PODIUM_SIZE } = require('./create_market_instruction.cjs');
+const {
+  PAYLOAD_EVENT_ID_KEY,
+  PAYLOAD_WINNING_OUTCOMES_KEY,
+  winningOutcomesField,
+  computePayloadHashHex,
+  committeeQuorumReached,
+} = require('./protocol_primitives.cjs');
+
+function requiredWinnerCount(marketState) {
+  // Winner markets have one winner; podium markets the top PODIUM_SIZE (fewer if the
+  // market has fewer outcomes).
+  if (marketState.marketType === MARKET_TYPE_PODIUM) return Math.min(PODIUM_SIZE, marketState.outcomeCount);
+  return 1;
+}
+
 function validateResolveMarketInput(input) {
-  // RSM-REJ-001: only oracle signer can resolve markets.
-  if (input.oracle !== input.configOracle) return 'UnauthorizedOracle';
+  // RSM-REJ-010: committee mode (`committee` set while config.oracle is the committee
+  // PDA) needs `threshold` member votes for exactly this winner set and hash.
+  if (input.committee) {
+    const winningOutcomes = [input.winningOutcomeId, ...(input.additionalWinningOutcomeIds || [])];
+    if (!committeeQuorumReached(input.committee, input.votes, { winningOutcomes }, input.payloadHashHex)) {
+      return 'QuorumNotReached';
+    }
+  } else if (input.oracle !== input.configOracle && !(input.attesters || []).includes(input.configOracle)) {
+    // RSM-REJ-001: the oracle signs, or a keeper relays the oracle's Ed25519 attestation
+    // (`attesters` = keys whose precompile signature covers this resolution's message).
+    return 'UnauthorizedOracle';
+  }
   // RSM-REJ-002: market must be Locked before resolving.
   if (input.marketState.status !== 'Locked') return 'MarketNotLocked';
   // RSM-REJ-003: winning_outcome_id must be in range [0, 99].
@@ -20,6 +46,44 @@ function validateResolveMarketInput(input) {
     return 'OutcomeMismatch';
   }
 
+  // RSM-REJ-005: winner set size must match the market type; ids in range and distinct.
+  const additionalIds = input.additionalWinningOutcomeIds || [];
+  const winningOutcomes = [input.winningOutcomeId, ...additionalIds];
+  if (winningOutcomes.length !== requiredWinnerCount(input.marketState)) return 'InvalidOutcomeId';
+  for (const id of additionalIds) {
+    if (!Number.isInteger(id) || id < 0 || id >= input.marketState.outcomeCount) return 'InvalidOutcomeId';
+  }
+  if (new Set(winningOutcomes).size !== winningOutcomes.length) return 'InvalidOutcomeId';
+
+  // RSM-REJ-006: one pool per additional winner, in the same order.
+  const additionalPools = input.additionalWinningPoolStates || [];
+  if (
+    additionalPools.length !== additionalIds.length ||
+    additionalPools.some((p, i) => !p || p.market !== input.market || p.outcomeId !== additionalIds[i])
+  ) {
+    return 'OutcomeMismatch';
+  }
+
+  // RSM-REJ-007..009, 011: a submitted result payload must reference this market's
+  // event, state this winner set and hash to payloadHashHex.
+  if (input.resultPayload) {
+    const err = verifyResultPayload(input.resultPayload, input.marketState.eventIdHex, winningOutcomes, input.payloadHashHex);
+    if (err) return err;
+  }
+
+  return null;
+}
+
+// resultPayload is a list of { key, value } string fields; order is irrelevant.
+function verifyResultPayload(fields, eventIdHex, winningOutcomes, payloadHashHex) {
+  const payload = {};
+  for (const { key, value } of fields) {
+    if (Object.prototype.hasOwnProperty.call(payload, key)) return 'InvalidResolutionPayload';
+    payload[key] = value;
+  }
+  if (payload[PAYLOAD_EVENT_ID_KEY] !== eventIdHex) return 'PayloadEventMismatch';
+  if (payload[PAYLOAD_WINNING_OUTCOMES_KEY] !== winningOutcomesField(winningOutcomes)) return 'PayloadOutcomeMismatch';
+  if (computePayloadHashHex(payload) !== payloadHashHex) return 'PayloadHashMismatch';
   return null;
 }
 
@@ -27,10 +91,24 @@ function executeResolveMarket(input) {
   const err = validateResolveMarketInput(input);
   if (err) return { ok: false, error: err };
 
+  const additionalIds = input.additionalWinningOutcomeIds || [];
+  const additionalPools = input.additionalWinningPoolStates || [];
+  const winningOutcomes = [input.winningOutcomeId, ...additionalIds];
+  // Combined pool of every winning outcome; podium payouts divide by this.
+  const winnerPool = additionalPools.reduce((sum, p) => sum + p.poolAmount, input.winningOutcomePoolState.poolAmount);
+  if (!Number.isSafeInteger(winnerPool)) return { ok: false, error: 'Overflow' };
+
+  // A configured dispute window keeps the result overturnable until disputeDeadline.
+  const disputeWindowSecs = input.disputeWindowSecs || 0;
+  const disputeDeadline = disputeWindowSecs > 0 ? input.nowTs + disputeWindowSecs : 0;
+
   const market = {
     ...input.marketState,
-    status: 'Resolved',
+    status: disputeWindowSecs > 0 ? 'PendingResolution' : 'Resolved',
+    disputeDeadline,
     resolvedOutcome: input.winningOutcomeId,
+    winningOutcomes,
+    winnerPool,
     resolutionPayloadHash: input.payloadHashHex,
     resolutionTimestamp: input.nowTs,
   };
@@ -39,11 +117,31 @@ function executeResolveMarket(input) {
     name: 'MarketResolved',
     market: input.market,
     winning_outcome: input.winningOutcomeId,
+    winning_outcomes: winningOutcomes,
     payload_hash: input.payloadHashHex,
+    payload_verified: Boolean(input.resultPayload),
     resolution_timestamp: input.nowTs,
+    dispute_deadline: disputeDeadline,
   };
 
   return { ok: true, market, event };
 }
 
-module.exports = { validateResolveMarketInput, executeResolveMarket };
+// PendingResolution -> Resolved once nowTs is past disputeDeadline (overturn is allowed
+// up to and including it). Applied by the first claim or sweep after the deadline;
+// null when there is nothing to finalize.
+function finalizeResolution(market, marketState, nowTs) {
+  if (marketState.status !== 'PendingResolution' || nowTs <= marketState.disputeDeadline) return null;
+  return {
+    market: { ...marketState, status: 'Resolved' },
+    event: {
+      name: 'ResolutionFinalized',
+      market,
+      winning_outcomes: marketState.winningOutcomes,
+      dispute_deadline: marketState.disputeDeadline,
+      timestamp: nowTs,
+    },
+  };
+}
+
+module.exports = { requiredWinnerCount, validateResolveMarketInput, executeResolveMarket, finalizeResolution };
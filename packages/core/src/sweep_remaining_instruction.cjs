@@ -1,4 +1,6 @@
 const constants = require('../../../specs/constants.json');
+const { claimWindowEnd } = require('./protocol_primitives.cjs');
+const { finalizeResolution } = require('./resolve_market_instruction.cjs');
 
 function validateSweepRemainingInput(input) {
   // SWP-AUTH-001: authority must match config authority.
@@ -7,17 +9,16 @@ function validateSweepRemainingInput(input) {
   // Token program pinned.
   if (input.tokenProgram !== constants.REQUIRED_TOKEN_PROGRAM) return 'InvalidTokenProgram';
 
-  // SWP-REJ-002: market must be in {Resolved, Voided}.
-  if (input.marketState.status !== 'Resolved' && input.marketState.status !== 'Voided') return 'MarketNotResolved';
+  // SWP-REJ-002: market must be in {PendingResolution, Resolved, Voided}.
+  if (!['PendingResolution', 'Resolved', 'Voided'].includes(input.marketState.status)) return 'MarketNotResolved';
 
-  // SWP-WIN-001: claim window must be expired.
-  const claimWindowEnd = input.marketState.resolutionTimestamp + input.claimWindowSecs;
-  if (input.nowTs <= claimWindowEnd) return 'ClaimWindowNotExpired';
+  // SWP-WIN-001: claim window (started after any dispute window) must be expired.
+  const claimEnd = claimWindowEnd(input.marketState.resolutionTimestamp, input.marketState.disputeDeadline, input.claimWindowSecs);
+  if (input.nowTs <= claimEnd) return 'ClaimWindowNotExpired';
 
-  // Treasury constraints (mint + owner) must match config.
-  // treasury account must match configured treasury address
-  if (input.treasury !== input.configTreasury) return 'InvalidTreasuryOwner';
-  if (input.treasuryMint !== input.usdcMint) return 'InvalidTreasuryMint';
+  // Treasury must be the one recorded for the market's collateral mint at create_market.
+  if (input.treasury !== input.marketState.treasury) return 'InvalidTreasuryOwner';
+  if (input.treasuryMint !== input.marketState.collateralMint) return 'InvalidTreasuryMint';
   if (input.treasuryOwner !== input.treasuryAuthority) return 'InvalidTreasuryOwner';
 
   return null;
@@ -30,6 +31,8 @@ function executeSweepRemaining(input) {
   const amount = input.vaultAmount;
   const treasuryAmount = input.treasuryAmount + amount;
 
+  // A pending result nobody claimed is finalized on the way to Swept.
+  const finalized = finalizeResolution(input.market, input.marketState, input.nowTs);
   const market = { ...input.marketState, status: 'Swept' };
 
   const event = {
@@ -50,6 +53,7 @@ function executeSweepRemaining(input) {
     vaultAccountExists: false,
     closeUsedMarketPdaSeeds: true,
     event,
+    resolutionFinalizedEvent: finalized ? finalized.event : null,
   };
 }
 
@@ -1,6 +1,16 @@
+const { committeeQuorumReached } = require('./protocol_primitives.cjs');
+
 function validateVoidMarketInput(input) {
-  // VDM-REJ-001: oracle signer must match config.oracle.
-  if (input.oracle !== input.configOracle) return 'UnauthorizedOracle';
+  // VDM-REJ-004: committee mode needs the same quorum for a void as for a resolution.
+  if (input.committee) {
+    if (!committeeQuorumReached(input.committee, input.votes, { void: true }, input.payloadHash)) {
+      return 'QuorumNotReached';
+    }
+  } else if (input.oracle !== input.configOracle && !(input.attesters || []).includes(input.configOracle)) {
+    // VDM-REJ-001: oracle signer must match config.oracle, unless a keeper relays the
+    // oracle's Ed25519 attestation over this void.
+    return 'UnauthorizedOracle';
+  }
 
   // VDM-REJ-002/003: only Locked markets can transition to Voided.
   // Use canonical market state as source-of-truth for lifecycle checks.
@@ -1,19 +1,17 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
+use crate::constants::{MAX_COLLATERAL_MINTS, MAX_COMMITTEE_MEMBERS, MAX_OUTCOMES, PODIUM_SIZE};
 use crate::state as parity_state;
 
-/// Canonical PDA seed for the singleton Config account.
-pub const CONFIG_SEED: &[u8] = b"config";
-/// Canonical PDA seed for Market accounts.
-pub const MARKET_SEED: &[u8] = b"market";
-/// Canonical PDA seed for OutcomePool accounts.
-pub const OUTCOME_SEED: &[u8] = b"outcome";
-/// Canonical PDA seed for Position accounts.
-pub const POSITION_SEED: &[u8] = b"position";
+pub use crate::pda::{
+    COLLATERAL_SEED, COMMITTEE_SEED, CONFIG_SEED, MARKET_SEED, OUTCOME_SEED, POSITION_SEED,
+    TOMBSTONE_SEED, USER_STATS_SEED, VOTES_SEED,
+};
 
 /// Canonical protocol configuration PDA (`seeds = ["config"]`).
 ///
@@ -33,6 +31,11 @@ pub struct Config {
     pub max_bet_per_user_per_market: u64,
     pub claim_window_secs: i64,
     pub token_program: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub dispute_window_secs: i64,
+    pub resolution_deadline_secs: i64,
+    pub early_exit_fee_bps: u16,
+    pub payout_keeper: Option<Pubkey>,
 }
 
 impl Config {
@@ -48,7 +51,48 @@ impl Config {
         + 8 // max_total_pool_per_market
         + 8 // max_bet_per_user_per_market
         + 8 // claim_window_secs
-        + 32; // token_program
+        + 32 // token_program
+        + 1 + 32 // pending_authority (Option<Pubkey>)
+        + 8 // dispute_window_secs
+        + 8 // resolution_deadline_secs
+        + 2 // early_exit_fee_bps
+        + 1 + 32; // payout_keeper (Option<Pubkey>)
+
+    /// Anchor -> parity projection used before invoking pure instruction logic.
+    pub fn to_parity(&self) -> parity_state::Config {
+        parity_state::Config {
+            authority: self.authority.to_string(),
+            oracle: self.oracle.to_string(),
+            usdc_mint: self.usdc_mint.to_string(),
+            treasury: self.treasury.to_string(),
+            treasury_authority: self.treasury_authority.to_string(),
+            fee_bps: self.fee_bps,
+            paused: self.paused,
+            max_total_pool_per_market: self.max_total_pool_per_market,
+            max_bet_per_user_per_market: self.max_bet_per_user_per_market,
+            claim_window_secs: self.claim_window_secs,
+            token_program: self.token_program.to_string(),
+            pending_authority: self.pending_authority.map(|k| k.to_string()),
+            dispute_window_secs: self.dispute_window_secs,
+            resolution_deadline_secs: self.resolution_deadline_secs,
+            early_exit_fee_bps: self.early_exit_fee_bps,
+            payout_keeper: self.payout_keeper.map(|k| k.to_string()),
+        }
+    }
+
+    /// Parity -> Anchor commit for the mutable scalar parameters.
+    ///
+    /// Key fields are written explicitly by the handlers that own them.
+    pub fn apply_parity(&mut self, p: &parity_state::Config) {
+        self.fee_bps = p.fee_bps;
+        self.paused = p.paused;
+        self.max_total_pool_per_market = p.max_total_pool_per_market;
+        self.max_bet_per_user_per_market = p.max_bet_per_user_per_market;
+        self.claim_window_secs = p.claim_window_secs;
+        self.dispute_window_secs = p.dispute_window_secs;
+        self.resolution_deadline_secs = p.resolution_deadline_secs;
+        self.early_exit_fee_bps = p.early_exit_fee_bps;
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
@@ -59,6 +103,8 @@ pub enum MarketStatus {
     Resolved,
     Voided,
     Swept,
+    /// Appended so existing status discriminants are unchanged.
+    PendingResolution,
 }
 
 /// Market account PDA (`seeds = ["market", market_id]`).
@@ -81,6 +127,32 @@ pub struct Market {
     pub vault: Pubkey,
     pub market_type: u8,
     pub rules_version: u16,
+    pub fee_bps: u16,
+    /// Canonical bump, stored at create_market so signing skips the PDA search.
+    pub bump: u8,
+    /// OutcomePools already closed by `close_market`; the market closes with the last one.
+    pub pools_closed: u8,
+    /// Winning outcome ids, first `winning_outcome_count` entries are meaningful.
+    pub winning_outcomes: [u8; PODIUM_SIZE as usize],
+    pub winning_outcome_count: u8,
+    /// Sum of the winning outcome pools, snapshotted at resolution.
+    pub winner_pool: u64,
+    /// Collateral mint chosen at create_market (`config.usdc_mint` or allowlisted).
+    pub collateral_mint: Pubkey,
+    /// Sweep destination for `collateral_mint`, snapshotted at create_market.
+    pub treasury: Pubkey,
+    /// Claims open after this time (end of the dispute window); 0 = no dispute window.
+    pub dispute_deadline: i64,
+    pub locked_at: i64,
+    /// create_market cap overrides; `None` uses the Config cap.
+    pub max_total_pool_per_market: Option<u64>,
+    pub max_bet_per_user_per_market: Option<u64>,
+    /// Config snapshots taken at create_market, like `fee_bps`.
+    pub claim_window_secs: i64,
+    pub resolution_deadline_secs: i64,
+    /// Owner of `collateral_mint` (SPL Token or Token-2022), recorded at create_market;
+    /// every transfer for the market must go through it.
+    pub token_program: Pubkey,
 }
 
 impl Market {
@@ -97,7 +169,22 @@ impl Market {
         + 8 // resolution_timestamp
         + 32 // vault
         + 1 // market_type
-        + 2; // rules_version
+        + 2 // rules_version
+        + 2 // fee_bps
+        + 1 // bump
+        + 1 // pools_closed
+        + PODIUM_SIZE as usize // winning_outcomes
+        + 1 // winning_outcome_count
+        + 8 // winner_pool
+        + 32 // collateral_mint
+        + 32 // treasury
+        + 8 // dispute_deadline
+        + 8 // locked_at
+        + 1 + 8 // max_total_pool_per_market (Option<u64>)
+        + 1 + 8 // max_bet_per_user_per_market (Option<u64>)
+        + 8 // claim_window_secs
+        + 8 // resolution_deadline_secs
+        + 32; // token_program
 
     /// Anchor -> parity projection used before invoking pure instruction logic.
     pub fn to_parity(&self) -> parity_state::Market {
@@ -112,6 +199,7 @@ impl Market {
                 MarketStatus::Seeding => parity_state::MarketStatus::Seeding,
                 MarketStatus::Open => parity_state::MarketStatus::Open,
                 MarketStatus::Locked => parity_state::MarketStatus::Locked,
+                MarketStatus::PendingResolution => parity_state::MarketStatus::PendingResolution,
                 MarketStatus::Resolved => parity_state::MarketStatus::Resolved,
                 MarketStatus::Voided => parity_state::MarketStatus::Voided,
                 MarketStatus::Swept => parity_state::MarketStatus::Swept,
@@ -122,6 +210,17 @@ impl Market {
             vault: self.vault.to_string(),
             market_type: self.market_type,
             rules_version: self.rules_version,
+            fee_bps: self.fee_bps,
+            winning_outcomes: self.winning_outcomes[..usize::from(self.winning_outcome_count)].to_vec(),
+            winner_pool: self.winner_pool,
+            collateral_mint: self.collateral_mint.to_string(),
+            treasury: self.treasury.to_string(),
+            dispute_deadline: self.dispute_deadline,
+            locked_at: self.locked_at,
+            max_total_pool_per_market: self.max_total_pool_per_market,
+            max_bet_per_user_per_market: self.max_bet_per_user_per_market,
+            claim_window_secs: self.claim_window_secs,
+            resolution_deadline_secs: self.resolution_deadline_secs,
         }
     }
 
@@ -137,6 +236,7 @@ impl Market {
             parity_state::MarketStatus::Seeding => MarketStatus::Seeding,
             parity_state::MarketStatus::Open => MarketStatus::Open,
             parity_state::MarketStatus::Locked => MarketStatus::Locked,
+            parity_state::MarketStatus::PendingResolution => MarketStatus::PendingResolution,
             parity_state::MarketStatus::Resolved => MarketStatus::Resolved,
             parity_state::MarketStatus::Voided => MarketStatus::Voided,
             parity_state::MarketStatus::Swept => MarketStatus::Swept,
@@ -147,6 +247,148 @@ impl Market {
         // vault Pubkey is set at create_market time and should not change.
         self.market_type = p.market_type;
         self.rules_version = p.rules_version;
+        self.fee_bps = p.fee_bps;
+        // Parity caps the set at PODIUM_SIZE; zero-fill keeps unused slots deterministic.
+        self.winning_outcomes = [0u8; PODIUM_SIZE as usize];
+        self.winning_outcomes[..p.winning_outcomes.len()].copy_from_slice(&p.winning_outcomes);
+        self.winning_outcome_count = p.winning_outcomes.len() as u8;
+        self.winner_pool = p.winner_pool;
+        // collateral_mint/treasury/token_program, the cap overrides and the claim window /
+        // resolution deadline snapshots are set at create_market time and should not change.
+        self.dispute_deadline = p.dispute_deadline;
+        self.locked_at = p.locked_at;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct CollateralMint {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+}
+
+/// Authority-managed collateral allowlist PDA (`seeds = ["collateral"]`).
+///
+/// Lists the mints new markets may use besides `config.usdc_mint`, each with the
+/// treasury its sweeps go to. Created by the first `add_collateral_mint`.
+#[account]
+#[derive(Debug)]
+pub struct CollateralAllowlist {
+    /// First `count` entries are meaningful.
+    pub entries: [CollateralMint; MAX_COLLATERAL_MINTS as usize],
+    pub count: u8,
+    pub bump: u8,
+}
+
+impl CollateralAllowlist {
+    pub const LEN: usize = 8
+        + (32 + 32) * MAX_COLLATERAL_MINTS as usize // entries
+        + 1 // count
+        + 1; // bump
+
+    pub fn to_parity(&self) -> parity_state::CollateralAllowlist {
+        parity_state::CollateralAllowlist {
+            entries: self.entries[..usize::from(self.count)]
+                .iter()
+                .map(|entry| parity_state::CollateralMint {
+                    mint: entry.mint.to_string(),
+                    treasury: entry.treasury.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Oracle committee PDA (`seeds = ["committee"]`).
+///
+/// Resolves and voids markets by quorum while `config.oracle` is this account's
+/// address. Created by the first `set_oracle_committee`.
+#[account]
+#[derive(Debug)]
+pub struct OracleCommittee {
+    /// First `member_count` entries are meaningful.
+    pub members: [Pubkey; MAX_COMMITTEE_MEMBERS as usize],
+    pub member_count: u8,
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+impl OracleCommittee {
+    pub const LEN: usize = 8
+        + 32 * MAX_COMMITTEE_MEMBERS as usize // members
+        + 1 // member_count
+        + 1 // threshold
+        + 1; // bump
+
+    pub fn members(&self) -> &[Pubkey] {
+        &self.members[..usize::from(self.member_count)]
+    }
+
+    pub fn to_parity(&self) -> parity_state::OracleCommittee {
+        parity_state::OracleCommittee {
+            members: self.members().iter().map(ToString::to_string).collect(),
+            threshold: self.threshold,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct CommitteeVote {
+    pub member: Pubkey,
+    pub void: bool,
+    /// Resolve votes only: winner set in finishing order, first `winning_outcome_count`
+    /// entries meaningful.
+    pub winning_outcomes: [u8; PODIUM_SIZE as usize],
+    pub winning_outcome_count: u8,
+    pub payload_hash: [u8; 32],
+}
+
+impl CommitteeVote {
+    pub fn to_parity(&self) -> parity_state::ResolutionVote {
+        parity_state::ResolutionVote {
+            member: self.member.to_string(),
+            decision: if self.void {
+                parity_state::ResolutionDecision::Void
+            } else {
+                parity_state::ResolutionDecision::Resolve {
+                    winning_outcomes: self.winning_outcomes
+                        [..usize::from(self.winning_outcome_count)]
+                        .to_vec(),
+                }
+            },
+            payload_hash: self.payload_hash,
+        }
+    }
+}
+
+/// Committee votes on one market (`seeds = ["votes", market]`).
+///
+/// Created by the market's first `submit_resolution_vote`; holds at most one vote per
+/// current committee member. Closed to `payer` when the committee resolves or voids.
+#[account]
+#[derive(Debug)]
+pub struct ResolutionVotes {
+    pub market: Pubkey,
+    /// Member whose first vote created (and paid for) this account.
+    pub payer: Pubkey,
+    /// First `count` entries are meaningful.
+    pub votes: [CommitteeVote; MAX_COMMITTEE_MEMBERS as usize],
+    pub count: u8,
+    pub bump: u8,
+}
+
+impl ResolutionVotes {
+    pub const LEN: usize = 8
+        + 32 // market
+        + 32 // payer
+        + (32 + 1 + PODIUM_SIZE as usize + 1 + 32) * MAX_COMMITTEE_MEMBERS as usize // votes
+        + 1 // count
+        + 1; // bump
+
+    pub fn to_parity(&self) -> Vec<parity_state::ResolutionVote> {
+        self.votes[..usize::from(self.count)]
+            .iter()
+            .map(CommitteeVote::to_parity)
+            .collect()
     }
 }
 
@@ -156,10 +398,11 @@ pub struct OutcomePool {
     pub market: Pubkey,
     pub outcome_id: u8,
     pub pool_amount: u64,
+    pub bump: u8,
 }
 
 impl OutcomePool {
-    pub const LEN: usize = 8 + 32 + 1 + 8;
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 1;
 }
 
 /// User position for a given (market, user, outcome).
@@ -174,6 +417,7 @@ pub struct Position {
     pub amount: u64,
     pub claimed: bool,
     pub payout: u64,
+    pub bump: u8,
 }
 
 impl Position {
@@ -183,7 +427,8 @@ impl Position {
         + 1 // outcome_id
         + 8 // amount
         + 1 // claimed
-        + 8; // payout
+        + 8 // payout
+        + 1; // bump
 
     pub fn to_parity(&self) -> parity_state::Position {
         parity_state::Position {
@@ -204,8 +449,78 @@ impl Position {
     }
 }
 
+/// A user's stake across all outcomes of one market.
+///
+/// PDA: seeds = ["user_stats", market, user]. Created by the user's first place_bet;
+/// the per-user cap is checked against `total_staked`.
+#[account]
+#[derive(Debug)]
+pub struct UserMarketStats {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub total_staked: u64,
+    /// Outcomes the user holds a Position on, ascending; first `outcome_count`
+    /// entries are meaningful.
+    pub outcome_ids: [u8; MAX_OUTCOMES as usize],
+    pub outcome_count: u8,
+    pub bump: u8,
+}
+
+impl UserMarketStats {
+    pub const LEN: usize = 8
+        + 32 // market
+        + 32 // user
+        + 8 // total_staked
+        + MAX_OUTCOMES as usize // outcome_ids
+        + 1 // outcome_count
+        + 1; // bump
+
+    pub fn outcome_ids(&self) -> &[u8] {
+        &self.outcome_ids[..usize::from(self.outcome_count)]
+    }
+
+    pub fn to_parity(&self) -> parity_state::UserMarketStats {
+        parity_state::UserMarketStats {
+            market: self.market.to_string(),
+            user: self.user.to_string(),
+            total_staked: self.total_staked,
+            outcome_ids: self.outcome_ids().to_vec(),
+        }
+    }
+
+    /// Outcome ids are < MAX_OUTCOMES, so the parity list always fits.
+    pub fn apply_parity(&mut self, s: &parity_state::UserMarketStats) {
+        self.total_staked = s.total_staked;
+        self.outcome_ids = [0; MAX_OUTCOMES as usize];
+        self.outcome_ids[..s.outcome_ids.len()].copy_from_slice(&s.outcome_ids);
+        self.outcome_count = s.outcome_ids.len() as u8;
+    }
+}
+
+/// Left behind by close_market once the Market account is gone.
+///
+/// PDA: seeds = ["tombstone", market]. create_market refuses a market whose tombstone
+/// exists, so a closed market_id can never be re-created over stale Position and
+/// UserMarketStats accounts or replayed attestations. Never closed.
+#[account]
+#[derive(Debug)]
+pub struct MarketTombstone {
+    pub market_id: [u8; 32],
+    /// Set by the close_market call that closes the Market account; 0 before that.
+    pub closed_at: i64,
+    pub bump: u8,
+}
+
+impl MarketTombstone {
+    pub const LEN: usize = 8
+        + 32 // market_id
+        + 8 // closed_at
+        + 1; // bump
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct InitializeArgs {
+    pub oracle: Pubkey,
     pub treasury_authority: Pubkey,
     pub max_total_pool_per_market: u64,
     pub max_bet_per_user_per_market: u64,
@@ -239,6 +554,202 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UpdateConfigArgs {
+    pub fee_bps: Option<u16>,
+    pub max_total_pool_per_market: Option<u64>,
+    pub max_bet_per_user_per_market: Option<u64>,
+    pub claim_window_secs: Option<i64>,
+    pub dispute_window_secs: Option<i64>,
+    pub resolution_deadline_secs: Option<i64>,
+    pub early_exit_fee_bps: Option<u16>,
+    /// `Some(None)` removes the keeper.
+    pub payout_keeper: Option<Option<Pubkey>>,
+}
+
+/// Accounts for `update_config`.
+///
+/// Mutates the Config PDA in place; `None` args leave the current value untouched.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SetPausedArgs {
+    pub paused: bool,
+}
+
+/// Accounts for `set_paused`.
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SetOracleArgs {
+    pub new_oracle: Pubkey,
+}
+
+/// Accounts for `set_oracle`.
+#[derive(Accounts)]
+pub struct SetOracle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SetOracleCommitteeArgs {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+/// Accounts for `set_oracle_committee`.
+///
+/// Creates the OracleCommittee PDA on first use; later calls replace its members.
+#[derive(Accounts)]
+pub struct SetOracleCommittee<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OracleCommittee::LEN,
+        seeds = [COMMITTEE_SEED],
+        bump
+    )]
+    pub oracle_committee: Account<'info, OracleCommittee>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProposeAuthorityArgs {
+    pub new_authority: Pubkey,
+}
+
+/// Accounts for `propose_authority`.
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Accounts for `accept_authority`.
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut)]
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Accounts for `cancel_authority_transfer`.
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Accounts for `add_collateral_mint`.
+///
+/// Creates the CollateralAllowlist PDA on first use.
+#[derive(Accounts)]
+pub struct AddCollateralMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = CollateralAllowlist::LEN,
+        seeds = [COLLATERAL_SEED],
+        bump
+    )]
+    pub collateral_allowlist: Account<'info, CollateralAllowlist>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RemoveCollateralMintArgs {
+    pub mint: Pubkey,
+}
+
+/// Accounts for `remove_collateral_mint`.
+#[derive(Accounts)]
+pub struct RemoveCollateralMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_SEED],
+        bump = collateral_allowlist.bump
+    )]
+    pub collateral_allowlist: Account<'info, CollateralAllowlist>,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct CreateMarketArgs {
     pub market_id: [u8; 32],
@@ -247,6 +758,10 @@ pub struct CreateMarketArgs {
     pub max_outcomes: u8,
     pub market_type: u8,
     pub rules_version: u16,
+    /// Tighter market cap for this market; must not exceed the Config cap.
+    pub max_total_pool_per_market: Option<u64>,
+    /// Tighter per-user cap for this market; must not exceed the Config cap.
+    pub max_bet_per_user_per_market: Option<u64>,
 }
 
 /// Accounts for `create_market`.
@@ -280,8 +795,17 @@ pub struct CreateMarket<'info> {
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// CHECK: `["tombstone", market]`; only inspected to confirm it was never created.
+    #[account(seeds = [TOMBSTONE_SEED, market.key().as_ref()], bump)]
+    pub market_tombstone: UncheckedAccount<'info>,
+
+    /// Market collateral: `config.usdc_mint` or a mint on the collateral allowlist.
     pub usdc_mint: InterfaceAccount<'info, Mint>,
 
+    /// Required only when `usdc_mint` is not `config.usdc_mint`.
+    #[account(seeds = [COLLATERAL_SEED], bump = collateral_allowlist.bump)]
+    pub collateral_allowlist: Option<Account<'info, CollateralAllowlist>>,
+
     pub token_program: Interface<'info, TokenInterface>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -309,7 +833,7 @@ pub struct AddOutcome<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
-        bump
+        bump = market.bump
     )]
     pub market: Account<'info, Market>,
 
@@ -340,7 +864,7 @@ pub struct FinalizeSeeding<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
-        bump
+        bump = market.bump
     )]
     pub market: Account<'info, Market>,
 }
@@ -349,6 +873,10 @@ pub struct FinalizeSeeding<'info> {
 pub struct PlaceBetArgs {
     pub outcome_id: u8,
     pub amount: u64,
+    /// Reject if this outcome would hold more than this share of the pool after the bet (bps).
+    pub max_outcome_share_bps: Option<u16>,
+    /// Reject if the stake would pay out less than this should the outcome win at post-bet pools.
+    pub min_implied_payout: Option<u64>,
 }
 
 /// Accounts for `place_bet`.
@@ -357,6 +885,7 @@ pub struct PlaceBetArgs {
 /// - market.total_pool
 /// - outcome_pool.pool_amount
 /// - position.amount
+/// - user_stats.total_staked / outcome_ids
 #[derive(Accounts)]
 #[instruction(args: PlaceBetArgs)]
 pub struct PlaceBet<'info> {
@@ -369,7 +898,7 @@ pub struct PlaceBet<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
-        bump
+        bump = market.bump
     )]
     pub market: Account<'info, Market>,
 
@@ -386,6 +915,15 @@ pub struct PlaceBet<'info> {
     )]
     pub position: Account<'info, Position>,
 
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserMarketStats::LEN,
+        seeds = [USER_STATS_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserMarketStats>,
+
     #[account(mut)]
     pub user_usdc: InterfaceAccount<'info, TokenAccount>,
 
@@ -399,11 +937,21 @@ pub struct PlaceBet<'info> {
     pub system_program: Program<'info, System>,
 }
 
-/// Accounts for `lock_market`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ReducePositionArgs {
+    pub outcome_id: u8,
+    /// Stake to remove; the user receives it less `config.early_exit_fee_bps`.
+    pub amount: u64,
+}
+
+/// Accounts for `reduce_position`.
+///
+/// Refunds part of a position from the market vault before lock and sends the
+/// early-exit fee to the treasury.
 #[derive(Accounts)]
-pub struct LockMarket<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[instruction(args: ReducePositionArgs)]
+pub struct ReducePosition<'info> {
+    pub user: Signer<'info>,
 
     #[account(seeds = [CONFIG_SEED], bump)]
     pub config: Account<'info, Config>,
@@ -411,8 +959,114 @@ pub struct LockMarket<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: validated/decoded in handler so missing/wrong relation can map to OutcomeMismatch.
+    #[account(mut)]
+    pub outcome_pool: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, market.key().as_ref(), user.key().as_ref(), &[args.outcome_id]],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserMarketStats>,
+
+    #[account(mut)]
+    pub user_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the early-exit fee; same mint/owner rules as `sweep_remaining`.
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwitchOutcomeArgs {
+    pub from_outcome_id: u8,
+    pub to_outcome_id: u8,
+    /// Stake to move; the vault and `market.total_pool` are untouched.
+    pub amount: u64,
+}
+
+/// Accounts for `switch_outcome`.
+///
+/// Moves stake between two of the user's positions in one market before lock. No
+/// token accounts: only pools and positions change.
+#[derive(Accounts)]
+#[instruction(args: SwitchOutcomeArgs)]
+pub struct SwitchOutcome<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [MARKET_SEED, market.market_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: validated/decoded in handler so missing/wrong relation can map to OutcomeMismatch.
+    #[account(mut)]
+    pub from_outcome_pool: AccountInfo<'info>,
+
+    /// CHECK: validated/decoded in handler so missing/wrong relation can map to OutcomeMismatch.
+    #[account(mut)]
+    pub to_outcome_pool: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, market.key().as_ref(), user.key().as_ref(), &[args.from_outcome_id]],
+        bump = from_position.bump
+    )]
+    pub from_position: Account<'info, Position>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Position::LEN,
+        seeds = [POSITION_SEED, market.key().as_ref(), user.key().as_ref(), &[args.to_outcome_id]],
         bump
     )]
+    pub to_position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserMarketStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for `lock_market` (permissionless once `lock_timestamp` has passed).
+#[derive(Accounts)]
+pub struct LockMarket<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.market_id.as_ref()],
+        bump = market.bump
+    )]
     pub market: Account<'info, Market>,
 }
 
@@ -420,12 +1074,28 @@ pub struct LockMarket<'info> {
 pub struct ResolveMarketArgs {
     pub winning_outcome_id: u8,
     pub payload_hash: [u8; 32],
+    /// Podium only (empty for Winner): remaining winners in finishing order. Their
+    /// OutcomePools are passed as remaining accounts in the same order.
+    pub additional_winning_outcome_ids: Vec<u8>,
+    /// Verified mode: the result record behind `payload_hash`. Must carry the market's
+    /// `event_id` (lowercase hex) and the resolved `winning_outcomes`, and hash to
+    /// `payload_hash` under SPEC_CANONICAL rules.
+    pub result_payload: Option<Vec<PayloadField>>,
+}
+
+/// One string field of a resolution result payload; key order is irrelevant.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PayloadField {
+    pub key: String,
+    pub value: String,
 }
 
 /// Accounts for `resolve_market`.
 #[derive(Accounts)]
 #[instruction(args: ResolveMarketArgs)]
 pub struct ResolveMarket<'info> {
+    /// `config.oracle`, or any keeper when `instructions_sysvar` is passed and the
+    /// transaction carries the oracle's Ed25519 attestation. Any signer in committee mode.
     #[account(mut)]
     pub oracle: Signer<'info>,
 
@@ -435,13 +1105,33 @@ pub struct ResolveMarket<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
-        bump
+        bump = market.bump
     )]
     pub market: Account<'info, Market>,
 
     /// CHECK: validated/decoded in handler so missing/wrong relation can map to OutcomeMismatch.
     pub winning_outcome_pool: AccountInfo<'info>,
 
+    /// CHECK: address-pinned; read for Ed25519 precompile attestations.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Committee mode: required while `config.oracle` is the committee PDA.
+    #[account(seeds = [COMMITTEE_SEED], bump = oracle_committee.bump)]
+    pub oracle_committee: Option<Account<'info, OracleCommittee>>,
+
+    /// Committee mode: closed to `votes_payer` once the quorum finalizes.
+    #[account(
+        mut,
+        seeds = [VOTES_SEED, market.key().as_ref()],
+        bump = resolution_votes.bump
+    )]
+    pub resolution_votes: Option<Account<'info, ResolutionVotes>>,
+
+    /// CHECK: must be `resolution_votes.payer` (checked in handler); receives its rent.
+    #[account(mut)]
+    pub votes_payer: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -453,6 +1143,8 @@ pub struct VoidMarketArgs {
 /// Accounts for `void_market`.
 #[derive(Accounts)]
 pub struct VoidMarket<'info> {
+    /// `config.oracle`, or any keeper relaying the oracle's Ed25519 attestation. Any
+    /// signer in committee mode.
     #[account(mut)]
     pub oracle: Signer<'info>,
 
@@ -462,14 +1154,124 @@ pub struct VoidMarket<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
-        bump
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: address-pinned; read for Ed25519 precompile attestations.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Committee mode: required while `config.oracle` is the committee PDA.
+    #[account(seeds = [COMMITTEE_SEED], bump = oracle_committee.bump)]
+    pub oracle_committee: Option<Account<'info, OracleCommittee>>,
+
+    /// Committee mode: closed to `votes_payer` once the quorum finalizes.
+    #[account(
+        mut,
+        seeds = [VOTES_SEED, market.key().as_ref()],
+        bump = resolution_votes.bump
+    )]
+    pub resolution_votes: Option<Account<'info, ResolutionVotes>>,
+
+    /// CHECK: must be `resolution_votes.payer` (checked in handler); receives its rent.
+    #[account(mut)]
+    pub votes_payer: Option<UncheckedAccount<'info>>,
+}
+
+/// Accounts for `void_stale_market` (permissionless).
+#[derive(Accounts)]
+pub struct VoidStaleMarket<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.market_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OverturnResolutionArgs {
+    /// `true` voids the market; the outcome fields are then ignored.
+    pub void: bool,
+    pub winning_outcome_id: u8,
+    /// Podium only: remaining winners in finishing order, pools as remaining accounts.
+    pub additional_winning_outcome_ids: Vec<u8>,
+    pub payload_hash: [u8; 32],
+    /// The result record behind `payload_hash`, stating the new winner set (empty when
+    /// voiding). Without it the overturn is unverified and the market's payload hash
+    /// is cleared.
+    pub result_payload: Option<Vec<PayloadField>>,
+}
+
+/// Accounts for `overturn_resolution`.
+#[derive(Accounts)]
+pub struct OverturnResolution<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.market_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: decoded in handler (OutcomeMismatch); omitted when voiding.
+    pub winning_outcome_pool: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SubmitResolutionVoteArgs {
+    /// `true` votes to void; the outcome fields are then ignored.
+    pub void: bool,
+    pub winning_outcome_id: u8,
+    /// Podium only: remaining winners in finishing order.
+    pub additional_winning_outcome_ids: Vec<u8>,
+    pub payload_hash: [u8; 32],
+}
+
+/// Accounts for `submit_resolution_vote`.
+///
+/// The market's ResolutionVotes PDA is created by its first vote, paid by that member.
+#[derive(Accounts)]
+pub struct SubmitResolutionVote<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [COMMITTEE_SEED], bump = oracle_committee.bump)]
+    pub oracle_committee: Account<'info, OracleCommittee>,
+
+    #[account(
+        seeds = [MARKET_SEED, market.market_id.as_ref()],
+        bump = market.bump
     )]
     pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = ResolutionVotes::LEN,
+        seeds = [VOTES_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub resolution_votes: Account<'info, ResolutionVotes>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct ClaimResolvedArgs {
     pub outcome_id: u8,
+    /// Close the position in the same instruction and return its rent to `user`.
+    pub close_position: bool,
 }
 
 /// Accounts for `claim_resolved`.
@@ -485,14 +1287,14 @@ pub struct ClaimResolved<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
-        bump
+        bump = market.bump
     )]
     pub market: Account<'info, Market>,
 
     #[account(
         mut,
         seeds = [POSITION_SEED, market.key().as_ref(), user.key().as_ref(), &[args.outcome_id]],
-        bump
+        bump = position.bump
     )]
     pub position: Account<'info, Position>,
 
@@ -511,9 +1313,57 @@ pub struct ClaimResolved<'info> {
     pub token_program: Interface<'info, TokenInterface>,
 }
 
+/// Accounts for `claim_many`. Each claim is a `[market, outcome_pool, position (mut),
+/// vault (mut)]` group in `remaining_accounts`; every market must use `usdc_mint`.
+/// A market passed writable is finalized if its dispute window has ended.
+#[derive(Accounts)]
+pub struct ClaimMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub user_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Accounts for `distribute_payouts`. Each payout is a `[position (mut), outcome_pool,
+/// destination (mut)]` group in `remaining_accounts`; the destination must be
+/// `position.user`'s associated token account for `usdc_mint`.
+#[derive(Accounts)]
+pub struct DistributePayouts<'info> {
+    /// config.authority or config.payout_keeper; gains nothing but pays the fees.
+    pub caller: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    /// Mutable so a pending result past its dispute deadline can be finalized.
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.market_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct ClaimVoidedArgs {
     pub outcome_id: u8,
+    /// Close the position in the same instruction and return its rent to `user`.
+    pub close_position: bool,
 }
 
 /// Accounts for `claim_voided`.
@@ -529,14 +1379,14 @@ pub struct ClaimVoided<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
-        bump
+        bump = market.bump
     )]
     pub market: Account<'info, Market>,
 
     #[account(
         mut,
         seeds = [POSITION_SEED, market.key().as_ref(), user.key().as_ref(), &[args.outcome_id]],
-        bump
+        bump = position.bump
     )]
     pub position: Account<'info, Position>,
 
@@ -551,6 +1401,75 @@ pub struct ClaimVoided<'info> {
     pub token_program: Interface<'info, TokenInterface>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ClosePositionArgs {
+    pub outcome_id: u8,
+}
+
+/// Accounts for `close_position`.
+#[derive(Accounts)]
+#[instruction(args: ClosePositionArgs)]
+pub struct ClosePosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: bound by the position seeds; decoded in handler. May already be
+    /// closed by `close_market`, which only happens after the market is finished.
+    pub market: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [POSITION_SEED, market.key().as_ref(), user.key().as_ref(), &[args.outcome_id]],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Closed along with the position when passed. Positions are only closable once
+    /// betting is over, so the stats are no longer needed for the cap.
+    #[account(
+        mut,
+        close = user,
+        seeds = [USER_STATS_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Option<Account<'info, UserMarketStats>>,
+}
+
+/// Accounts for `close_market`.
+///
+/// Remaining accounts: the market's OutcomePool PDAs to close (mut), any subset.
+#[derive(Accounts)]
+pub struct CloseMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.market_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: must equal market.vault; only inspected to confirm it is closed.
+    pub vault: UncheckedAccount<'info>,
+
+    /// Created on the first call and kept after the market closes; see MarketTombstone.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MarketTombstone::LEN,
+        seeds = [TOMBSTONE_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub market_tombstone: Account<'info, MarketTombstone>,
+
+    pub system_program: Program<'info, System>,
+}
+
 /// Accounts for `sweep_remaining`.
 #[derive(Accounts)]
 pub struct SweepRemaining<'info> {
@@ -563,7 +1482,7 @@ pub struct SweepRemaining<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
-        bump
+        bump = market.bump
     )]
     pub market: Account<'info, Market>,
 
@@ -577,6 +1496,8 @@ pub struct SweepRemaining<'info> {
     #[account(mut)]
     pub close_destination: SystemAccount<'info>,
 
+    /// Writable so Token-2022 transfer fees withheld in the vault can be harvested to it.
+    #[account(mut)]
     pub usdc_mint: InterfaceAccount<'info, Mint>,
 
     pub token_program: Interface<'info, TokenInterface>,
@@ -594,7 +1515,7 @@ pub struct CancelMarket<'info> {
     #[account(
         mut,
         seeds = [MARKET_SEED, market.market_id.as_ref()],
-        bump
+        bump = market.bump
     )]
     pub market: Account<'info, Market>,
 
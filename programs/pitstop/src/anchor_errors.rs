@@ -93,6 +93,83 @@ pub enum PitStopAnchorError {
     Underflow,
     #[msg("DivisionByZero")]
     DivisionByZero,
+
+    #[msg("FeeTooHigh")]
+    FeeTooHigh,
+
+    #[msg("NoPendingAuthority")]
+    NoPendingAuthority,
+
+    #[msg("PositionNotClosable")]
+    PositionNotClosable,
+
+    #[msg("MarketNotClosable")]
+    MarketNotClosable,
+    #[msg("VaultNotClosed")]
+    VaultNotClosed,
+
+    #[msg("UnsupportedMintExtension")]
+    UnsupportedMintExtension,
+
+    #[msg("CollateralMintNotAllowed")]
+    CollateralMintNotAllowed,
+
+    #[msg("CollateralMintAlreadyAllowed")]
+    CollateralMintAlreadyAllowed,
+
+    #[msg("CollateralAllowlistFull")]
+    CollateralAllowlistFull,
+
+    #[msg("InvalidResolutionPayload")]
+    InvalidResolutionPayload,
+
+    #[msg("PayloadEventMismatch")]
+    PayloadEventMismatch,
+
+    #[msg("PayloadHashMismatch")]
+    PayloadHashMismatch,
+
+    #[msg("InvalidCommittee")]
+    InvalidCommittee,
+
+    #[msg("NotCommitteeMember")]
+    NotCommitteeMember,
+
+    #[msg("QuorumNotReached")]
+    QuorumNotReached,
+
+    #[msg("InvalidDisputeWindow")]
+    InvalidDisputeWindow,
+    #[msg("DisputeWindowActive")]
+    DisputeWindowActive,
+    #[msg("DisputeWindowClosed")]
+    DisputeWindowClosed,
+
+    #[msg("InvalidResolutionDeadline")]
+    InvalidResolutionDeadline,
+    #[msg("ResolutionDeadlineNotReached")]
+    ResolutionDeadlineNotReached,
+
+    #[msg("InvalidClaimBatch")]
+    InvalidClaimBatch,
+
+    #[msg("ReductionExceedsPosition")]
+    ReductionExceedsPosition,
+
+    #[msg("SameOutcomeSwitch")]
+    SameOutcomeSwitch,
+
+    #[msg("SlippageExceeded")]
+    SlippageExceeded,
+
+    #[msg("MarketIdRetired")]
+    MarketIdRetired,
+    #[msg("VotesPayerMismatch")]
+    VotesPayerMismatch,
+    #[msg("PayloadOutcomeMismatch")]
+    PayloadOutcomeMismatch,
+    #[msg("InvalidPayoutDestination")]
+    InvalidPayoutDestination,
 }
 
 impl From<PitStopError> for PitStopAnchorError {
@@ -138,6 +215,34 @@ impl From<PitStopError> for PitStopAnchorError {
             PitStopError::Overflow => Self::Overflow,
             PitStopError::Underflow => Self::Underflow,
             PitStopError::DivisionByZero => Self::DivisionByZero,
+            PitStopError::FeeTooHigh => Self::FeeTooHigh,
+            PitStopError::NoPendingAuthority => Self::NoPendingAuthority,
+            PitStopError::PositionNotClosable => Self::PositionNotClosable,
+            PitStopError::MarketNotClosable => Self::MarketNotClosable,
+            PitStopError::VaultNotClosed => Self::VaultNotClosed,
+            PitStopError::UnsupportedMintExtension => Self::UnsupportedMintExtension,
+            PitStopError::CollateralMintNotAllowed => Self::CollateralMintNotAllowed,
+            PitStopError::CollateralMintAlreadyAllowed => Self::CollateralMintAlreadyAllowed,
+            PitStopError::CollateralAllowlistFull => Self::CollateralAllowlistFull,
+            PitStopError::InvalidResolutionPayload => Self::InvalidResolutionPayload,
+            PitStopError::PayloadEventMismatch => Self::PayloadEventMismatch,
+            PitStopError::PayloadHashMismatch => Self::PayloadHashMismatch,
+            PitStopError::InvalidCommittee => Self::InvalidCommittee,
+            PitStopError::NotCommitteeMember => Self::NotCommitteeMember,
+            PitStopError::QuorumNotReached => Self::QuorumNotReached,
+            PitStopError::InvalidDisputeWindow => Self::InvalidDisputeWindow,
+            PitStopError::DisputeWindowActive => Self::DisputeWindowActive,
+            PitStopError::DisputeWindowClosed => Self::DisputeWindowClosed,
+            PitStopError::InvalidResolutionDeadline => Self::InvalidResolutionDeadline,
+            PitStopError::ResolutionDeadlineNotReached => Self::ResolutionDeadlineNotReached,
+            PitStopError::InvalidClaimBatch => Self::InvalidClaimBatch,
+            PitStopError::ReductionExceedsPosition => Self::ReductionExceedsPosition,
+            PitStopError::SameOutcomeSwitch => Self::SameOutcomeSwitch,
+            PitStopError::SlippageExceeded => Self::SlippageExceeded,
+            PitStopError::MarketIdRetired => Self::MarketIdRetired,
+            PitStopError::VotesPayerMismatch => Self::VotesPayerMismatch,
+            PitStopError::PayloadOutcomeMismatch => Self::PayloadOutcomeMismatch,
+            PitStopError::InvalidPayoutDestination => Self::InvalidPayoutDestination,
         }
     }
 }
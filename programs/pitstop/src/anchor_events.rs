@@ -10,6 +10,64 @@ pub struct ConfigInitialized {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+    pub old_max_total_pool_per_market: u64,
+    pub new_max_total_pool_per_market: u64,
+    pub old_max_bet_per_user_per_market: u64,
+    pub new_max_bet_per_user_per_market: u64,
+    pub old_claim_window_secs: i64,
+    pub new_claim_window_secs: i64,
+    pub old_dispute_window_secs: i64,
+    pub new_dispute_window_secs: i64,
+    pub old_resolution_deadline_secs: i64,
+    pub new_resolution_deadline_secs: i64,
+    pub old_early_exit_fee_bps: u16,
+    pub new_early_exit_fee_bps: u16,
+    pub old_payout_keeper: Option<Pubkey>,
+    pub new_payout_keeper: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolPauseChanged {
+    pub authority: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleRotated {
+    pub authority: Pubkey,
+    pub old_oracle: Pubkey,
+    pub new_oracle: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MarketCreated {
     pub market: Pubkey,
@@ -19,6 +77,10 @@ pub struct MarketCreated {
     pub max_outcomes: u8,
     pub market_type: u8,
     pub rules_version: u16,
+    pub collateral_mint: Pubkey,
+    /// Cap overrides set at creation; `None` = the Config cap applies.
+    pub max_total_pool_per_market: Option<u64>,
+    pub max_bet_per_user_per_market: Option<u64>,
     pub timestamp: i64,
 }
 
@@ -47,9 +109,41 @@ pub struct BetPlaced {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct BetReduced {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome_id: u8,
+    /// Stake removed from the position; `refund + fee`.
+    pub amount: u64,
+    pub fee: u64,
+    pub refund: u64,
+    pub position_amount: u64,
+    pub market_total_pool: u64,
+    pub outcome_pool_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OutcomeSwitched {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub from_outcome_id: u8,
+    pub to_outcome_id: u8,
+    /// Stake moved; no tokens leave the vault.
+    pub amount: u64,
+    pub from_position_amount: u64,
+    pub to_position_amount: u64,
+    pub from_outcome_pool_amount: u64,
+    pub to_outcome_pool_amount: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MarketLocked {
     pub market: Pubkey,
+    pub locked_by: Pubkey,
+    pub lock_timestamp: i64,
     pub timestamp: i64,
 }
 
@@ -57,8 +151,11 @@ pub struct MarketLocked {
 pub struct MarketResolved {
     pub market: Pubkey,
     pub winning_outcome: u8,
+    pub winning_outcomes: Vec<u8>,
     pub payload_hash: [u8; 32],
+    pub payload_verified: bool,
     pub resolution_timestamp: i64,
+    pub dispute_deadline: i64,
 }
 
 #[event]
@@ -68,6 +165,34 @@ pub struct MarketVoided {
     pub resolution_timestamp: i64,
 }
 
+#[event]
+pub struct StaleMarketVoided {
+    pub market: Pubkey,
+    pub caller: Pubkey,
+    pub resolution_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResolutionOverturned {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub void: bool,
+    pub old_winning_outcomes: Vec<u8>,
+    pub winning_outcomes: Vec<u8>,
+    pub payload_hash: [u8; 32],
+    pub payload_verified: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResolutionFinalized {
+    pub market: Pubkey,
+    pub winning_outcomes: Vec<u8>,
+    pub dispute_deadline: i64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct Claimed {
     pub market: Pubkey,
@@ -75,6 +200,8 @@ pub struct Claimed {
     pub outcome_id: u8,
     pub payout: u64,
     pub claimed_at: i64,
+    /// Signer that triggered the claim: `user`, or the distribute_payouts caller.
+    pub initiator: Pubkey,
 }
 
 #[event]
@@ -90,3 +217,58 @@ pub struct MarketCancelled {
     pub market: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct PositionClosed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome_id: u8,
+    pub claimed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketRentReclaimed {
+    pub market: Pubkey,
+    pub pools_closed: u8,
+    pub total_pools_closed: u8,
+    pub market_closed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralMintAdded {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralMintRemoved {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleCommitteeSet {
+    pub authority: Pubkey,
+    pub old_oracle: Pubkey,
+    pub committee: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResolutionVoteSubmitted {
+    pub market: Pubkey,
+    pub member: Pubkey,
+    pub void: bool,
+    pub winning_outcomes: Vec<u8>,
+    pub payload_hash: [u8; 32],
+    pub matching_votes: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
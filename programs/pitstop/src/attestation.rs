@@ -0,0 +1,182 @@
+//! Offline oracle attestations checked through the Ed25519 precompile.
+//!
+//! Spec: SPEC_PROTOCOL.md -> "Oracle attestations"
+//!
+//! `resolve_market` / `void_market` can be submitted by any keeper when the same
+//! transaction carries an Ed25519 precompile instruction in which `config.oracle`
+//! signed the message built here. The runtime verifies the signature before the
+//! program runs; the program only matches the signed pubkey and message bytes, read
+//! back through the instructions sysvar.
+
+use anchor_lang::prelude::Pubkey;
+
+/// Domain tag for resolution attestations.
+pub const RESOLVE_DOMAIN: &[u8] = b"pitstop:resolve:v1";
+/// Domain tag for void attestations.
+pub const VOID_DOMAIN: &[u8] = b"pitstop:void:v1";
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+const SIGNATURE_SIZE: usize = 64;
+/// Offsets instruction index meaning "this precompile instruction's own data".
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// `RESOLVE_DOMAIN || program_id || market || winning_outcome_id || n || additional[n] || payload_hash`
+pub fn resolve_message(
+    market: &Pubkey,
+    winning_outcome_id: u8,
+    additional_winning_outcome_ids: &[u8],
+    payload_hash: &[u8; 32],
+) -> Vec<u8> {
+    let mut msg =
+        Vec::with_capacity(RESOLVE_DOMAIN.len() + 98 + additional_winning_outcome_ids.len());
+    msg.extend_from_slice(RESOLVE_DOMAIN);
+    msg.extend_from_slice(crate::id().as_ref());
+    msg.extend_from_slice(market.as_ref());
+    msg.push(winning_outcome_id);
+    msg.push(additional_winning_outcome_ids.len() as u8);
+    msg.extend_from_slice(additional_winning_outcome_ids);
+    msg.extend_from_slice(payload_hash);
+    msg
+}
+
+/// `VOID_DOMAIN || program_id || market || payload_hash`
+pub fn void_message(market: &Pubkey, payload_hash: &[u8; 32]) -> Vec<u8> {
+    let mut msg = Vec::with_capacity(VOID_DOMAIN.len() + 96);
+    msg.extend_from_slice(VOID_DOMAIN);
+    msg.extend_from_slice(crate::id().as_ref());
+    msg.extend_from_slice(market.as_ref());
+    msg.extend_from_slice(payload_hash);
+    msg
+}
+
+/// Ed25519 precompile instruction data for one signature, with pubkey, signature and
+/// message inline (the layout `ed25519_signed_messages` accepts).
+pub fn ed25519_instruction_data(pubkey: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+    let pubkey_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE;
+    let signature_offset = pubkey_offset + PUBKEY_SIZE;
+    let message_offset = signature_offset + SIGNATURE_SIZE;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for field in [
+        signature_offset as u16,
+        CURRENT_INSTRUCTION,
+        pubkey_offset as u16,
+        CURRENT_INSTRUCTION,
+        message_offset as u16,
+        message.len() as u16,
+        CURRENT_INSTRUCTION,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    data
+}
+
+/// `(pubkey, message)` for each signature in an Ed25519 precompile instruction whose
+/// pubkey, signature and message all live in that instruction's own data. Entries
+/// pointing at other instructions, and malformed data, yield nothing.
+pub fn ed25519_signed_messages(data: &[u8]) -> Vec<(Pubkey, &[u8])> {
+    let Some(&count) = data.first() else {
+        return Vec::new();
+    };
+    let u16_at = |at: usize| -> Option<u16> {
+        Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+    };
+    let mut out = Vec::new();
+    for i in 0..usize::from(count) {
+        let base = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SIZE;
+        let fields: Option<Vec<u16>> = (0..7).map(|f| u16_at(base + f * 2)).collect();
+        let Some(&[_, sig_ix, pubkey_offset, pubkey_ix, msg_offset, msg_size, msg_ix]) =
+            fields.as_deref()
+        else {
+            return Vec::new();
+        };
+        if [sig_ix, pubkey_ix, msg_ix] != [CURRENT_INSTRUCTION; 3] {
+            continue;
+        }
+        let pubkey_offset = usize::from(pubkey_offset);
+        let msg_offset = usize::from(msg_offset);
+        let (Some(pubkey), Some(message)) = (
+            data.get(pubkey_offset..pubkey_offset + PUBKEY_SIZE),
+            data.get(msg_offset..msg_offset + usize::from(msg_size)),
+        ) else {
+            continue;
+        };
+        out.push((Pubkey::try_from(pubkey).unwrap_or_default(), message));
+    }
+    out
+}
+
+/// Keys that signed exactly `message` across the given Ed25519 precompile instruction datas.
+pub fn attesters<'a>(
+    ed25519_datas: impl IntoIterator<Item = &'a [u8]>,
+    message: &[u8],
+) -> Vec<Pubkey> {
+    ed25519_datas
+        .into_iter()
+        .flat_map(ed25519_signed_messages)
+        .filter(|(_, signed)| *signed == message)
+        .map(|(pubkey, _)| pubkey)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn att_msg_001_messages_bind_market_outcomes_and_hash() {
+        let market = Pubkey::new_from_array([7u8; 32]);
+        let msg = resolve_message(&market, 4, &[1, 2], &[9u8; 32]);
+        assert!(msg.starts_with(RESOLVE_DOMAIN));
+        assert_eq!(msg.len(), RESOLVE_DOMAIN.len() + 32 + 32 + 1 + 1 + 2 + 32);
+        assert_ne!(msg, resolve_message(&market, 4, &[2, 1], &[9u8; 32]));
+        assert_ne!(msg, resolve_message(&market, 4, &[1, 2], &[8u8; 32]));
+
+        // Resolve and void attestations never collide.
+        let void = void_message(&market, &[9u8; 32]);
+        assert!(void.starts_with(VOID_DOMAIN));
+        assert_ne!(void, resolve_message(&market, 0, &[], &[9u8; 32]));
+    }
+
+    #[test]
+    fn att_ed_001_round_trips_inline_signature_data() {
+        let oracle = Pubkey::new_from_array([3u8; 32]);
+        let message = void_message(&Pubkey::new_from_array([7u8; 32]), &[1u8; 32]);
+        let data = ed25519_instruction_data(&oracle, &[5u8; 64], &message);
+
+        assert_eq!(
+            ed25519_signed_messages(&data),
+            vec![(oracle, message.as_slice())]
+        );
+        assert_eq!(attesters([data.as_slice()], &message), vec![oracle]);
+        assert!(attesters([data.as_slice()], b"other").is_empty());
+    }
+
+    #[test]
+    fn att_ed_002_ignores_foreign_offsets_and_malformed_data() {
+        let oracle = Pubkey::new_from_array([3u8; 32]);
+        let mut data = ed25519_instruction_data(&oracle, &[5u8; 64], b"msg");
+
+        // Message read from another instruction: not attributable to this data.
+        let mut foreign = data.clone();
+        foreign[14..16].copy_from_slice(&0u16.to_le_bytes());
+        assert!(ed25519_signed_messages(&foreign).is_empty());
+
+        // Message range past the end of the data.
+        let mut short = data.clone();
+        short.truncate(data.len() - 1);
+        assert!(ed25519_signed_messages(&short).is_empty());
+
+        // Claims two signatures but only carries one offsets record.
+        data[0] = 2;
+        data.truncate(SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE + 4);
+        assert!(ed25519_signed_messages(&data).is_empty());
+        assert!(ed25519_signed_messages(&[]).is_empty());
+    }
+}
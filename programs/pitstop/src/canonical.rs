@@ -0,0 +1,192 @@
+//! Canonical descriptor encoding and id derivation.
+//!
+//! Spec: SPEC_CANONICAL.md. Golden vectors: specs/vectors/canonical_vectors.json
+//!
+//! Off-chain callers (backend, tooling) derive `event_id` / `market_id` here; on-chain
+//! `create_market` only re-checks `market_id` from the supplied `event_id`, and
+//! `resolve_market` recomputes `payload_hash` when the oracle submits its result payload.
+
+use std::collections::BTreeMap;
+
+use sha2::{Digest, Sha256};
+
+fn push_json_string(out: &mut String, s: &str) {
+    // Same escaping as JSON.stringify for strings.
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Canonical JSON for a flat string descriptor: keys sorted, no whitespace.
+///
+/// Key order comes from the map, so callers cannot influence it.
+pub fn canonical_json<K: AsRef<str>, V: AsRef<str>>(descriptor: &BTreeMap<K, V>) -> String {
+    let mut out = String::from("{");
+    for (i, (k, v)) in descriptor.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        push_json_string(&mut out, k.as_ref());
+        out.push(':');
+        push_json_string(&mut out, v.as_ref());
+    }
+    out.push('}');
+    out
+}
+
+/// UTF-8 bytes of `canonical_json`, i.e. the `event_id` preimage.
+pub fn canonical_bytes<K: AsRef<str>, V: AsRef<str>>(descriptor: &BTreeMap<K, V>) -> Vec<u8> {
+    canonical_json(descriptor).into_bytes()
+}
+
+/// event_id = sha256(canonical_json_bytes)
+pub fn event_id<K: AsRef<str>, V: AsRef<str>>(descriptor: &BTreeMap<K, V>) -> [u8; 32] {
+    Sha256::digest(canonical_bytes(descriptor)).into()
+}
+
+/// Field a result payload uses to reference its market's `event_id` (lowercase hex).
+pub const PAYLOAD_EVENT_ID_KEY: &str = "event_id";
+
+/// Field a result payload uses to state the winner set: outcome ids in finishing order,
+/// decimal, comma-separated (`"1"`, `"3,0,2"`).
+pub const PAYLOAD_WINNING_OUTCOMES_KEY: &str = "winning_outcomes";
+
+/// Encodes a winner set as the `PAYLOAD_WINNING_OUTCOMES_KEY` value.
+pub fn winning_outcomes_field(winning_outcomes: &[u8]) -> String {
+    winning_outcomes
+        .iter()
+        .map(u8::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Lowercase hex, the encoding ids take inside descriptors and result payloads.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// payload_hash = sha256(canonical_json_bytes), same canonicalization as `event_id`.
+pub fn payload_hash<K: AsRef<str>, V: AsRef<str>>(payload: &BTreeMap<K, V>) -> [u8; 32] {
+    Sha256::digest(canonical_bytes(payload)).into()
+}
+
+/// market_id = sha256(event_id[32] || market_type[u8] || rules_version[u16-le])
+pub fn market_id(event_id: [u8; 32], market_type: u8, rules_version: u16) -> [u8; 32] {
+    let mut bytes = [0u8; 35];
+    bytes[0..32].copy_from_slice(&event_id);
+    bytes[32] = market_type;
+    bytes[33..35].copy_from_slice(&rules_version.to_le_bytes());
+
+    Sha256::digest(bytes).into()
+}
+
+/// Descriptor -> (event_id, market_id) in one step.
+pub fn derive_ids<K: AsRef<str>, V: AsRef<str>>(
+    descriptor: &BTreeMap<K, V>,
+    market_type: u8,
+    rules_version: u16,
+) -> ([u8; 32], [u8; 32]) {
+    let event_id = event_id(descriptor);
+    (event_id, market_id(event_id, market_type, rules_version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const CANONICAL_VECTORS: &str = include_str!("../../../specs/vectors/canonical_vectors.json");
+
+    fn vectors() -> Value {
+        serde_json::from_str(CANONICAL_VECTORS).expect("valid vector json")
+    }
+
+    #[test]
+    fn can_vec_001_vector_a_canonical_bytes_and_event_id() {
+        let doc = vectors();
+        let a = &doc["vectorA"];
+        let descriptor: BTreeMap<String, String> = a["descriptor"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.as_str().unwrap().to_string()))
+            .collect();
+
+        assert_eq!(canonical_json(&descriptor), a["canonicalJson"].as_str().unwrap());
+        assert_eq!(
+            hex(&canonical_bytes(&descriptor)),
+            "7b22726f756e64223a223035222c22736561736f6e223a2232303236222c2273657373696f6e223a2272616365222c2273706f7274223a226631227d"
+        );
+        assert_eq!(hex(&event_id(&descriptor)), a["eventIdHex"].as_str().unwrap());
+    }
+
+    #[test]
+    fn can_vec_002_vector_b_market_id() {
+        let doc = vectors();
+        let b = &doc["vectorB"];
+        let got = market_id(
+            [0u8; 32],
+            b["marketTypeByte"].as_u64().unwrap() as u8,
+            b["rulesVersion"].as_u64().unwrap() as u16,
+        );
+        assert_eq!(hex(&got), b["marketIdHex"].as_str().unwrap());
+    }
+
+    #[test]
+    fn can_det_001_insertion_order_does_not_change_ids() {
+        let mut forward = BTreeMap::new();
+        forward.insert("sport", "f1");
+        forward.insert("season", "2026");
+        forward.insert("round", "05");
+        forward.insert("session", "race");
+        let reversed: BTreeMap<&str, &str> = forward.iter().rev().map(|(k, v)| (*k, *v)).collect();
+
+        assert_eq!(derive_ids(&forward, 0, 1), derive_ids(&reversed, 0, 1));
+        let (event, market) = derive_ids(&forward, 0, 1);
+        assert_eq!(hex(&event), vectors()["vectorA"]["eventIdHex"].as_str().unwrap());
+        assert_eq!(market, market_id(event, 0, 1));
+        assert_ne!(market, market_id(event, 0, 2));
+    }
+
+    #[test]
+    fn can_vec_003_vector_c_result_payload_hash() {
+        let doc = vectors();
+        let c = &doc["vectorC"];
+        let payload: BTreeMap<String, String> = c["resultPayload"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.as_str().unwrap().to_string()))
+            .collect();
+
+        assert_eq!(canonical_json(&payload), c["canonicalJson"].as_str().unwrap());
+        assert_eq!(hex(&payload_hash(&payload)), c["payloadHashHex"].as_str().unwrap());
+        assert_eq!(
+            payload[PAYLOAD_EVENT_ID_KEY],
+            doc["vectorA"]["eventIdHex"].as_str().unwrap()
+        );
+        assert_eq!(
+            payload[PAYLOAD_WINNING_OUTCOMES_KEY],
+            winning_outcomes_field(&[1])
+        );
+        assert_eq!(winning_outcomes_field(&[3, 0, 2]), "3,0,2");
+    }
+
+    #[test]
+    fn can_esc_001_strings_are_json_escaped() {
+        let descriptor = BTreeMap::from([("name", "a\"b\\c\n\u{1}")]);
+        assert_eq!(canonical_json(&descriptor), r#"{"name":"a\"b\\c\n\u0001"}"#);
+    }
+}
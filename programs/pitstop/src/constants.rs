@@ -2,9 +2,47 @@ use anchor_lang::prelude::{pubkey, Pubkey};
 
 pub const USDC_DECIMALS: u8 = 6;
 pub const MAX_CLAIM_WINDOW_SECS: i64 = 7_776_000;
+/// Upper bound for `Config.dispute_window_secs` (0 disables the dispute window).
+pub const MAX_DISPUTE_WINDOW_SECS: i64 = 604_800;
+/// Upper bound for `Config.resolution_deadline_secs` (0 disables stale-market voids).
+pub const MAX_RESOLUTION_DEADLINE_SECS: i64 = 2_592_000;
+pub const MAX_FEE_BPS: u16 = 1_000;
 pub const REQUIRED_TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
 pub const REQUIRED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+/// Token-2022; accepted only when built with the `token-2022` feature.
+pub const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// SPL Token v1 always; Token-2022 only with the `token-2022` feature.
+pub fn is_supported_token_program(token_program: &str) -> bool {
+    token_program == REQUIRED_TOKEN_PROGRAM
+        || (cfg!(feature = "token-2022") && token_program == TOKEN_2022_PROGRAM)
+}
 
 pub const MAX_OUTCOMES: u8 = 100;
+/// Collateral mints the authority can allowlist in addition to `config.usdc_mint`.
+pub const MAX_COLLATERAL_MINTS: u8 = 8;
+/// Oracle committee size bound; also bounds the votes a market can hold.
+pub const MAX_COMMITTEE_MEMBERS: u8 = 10;
+/// Positions per claim_many call; 4 accounts each keeps the batch under the
+/// 64-account transaction lock limit.
+pub const MAX_CLAIM_BATCH: usize = 12;
 pub const SUPPORTED_MARKET_TYPE: u8 = 0;
 pub const SUPPORTED_RULES_VERSION: u16 = 1;
+
+/// Winner market: exactly one winning outcome (rules_version 1).
+pub const MARKET_TYPE_WINNER: u8 = SUPPORTED_MARKET_TYPE;
+pub const RULES_VERSION_WINNER: u16 = SUPPORTED_RULES_VERSION;
+/// Podium market: the top `PODIUM_SIZE` outcomes win and share one prize pool (rules_version 2).
+pub const MARKET_TYPE_PODIUM: u8 = 1;
+pub const RULES_VERSION_PODIUM: u16 = 2;
+pub const PODIUM_SIZE: u8 = 3;
+
+/// rules_version paired with each supported market_type; `None` = unsupported type.
+pub const fn rules_version_for(market_type: u8) -> Option<u16> {
+    match market_type {
+        MARKET_TYPE_WINNER => Some(RULES_VERSION_WINNER),
+        MARKET_TYPE_PODIUM => Some(RULES_VERSION_PODIUM),
+        _ => None,
+    }
+}
@@ -49,4 +49,50 @@ pub enum PitStopError {
     Overflow,
     Underflow,
     DivisionByZero,
+
+    FeeTooHigh,
+
+    NoPendingAuthority,
+
+    PositionNotClosable,
+
+    MarketNotClosable,
+    VaultNotClosed,
+
+    UnsupportedMintExtension,
+
+    CollateralMintNotAllowed,
+    CollateralMintAlreadyAllowed,
+    CollateralAllowlistFull,
+
+    InvalidResolutionPayload,
+    PayloadEventMismatch,
+    PayloadHashMismatch,
+
+    InvalidCommittee,
+    NotCommitteeMember,
+    QuorumNotReached,
+
+    InvalidDisputeWindow,
+    DisputeWindowActive,
+    DisputeWindowClosed,
+
+    InvalidResolutionDeadline,
+    ResolutionDeadlineNotReached,
+
+    InvalidClaimBatch,
+
+    ReductionExceedsPosition,
+
+    SameOutcomeSwitch,
+
+    SlippageExceeded,
+
+    MarketIdRetired,
+
+    VotesPayerMismatch,
+
+    PayloadOutcomeMismatch,
+
+    InvalidPayoutDestination,
 }
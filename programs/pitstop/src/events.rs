@@ -8,6 +8,64 @@ pub struct ConfigInitialized {
     pub timestamp: i64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigUpdated {
+    pub authority: String,
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+    pub old_max_total_pool_per_market: u64,
+    pub new_max_total_pool_per_market: u64,
+    pub old_max_bet_per_user_per_market: u64,
+    pub new_max_bet_per_user_per_market: u64,
+    pub old_claim_window_secs: i64,
+    pub new_claim_window_secs: i64,
+    pub old_dispute_window_secs: i64,
+    pub new_dispute_window_secs: i64,
+    pub old_resolution_deadline_secs: i64,
+    pub new_resolution_deadline_secs: i64,
+    pub old_early_exit_fee_bps: u16,
+    pub new_early_exit_fee_bps: u16,
+    pub old_payout_keeper: Option<String>,
+    pub new_payout_keeper: Option<String>,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolPauseChanged {
+    pub authority: String,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OracleRotated {
+    pub authority: String,
+    pub old_oracle: String,
+    pub new_oracle: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorityTransferProposed {
+    pub authority: String,
+    pub pending_authority: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorityTransferred {
+    pub old_authority: String,
+    pub new_authority: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorityTransferCancelled {
+    pub authority: String,
+    pub cancelled_authority: String,
+    pub timestamp: i64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MarketCreated {
     pub market: String,
@@ -17,6 +75,10 @@ pub struct MarketCreated {
     pub max_outcomes: u8,
     pub market_type: u8,
     pub rules_version: u16,
+    pub collateral_mint: String,
+    /// Cap overrides set at creation; `None` = the Config cap applies.
+    pub max_total_pool_per_market: Option<u64>,
+    pub max_bet_per_user_per_market: Option<u64>,
     pub timestamp: i64,
 }
 
@@ -45,9 +107,41 @@ pub struct BetPlaced {
     pub timestamp: i64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BetReduced {
+    pub market: String,
+    pub user: String,
+    pub outcome_id: u8,
+    /// Stake removed from the position; `refund + fee`.
+    pub amount: u64,
+    pub fee: u64,
+    pub refund: u64,
+    pub position_amount: u64,
+    pub market_total_pool: u64,
+    pub outcome_pool_amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutcomeSwitched {
+    pub market: String,
+    pub user: String,
+    pub from_outcome_id: u8,
+    pub to_outcome_id: u8,
+    /// Stake moved; no tokens leave the vault.
+    pub amount: u64,
+    pub from_position_amount: u64,
+    pub to_position_amount: u64,
+    pub from_outcome_pool_amount: u64,
+    pub to_outcome_pool_amount: u64,
+    pub timestamp: i64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MarketLocked {
     pub market: String,
+    pub locked_by: String,
+    pub lock_timestamp: i64,
     pub timestamp: i64,
 }
 
@@ -55,8 +149,13 @@ pub struct MarketLocked {
 pub struct MarketResolved {
     pub market: String,
     pub winning_outcome: u8,
+    pub winning_outcomes: Vec<u8>,
     pub payload_hash: [u8; 32],
+    /// True when the oracle submitted the result payload and it was hashed on-chain.
+    pub payload_verified: bool,
     pub resolution_timestamp: i64,
+    /// Claims open after this time; 0 when the result is final immediately.
+    pub dispute_deadline: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -66,6 +165,39 @@ pub struct MarketVoided {
     pub resolution_timestamp: i64,
 }
 
+/// A Locked market voided by anyone after the oracle missed its resolution deadline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleMarketVoided {
+    pub market: String,
+    pub caller: String,
+    pub resolution_deadline: i64,
+    pub timestamp: i64,
+}
+
+/// A pending result replaced (or voided) by the authority inside its dispute window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionOverturned {
+    pub market: String,
+    pub authority: String,
+    pub void: bool,
+    pub old_winning_outcomes: Vec<u8>,
+    pub winning_outcomes: Vec<u8>,
+    pub payload_hash: [u8; 32],
+    /// True when a result payload for the new winner set was hashed on-chain.
+    pub payload_verified: bool,
+    pub timestamp: i64,
+}
+
+/// A pending result that outlived its dispute window, recorded as Resolved by the
+/// first payout instruction after the deadline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionFinalized {
+    pub market: String,
+    pub winning_outcomes: Vec<u8>,
+    pub dispute_deadline: i64,
+    pub timestamp: i64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Claimed {
     pub market: String,
@@ -73,6 +205,8 @@ pub struct Claimed {
     pub outcome_id: u8,
     pub payout: u64,
     pub claimed_at: i64,
+    /// Signer that triggered the claim: `user`, or the distribute_payouts caller.
+    pub initiator: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -88,3 +222,61 @@ pub struct MarketCancelled {
     pub market: String,
     pub timestamp: i64,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionClosed {
+    pub market: String,
+    pub user: String,
+    pub outcome_id: u8,
+    pub claimed: bool,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketRentReclaimed {
+    pub market: String,
+    pub pools_closed: u8,
+    pub total_pools_closed: u8,
+    pub market_closed: bool,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollateralMintAdded {
+    pub authority: String,
+    pub mint: String,
+    pub treasury: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollateralMintRemoved {
+    pub authority: String,
+    pub mint: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OracleCommitteeSet {
+    pub authority: String,
+    pub old_oracle: String,
+    /// Committee PDA, now `config.oracle`.
+    pub committee: String,
+    pub members: Vec<String>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionVoteSubmitted {
+    pub market: String,
+    pub member: String,
+    pub void: bool,
+    /// Empty for a void vote.
+    pub winning_outcomes: Vec<u8>,
+    pub payload_hash: [u8; 32],
+    /// Current member votes identical to this one, including it.
+    pub matching_votes: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
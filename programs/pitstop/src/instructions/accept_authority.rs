@@ -0,0 +1,118 @@
+//! accept_authority Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/accept_authority.md
+//!
+//! Second half of the two-step authority transfer. The proposed key must sign,
+//! so a mistyped proposal can never take control of the protocol.
+
+use crate::{error::PitStopError, events::AuthorityTransferred, state::Config};
+
+#[derive(Debug, Clone)]
+pub struct AcceptAuthorityInput {
+    pub new_authority: String,
+    pub now_ts: i64,
+    pub config_state: Config,
+}
+
+fn validate_accept_authority_preconditions(
+    input: &AcceptAuthorityInput,
+) -> Result<(), PitStopError> {
+    // ACA-REJ-001: a transfer must be pending.
+    let pending = input
+        .config_state
+        .pending_authority
+        .as_ref()
+        .ok_or(PitStopError::NoPendingAuthority)?;
+
+    // ACA-REJ-002: only the proposed key can accept.
+    if input.new_authority != *pending {
+        return Err(PitStopError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - config.authority = pending_authority
+/// - config.pending_authority = None
+/// - emit AuthorityTransferred
+pub fn accept_authority(
+    input: AcceptAuthorityInput,
+) -> Result<(Config, AuthorityTransferred), PitStopError> {
+    validate_accept_authority_preconditions(&input)?;
+
+    let mut config = input.config_state;
+    config.pending_authority = None;
+    let old_authority = std::mem::replace(&mut config.authority, input.new_authority.clone());
+
+    let evt = AuthorityTransferred {
+        old_authority,
+        new_authority: input.new_authority,
+        timestamp: input.now_ts,
+    };
+
+    Ok((config, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
+
+    fn base_config() -> Config {
+        Config {
+            authority: "AuthA".to_string(),
+            oracle: "OracleA".to_string(),
+            usdc_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            treasury_authority: "TreasuryOwnerA".to_string(),
+            fee_bps: 0,
+            paused: false,
+            max_total_pool_per_market: 1_000_000,
+            max_bet_per_user_per_market: 100_000,
+            claim_window_secs: 3600,
+            dispute_window_secs: 0,
+            resolution_deadline_secs: 0,
+            early_exit_fee_bps: 0,
+            token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+            pending_authority: Some("AuthB".to_string()),
+            payout_keeper: None,
+        }
+    }
+
+    fn base_input() -> AcceptAuthorityInput {
+        AcceptAuthorityInput {
+            new_authority: "AuthB".to_string(),
+            now_ts: 1_800_000_000,
+            config_state: base_config(),
+        }
+    }
+
+    #[test]
+    fn aca_hp_001_moves_authority_and_clears_pending() {
+        let (cfg, evt) = accept_authority(base_input()).expect("accept should pass");
+        assert_eq!(cfg.authority, "AuthB");
+        assert_eq!(cfg.pending_authority, None);
+        assert_eq!(cfg.oracle, "OracleA");
+
+        assert_eq!(evt.old_authority, "AuthA");
+        assert_eq!(evt.new_authority, "AuthB");
+        assert_eq!(evt.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn aca_rej_001_nothing_pending() {
+        let mut bad = base_input();
+        bad.config_state.pending_authority = None;
+        assert_eq!(accept_authority(bad).unwrap_err(), PitStopError::NoPendingAuthority);
+    }
+
+    #[test]
+    fn aca_rej_002_only_proposed_key_can_accept() {
+        // Neither a third party nor the outgoing authority can complete the transfer.
+        for signer in ["Other", "AuthA"] {
+            let mut bad = base_input();
+            bad.new_authority = signer.to_string();
+            assert_eq!(accept_authority(bad).unwrap_err(), PitStopError::Unauthorized);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! add_collateral_mint Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/add_collateral_mint.md
+//!
+//! Allowlists a collateral mint (and the treasury that receives its sweeps) for new
+//! markets. `config.usdc_mint` is always usable and is never listed. Existing markets
+//! are unaffected: they snapshot their mint and treasury at create_market.
+
+use crate::{
+    constants::{MAX_COLLATERAL_MINTS, USDC_DECIMALS},
+    error::PitStopError,
+    events::CollateralMintAdded,
+    state::{CollateralAllowlist, CollateralMint, Config},
+};
+
+#[derive(Debug, Clone)]
+pub struct AddCollateralMintInput {
+    pub authority: String,
+    pub mint: String,
+    pub mint_decimals: u8,
+    pub treasury: String,
+    pub treasury_mint: String,
+    pub treasury_owner: String,
+    pub now_ts: i64,
+    pub config_state: Config,
+    pub allowlist_state: CollateralAllowlist,
+}
+
+fn validate_add_collateral_mint_preconditions(
+    input: &AddCollateralMintInput,
+) -> Result<(), PitStopError> {
+    // ACM-REJ-001: only config authority manages the allowlist.
+    if input.authority != input.config_state.authority {
+        return Err(PitStopError::Unauthorized);
+    }
+    // ACM-REJ-002: caps are in base units shared by every mint, so decimals must match USDC.
+    if input.mint_decimals != USDC_DECIMALS {
+        return Err(PitStopError::InvalidMintDecimals);
+    }
+    // ACM-REJ-003
+    if input.treasury_mint != input.mint {
+        return Err(PitStopError::InvalidTreasuryMint);
+    }
+    // ACM-REJ-004: every treasury is owned by the one config.treasury_authority.
+    if input.treasury_owner != input.config_state.treasury_authority {
+        return Err(PitStopError::InvalidTreasuryOwner);
+    }
+    // ACM-REJ-005: the config mint is implicitly allowed; no mint is listed twice.
+    if input.mint == input.config_state.usdc_mint
+        || input.allowlist_state.entries.iter().any(|entry| entry.mint == input.mint)
+    {
+        return Err(PitStopError::CollateralMintAlreadyAllowed);
+    }
+    // ACM-REJ-006
+    if input.allowlist_state.entries.len() >= usize::from(MAX_COLLATERAL_MINTS) {
+        return Err(PitStopError::CollateralAllowlistFull);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - allowlist.entries += { mint, treasury }
+/// - emit CollateralMintAdded
+pub fn add_collateral_mint(
+    input: AddCollateralMintInput,
+) -> Result<(CollateralAllowlist, CollateralMintAdded), PitStopError> {
+    validate_add_collateral_mint_preconditions(&input)?;
+
+    let mut allowlist = input.allowlist_state;
+    allowlist.entries.push(CollateralMint {
+        mint: input.mint.clone(),
+        treasury: input.treasury.clone(),
+    });
+
+    let evt = CollateralMintAdded {
+        authority: input.authority,
+        mint: input.mint,
+        treasury: input.treasury,
+        timestamp: input.now_ts,
+    };
+
+    Ok((allowlist, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
+
+    fn base_config() -> Config {
+        Config {
+            authority: "AuthA".to_string(),
+            oracle: "OracleA".to_string(),
+            usdc_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            treasury_authority: "TreasuryOwnerA".to_string(),
+            fee_bps: 0,
+            paused: false,
+            max_total_pool_per_market: 1_000_000,
+            max_bet_per_user_per_market: 100_000,
+            claim_window_secs: 3600,
+            dispute_window_secs: 0,
+            resolution_deadline_secs: 0,
+            early_exit_fee_bps: 0,
+            token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+            pending_authority: None,
+            payout_keeper: None,
+        }
+    }
+
+    fn base_input() -> AddCollateralMintInput {
+        AddCollateralMintInput {
+            authority: "AuthA".to_string(),
+            mint: "PyusdMint".to_string(),
+            mint_decimals: USDC_DECIMALS,
+            treasury: "PyusdTreasury".to_string(),
+            treasury_mint: "PyusdMint".to_string(),
+            treasury_owner: "TreasuryOwnerA".to_string(),
+            now_ts: 1_800_000_000,
+            config_state: base_config(),
+            allowlist_state: CollateralAllowlist::default(),
+        }
+    }
+
+    #[test]
+    fn acm_hp_001_appends_entry_and_emits_event() {
+        let (allowlist, evt) = add_collateral_mint(base_input()).expect("add should pass");
+        assert_eq!(
+            allowlist.entries,
+            vec![CollateralMint {
+                mint: "PyusdMint".to_string(),
+                treasury: "PyusdTreasury".to_string(),
+            }]
+        );
+
+        assert_eq!(evt.authority, "AuthA");
+        assert_eq!(evt.mint, "PyusdMint");
+        assert_eq!(evt.treasury, "PyusdTreasury");
+        assert_eq!(evt.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn acm_rej_001_to_006_error_mapping() {
+        let mut bad = base_input();
+        bad.authority = "Other".to_string();
+        assert_eq!(add_collateral_mint(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        let mut bad = base_input();
+        bad.mint_decimals = 9;
+        assert_eq!(add_collateral_mint(bad).unwrap_err(), PitStopError::InvalidMintDecimals);
+
+        let mut bad = base_input();
+        bad.treasury_mint = "MintA".to_string();
+        assert_eq!(add_collateral_mint(bad).unwrap_err(), PitStopError::InvalidTreasuryMint);
+
+        let mut bad = base_input();
+        bad.treasury_owner = "Other".to_string();
+        assert_eq!(add_collateral_mint(bad).unwrap_err(), PitStopError::InvalidTreasuryOwner);
+
+        let mut bad = base_input();
+        bad.mint = "MintA".to_string();
+        bad.treasury_mint = "MintA".to_string();
+        assert_eq!(
+            add_collateral_mint(bad).unwrap_err(),
+            PitStopError::CollateralMintAlreadyAllowed
+        );
+
+        let (listed, _) = add_collateral_mint(base_input()).unwrap();
+        let mut bad = base_input();
+        bad.allowlist_state = listed;
+        assert_eq!(
+            add_collateral_mint(bad).unwrap_err(),
+            PitStopError::CollateralMintAlreadyAllowed
+        );
+
+        let mut bad = base_input();
+        bad.allowlist_state.entries = (0..MAX_COLLATERAL_MINTS)
+            .map(|i| CollateralMint {
+                mint: format!("Mint{i}"),
+                treasury: format!("Treasury{i}"),
+            })
+            .collect();
+        assert_eq!(add_collateral_mint(bad).unwrap_err(), PitStopError::CollateralAllowlistFull);
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
 pub struct AddOutcomeInput {
     pub authority: String,
     pub config_authority: String,
+    pub config_paused: bool,
     pub market: String,
     pub market_status: MarketStatus,
     pub market_outcome_count: u8,
@@ -23,6 +24,10 @@ fn validate_add_outcome_preconditions(input: &AddOutcomeInput) -> Result<(), Pit
     if input.authority != input.config_authority {
         return Err(PitStopError::Unauthorized);
     }
+    // ADO-REJ-006: seeding halts while the protocol is paused.
+    if input.config_paused {
+        return Err(PitStopError::ProtocolPaused);
+    }
     // ADO-REJ-002: market must still be in Seeding lifecycle phase.
     if input.market_status != MarketStatus::Seeding {
         return Err(PitStopError::MarketNotSeeding);
@@ -97,6 +102,17 @@ mod tests {
             vault: "VaultAtaA".to_string(),
             market_type: 0,
             rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
         }
     }
 
@@ -104,6 +120,7 @@ mod tests {
         AddOutcomeInput {
             authority: "AuthA".to_string(),
             config_authority: "AuthA".to_string(),
+            config_paused: false,
             market: "MarketPdaA".to_string(),
             market_status: MarketStatus::Seeding,
             market_outcome_count: 1,
@@ -138,6 +155,10 @@ mod tests {
         bad.market_status = MarketStatus::Open;
         assert_eq!(add_outcome(bad).unwrap_err(), PitStopError::MarketNotSeeding);
 
+        let mut bad = base_input();
+        bad.config_paused = true;
+        assert_eq!(add_outcome(bad).unwrap_err(), PitStopError::ProtocolPaused);
+
         let mut bad = base_input();
         bad.outcome_id = 100;
         assert_eq!(add_outcome(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
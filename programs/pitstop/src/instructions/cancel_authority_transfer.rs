@@ -0,0 +1,113 @@
+//! cancel_authority_transfer Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/cancel_authority_transfer.md
+//!
+//! Lets the current authority withdraw a pending proposal (e.g. a mistyped key)
+//! before it is accepted.
+
+use crate::{error::PitStopError, events::AuthorityTransferCancelled, state::Config};
+
+#[derive(Debug, Clone)]
+pub struct CancelAuthorityTransferInput {
+    pub authority: String,
+    pub now_ts: i64,
+    pub config_state: Config,
+}
+
+fn validate_cancel_authority_transfer_preconditions(
+    input: &CancelAuthorityTransferInput,
+) -> Result<(), PitStopError> {
+    // CAT-REJ-001: only config authority can cancel.
+    if input.authority != input.config_state.authority {
+        return Err(PitStopError::Unauthorized);
+    }
+    // CAT-REJ-002: a transfer must be pending.
+    if input.config_state.pending_authority.is_none() {
+        return Err(PitStopError::NoPendingAuthority);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - config.pending_authority = None
+/// - emit AuthorityTransferCancelled
+pub fn cancel_authority_transfer(
+    input: CancelAuthorityTransferInput,
+) -> Result<(Config, AuthorityTransferCancelled), PitStopError> {
+    validate_cancel_authority_transfer_preconditions(&input)?;
+
+    let mut config = input.config_state;
+    let cancelled = config
+        .pending_authority
+        .take()
+        .ok_or(PitStopError::NoPendingAuthority)?;
+
+    let evt = AuthorityTransferCancelled {
+        authority: input.authority,
+        cancelled_authority: cancelled,
+        timestamp: input.now_ts,
+    };
+
+    Ok((config, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
+
+    fn base_config() -> Config {
+        Config {
+            authority: "AuthA".to_string(),
+            oracle: "OracleA".to_string(),
+            usdc_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            treasury_authority: "TreasuryOwnerA".to_string(),
+            fee_bps: 0,
+            paused: false,
+            max_total_pool_per_market: 1_000_000,
+            max_bet_per_user_per_market: 100_000,
+            claim_window_secs: 3600,
+            dispute_window_secs: 0,
+            resolution_deadline_secs: 0,
+            early_exit_fee_bps: 0,
+            token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+            pending_authority: Some("Typo".to_string()),
+            payout_keeper: None,
+        }
+    }
+
+    fn base_input() -> CancelAuthorityTransferInput {
+        CancelAuthorityTransferInput {
+            authority: "AuthA".to_string(),
+            now_ts: 1_800_000_000,
+            config_state: base_config(),
+        }
+    }
+
+    #[test]
+    fn cat_hp_001_clears_pending_and_keeps_authority() {
+        let (cfg, evt) = cancel_authority_transfer(base_input()).expect("cancel should pass");
+        assert_eq!(cfg.authority, "AuthA");
+        assert_eq!(cfg.pending_authority, None);
+
+        assert_eq!(evt.authority, "AuthA");
+        assert_eq!(evt.cancelled_authority, "Typo");
+        assert_eq!(evt.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn cat_rej_001_002_error_mapping() {
+        // The pending key itself cannot cancel; it simply never accepts.
+        let mut bad = base_input();
+        bad.authority = "Typo".to_string();
+        assert_eq!(cancel_authority_transfer(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        let mut bad = base_input();
+        bad.config_state.pending_authority = None;
+        assert_eq!(
+            cancel_authority_transfer(bad).unwrap_err(),
+            PitStopError::NoPendingAuthority
+        );
+    }
+}
@@ -94,6 +94,17 @@ mod tests {
             vault: "VaultA".to_string(),
             market_type: 0,
             rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
         }
     }
 
@@ -0,0 +1,284 @@
+//! claim_many Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/claim_many.md
+//!
+//! Batches claim_resolved across several positions owned by one user, possibly in
+//! different markets. Every entry runs the full claim_resolved checks against the
+//! balances left by the entries before it; payouts are then summed per vault so the
+//! handler makes one transfer per vault. The batch is all-or-nothing: entries are
+//! checked in order and the first failing entry's error aborts the whole call.
+
+use crate::{
+    constants::MAX_CLAIM_BATCH,
+    error::PitStopError,
+    events::Claimed,
+    instructions::claim_resolved::{claim_resolved, ClaimResolvedInput},
+    state::Position,
+};
+
+#[derive(Debug, Clone)]
+pub struct ClaimManyEntry {
+    /// Position account key; listing the same position twice fails as AlreadyClaimed.
+    pub position: String,
+    /// Market vault the payout is debited from; entries may share a vault.
+    pub vault: String,
+    /// Same input claim_resolved takes for this position on its own.
+    pub claim: ClaimResolvedInput,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClaimManyInput {
+    pub user: String,
+    pub entries: Vec<ClaimManyEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClaimManyOutput {
+    /// Updated positions, in entry order.
+    pub positions: Vec<Position>,
+    /// One Claimed event per entry, in entry order.
+    pub events: Vec<Claimed>,
+    /// Total payout per vault in first-seen order; one transfer each, none when 0.
+    pub vault_payouts: Vec<(String, u64)>,
+    pub user_usdc_amount: u64,
+}
+
+fn validate_claim_many_preconditions(input: &ClaimManyInput) -> Result<(), PitStopError> {
+    // CLM-REJ-001: non-empty and bounded so the batch fits one transaction.
+    if input.entries.is_empty() || input.entries.len() > MAX_CLAIM_BATCH {
+        return Err(PitStopError::InvalidClaimBatch);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - for each entry in order: claim_resolved effects on its position
+/// - vault balances and the user balance carry over between entries
+/// - emit Claimed per entry
+pub fn claim_many(input: ClaimManyInput) -> Result<ClaimManyOutput, PitStopError> {
+    validate_claim_many_preconditions(&input)?;
+
+    let mut user_usdc_amount = input.entries[0].claim.user_usdc_amount;
+    // (vault, running balance, total paid out)
+    let mut vaults: Vec<(String, u64, u64)> = Vec::new();
+    let mut seen: Vec<String> = Vec::new();
+    let mut positions = Vec::with_capacity(input.entries.len());
+    let mut events = Vec::with_capacity(input.entries.len());
+
+    for entry in input.entries {
+        // CLM-REJ-002: every position must belong to the signer.
+        if entry.claim.user != input.user || entry.claim.position_state.user != input.user {
+            return Err(PitStopError::Unauthorized);
+        }
+        // CLM-REJ-003: the position must belong to the market it is grouped with.
+        if entry.claim.position_state.market != entry.claim.market {
+            return Err(PitStopError::OutcomeMismatch);
+        }
+
+        let slot = match vaults.iter().position(|(v, _, _)| *v == entry.vault) {
+            Some(i) => i,
+            None => {
+                vaults.push((entry.vault.clone(), entry.claim.vault_amount, 0));
+                vaults.len() - 1
+            }
+        };
+
+        let mut claim = entry.claim;
+        claim.vault_amount = vaults[slot].1;
+        claim.user_usdc_amount = user_usdc_amount;
+        // CLM-REJ-004: a position repeated in the batch is already claimed by then.
+        claim.position_claimed |= seen.contains(&entry.position);
+
+        let (position, vault_amount, user_amount, evt) = claim_resolved(claim)?;
+        vaults[slot].2 = vaults[slot]
+            .2
+            .checked_add(evt.payout)
+            .ok_or(PitStopError::Overflow)?;
+        vaults[slot].1 = vault_amount;
+        user_usdc_amount = user_amount;
+
+        seen.push(entry.position);
+        positions.push(position);
+        events.push(evt);
+    }
+
+    Ok(ClaimManyOutput {
+        positions,
+        events,
+        vault_payouts: vaults.into_iter().map(|(v, _, paid)| (v, paid)).collect(),
+        user_usdc_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Market, MarketStatus, OutcomePool};
+
+    fn base_market() -> Market {
+        Market {
+            market_id: [1u8; 32],
+            event_id: [2u8; 32],
+            lock_timestamp: 1_800_000_000,
+            outcome_count: 3,
+            max_outcomes: 3,
+            total_pool: 1_000,
+            status: MarketStatus::Resolved,
+            resolved_outcome: Some(1),
+            resolution_payload_hash: [9u8; 32],
+            resolution_timestamp: 1_800_000_100,
+            vault: "VaultA".to_string(),
+            market_type: 0,
+            rules_version: 1,
+            fee_bps: 200,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
+        }
+    }
+
+    /// Position `name` in `market` on `outcome_id`; outcome 1 wins with a pool of 250.
+    fn entry(name: &str, market: &str, vault: &str, outcome_id: u8, amount: u64) -> ClaimManyEntry {
+        let pool_amount = if outcome_id == 1 { 250 } else { 750 };
+        ClaimManyEntry {
+            position: name.to_string(),
+            vault: vault.to_string(),
+            claim: ClaimResolvedInput {
+                market: market.to_string(),
+                user: "UserA".to_string(),
+                market_status: MarketStatus::Resolved,
+                now_ts: 1_800_000_101,
+                resolution_timestamp: 1_800_000_100,
+                dispute_deadline: 0,
+                claim_window_secs: 600,
+                fee_bps: 200,
+                resolved_outcome: Some(1),
+                outcome_id,
+                position_claimed: false,
+                position_amount: amount,
+                outcome_pool_exists: true,
+                outcome_pool_market: market.to_string(),
+                outcome_pool_outcome_id: outcome_id,
+                outcome_pool_amount: pool_amount,
+                vault_amount: 1_000,
+                user_usdc_amount: 10,
+                market_state: base_market(),
+                outcome_pool_state: OutcomePool {
+                    market: market.to_string(),
+                    outcome_id,
+                    pool_amount,
+                },
+                position_state: Position {
+                    market: market.to_string(),
+                    user: "UserA".to_string(),
+                    outcome_id,
+                    amount,
+                    claimed: false,
+                    payout: 0,
+                },
+            },
+        }
+    }
+
+    fn input(entries: Vec<ClaimManyEntry>) -> ClaimManyInput {
+        ClaimManyInput {
+            user: "UserA".to_string(),
+            entries,
+        }
+    }
+
+    #[test]
+    fn clm_hp_001_claims_across_markets_with_one_payout_per_vault() {
+        // prize_pool = 980; 100/250 -> 392, 150/250 -> 588, loser -> 0.
+        let out = claim_many(input(vec![
+            entry("PosA", "MarketA", "VaultA", 1, 100),
+            entry("PosB", "MarketB", "VaultB", 1, 150),
+            entry("PosC", "MarketA", "VaultA", 0, 50),
+        ]))
+        .expect("batch should pass");
+
+        assert!(out.positions.iter().all(|p| p.claimed));
+        let payouts: Vec<u64> = out.positions.iter().map(|p| p.payout).collect();
+        assert_eq!(payouts, vec![392, 588, 0]);
+        assert_eq!(out.events.len(), 3);
+        assert_eq!(out.events[1].market, "MarketB");
+        assert_eq!(out.events[2].payout, 0);
+        assert!(out.events.iter().all(|e| e.initiator == "UserA"));
+        assert_eq!(
+            out.vault_payouts,
+            vec![("VaultA".to_string(), 392), ("VaultB".to_string(), 588)]
+        );
+        assert_eq!(out.user_usdc_amount, 10 + 392 + 588);
+    }
+
+    #[test]
+    fn clm_hp_002_shared_vault_balance_carries_between_entries() {
+        let mut a = entry("PosA", "MarketA", "VaultA", 1, 100);
+        let mut b = entry("PosB", "MarketA", "VaultA", 1, 150);
+        a.claim.vault_amount = 980;
+        b.claim.vault_amount = 980;
+        let out = claim_many(input(vec![a, b])).expect("vault covers both payouts");
+        assert_eq!(out.vault_payouts, vec![("VaultA".to_string(), 980)]);
+
+        // A vault short by one unit fails on the entry that overdraws it.
+        let mut a = entry("PosA", "MarketA", "VaultA", 1, 100);
+        let mut b = entry("PosB", "MarketA", "VaultA", 1, 150);
+        a.claim.vault_amount = 979;
+        b.claim.vault_amount = 979;
+        assert_eq!(claim_many(input(vec![a, b])).unwrap_err(), PitStopError::Underflow);
+    }
+
+    #[test]
+    fn clm_rej_001_empty_or_oversized_batch() {
+        assert_eq!(claim_many(input(Vec::new())).unwrap_err(), PitStopError::InvalidClaimBatch);
+
+        let entries = (0..=MAX_CLAIM_BATCH)
+            .map(|i| entry(&format!("Pos{i}"), "MarketA", "VaultA", 0, 1))
+            .collect();
+        assert_eq!(claim_many(input(entries)).unwrap_err(), PitStopError::InvalidClaimBatch);
+    }
+
+    #[test]
+    fn clm_rej_002_to_004_first_failing_entry_aborts_batch() {
+        let mut other_user = entry("PosB", "MarketA", "VaultA", 1, 150);
+        other_user.claim.position_state.user = "UserB".to_string();
+        let err = claim_many(input(vec![entry("PosA", "MarketA", "VaultA", 1, 100), other_user]))
+            .unwrap_err();
+        assert_eq!(err, PitStopError::Unauthorized);
+
+        let mut wrong_market = entry("PosB", "MarketA", "VaultA", 1, 150);
+        wrong_market.claim.position_state.market = "MarketB".to_string();
+        let err = claim_many(input(vec![wrong_market])).unwrap_err();
+        assert_eq!(err, PitStopError::OutcomeMismatch);
+
+        let err = claim_many(input(vec![
+            entry("PosA", "MarketA", "VaultA", 1, 100),
+            entry("PosA", "MarketA", "VaultA", 1, 100),
+        ]))
+        .unwrap_err();
+        assert_eq!(err, PitStopError::AlreadyClaimed);
+    }
+
+    #[test]
+    fn clm_ord_001_entries_fail_with_claim_resolved_errors_in_order() {
+        // Entry 2 fails by status before entry 3's claim window is considered.
+        let mut unresolved = entry("PosB", "MarketB", "VaultB", 1, 150);
+        unresolved.claim.market_status = MarketStatus::Locked;
+        let mut expired = entry("PosC", "MarketC", "VaultC", 1, 150);
+        expired.claim.now_ts = i64::MAX;
+        let err = claim_many(input(vec![
+            entry("PosA", "MarketA", "VaultA", 1, 100),
+            unresolved,
+            expired,
+        ]))
+        .unwrap_err();
+        assert_eq!(err, PitStopError::MarketNotResolved);
+    }
+}
@@ -1,12 +1,14 @@
 /// claim_resolved Rust parity model for LOCKED spec semantics.
 ///
-/// Spec: SPEC_INSTRUCTIONS/claim_resolved.md (LOCKED v1.0.3)
+/// Spec: SPEC_INSTRUCTIONS/claim_resolved.md (LOCKED v1.0.5)
 ///
 /// Deterministic model used by Rust unit tests and spec-gate parity checks.
 
 use crate::{
+    constants::MARKET_TYPE_PODIUM,
     error::PitStopError,
     events::Claimed,
+    math,
     state::{Market, MarketStatus, OutcomePool, Position},
 };
 
@@ -19,6 +21,7 @@ pub struct ClaimResolvedInput {
     pub market_status: MarketStatus,
     pub now_ts: i64,
     pub resolution_timestamp: i64,
+    pub dispute_deadline: i64,
     pub claim_window_secs: i64,
     pub fee_bps: u16,
     pub resolved_outcome: Option<u8>,
@@ -46,10 +49,19 @@ pub struct ClaimResolvedInput {
 
 fn validate_claim_resolved_preconditions(input: &ClaimResolvedInput) -> Result<(), PitStopError> {
     // CLR-REJ-001 / CLR-ORD-001: status gate first (Swept fails here too).
-    if input.market_status != MarketStatus::Resolved {
+    if input.market_status != MarketStatus::Resolved
+        && input.market_status != MarketStatus::PendingResolution
+    {
         return Err(PitStopError::MarketNotResolved);
     }
 
+    // CLR-REJ-005: a pending result becomes claimable once its dispute window has ended.
+    if input.market_status == MarketStatus::PendingResolution
+        && input.now_ts <= input.dispute_deadline
+    {
+        return Err(PitStopError::DisputeWindowActive);
+    }
+
     // CLR-REJ-002: no double claim.
     if input.position_claimed {
         return Err(PitStopError::AlreadyClaimed);
@@ -64,10 +76,11 @@ fn validate_claim_resolved_preconditions(input: &ClaimResolvedInput) -> Result<(
     }
 
     // CLR-REJ-004: now must be within claim window (inclusive end).
-    let claim_window_end = input
-        .resolution_timestamp
-        .checked_add(input.claim_window_secs)
-        .ok_or(PitStopError::Overflow)?;
+    let claim_window_end = claim_window_end(
+        input.resolution_timestamp,
+        input.dispute_deadline,
+        input.claim_window_secs,
+    )?;
     if input.now_ts > claim_window_end {
         return Err(PitStopError::ClaimWindowExpired);
     }
@@ -75,30 +88,33 @@ fn validate_claim_resolved_preconditions(input: &ClaimResolvedInput) -> Result<(
     Ok(())
 }
 
-fn compute_winner_payout(
-    total_pool: u64,
-    winner_pool: u64,
-    position_amount: u64,
-    fee_bps: u16,
-) -> Result<u64, PitStopError> {
-    // fee = floor(total_pool * fee_bps / 10_000)
-    let fee = total_pool
-        .checked_mul(fee_bps as u64)
-        .ok_or(PitStopError::Overflow)?
-        / 10_000u64;
-
-    let prize_pool = total_pool.checked_sub(fee).ok_or(PitStopError::Underflow)?;
-
-    if winner_pool == 0 {
-        return Err(PitStopError::DivisionByZero);
-    }
-
-    // payout = floor(position_amount * prize_pool / winner_pool)
-    let numerator = position_amount
-        .checked_mul(prize_pool)
-        .ok_or(PitStopError::Overflow)?;
+/// Inclusive end of the claim window. The window opens when the result is final: at
+/// resolution, or at `dispute_deadline` when a dispute window applied, so disputes delay
+/// claims instead of shortening the time users have to claim. Shared with sweep_remaining.
+pub(crate) fn claim_window_end(
+    resolution_timestamp: i64,
+    dispute_deadline: i64,
+    claim_window_secs: i64,
+) -> Result<i64, PitStopError> {
+    resolution_timestamp
+        .max(dispute_deadline)
+        .checked_add(claim_window_secs)
+        .ok_or(PitStopError::Overflow)
+}
 
-    Ok(numerator / winner_pool)
+/// Winner markets pay against the resolved outcome's pool. Podium markets pay every
+/// outcome in `winning_outcomes` against their combined `winner_pool` (snapshotted
+/// at resolve), so all podium positions share one prize pool pro rata.
+fn winning_pool(input: &ClaimResolvedInput) -> Option<u64> {
+    if input.market_state.market_type == MARKET_TYPE_PODIUM {
+        input
+            .market_state
+            .winning_outcomes
+            .contains(&input.outcome_id)
+            .then_some(input.market_state.winner_pool)
+    } else {
+        (input.resolved_outcome == Some(input.outcome_id)).then_some(input.outcome_pool_amount)
+    }
 }
 
 /// Effects:
@@ -112,27 +128,18 @@ pub fn claim_resolved(
 ) -> Result<(Position, u64, u64, Claimed), PitStopError> {
     validate_claim_resolved_preconditions(&input)?;
 
-    let is_winner = input.resolved_outcome == Some(input.outcome_id);
-
-    let payout = if is_winner {
-        compute_winner_payout(
+    let payout = match winning_pool(&input) {
+        Some(winner_pool) => math::resolved_payout(
             input.market_state.total_pool,
-            input.outcome_pool_amount,
+            winner_pool,
             input.position_amount,
             input.fee_bps,
-        )?
-    } else {
-        0
+        )?,
+        None => 0,
     };
 
-    let vault_amount = input
-        .vault_amount
-        .checked_sub(payout)
-        .ok_or(PitStopError::Underflow)?;
-    let user_usdc_amount = input
-        .user_usdc_amount
-        .checked_add(payout)
-        .ok_or(PitStopError::Overflow)?;
+    let vault_amount = math::debit(input.vault_amount, payout)?;
+    let user_usdc_amount = math::credit(input.user_usdc_amount, payout)?;
 
     let mut position = input.position_state;
     position.claimed = true;
@@ -140,10 +147,11 @@ pub fn claim_resolved(
 
     let evt = Claimed {
         market: input.market,
-        user: input.user,
+        user: input.user.clone(),
         outcome_id: input.outcome_id,
         payout,
         claimed_at: input.now_ts,
+        initiator: input.user,
     };
 
     Ok((position, vault_amount, user_usdc_amount, evt))
@@ -152,6 +160,9 @@ pub fn claim_resolved(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::Value;
+
+    const PODIUM_VECTORS: &str = include_str!("../../../../specs/vectors/podium_vectors.json");
 
     fn base_market() -> Market {
         Market {
@@ -168,6 +179,17 @@ mod tests {
             vault: "VaultA".to_string(),
             market_type: 0,
             rules_version: 1,
+            fee_bps: 200,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
         }
     }
 
@@ -178,6 +200,7 @@ mod tests {
             market_status: MarketStatus::Resolved,
             now_ts: 1_800_000_101,
             resolution_timestamp: 1_800_000_100,
+            dispute_deadline: 0,
             claim_window_secs: 600,
             fee_bps: 200, // 2%
             resolved_outcome: Some(1),
@@ -217,6 +240,7 @@ mod tests {
         assert_eq!(vault, 608);
         assert_eq!(user_bal, 402);
         assert_eq!(e.payout, 392);
+        assert_eq!(e.initiator, "UserA");
     }
 
     #[test]
@@ -264,6 +288,33 @@ mod tests {
         assert_eq!(claim_resolved(bad).unwrap_err(), PitStopError::ClaimWindowExpired);
     }
 
+    #[test]
+    fn clr_rej_005_pending_result_claimable_after_dispute_window() {
+        let pending = || {
+            let mut input = base_input();
+            input.market_status = MarketStatus::PendingResolution;
+            input.dispute_deadline = input.resolution_timestamp + 3600;
+            input
+        };
+
+        let mut bad = pending();
+        bad.now_ts = bad.dispute_deadline;
+        assert_eq!(claim_resolved(bad).unwrap_err(), PitStopError::DisputeWindowActive);
+
+        let mut input = pending();
+        input.now_ts = input.dispute_deadline + 1;
+        assert!(claim_resolved(input).is_ok());
+
+        // The claim window runs from the dispute deadline, not from resolution.
+        let mut input = pending();
+        input.now_ts = input.dispute_deadline + input.claim_window_secs;
+        assert!(claim_resolved(input).is_ok());
+
+        let mut bad = pending();
+        bad.now_ts = bad.dispute_deadline + bad.claim_window_secs + 1;
+        assert_eq!(claim_resolved(bad).unwrap_err(), PitStopError::ClaimWindowExpired);
+    }
+
     #[test]
     fn clr_ord_001_swept_fails_by_status_before_outcome_mismatch() {
         let mut bad = base_input();
@@ -293,4 +344,118 @@ mod tests {
         bad.outcome_pool_amount = 1;
         assert_eq!(claim_resolved(bad).unwrap_err(), PitStopError::Overflow);
     }
+
+    #[test]
+    fn clr_inv_003_realistic_large_pool_does_not_overflow() {
+        // 10M USDC position in a 20M USDC pool: position * prize exceeds u64.
+        let mut input = base_input();
+        input.market_state.total_pool = 20_000_000_000_000;
+        input.outcome_pool_amount = 10_000_000_000_000;
+        input.position_amount = 10_000_000_000_000;
+        input.position_state.amount = 10_000_000_000_000;
+        input.vault_amount = 20_000_000_000_000;
+        input.fee_bps = 200;
+
+        let (position, vault_amount, _user, _evt) =
+            claim_resolved(input).expect("large pool claim should pass");
+        assert_eq!(position.payout, 19_600_000_000_000);
+        assert_eq!(vault_amount, 400_000_000_000);
+    }
+
+    fn podium_input(outcome_id: u8, amount: u64) -> ClaimResolvedInput {
+        // Pools [300, 100, 200, 400], podium [1, 3, 0] -> winner_pool 800.
+        let mut input = base_input();
+        input.market_state.market_type = MARKET_TYPE_PODIUM;
+        input.market_state.rules_version = 2;
+        input.market_state.winning_outcomes = vec![1, 3, 0];
+        input.market_state.winner_pool = 800;
+        input.outcome_id = outcome_id;
+        input.outcome_pool_outcome_id = outcome_id;
+        input.outcome_pool_amount = [300, 100, 200, 400][outcome_id as usize];
+        input.position_amount = amount;
+        input.position_state.outcome_id = outcome_id;
+        input.position_state.amount = amount;
+        input
+    }
+
+    #[test]
+    fn clr_hp_004_podium_winners_share_prize_against_combined_winner_pool() {
+        // prize_pool = 980; each podium position gets floor(amount * 980 / 800).
+        let (p, vault, _user, _e) = claim_resolved(podium_input(3, 400)).expect("podium claim");
+        assert_eq!(p.payout, 490);
+        assert_eq!(vault, 510);
+
+        // Third place pays at the same rate as first; no per-place weighting.
+        let (p, _, _, _) = claim_resolved(podium_input(0, 150)).expect("third place claim");
+        assert_eq!(p.payout, 183);
+
+        // resolved_outcome is ignored for podium markets.
+        let (p, _, _, _) = claim_resolved(podium_input(2, 200)).expect("off-podium claim");
+        assert_eq!(p.payout, 0);
+        assert!(p.claimed);
+    }
+
+    #[test]
+    fn clr_vec_001_podium_golden_vectors() {
+        let doc: Value = serde_json::from_str(PODIUM_VECTORS).expect("valid vector json");
+        let num = |v: &Value| -> u64 { v.as_str().unwrap().parse().unwrap() };
+
+        for case in doc["podium"].as_array().expect("podium cases") {
+            let name = case["name"].as_str().unwrap();
+            let pools: Vec<u64> = case["outcomePools"].as_array().unwrap().iter().map(num).collect();
+            let total_pool = num(&case["totalPool"]);
+            assert_eq!(pools.iter().sum::<u64>(), total_pool, "{name}: totalPool");
+
+            let mut market = base_market();
+            market.market_type = MARKET_TYPE_PODIUM;
+            market.rules_version = 2;
+            market.outcome_count = pools.len() as u8;
+            market.total_pool = total_pool;
+            market.winning_outcomes = case["winningOutcomes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|o| o.as_u64().unwrap() as u8)
+                .collect();
+            market.winner_pool = num(&case["winnerPool"]);
+            let fee_bps = case["feeBps"].as_u64().unwrap() as u16;
+
+            let mut vault = total_pool;
+            for pos in case["positions"].as_array().unwrap() {
+                let outcome_id = pos["outcomeId"].as_u64().unwrap() as u8;
+                let mut input = base_input();
+                input.fee_bps = fee_bps;
+                input.market_state = market.clone();
+                input.outcome_id = outcome_id;
+                input.outcome_pool_outcome_id = outcome_id;
+                input.outcome_pool_amount = pools[outcome_id as usize];
+                input.position_amount = num(&pos["amount"]);
+                input.position_state.outcome_id = outcome_id;
+                input.position_state.amount = input.position_amount;
+                input.vault_amount = vault;
+
+                if let Some(err) = case["error"].as_str() {
+                    assert_eq!(err, "DivisionByZero", "{name}");
+                    assert_eq!(
+                        claim_resolved(input).unwrap_err(),
+                        PitStopError::DivisionByZero,
+                        "{name}"
+                    );
+                    continue;
+                }
+
+                let (p, vault_after, _, _) = claim_resolved(input).expect("vector claim");
+                assert_eq!(p.payout, num(&pos["payout"]), "{name}: payout");
+                vault = vault_after;
+            }
+
+            if case["error"].is_null() {
+                let paid = total_pool - vault;
+                assert_eq!(paid, num(&case["totalPaid"]), "{name}: totalPaid");
+                let prize = math::prize_pool(total_pool, fee_bps).unwrap();
+                assert_eq!(prize, num(&case["prizePool"]), "{name}: prizePool");
+                assert_eq!(math::payout_dust(prize, paid).unwrap(), num(&case["dust"]), "{name}");
+            }
+        }
+    }
 }
@@ -1,7 +1,7 @@
 /// claim_voided Rust parity model for LOCKED spec semantics.
 ///
 /// Spec reference:
-/// - SPEC_INSTRUCTIONS/claim_voided.md (LOCKED v1.0.3)
+/// - SPEC_INSTRUCTIONS/claim_voided.md (LOCKED v1.0.4)
 /// - SPEC_EVENTS.md (LOCKED) -> Claimed
 /// - SPEC_ERRORS.md -> claim_voided mapping
 ///
@@ -64,7 +64,7 @@ fn validate_claim_voided_preconditions(input: &ClaimVoidedInput) -> Result<(), P
 /// - payout := position.amount
 /// - transfer payout from vault -> user_usdc
 /// - position.claimed = true; position.payout = payout
-/// - emit Claimed { market, user, outcome_id, payout, claimed_at }
+/// - emit Claimed { market, user, outcome_id, payout, claimed_at, initiator = user }
 pub fn claim_voided(
     input: ClaimVoidedInput,
 ) -> Result<(Position, u64, u64, Claimed), PitStopError> {
@@ -88,10 +88,11 @@ pub fn claim_voided(
 
     let evt = Claimed {
         market: input.market,
-        user: input.user,
+        user: input.user.clone(),
         outcome_id: input.outcome_id,
         payout,
         claimed_at: input.now_ts,
+        initiator: input.user,
     };
 
     Ok((position, user_usdc_amount, vault_amount, evt))
@@ -144,6 +145,7 @@ mod tests {
         assert_eq!(evt.outcome_id, 7);
         assert_eq!(evt.payout, 250);
         assert_eq!(evt.claimed_at, 1_800_000_100);
+        assert_eq!(evt.initiator, evt.user);
     }
 
     #[test]
@@ -0,0 +1,176 @@
+//! close_market Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/close_market.md
+//!
+//! Reclaims Market/OutcomePool rent once a market is finished and its vault is
+//! gone. Pools are closed in batches (a 100-outcome market does not fit in one
+//! transaction); the market account closes with the batch that closes its last pool.
+
+use crate::{error::PitStopError, events::MarketRentReclaimed, state::MarketStatus};
+
+#[derive(Debug, Clone)]
+pub struct CloseMarketInput {
+    pub authority: String,
+    pub config_authority: String,
+    pub market: String,
+    pub market_status: MarketStatus,
+    pub total_pool: u64,
+    pub vault_closed: bool,
+    pub outcome_count: u8,
+    /// Pools closed by earlier calls (`market.pools_closed`).
+    pub pools_closed: u8,
+    /// Pools closed by this call (validated remaining accounts).
+    pub pools_in_call: u8,
+    pub now_ts: i64,
+}
+
+/// Swept markets, or cancelled ones (cancel_market leaves Voided with an empty pool
+/// and a closed vault).
+fn is_closable_status(status: MarketStatus, total_pool: u64) -> bool {
+    status == MarketStatus::Swept || (status == MarketStatus::Voided && total_pool == 0)
+}
+
+fn validate_close_market_preconditions(input: &CloseMarketInput) -> Result<(), PitStopError> {
+    // CLM-REJ-001: only config authority can reclaim rent.
+    if input.authority != input.config_authority {
+        return Err(PitStopError::Unauthorized);
+    }
+
+    // CLM-REJ-002: market must be Swept or cancelled.
+    if !is_closable_status(input.market_status, input.total_pool) {
+        return Err(PitStopError::MarketNotClosable);
+    }
+
+    // CLM-REJ-003: vault must already be closed (sweep/cancel close it).
+    if !input.vault_closed {
+        return Err(PitStopError::VaultNotClosed);
+    }
+
+    // CLM-REJ-004: cannot close more pools than the market ever had.
+    let total = input
+        .pools_closed
+        .checked_add(input.pools_in_call)
+        .ok_or(PitStopError::Overflow)?;
+    if total > input.outcome_count {
+        return Err(PitStopError::OutcomeMismatch);
+    }
+
+    Ok(())
+}
+
+/// Effects:
+/// - close `pools_in_call` pools, rent -> authority (account-level, applied by the handler)
+/// - market.pools_closed += pools_in_call
+/// - if every pool is closed: close market, rent -> authority
+/// - emit MarketRentReclaimed
+///
+/// Returns the new `pools_closed` count.
+pub fn close_market(input: CloseMarketInput) -> Result<(u8, MarketRentReclaimed), PitStopError> {
+    validate_close_market_preconditions(&input)?;
+
+    let pools_closed = input
+        .pools_closed
+        .checked_add(input.pools_in_call)
+        .ok_or(PitStopError::Overflow)?;
+
+    let evt = MarketRentReclaimed {
+        market: input.market,
+        pools_closed: input.pools_in_call,
+        total_pools_closed: pools_closed,
+        market_closed: pools_closed == input.outcome_count,
+        timestamp: input.now_ts,
+    };
+
+    Ok((pools_closed, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_input() -> CloseMarketInput {
+        CloseMarketInput {
+            authority: "AuthA".to_string(),
+            config_authority: "AuthA".to_string(),
+            market: "MarketA".to_string(),
+            market_status: MarketStatus::Swept,
+            total_pool: 1_000,
+            vault_closed: true,
+            outcome_count: 3,
+            pools_closed: 0,
+            pools_in_call: 3,
+            now_ts: 1_800_000_000,
+        }
+    }
+
+    #[test]
+    fn clm_hp_001_all_pools_in_one_call_closes_market() {
+        let (pools_closed, evt) = close_market(base_input()).expect("close should pass");
+        assert_eq!(pools_closed, 3);
+        assert_eq!(evt.pools_closed, 3);
+        assert_eq!(evt.total_pools_closed, 3);
+        assert!(evt.market_closed);
+        assert_eq!(evt.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn clm_hp_002_batches_keep_market_open_until_last_pool() {
+        let mut input = base_input();
+        input.pools_in_call = 2;
+        let (pools_closed, evt) = close_market(input).expect("first batch should pass");
+        assert_eq!(pools_closed, 2);
+        assert!(!evt.market_closed);
+
+        let mut input = base_input();
+        input.pools_closed = pools_closed;
+        input.pools_in_call = 1;
+        let (pools_closed, evt) = close_market(input).expect("last batch should pass");
+        assert_eq!(pools_closed, 3);
+        assert_eq!(evt.pools_closed, 1);
+        assert!(evt.market_closed);
+    }
+
+    #[test]
+    fn clm_hp_003_cancelled_market_without_outcomes_closes() {
+        let mut input = base_input();
+        input.market_status = MarketStatus::Voided;
+        input.total_pool = 0;
+        input.outcome_count = 0;
+        input.pools_in_call = 0;
+        let (_, evt) = close_market(input).expect("cancelled close should pass");
+        assert!(evt.market_closed);
+    }
+
+    #[test]
+    fn clm_rej_001_to_004_error_matrix() {
+        let mut bad = base_input();
+        bad.authority = "Mallory".to_string();
+        assert_eq!(close_market(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        for status in [
+            MarketStatus::Seeding,
+            MarketStatus::Open,
+            MarketStatus::Locked,
+            MarketStatus::Resolved,
+        ] {
+            let mut bad = base_input();
+            bad.market_status = status;
+            bad.total_pool = 0;
+            assert_eq!(close_market(bad).unwrap_err(), PitStopError::MarketNotClosable);
+        }
+
+        // Voided with bets still owes refunds until swept.
+        let mut bad = base_input();
+        bad.market_status = MarketStatus::Voided;
+        assert_eq!(close_market(bad).unwrap_err(), PitStopError::MarketNotClosable);
+
+        let mut bad = base_input();
+        bad.vault_closed = false;
+        assert_eq!(close_market(bad).unwrap_err(), PitStopError::VaultNotClosed);
+
+        let mut bad = base_input();
+        bad.pools_closed = 2;
+        bad.pools_in_call = 2;
+        assert_eq!(close_market(bad).unwrap_err(), PitStopError::OutcomeMismatch);
+    }
+}
@@ -0,0 +1,112 @@
+//! close_position Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/close_position.md
+//!
+//! Returns a Position's rent to its owner once the position can no longer move
+//! funds: it has been claimed, or the market has been swept (unclaimed balance
+//! already went to treasury).
+
+use crate::{
+    error::PitStopError,
+    events::PositionClosed,
+    state::{MarketStatus, Position},
+};
+
+#[derive(Debug, Clone)]
+pub struct ClosePositionInput {
+    pub market: String,
+    pub user: String,
+    pub market_status: MarketStatus,
+    pub now_ts: i64,
+    pub position_state: Position,
+}
+
+fn validate_close_position_preconditions(input: &ClosePositionInput) -> Result<(), PitStopError> {
+    // CLP-REJ-001: only the position owner can close (and receive the rent).
+    if input.user != input.position_state.user || input.market != input.position_state.market {
+        return Err(PitStopError::Unauthorized);
+    }
+
+    // CLP-REJ-002: claimed, or market Swept; anything else could still owe a payout.
+    if !input.position_state.claimed && input.market_status != MarketStatus::Swept {
+        return Err(PitStopError::PositionNotClosable);
+    }
+
+    Ok(())
+}
+
+/// Effects:
+/// - position account closed, rent -> user (account-level, applied by the handler)
+/// - emit PositionClosed
+pub fn close_position(input: ClosePositionInput) -> Result<PositionClosed, PitStopError> {
+    validate_close_position_preconditions(&input)?;
+
+    Ok(PositionClosed {
+        market: input.market,
+        user: input.user,
+        outcome_id: input.position_state.outcome_id,
+        claimed: input.position_state.claimed,
+        timestamp: input.now_ts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_input() -> ClosePositionInput {
+        ClosePositionInput {
+            market: "MarketA".to_string(),
+            user: "UserA".to_string(),
+            market_status: MarketStatus::Resolved,
+            now_ts: 1_800_000_200,
+            position_state: Position {
+                market: "MarketA".to_string(),
+                user: "UserA".to_string(),
+                outcome_id: 1,
+                amount: 100,
+                claimed: true,
+                payout: 392,
+            },
+        }
+    }
+
+    #[test]
+    fn clp_hp_001_claimed_position_closes_and_emits_event() {
+        let evt = close_position(base_input()).expect("close should pass");
+        assert_eq!(evt.market, "MarketA");
+        assert_eq!(evt.user, "UserA");
+        assert_eq!(evt.outcome_id, 1);
+        assert!(evt.claimed);
+        assert_eq!(evt.timestamp, 1_800_000_200);
+    }
+
+    #[test]
+    fn clp_hp_002_unclaimed_position_in_swept_market_closes() {
+        let mut input = base_input();
+        input.market_status = MarketStatus::Swept;
+        input.position_state.claimed = false;
+        input.position_state.payout = 0;
+        let evt = close_position(input).expect("swept close should pass");
+        assert!(!evt.claimed);
+    }
+
+    #[test]
+    fn clp_rej_001_002_error_mapping() {
+        let mut bad = base_input();
+        bad.user = "UserB".to_string();
+        assert_eq!(close_position(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        for status in [
+            MarketStatus::Open,
+            MarketStatus::Locked,
+            MarketStatus::Resolved,
+            MarketStatus::Voided,
+        ] {
+            let mut bad = base_input();
+            bad.market_status = status;
+            bad.position_state.claimed = false;
+            assert_eq!(close_position(bad).unwrap_err(), PitStopError::PositionNotClosable);
+        }
+    }
+}
@@ -1,14 +1,17 @@
 use crate::{
-    constants::{MAX_OUTCOMES, REQUIRED_TOKEN_PROGRAM, SUPPORTED_MARKET_TYPE, SUPPORTED_RULES_VERSION},
+    canonical,
+    constants::{is_supported_token_program, rules_version_for, MAX_OUTCOMES},
     error::PitStopError,
     events::MarketCreated,
-    state::{Market, MarketStatus},
+    instructions::initialize::validate_caps,
+    state::{CollateralAllowlist, Market, MarketStatus},
 };
 
 #[derive(Debug, Clone)]
 pub struct CreateMarketInput {
     pub authority: String,
     pub config_authority: String,
+    pub config_paused: bool,
     pub token_program: String,
     pub market: String,
     pub vault: String,
@@ -19,22 +22,21 @@ pub struct CreateMarketInput {
     pub max_outcomes: u8,
     pub market_type: u8,
     pub rules_version: u16,
-}
-
-fn recompute_market_id(event_id: [u8; 32], market_type: u8, rules_version: u16) -> [u8; 32] {
-    // Canonical formula (SPEC_CANONICAL):
-    // market_id = sha256(event_id[32] || market_type[u8] || rules_version[u16-le])
-    use sha2::{Digest, Sha256};
-
-    let mut bytes = [0u8; 35];
-    bytes[0..32].copy_from_slice(&event_id);
-    bytes[32] = market_type;
-    bytes[33..35].copy_from_slice(&rules_version.to_le_bytes());
-
-    let digest = Sha256::digest(bytes);
-    let mut out = [0u8; 32];
-    out.copy_from_slice(&digest);
-    out
+    pub config_fee_bps: u16,
+    pub config_claim_window_secs: i64,
+    pub config_resolution_deadline_secs: i64,
+    pub collateral_mint: String,
+    pub config_usdc_mint: String,
+    pub config_treasury: String,
+    /// Empty when the allowlist account has not been created.
+    pub collateral_allowlist: CollateralAllowlist,
+    /// Per-market cap overrides; `None` keeps the Config cap.
+    pub max_total_pool_per_market: Option<u64>,
+    pub max_bet_per_user_per_market: Option<u64>,
+    pub config_max_total_pool_per_market: u64,
+    pub config_max_bet_per_user_per_market: u64,
+    /// `["tombstone", market]` exists: this market_id was created and closed before.
+    pub market_tombstone_exists: bool,
 }
 
 fn validate_create_market_preconditions(input: &CreateMarketInput) -> Result<(), PitStopError> {
@@ -42,8 +44,12 @@ fn validate_create_market_preconditions(input: &CreateMarketInput) -> Result<(),
     if input.authority != input.config_authority {
         return Err(PitStopError::Unauthorized);
     }
-    // CRM-REJ-002: token program must remain pinned to SPL Token v1 for custody safety.
-    if input.token_program != REQUIRED_TOKEN_PROGRAM {
+    // CRM-REJ-007: market creation halts while the protocol is paused.
+    if input.config_paused {
+        return Err(PitStopError::ProtocolPaused);
+    }
+    // CRM-REJ-002: token program must be a supported one (the handler pins it to the mint's owner).
+    if !is_supported_token_program(&input.token_program) {
         return Err(PitStopError::InvalidTokenProgram);
     }
     // CRM-REJ-003: lock timestamp must be strictly greater than current timestamp.
@@ -58,26 +64,66 @@ fn validate_create_market_preconditions(input: &CreateMarketInput) -> Result<(),
     if input.max_outcomes > MAX_OUTCOMES {
         return Err(PitStopError::TooManyOutcomes);
     }
-    // CRM-REJ-005a: MVP supports only market_type=0 (Winner).
-    if input.market_type != SUPPORTED_MARKET_TYPE {
-        return Err(PitStopError::UnsupportedMarketType);
-    }
-    // CRM-REJ-005b: MVP supports only rules_version=1.
-    if input.rules_version != SUPPORTED_RULES_VERSION {
+    // CRM-REJ-005a: market_type must be Winner(0) or Podium(1).
+    let expected_rules_version =
+        rules_version_for(input.market_type).ok_or(PitStopError::UnsupportedMarketType)?;
+    // CRM-REJ-005b: rules_version must be the one paired with market_type (Winner=1, Podium=2).
+    if input.rules_version != expected_rules_version {
         return Err(PitStopError::UnsupportedRulesVersion);
     }
     // CRM-REJ-006: recomputed market_id must exactly match provided market_id to prevent canonicalization drift.
-    let recomputed = recompute_market_id(input.event_id, input.market_type, input.rules_version);
+    let recomputed = canonical::market_id(input.event_id, input.market_type, input.rules_version);
     if recomputed != input.market_id {
         return Err(PitStopError::InvalidMarketId);
     }
+    // CRM-REJ-010: a closed market_id stays retired; stale positions and attestations
+    // still reference it.
+    if input.market_tombstone_exists {
+        return Err(PitStopError::MarketIdRetired);
+    }
+    // CRM-REJ-008: collateral must be config.usdc_mint or an allowlisted mint.
+    collateral_treasury(input)?;
+    // CRM-REJ-009: overrides may only tighten the Config caps, and the resulting pair
+    // must satisfy the initialize cap rule.
+    validate_cap_overrides(input)?;
 
     Ok(())
 }
 
+fn validate_cap_overrides(input: &CreateMarketInput) -> Result<(), PitStopError> {
+    let max_total = input
+        .max_total_pool_per_market
+        .unwrap_or(input.config_max_total_pool_per_market);
+    let max_bet = input
+        .max_bet_per_user_per_market
+        .unwrap_or(input.config_max_bet_per_user_per_market);
+    if max_total > input.config_max_total_pool_per_market
+        || max_bet > input.config_max_bet_per_user_per_market
+    {
+        return Err(PitStopError::InvalidCap);
+    }
+    validate_caps(max_total, max_bet)
+}
+
+/// Treasury paired with the market's collateral: `config.treasury` for `config.usdc_mint`,
+/// otherwise the allowlist entry's treasury.
+fn collateral_treasury(input: &CreateMarketInput) -> Result<String, PitStopError> {
+    if input.collateral_mint == input.config_usdc_mint {
+        return Ok(input.config_treasury.clone());
+    }
+    input
+        .collateral_allowlist
+        .entries
+        .iter()
+        .find(|entry| entry.mint == input.collateral_mint)
+        .map(|entry| entry.treasury.clone())
+        .ok_or(PitStopError::CollateralMintNotAllowed)
+}
+
 pub fn create_market(input: CreateMarketInput) -> Result<(Market, MarketCreated), PitStopError> {
     // CRM-HP-001: all create_market preconditions must pass before state/event creation.
     validate_create_market_preconditions(&input)?;
+    let treasury = collateral_treasury(&input)?;
 
     // Effects contract: initialize market in Seeding with zeroed accounting/resolution fields.
     let market = Market {
@@ -94,6 +140,20 @@ pub fn create_market(input: CreateMarketInput) -> Result<(Market, MarketCreated)
         vault: input.vault.clone(),
         market_type: input.market_type,
         rules_version: input.rules_version,
+        // Fee is frozen per market so update_config cannot reprice existing pools.
+        fee_bps: input.config_fee_bps,
+        winning_outcomes: Vec::new(),
+        winner_pool: 0,
+        collateral_mint: input.collateral_mint.clone(),
+        treasury,
+        dispute_deadline: 0,
+        locked_at: 0,
+        max_total_pool_per_market: input.max_total_pool_per_market,
+        max_bet_per_user_per_market: input.max_bet_per_user_per_market,
+        // Likewise the claim window and resolution deadline, so update_config cannot
+        // expire unclaimed payouts or make a Locked market voidable early.
+        claim_window_secs: input.config_claim_window_secs,
+        resolution_deadline_secs: input.config_resolution_deadline_secs,
     };
 
     // Event contract: emit MarketCreated only after successful market initialization.
@@ -105,6 +165,9 @@ pub fn create_market(input: CreateMarketInput) -> Result<(Market, MarketCreated)
         max_outcomes: input.max_outcomes,
         market_type: input.market_type,
         rules_version: input.rules_version,
+        collateral_mint: input.collateral_mint,
+        max_total_pool_per_market: input.max_total_pool_per_market,
+        max_bet_per_user_per_market: input.max_bet_per_user_per_market,
         timestamp: input.now_ts,
     };
 
@@ -114,13 +177,19 @@ pub fn create_market(input: CreateMarketInput) -> Result<(Market, MarketCreated)
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::CollateralMint;
+    use crate::constants::{
+        MARKET_TYPE_PODIUM, REQUIRED_TOKEN_PROGRAM, RULES_VERSION_PODIUM, SUPPORTED_MARKET_TYPE,
+        SUPPORTED_RULES_VERSION,
+    };
 
     fn base_input() -> CreateMarketInput {
         let event_id = [7u8; 32];
-        let market_id = recompute_market_id(event_id, SUPPORTED_MARKET_TYPE, SUPPORTED_RULES_VERSION);
+        let market_id = canonical::market_id(event_id, SUPPORTED_MARKET_TYPE, SUPPORTED_RULES_VERSION);
         CreateMarketInput {
             authority: "AuthA".to_string(),
             config_authority: "AuthA".to_string(),
+            config_paused: false,
             token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
             market: "MarketPdaA".to_string(),
             vault: "VaultAtaA".to_string(),
@@ -131,6 +200,18 @@ mod tests {
             max_outcomes: 20,
             market_type: SUPPORTED_MARKET_TYPE,
             rules_version: SUPPORTED_RULES_VERSION,
+            config_fee_bps: 150,
+            config_claim_window_secs: 3600,
+            config_resolution_deadline_secs: 86_400,
+            collateral_mint: "MintA".to_string(),
+            config_usdc_mint: "MintA".to_string(),
+            config_treasury: "TreasuryA".to_string(),
+            collateral_allowlist: CollateralAllowlist::default(),
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            config_max_total_pool_per_market: 1_000_000,
+            config_max_bet_per_user_per_market: 100_000,
+            market_tombstone_exists: false,
         }
     }
 
@@ -142,7 +223,7 @@ mod tests {
         // event_id = 32 zero bytes, market_type=0, rules_version=1 (LE 0100)
         // expected market_id hex = b17820b1fb10fa804a7147ca7fd1e1666c62ef002e9adfd12019b35a28377664
         let event_id = [0u8; 32];
-        let got = recompute_market_id(event_id, 0, 1);
+        let got = canonical::market_id(event_id, 0, 1);
         let expected: [u8; 32] = [
             0xb1, 0x78, 0x20, 0xb1, 0xfb, 0x10, 0xfa, 0x80,
             0x4a, 0x71, 0x47, 0xca, 0x7f, 0xd1, 0xe1, 0x66,
@@ -162,6 +243,11 @@ mod tests {
         assert_eq!(m.resolution_timestamp, 0);
         assert_eq!(m.resolution_payload_hash, [0u8; 32]);
         assert_eq!(m.vault, "VaultAtaA");
+        assert_eq!(m.fee_bps, 150);
+        assert_eq!(m.claim_window_secs, 3600);
+        assert_eq!(m.resolution_deadline_secs, 86_400);
+        assert_eq!(m.collateral_mint, "MintA");
+        assert_eq!(m.treasury, "TreasuryA");
 
         assert_eq!(e.market, "MarketPdaA");
         assert_eq!(e.market_id, m.market_id);
@@ -202,8 +288,106 @@ mod tests {
         bad.rules_version = 2;
         assert_eq!(create_market(bad).unwrap_err(), PitStopError::UnsupportedRulesVersion);
 
+        // rules_version is bound to market_type: a podium market cannot run Winner rules.
+        let mut bad = base_input();
+        bad.market_type = MARKET_TYPE_PODIUM;
+        bad.market_id = canonical::market_id(bad.event_id, MARKET_TYPE_PODIUM, 1);
+        assert_eq!(create_market(bad).unwrap_err(), PitStopError::UnsupportedRulesVersion);
+
         let mut bad = base_input();
         bad.market_id = [9u8; 32];
         assert_eq!(create_market(bad).unwrap_err(), PitStopError::InvalidMarketId);
+
+        let mut bad = base_input();
+        bad.config_paused = true;
+        assert_eq!(create_market(bad).unwrap_err(), PitStopError::ProtocolPaused);
+    }
+
+    #[test]
+    fn crm_hp_002_podium_market_type_with_rules_version_2() {
+        let mut input = base_input();
+        input.market_type = MARKET_TYPE_PODIUM;
+        input.rules_version = RULES_VERSION_PODIUM;
+        input.market_id =
+            canonical::market_id(input.event_id, MARKET_TYPE_PODIUM, RULES_VERSION_PODIUM);
+        let (m, e) = create_market(input).expect("podium create_market should pass");
+        assert_eq!(m.market_type, MARKET_TYPE_PODIUM);
+        assert_eq!(m.rules_version, RULES_VERSION_PODIUM);
+        assert_eq!(e.market_type, MARKET_TYPE_PODIUM);
+    }
+
+    #[test]
+    fn crm_col_001_allowlisted_mint_records_its_own_treasury() {
+        let mut input = base_input();
+        input.collateral_mint = "PyusdMint".to_string();
+        input.collateral_allowlist.entries.push(CollateralMint {
+            mint: "PyusdMint".to_string(),
+            treasury: "PyusdTreasury".to_string(),
+        });
+        let (m, e) = create_market(input).expect("allowlisted collateral should pass");
+        assert_eq!(m.collateral_mint, "PyusdMint");
+        assert_eq!(m.treasury, "PyusdTreasury");
+        assert_eq!(e.collateral_mint, "PyusdMint");
+    }
+
+    #[test]
+    fn crm_rej_008_unlisted_collateral_mint() {
+        let mut bad = base_input();
+        bad.collateral_mint = "PyusdMint".to_string();
+        assert_eq!(create_market(bad).unwrap_err(), PitStopError::CollateralMintNotAllowed);
+
+        let mut bad = base_input();
+        bad.collateral_mint = "PyusdMint".to_string();
+        bad.collateral_allowlist.entries.push(CollateralMint {
+            mint: "OtherMint".to_string(),
+            treasury: "OtherTreasury".to_string(),
+        });
+        assert_eq!(create_market(bad).unwrap_err(), PitStopError::CollateralMintNotAllowed);
+    }
+
+    #[test]
+    fn crm_cap_001_overrides_stored_and_emitted() {
+        let mut input = base_input();
+        input.max_total_pool_per_market = Some(50_000);
+        input.max_bet_per_user_per_market = Some(5_000);
+        let (m, e) = create_market(input).expect("tighter caps should pass");
+        assert_eq!(m.max_total_pool_per_market, Some(50_000));
+        assert_eq!(m.max_bet_per_user_per_market, Some(5_000));
+        assert_eq!(e.max_total_pool_per_market, Some(50_000));
+        assert_eq!(e.max_bet_per_user_per_market, Some(5_000));
+
+        // One override alone is fine while the pair stays valid against the other Config cap.
+        let mut input = base_input();
+        input.max_total_pool_per_market = Some(100_000);
+        let (m, e) = create_market(input).expect("market cap equal to the user cap passes");
+        assert_eq!(m.max_bet_per_user_per_market, None);
+        assert_eq!(e.max_bet_per_user_per_market, None);
+    }
+
+    #[test]
+    fn crm_rej_009_cap_overrides_bounded_by_config() {
+        let mut bad = base_input();
+        bad.max_total_pool_per_market = Some(1_000_001);
+        assert_eq!(create_market(bad).unwrap_err(), PitStopError::InvalidCap);
+
+        let mut bad = base_input();
+        bad.max_bet_per_user_per_market = Some(100_001);
+        assert_eq!(create_market(bad).unwrap_err(), PitStopError::InvalidCap);
+
+        let mut bad = base_input();
+        bad.max_bet_per_user_per_market = Some(0);
+        assert_eq!(create_market(bad).unwrap_err(), PitStopError::InvalidCap);
+
+        // A market cap below the inherited user cap breaks the cap pair rule.
+        let mut bad = base_input();
+        bad.max_total_pool_per_market = Some(99_999);
+        assert_eq!(create_market(bad).unwrap_err(), PitStopError::InvalidCap);
+    }
+
+    #[test]
+    fn crm_rej_010_closed_market_id_is_retired() {
+        let mut bad = base_input();
+        bad.market_tombstone_exists = true;
+        assert_eq!(create_market(bad).unwrap_err(), PitStopError::MarketIdRetired);
     }
 }
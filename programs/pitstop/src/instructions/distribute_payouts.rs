@@ -0,0 +1,298 @@
+//! distribute_payouts Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/distribute_payouts.md
+//!
+//! Push counterpart of claim_resolved: the authority or `config.payout_keeper` claims
+//! positions of one market on their owners' behalf, so winnings reach users who never
+//! come back before `sweep_remaining`. Each entry runs the claim_resolved checks; the
+//! payout can only go to `position.user`'s associated token account for the market's
+//! collateral mint. The batch is all-or-nothing, like claim_many.
+
+use crate::{
+    constants::MAX_CLAIM_BATCH,
+    error::PitStopError,
+    events::Claimed,
+    instructions::claim_resolved::{claim_resolved, ClaimResolvedInput},
+    state::Position,
+};
+
+#[derive(Debug, Clone)]
+pub struct DistributePayoutsEntry {
+    /// Position account key; listing the same position twice fails as AlreadyClaimed.
+    pub position: String,
+    /// Owner of the destination token account; must be `position.user`.
+    pub destination_owner: String,
+    /// Destination is the associated token account of (`position.user`, collateral
+    /// mint, market token program).
+    pub destination_is_owner_ata: bool,
+    /// claim_resolved input with `user` = `position.user`.
+    pub claim: ClaimResolvedInput,
+}
+
+#[derive(Debug, Clone)]
+pub struct DistributePayoutsInput {
+    /// config.authority or config.payout_keeper; recorded as `Claimed.initiator`.
+    pub caller: String,
+    pub config_authority: String,
+    pub config_payout_keeper: Option<String>,
+    pub market: String,
+    pub entries: Vec<DistributePayoutsEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DistributePayoutsOutput {
+    /// Updated positions, in entry order.
+    pub positions: Vec<Position>,
+    /// One Claimed event per entry, in entry order; `payout` is that entry's transfer.
+    pub events: Vec<Claimed>,
+    pub vault_amount: u64,
+}
+
+fn validate_distribute_payouts_preconditions(
+    input: &DistributePayoutsInput,
+) -> Result<(), PitStopError> {
+    // DSP-REJ-005: only the authority or the configured keeper may push payouts.
+    if input.caller != input.config_authority
+        && input.config_payout_keeper.as_deref() != Some(input.caller.as_str())
+    {
+        return Err(PitStopError::Unauthorized);
+    }
+    // DSP-REJ-001: non-empty and bounded so the batch fits one transaction.
+    if input.entries.is_empty() || input.entries.len() > MAX_CLAIM_BATCH {
+        return Err(PitStopError::InvalidClaimBatch);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - for each entry in order: claim_resolved effects on its position
+/// - payout goes vault -> the position owner's associated token account
+/// - emit Claimed per entry with initiator = caller
+pub fn distribute_payouts(
+    input: DistributePayoutsInput,
+) -> Result<DistributePayoutsOutput, PitStopError> {
+    validate_distribute_payouts_preconditions(&input)?;
+
+    let mut vault_amount = input.entries[0].claim.vault_amount;
+    let mut seen: Vec<String> = Vec::new();
+    let mut positions = Vec::with_capacity(input.entries.len());
+    let mut events = Vec::with_capacity(input.entries.len());
+
+    for entry in input.entries {
+        // DSP-REJ-002: the position must belong to this market.
+        if entry.claim.market != input.market || entry.claim.position_state.market != input.market
+        {
+            return Err(PitStopError::OutcomeMismatch);
+        }
+        // DSP-REJ-003: payouts only go to the position owner.
+        if entry.destination_owner != entry.claim.position_state.user
+            || entry.claim.user != entry.claim.position_state.user
+        {
+            return Err(PitStopError::Unauthorized);
+        }
+        // DSP-REJ-006: and only to their canonical ATA, never another account they own.
+        if !entry.destination_is_owner_ata {
+            return Err(PitStopError::InvalidPayoutDestination);
+        }
+
+        let mut claim = entry.claim;
+        claim.vault_amount = vault_amount;
+        // DSP-REJ-004: a position repeated in the batch is already claimed by then.
+        claim.position_claimed |= seen.contains(&entry.position);
+
+        let (position, new_vault_amount, _user_amount, mut evt) = claim_resolved(claim)?;
+        evt.initiator = input.caller.clone();
+        vault_amount = new_vault_amount;
+
+        seen.push(entry.position);
+        positions.push(position);
+        events.push(evt);
+    }
+
+    Ok(DistributePayoutsOutput {
+        positions,
+        events,
+        vault_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Market, MarketStatus, OutcomePool};
+
+    fn base_market() -> Market {
+        Market {
+            market_id: [1u8; 32],
+            event_id: [2u8; 32],
+            lock_timestamp: 1_800_000_000,
+            outcome_count: 3,
+            max_outcomes: 3,
+            total_pool: 1_000,
+            status: MarketStatus::Resolved,
+            resolved_outcome: Some(1),
+            resolution_payload_hash: [9u8; 32],
+            resolution_timestamp: 1_800_000_100,
+            vault: "VaultA".to_string(),
+            market_type: 0,
+            rules_version: 1,
+            fee_bps: 200,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
+        }
+    }
+
+    /// `user`'s position on `outcome_id` in MarketA; outcome 1 wins with a pool of 250.
+    fn entry(user: &str, outcome_id: u8, amount: u64) -> DistributePayoutsEntry {
+        let pool_amount = if outcome_id == 1 { 250 } else { 750 };
+        DistributePayoutsEntry {
+            position: format!("Pos{user}{outcome_id}"),
+            destination_owner: user.to_string(),
+            destination_is_owner_ata: true,
+            claim: ClaimResolvedInput {
+                market: "MarketA".to_string(),
+                user: user.to_string(),
+                market_status: MarketStatus::Resolved,
+                now_ts: 1_800_000_101,
+                resolution_timestamp: 1_800_000_100,
+                dispute_deadline: 0,
+                claim_window_secs: 600,
+                fee_bps: 200,
+                resolved_outcome: Some(1),
+                outcome_id,
+                position_claimed: false,
+                position_amount: amount,
+                outcome_pool_exists: true,
+                outcome_pool_market: "MarketA".to_string(),
+                outcome_pool_outcome_id: outcome_id,
+                outcome_pool_amount: pool_amount,
+                vault_amount: 1_000,
+                user_usdc_amount: 0,
+                market_state: base_market(),
+                outcome_pool_state: OutcomePool {
+                    market: "MarketA".to_string(),
+                    outcome_id,
+                    pool_amount,
+                },
+                position_state: Position {
+                    market: "MarketA".to_string(),
+                    user: user.to_string(),
+                    outcome_id,
+                    amount,
+                    claimed: false,
+                    payout: 0,
+                },
+            },
+        }
+    }
+
+    fn input(entries: Vec<DistributePayoutsEntry>) -> DistributePayoutsInput {
+        DistributePayoutsInput {
+            caller: "KeeperA".to_string(),
+            config_authority: "AuthA".to_string(),
+            config_payout_keeper: Some("KeeperA".to_string()),
+            market: "MarketA".to_string(),
+            entries,
+        }
+    }
+
+    #[test]
+    fn dsp_hp_001_pays_each_owner_and_records_initiator() {
+        // prize_pool = 980; 100/250 -> 392, 150/250 -> 588, loser -> 0.
+        let out = distribute_payouts(input(vec![
+            entry("UserA", 1, 100),
+            entry("UserB", 1, 150),
+            entry("UserC", 0, 750),
+        ]))
+        .expect("distribution should pass");
+
+        let payouts: Vec<u64> = out.positions.iter().map(|p| p.payout).collect();
+        assert_eq!(payouts, vec![392, 588, 0]);
+        assert!(out.positions.iter().all(|p| p.claimed));
+        assert_eq!(out.vault_amount, 20);
+
+        let users: Vec<&str> = out.events.iter().map(|e| e.user.as_str()).collect();
+        assert_eq!(users, vec!["UserA", "UserB", "UserC"]);
+        assert!(out.events.iter().all(|e| e.initiator == "KeeperA"));
+    }
+
+    #[test]
+    fn dsp_rej_001_empty_or_oversized_batch() {
+        assert_eq!(
+            distribute_payouts(input(Vec::new())).unwrap_err(),
+            PitStopError::InvalidClaimBatch
+        );
+
+        let entries = (0..=MAX_CLAIM_BATCH)
+            .map(|i| entry(&format!("User{i}"), 0, 1))
+            .collect();
+        assert_eq!(
+            distribute_payouts(input(entries)).unwrap_err(),
+            PitStopError::InvalidClaimBatch
+        );
+    }
+
+    #[test]
+    fn dsp_rej_002_to_004_first_failing_entry_aborts_batch() {
+        let mut other_market = entry("UserB", 1, 150);
+        other_market.claim.position_state.market = "MarketB".to_string();
+        let err = distribute_payouts(input(vec![entry("UserA", 1, 100), other_market]))
+            .unwrap_err();
+        assert_eq!(err, PitStopError::OutcomeMismatch);
+
+        let mut redirected = entry("UserB", 1, 150);
+        redirected.destination_owner = "KeeperA".to_string();
+        let err = distribute_payouts(input(vec![entry("UserA", 1, 100), redirected]))
+            .unwrap_err();
+        assert_eq!(err, PitStopError::Unauthorized);
+
+        let err = distribute_payouts(input(vec![entry("UserA", 1, 100), entry("UserA", 1, 100)]))
+            .unwrap_err();
+        assert_eq!(err, PitStopError::AlreadyClaimed);
+    }
+
+    #[test]
+    fn dsp_rej_005_caller_must_be_authority_or_keeper() {
+        let mut by_authority = input(vec![entry("UserA", 1, 100)]);
+        by_authority.caller = "AuthA".to_string();
+        assert!(distribute_payouts(by_authority).is_ok());
+
+        let mut stranger = input(vec![entry("UserA", 1, 100)]);
+        stranger.caller = "Other".to_string();
+        assert_eq!(distribute_payouts(stranger).unwrap_err(), PitStopError::Unauthorized);
+
+        let mut no_keeper = input(vec![entry("UserA", 1, 100)]);
+        no_keeper.config_payout_keeper = None;
+        assert_eq!(distribute_payouts(no_keeper).unwrap_err(), PitStopError::Unauthorized);
+    }
+
+    #[test]
+    fn dsp_rej_006_destination_must_be_owner_ata() {
+        let mut side_account = entry("UserB", 1, 150);
+        side_account.destination_is_owner_ata = false;
+        let err = distribute_payouts(input(vec![entry("UserA", 1, 100), side_account]))
+            .unwrap_err();
+        assert_eq!(err, PitStopError::InvalidPayoutDestination);
+    }
+
+    #[test]
+    fn dsp_ord_001_claim_resolved_gates_apply_per_entry() {
+        let mut claimed = entry("UserB", 1, 150);
+        claimed.claim.position_claimed = true;
+        let err = distribute_payouts(input(vec![entry("UserA", 1, 100), claimed])).unwrap_err();
+        assert_eq!(err, PitStopError::AlreadyClaimed);
+
+        let mut expired = entry("UserA", 1, 100);
+        expired.claim.now_ts = i64::MAX;
+        let err = distribute_payouts(input(vec![expired])).unwrap_err();
+        assert_eq!(err, PitStopError::ClaimWindowExpired);
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
 pub struct FinalizeSeedingInput {
     pub authority: String,
     pub config_authority: String,
+    pub config_paused: bool,
     pub market: String,
     pub market_status: MarketStatus,
     pub market_outcome_count: u8,
@@ -22,6 +23,10 @@ fn validate_finalize_seeding_preconditions(input: &FinalizeSeedingInput) -> Resu
     if input.authority != input.config_authority {
         return Err(PitStopError::Unauthorized);
     }
+    // FSE-REJ-005: opening new betting surface halts while the protocol is paused.
+    if input.config_paused {
+        return Err(PitStopError::ProtocolPaused);
+    }
     // FSE-REJ-002: market must still be in Seeding.
     if input.market_status != MarketStatus::Seeding {
         return Err(PitStopError::MarketNotSeeding);
@@ -73,6 +78,17 @@ mod tests {
             vault: "VaultAtaA".to_string(),
             market_type: 0,
             rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
         }
     }
 
@@ -80,6 +96,7 @@ mod tests {
         FinalizeSeedingInput {
             authority: "AuthA".to_string(),
             config_authority: "AuthA".to_string(),
+            config_paused: false,
             market: "MarketPdaA".to_string(),
             market_status: MarketStatus::Seeding,
             market_outcome_count: 3,
@@ -115,5 +132,9 @@ mod tests {
         let mut bad = base_input();
         bad.now_ts = 1_800_000_100;
         assert_eq!(finalize_seeding(bad).unwrap_err(), PitStopError::TooLateToOpen);
+
+        let mut bad = base_input();
+        bad.config_paused = true;
+        assert_eq!(finalize_seeding(bad).unwrap_err(), PitStopError::ProtocolPaused);
     }
 }
@@ -1,5 +1,8 @@
 use crate::{
-    constants::{MAX_CLAIM_WINDOW_SECS, REQUIRED_TOKEN_PROGRAM, USDC_DECIMALS},
+    constants::{
+        is_supported_token_program, MAX_CLAIM_WINDOW_SECS, MAX_DISPUTE_WINDOW_SECS,
+        MAX_RESOLUTION_DEADLINE_SECS, USDC_DECIMALS,
+    },
     error::PitStopError,
     events::ConfigInitialized,
     state::Config,
@@ -8,6 +11,7 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct InitializeInput {
     pub authority: String,
+    pub oracle: String,
     pub treasury_authority: String,
     pub usdc_mint: String,
     pub treasury: String,
@@ -22,8 +26,8 @@ pub struct InitializeInput {
 }
 
 fn validate_initialize_preconditions(input: &InitializeInput) -> Result<(), PitStopError> {
-    // INIT-REJ-001
-    if input.token_program != REQUIRED_TOKEN_PROGRAM {
+    // INIT-REJ-001: SPL Token v1, or Token-2022 when built with `token-2022`.
+    if !is_supported_token_program(&input.token_program) {
         return Err(PitStopError::InvalidTokenProgram);
     }
     // INIT-REJ-002
@@ -39,17 +43,50 @@ fn validate_initialize_preconditions(input: &InitializeInput) -> Result<(), PitS
         return Err(PitStopError::InvalidTreasuryOwner);
     }
     // INIT-REJ-005
-    if input.max_total_pool_per_market == 0
-        || input.max_bet_per_user_per_market == 0
-        || input.max_bet_per_user_per_market > input.max_total_pool_per_market
+    validate_caps(input.max_total_pool_per_market, input.max_bet_per_user_per_market)?;
+    // INIT-REJ-006
+    validate_claim_window(input.claim_window_secs)?;
+
+    Ok(())
+}
+
+/// Cap rule shared by every instruction that writes Config caps.
+pub(crate) fn validate_caps(
+    max_total_pool_per_market: u64,
+    max_bet_per_user_per_market: u64,
+) -> Result<(), PitStopError> {
+    if max_total_pool_per_market == 0
+        || max_bet_per_user_per_market == 0
+        || max_bet_per_user_per_market > max_total_pool_per_market
     {
         return Err(PitStopError::InvalidCap);
     }
-    // INIT-REJ-006
-    if input.claim_window_secs < 1 || input.claim_window_secs > MAX_CLAIM_WINDOW_SECS {
-        return Err(PitStopError::InvalidClaimWindow);
+    Ok(())
+}
+
+/// Dispute window rule for Config.dispute_window_secs; 0 disables the window.
+pub(crate) fn validate_dispute_window(dispute_window_secs: i64) -> Result<(), PitStopError> {
+    if !(0..=MAX_DISPUTE_WINDOW_SECS).contains(&dispute_window_secs) {
+        return Err(PitStopError::InvalidDisputeWindow);
+    }
+    Ok(())
+}
+
+/// Resolution deadline rule for Config.resolution_deadline_secs; 0 disables the deadline.
+pub(crate) fn validate_resolution_deadline(
+    resolution_deadline_secs: i64,
+) -> Result<(), PitStopError> {
+    if !(0..=MAX_RESOLUTION_DEADLINE_SECS).contains(&resolution_deadline_secs) {
+        return Err(PitStopError::InvalidResolutionDeadline);
     }
+    Ok(())
+}
 
+/// Claim window rule shared by every instruction that writes Config.claim_window_secs.
+pub(crate) fn validate_claim_window(claim_window_secs: i64) -> Result<(), PitStopError> {
+    if !(1..=MAX_CLAIM_WINDOW_SECS).contains(&claim_window_secs) {
+        return Err(PitStopError::InvalidClaimWindow);
+    }
     Ok(())
 }
 
@@ -58,8 +95,7 @@ pub fn initialize(input: InitializeInput) -> Result<(Config, ConfigInitialized),
 
     let config = Config {
         authority: input.authority.clone(),
-        // MVP default: authority is oracle until oracle-admin update instructions are added.
-        oracle: input.authority.clone(),
+        oracle: input.oracle.clone(),
         usdc_mint: input.usdc_mint.clone(),
         treasury: input.treasury.clone(),
         treasury_authority: input.treasury_authority.clone(),
@@ -69,12 +105,18 @@ pub fn initialize(input: InitializeInput) -> Result<(Config, ConfigInitialized),
         max_total_pool_per_market: input.max_total_pool_per_market,
         max_bet_per_user_per_market: input.max_bet_per_user_per_market,
         claim_window_secs: input.claim_window_secs,
-        token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+        // Dispute window, resolution deadline and early-exit fee are opt-in via update_config.
+        dispute_window_secs: 0,
+        resolution_deadline_secs: 0,
+        early_exit_fee_bps: 0,
+        token_program: input.token_program.clone(),
+        pending_authority: None,
+        payout_keeper: None,
     };
 
     let evt = ConfigInitialized {
         authority: input.authority.clone(),
-        oracle: input.authority,
+        oracle: input.oracle,
         usdc_mint: input.usdc_mint,
         treasury: input.treasury,
         fee_bps: 0,
@@ -87,10 +129,12 @@ pub fn initialize(input: InitializeInput) -> Result<(Config, ConfigInitialized),
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::{REQUIRED_TOKEN_PROGRAM, TOKEN_2022_PROGRAM};
 
     fn base_input() -> InitializeInput {
         InitializeInput {
             authority: "AuthA".to_string(),
+            oracle: "OracleA".to_string(),
             treasury_authority: "TreasuryOwnerA".to_string(),
             usdc_mint: "MintA".to_string(),
             treasury: "TreasuryA".to_string(),
@@ -111,7 +155,7 @@ mod tests {
         let (cfg, evt) = out;
 
         assert_eq!(cfg.authority, "AuthA");
-        assert_eq!(cfg.oracle, "AuthA");
+        assert_eq!(cfg.oracle, "OracleA");
         assert_eq!(cfg.usdc_mint, "MintA");
         assert_eq!(cfg.treasury, "TreasuryA");
         assert_eq!(cfg.treasury_authority, "TreasuryOwnerA");
@@ -123,13 +167,25 @@ mod tests {
         assert_eq!(cfg.token_program, REQUIRED_TOKEN_PROGRAM);
 
         assert_eq!(evt.authority, "AuthA");
-        assert_eq!(evt.oracle, "AuthA");
+        assert_eq!(evt.oracle, "OracleA");
         assert_eq!(evt.usdc_mint, "MintA");
         assert_eq!(evt.treasury, "TreasuryA");
         assert_eq!(evt.fee_bps, 0);
         assert_eq!(evt.timestamp, 1_800_000_000);
     }
 
+    #[test]
+    fn init_tkn_001_token_2022_is_accepted_only_with_feature() {
+        let mut input = base_input();
+        input.token_program = TOKEN_2022_PROGRAM.to_string();
+        if cfg!(feature = "token-2022") {
+            let (cfg, _) = initialize(input).expect("token-2022 initialize should pass");
+            assert_eq!(cfg.token_program, TOKEN_2022_PROGRAM);
+        } else {
+            assert_eq!(initialize(input).unwrap_err(), PitStopError::InvalidTokenProgram);
+        }
+    }
+
     #[test]
     fn init_rej_001_to_006_error_mapping() {
         let mut bad = base_input();
@@ -1,3 +1,10 @@
+//! lock_market Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/lock_market.md
+//!
+//! Permissionless crank: once `lock_timestamp` has passed, any signer can move an Open
+//! market to Locked, so market state never lags behind place_bet's `BettingClosed`.
+
 use crate::{
     error::PitStopError,
     events::MarketLocked,
@@ -6,8 +13,8 @@ use crate::{
 
 #[derive(Debug, Clone)]
 pub struct LockMarketInput {
-    pub authority: String,
-    pub config_authority: String,
+    /// Any signer (operator or keeper); recorded in the event only.
+    pub caller: String,
     pub market: String,
     pub market_status: MarketStatus,
     pub now_ts: i64,
@@ -16,10 +23,7 @@ pub struct LockMarketInput {
 }
 
 fn validate_lock_market_preconditions(input: &LockMarketInput) -> Result<(), PitStopError> {
-    // LKM-REJ-001: authority must match config authority.
-    if input.authority != input.config_authority {
-        return Err(PitStopError::Unauthorized);
-    }
+    // LKM-REJ-001 retired: locking no longer requires config.authority.
     // LKM-REJ-002: only Open markets can transition to Locked.
     if input.market_status != MarketStatus::Open {
         return Err(PitStopError::MarketNotOpen);
@@ -31,16 +35,22 @@ fn validate_lock_market_preconditions(input: &LockMarketInput) -> Result<(), Pit
     Ok(())
 }
 
+/// Effects:
+/// - market.status = Locked
+/// - market.locked_at = now (the scheduled time stays in lock_timestamp)
+/// - emit MarketLocked { locked_by, lock_timestamp, timestamp }
 pub fn lock_market(input: LockMarketInput) -> Result<(Market, MarketLocked), PitStopError> {
     validate_lock_market_preconditions(&input)?;
 
-    // Effect: transition status to Locked.
     let mut market = input.market_state;
     market.status = MarketStatus::Locked;
+    market.locked_at = input.now_ts;
 
     // Event emitted only on successful status transition.
     let evt = MarketLocked {
         market: input.market,
+        locked_by: input.caller,
+        lock_timestamp: input.lock_timestamp,
         timestamp: input.now_ts,
     };
 
@@ -66,13 +76,23 @@ mod tests {
             vault: "VaultA".to_string(),
             market_type: 0,
             rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
         }
     }
 
     fn base_input() -> LockMarketInput {
         LockMarketInput {
-            authority: "AuthA".to_string(),
-            config_authority: "AuthA".to_string(),
+            caller: "KeeperA".to_string(),
             market: "MarketA".to_string(),
             market_status: MarketStatus::Open,
             now_ts: 1_800_000_100,
@@ -85,16 +105,24 @@ mod tests {
     fn lkm_hp_001_transitions_to_locked_and_emits_event() {
         let (m, e) = lock_market(base_input()).expect("lock_market should pass");
         assert_eq!(m.status, MarketStatus::Locked);
+        assert_eq!(m.locked_at, 1_800_000_100);
+        assert_eq!(m.lock_timestamp, 1_800_000_000);
         assert_eq!(e.market, "MarketA");
+        assert_eq!(e.locked_by, "KeeperA");
+        assert_eq!(e.lock_timestamp, 1_800_000_000);
         assert_eq!(e.timestamp, 1_800_000_100);
     }
 
     #[test]
-    fn lkm_rej_001_to_003_error_mapping() {
-        let mut bad = base_input();
-        bad.authority = "Other".to_string();
-        assert_eq!(lock_market(bad).unwrap_err(), PitStopError::Unauthorized);
+    fn lkm_hp_002_lock_timestamp_itself_is_lockable() {
+        let mut input = base_input();
+        input.now_ts = input.lock_timestamp;
+        let (m, _e) = lock_market(input).expect("lock at lock_timestamp should pass");
+        assert_eq!(m.locked_at, m.lock_timestamp);
+    }
 
+    #[test]
+    fn lkm_rej_002_to_003_error_mapping() {
         let mut bad = base_input();
         bad.market_status = MarketStatus::Locked;
         assert_eq!(lock_market(bad).unwrap_err(), PitStopError::MarketNotOpen);
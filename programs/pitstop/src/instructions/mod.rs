@@ -10,3 +10,21 @@ pub mod claim_resolved;
 pub mod claim_voided;
 pub mod sweep_remaining;
 pub mod cancel_market;
+pub mod update_config;
+pub mod set_paused;
+pub mod set_oracle;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod cancel_authority_transfer;
+pub mod close_position;
+pub mod close_market;
+pub mod add_collateral_mint;
+pub mod remove_collateral_mint;
+pub mod set_oracle_committee;
+pub mod submit_resolution_vote;
+pub mod overturn_resolution;
+pub mod void_stale_market;
+pub mod claim_many;
+pub mod distribute_payouts;
+pub mod reduce_position;
+pub mod switch_outcome;
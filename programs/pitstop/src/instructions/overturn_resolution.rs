@@ -0,0 +1,321 @@
+//! overturn_resolution Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/overturn_resolution.md
+//!
+//! Lets config.authority correct a result while it is still in its dispute window:
+//! either replace the winner set (market becomes Resolved) or void the market. Either
+//! way the result is final at once and the claim window starts now. Without a result
+//! payload backing the correction, the market keeps no payload hash.
+
+use crate::{
+    error::PitStopError,
+    events::ResolutionOverturned,
+    instructions::resolve_market::{set_winners, validate_winner_set, verify_result_payload},
+    state::{Market, MarketStatus, OutcomePool},
+};
+
+#[derive(Debug, Clone)]
+pub struct OverturnResolutionInput {
+    pub authority: String,
+    pub config_authority: String,
+    pub market: String,
+    pub market_state: Market,
+    /// `true` voids the market; the outcome fields are then ignored.
+    pub void: bool,
+    pub winning_outcome_id: u8,
+    pub winning_outcome_pool_state: Option<OutcomePool>,
+    /// Podium only: the remaining winners after `winning_outcome_id`, in finishing order.
+    pub additional_winning_outcome_ids: Vec<u8>,
+    pub additional_winning_pool_states: Vec<OutcomePool>,
+    pub payload_hash: [u8; 32],
+    /// The result record behind `payload_hash`, checked as in resolve_market against
+    /// the new winner set (empty when voiding). `None` leaves the correction
+    /// unverified and clears `resolution_payload_hash`.
+    pub result_payload: Option<Vec<(String, String)>>,
+    pub now_ts: i64,
+}
+
+fn validate_overturn_resolution_preconditions(
+    input: &OverturnResolutionInput,
+) -> Result<(), PitStopError> {
+    // OVR-REJ-001: only config authority can overturn a result.
+    if input.authority != input.config_authority {
+        return Err(PitStopError::Unauthorized);
+    }
+    // OVR-REJ-002: only a pending result, and only until its dispute deadline (inclusive).
+    if input.market_state.status != MarketStatus::PendingResolution
+        || input.now_ts > input.market_state.dispute_deadline
+    {
+        return Err(PitStopError::DisputeWindowClosed);
+    }
+    // OVR-REJ-003: a replacement winner set follows the resolve_market rules.
+    if !input.void {
+        validate_winner_set(
+            &input.market,
+            &input.market_state,
+            input.winning_outcome_id,
+            input.winning_outcome_pool_state.as_ref(),
+            &input.additional_winning_outcome_ids,
+            &input.additional_winning_pool_states,
+        )?;
+    }
+    // OVR-REJ-004: a submitted payload must back the new result, not the overturned one.
+    if let Some(fields) = &input.result_payload {
+        let winning_outcomes: Vec<u8> = if input.void {
+            Vec::new()
+        } else {
+            std::iter::once(input.winning_outcome_id)
+                .chain(input.additional_winning_outcome_ids.iter().copied())
+                .collect()
+        };
+        verify_result_payload(
+            fields,
+            &input.market_state.event_id,
+            &winning_outcomes,
+            &input.payload_hash,
+        )?;
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - void: market.status = Voided, winners cleared
+/// - otherwise: market.status = Resolved, winner set and winner_pool replaced
+/// - resolution_payload_hash = payload_hash if verified, else zeroed
+/// - resolution_timestamp = dispute_deadline = now
+/// - emit ResolutionOverturned
+pub fn overturn_resolution(
+    input: OverturnResolutionInput,
+) -> Result<(Market, ResolutionOverturned), PitStopError> {
+    validate_overturn_resolution_preconditions(&input)?;
+
+    let mut market = input.market_state;
+    let old_winning_outcomes = std::mem::take(&mut market.winning_outcomes);
+    if input.void {
+        market.status = MarketStatus::Voided;
+        market.resolved_outcome = None;
+        market.winner_pool = 0;
+    } else {
+        market.status = MarketStatus::Resolved;
+        set_winners(
+            &mut market,
+            input.winning_outcome_id,
+            input.winning_outcome_pool_state.as_ref(),
+            &input.additional_winning_outcome_ids,
+            &input.additional_winning_pool_states,
+        )?;
+    }
+    let payload_verified = input.result_payload.is_some();
+    market.resolution_payload_hash = if payload_verified {
+        input.payload_hash
+    } else {
+        [0u8; 32]
+    };
+    // Claim windows (resolved and voided) start from the overturn.
+    market.resolution_timestamp = input.now_ts;
+    market.dispute_deadline = input.now_ts;
+
+    let evt = ResolutionOverturned {
+        market: input.market,
+        authority: input.authority,
+        void: input.void,
+        old_winning_outcomes,
+        winning_outcomes: market.winning_outcomes.clone(),
+        payload_hash: input.payload_hash,
+        payload_verified,
+        timestamp: input.now_ts,
+    };
+
+    Ok((market, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonical;
+    use std::collections::BTreeMap;
+
+    fn base_market() -> Market {
+        Market {
+            market_id: [1u8; 32],
+            event_id: [2u8; 32],
+            lock_timestamp: 1_800_000_000,
+            outcome_count: 3,
+            max_outcomes: 3,
+            total_pool: 1000,
+            status: MarketStatus::PendingResolution,
+            resolved_outcome: Some(1),
+            resolution_payload_hash: [0xabu8; 32],
+            resolution_timestamp: 1_800_000_500,
+            vault: "VaultA".to_string(),
+            market_type: 0,
+            rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: vec![1],
+            winner_pool: 500,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 1_800_004_100,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
+        }
+    }
+
+    fn base_input() -> OverturnResolutionInput {
+        OverturnResolutionInput {
+            authority: "AuthA".to_string(),
+            config_authority: "AuthA".to_string(),
+            market: "MarketA".to_string(),
+            market_state: base_market(),
+            void: false,
+            winning_outcome_id: 2,
+            winning_outcome_pool_state: Some(OutcomePool {
+                market: "MarketA".to_string(),
+                outcome_id: 2,
+                pool_amount: 300,
+            }),
+            additional_winning_outcome_ids: Vec::new(),
+            additional_winning_pool_states: Vec::new(),
+            payload_hash: [0xcdu8; 32],
+            result_payload: None,
+            now_ts: 1_800_001_000,
+        }
+    }
+
+    /// Overturn to `winning_outcomes` backed by a result payload for the market's event.
+    fn verified_input(winning_outcomes: &str) -> OverturnResolutionInput {
+        let mut input = base_input();
+        let fields = vec![
+            ("event_id".to_string(), canonical::hex(&input.market_state.event_id)),
+            ("source".to_string(), "fia_classification_v1".to_string()),
+            ("winning_outcomes".to_string(), winning_outcomes.to_string()),
+        ];
+        input.payload_hash =
+            canonical::payload_hash(&fields.iter().cloned().collect::<BTreeMap<_, _>>());
+        input.result_payload = Some(fields);
+        input
+    }
+
+    #[test]
+    fn ovr_hp_001_replaces_winner_and_finalizes() {
+        let (m, e) = overturn_resolution(base_input()).expect("overturn should pass");
+        assert_eq!(m.status, MarketStatus::Resolved);
+        assert_eq!(m.resolved_outcome, Some(2));
+        assert_eq!(m.winning_outcomes, vec![2]);
+        assert_eq!(m.winner_pool, 300);
+        assert_eq!(m.resolution_payload_hash, [0u8; 32]);
+        assert_eq!(m.resolution_timestamp, 1_800_001_000);
+        assert_eq!(m.dispute_deadline, 1_800_001_000);
+
+        assert_eq!(e.market, "MarketA");
+        assert_eq!(e.authority, "AuthA");
+        assert!(!e.void);
+        assert_eq!(e.old_winning_outcomes, vec![1]);
+        assert_eq!(e.winning_outcomes, vec![2]);
+        assert_eq!(e.payload_hash, [0xcdu8; 32]);
+        assert!(!e.payload_verified);
+        assert_eq!(e.timestamp, 1_800_001_000);
+    }
+
+    #[test]
+    fn ovr_hp_003_verified_payload_keeps_hash() {
+        let input = verified_input("2");
+        let payload_hash = input.payload_hash;
+        let (m, e) = overturn_resolution(input).expect("verified overturn should pass");
+        assert_eq!(m.winning_outcomes, vec![2]);
+        assert_eq!(m.resolution_payload_hash, payload_hash);
+        assert_eq!(e.payload_hash, payload_hash);
+        assert!(e.payload_verified);
+
+        let mut input = verified_input("");
+        input.void = true;
+        input.winning_outcome_pool_state = None;
+        let payload_hash = input.payload_hash;
+        let (m, e) = overturn_resolution(input).expect("verified void should pass");
+        assert_eq!(m.status, MarketStatus::Voided);
+        assert_eq!(m.resolution_payload_hash, payload_hash);
+        assert!(e.payload_verified);
+    }
+
+    #[test]
+    fn ovr_hp_002_voids_pending_market() {
+        let mut input = base_input();
+        input.void = true;
+        input.winning_outcome_pool_state = None;
+        let (m, e) = overturn_resolution(input).expect("overturn to void should pass");
+        assert_eq!(m.status, MarketStatus::Voided);
+        assert_eq!(m.resolved_outcome, None);
+        assert!(m.winning_outcomes.is_empty());
+        assert_eq!(m.winner_pool, 0);
+        assert_eq!(m.resolution_timestamp, 1_800_001_000);
+        assert!(e.void);
+        assert!(e.winning_outcomes.is_empty());
+    }
+
+    #[test]
+    fn ovr_rej_001_to_003_error_mapping() {
+        let mut bad = base_input();
+        bad.authority = "OracleA".to_string();
+        assert_eq!(overturn_resolution(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        // Deadline is inclusive.
+        let mut input = base_input();
+        input.now_ts = input.market_state.dispute_deadline;
+        assert!(overturn_resolution(input).is_ok());
+
+        let mut bad = base_input();
+        bad.now_ts = bad.market_state.dispute_deadline + 1;
+        assert_eq!(overturn_resolution(bad).unwrap_err(), PitStopError::DisputeWindowClosed);
+
+        for status in [MarketStatus::Locked, MarketStatus::Resolved, MarketStatus::Voided] {
+            let mut bad = base_input();
+            bad.market_state.status = status;
+            assert_eq!(overturn_resolution(bad).unwrap_err(), PitStopError::DisputeWindowClosed);
+        }
+
+        let mut bad = base_input();
+        bad.winning_outcome_id = 3;
+        assert_eq!(overturn_resolution(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
+
+        let mut bad = base_input();
+        bad.winning_outcome_pool_state = None;
+        assert_eq!(overturn_resolution(bad).unwrap_err(), PitStopError::OutcomeMismatch);
+    }
+
+    #[test]
+    fn ovr_rej_004_payload_must_back_new_result() {
+        // The payload states the overturned winner, not the replacement.
+        let bad = verified_input("1");
+        assert_eq!(
+            overturn_resolution(bad).unwrap_err(),
+            PitStopError::PayloadOutcomeMismatch
+        );
+
+        // A winner payload cannot back a void.
+        let mut bad = verified_input("2");
+        bad.void = true;
+        assert_eq!(
+            overturn_resolution(bad).unwrap_err(),
+            PitStopError::PayloadOutcomeMismatch
+        );
+
+        let mut bad = verified_input("2");
+        bad.payload_hash = [0xcdu8; 32];
+        assert_eq!(overturn_resolution(bad).unwrap_err(), PitStopError::PayloadHashMismatch);
+
+        let mut bad = verified_input("2");
+        bad.market_state.event_id = [3u8; 32];
+        assert_eq!(overturn_resolution(bad).unwrap_err(), PitStopError::PayloadEventMismatch);
+    }
+
+    #[test]
+    fn ovr_adv_001_overturned_market_cannot_be_overturned_again() {
+        let (m, _e) = overturn_resolution(base_input()).expect("overturn should pass");
+        let mut again = base_input();
+        again.market_state = m;
+        assert_eq!(overturn_resolution(again).unwrap_err(), PitStopError::DisputeWindowClosed);
+    }
+}
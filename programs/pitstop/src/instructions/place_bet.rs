@@ -2,7 +2,7 @@
 ///
 /// Scope:
 /// - deterministic precondition/error mapping (PBT-REJ-*)
-/// - deterministic state/effect modeling for market/outcome_pool/position/vault
+/// - deterministic state/effect modeling for market/outcome_pool/position/user stats/vault
 /// - BetPlaced event payload modeling
 ///
 /// Note:
@@ -10,10 +10,11 @@
 /// wiring is implemented in a later on-chain integration pass.
 
 use crate::{
-    constants::REQUIRED_TOKEN_PROGRAM,
+    constants::is_supported_token_program,
     error::PitStopError,
     events::BetPlaced,
-    state::{Market, MarketStatus, OutcomePool, Position},
+    math,
+    state::{Market, MarketStatus, OutcomePool, Position, UserMarketStats},
 };
 
 #[derive(Debug, Clone)]
@@ -26,6 +27,15 @@ pub struct PlaceBetInput {
     pub market_outcome_count: u8,
     pub market_max_outcomes: u8,
     pub amount: u64,
+    /// Token-2022 transfer fee withheld from `amount` on the way into the vault (0 for SPL
+    /// Token v1 and mints without a fee). Only `amount - transfer_fee` is staked.
+    pub transfer_fee: u64,
+    /// Slippage guard: highest acceptable post-bet share of `total_pool` held by this
+    /// outcome, in bps. `None` skips the check.
+    pub max_outcome_share_bps: Option<u16>,
+    /// Slippage guard: lowest acceptable payout for this stake if the outcome wins at
+    /// the post-bet pools (after `market.fee_bps`). `None` skips the check.
+    pub min_implied_payout: Option<u64>,
     pub token_program: String,
     pub outcome_pool_exists: bool,
     pub outcome_pool_market: String,
@@ -41,6 +51,8 @@ pub struct PlaceBetInput {
     pub market_state: Market,
     pub outcome_pool_state: OutcomePool,
     pub position_state: Position,
+    /// The user's stake across all outcomes of this market; fresh (0) on their first bet.
+    pub user_market_stats_state: UserMarketStats,
 }
 
 fn validate_place_bet_preconditions(input: &PlaceBetInput) -> Result<(), PitStopError> {
@@ -69,28 +81,32 @@ fn validate_place_bet_preconditions(input: &PlaceBetInput) -> Result<(), PitStop
     if input.amount == 0 {
         return Err(PitStopError::ZeroAmount);
     }
-    // PBT-REJ-010: token program must stay pinned to configured SPL Token v1.
-    if input.token_program != REQUIRED_TOKEN_PROGRAM {
+    // PBT-REJ-010: token program must be a supported one (the handler pins it to the market's).
+    if !is_supported_token_program(&input.token_program) {
         return Err(PitStopError::InvalidTokenProgram);
     }
+    // PBT-REJ-011: the stake that actually reaches the vault must be non-zero.
+    let stake = staked_amount(input)?;
 
     // Checked math first so u64 overflow is surfaced as protocol error (Overflow),
     // never as wraparound, panic, or misclassified cap rejection.
     let next_market_total = input
         .market_total_pool
-        .checked_add(input.amount)
+        .checked_add(stake)
         .ok_or(PitStopError::Overflow)?;
     // PBT-REJ-007: reject bets that would exceed market-level total cap.
     if next_market_total > input.max_total_pool_per_market {
         return Err(PitStopError::MarketCapExceeded);
     }
 
-    let next_user_pos = input
-        .user_position_amount
-        .checked_add(input.amount)
+    let next_user_total = input
+        .user_market_stats_state
+        .total_staked
+        .checked_add(stake)
         .ok_or(PitStopError::Overflow)?;
-    // PBT-REJ-008: reject bets that would exceed per-user cap for this market.
-    if next_user_pos > input.max_bet_per_user_per_market {
+    // PBT-REJ-008: reject bets that would exceed per-user cap for this market. The cap
+    // covers the user's stake on every outcome, not just this Position.
+    if next_user_total > input.max_bet_per_user_per_market {
         return Err(PitStopError::UserBetCapExceeded);
     }
 
@@ -105,28 +121,103 @@ fn validate_place_bet_preconditions(input: &PlaceBetInput) -> Result<(), PitStop
         return Err(PitStopError::OutcomeMismatch);
     }
 
+    // PBT-REJ-012: quoted odds must still hold once this stake lands.
+    validate_slippage_guards(input, stake, next_market_total)?;
+
     Ok(())
 }
 
+/// Checks the optional slippage guards against the pools as they would be after the bet.
+///
+/// - share: `(outcome_pool + stake) * 10_000 <= max_outcome_share_bps * (total_pool + stake)`,
+///   compared exactly so rounding cannot let a worse price through
+/// - payout: `stake * prize_pool(total_pool + stake) / (outcome_pool + stake)`, the claim
+///   this stake would get if the outcome won alone and no other bet arrived
+fn validate_slippage_guards(
+    input: &PlaceBetInput,
+    stake: u64,
+    next_market_total: u64,
+) -> Result<(), PitStopError> {
+    let next_outcome_pool = input
+        .outcome_pool_amount
+        .checked_add(stake)
+        .ok_or(PitStopError::Overflow)?;
+
+    if let Some(max_share_bps) = input.max_outcome_share_bps {
+        let share = u128::from(next_outcome_pool) * u128::from(math::BPS_DENOMINATOR);
+        if share > u128::from(max_share_bps) * u128::from(next_market_total) {
+            return Err(PitStopError::SlippageExceeded);
+        }
+    }
+
+    if let Some(min_payout) = input.min_implied_payout {
+        let implied = math::resolved_payout(
+            next_market_total,
+            next_outcome_pool,
+            stake,
+            input.market_state.fee_bps,
+        )?;
+        if implied < min_payout {
+            return Err(PitStopError::SlippageExceeded);
+        }
+    }
+
+    Ok(())
+}
+
+/// `amount` net of the Token-2022 transfer fee; pools, positions and caps all count this.
+fn staked_amount(input: &PlaceBetInput) -> Result<u64, PitStopError> {
+    let stake = input
+        .amount
+        .checked_sub(input.transfer_fee)
+        .ok_or(PitStopError::Underflow)?;
+    if stake == 0 {
+        return Err(PitStopError::ZeroAmount);
+    }
+    Ok(stake)
+}
+
+/// Caps that apply to bets on `market`: its create_market overrides, each bounded by the
+/// current Config cap so a later update_config that lowers a cap still binds the market.
+pub fn effective_caps(
+    market: &Market,
+    config_max_total_pool_per_market: u64,
+    config_max_bet_per_user_per_market: u64,
+) -> (u64, u64) {
+    let max_total = market
+        .max_total_pool_per_market
+        .map_or(config_max_total_pool_per_market, |cap| {
+            cap.min(config_max_total_pool_per_market)
+        });
+    let max_bet = market
+        .max_bet_per_user_per_market
+        .map_or(config_max_bet_per_user_per_market, |cap| {
+            cap.min(config_max_bet_per_user_per_market)
+        });
+    (max_total, max_bet)
+}
+
 /// Executes place_bet effects after preconditions pass.
 ///
-/// Effects modeled:
-/// - market.total_pool += amount
-/// - outcome_pool.pool_amount += amount
-/// - position.amount += amount
-/// - vault_amount += amount
+/// Effects modeled (stake = amount - transfer_fee):
+/// - market.total_pool += stake
+/// - outcome_pool.pool_amount += stake
+/// - position.amount += stake
+/// - user_market_stats.total_staked += stake, outcome_id recorded
+/// - vault_amount += stake
 ///
 /// Post-effect event:
 /// - BetPlaced { market, user, outcome_id, amount, market_total_pool, outcome_pool_amount, timestamp }
 pub fn place_bet(
     input: PlaceBetInput,
-) -> Result<(Market, OutcomePool, Position, u64, BetPlaced), PitStopError> {
+) -> Result<(Market, OutcomePool, Position, UserMarketStats, u64, BetPlaced), PitStopError> {
     validate_place_bet_preconditions(&input)?;
+    let stake = staked_amount(&input)?;
 
-    let market_total_pool = input.market_total_pool.checked_add(input.amount).ok_or(PitStopError::Overflow)?;
-    let outcome_pool_amount = input.outcome_pool_amount.checked_add(input.amount).ok_or(PitStopError::Overflow)?;
-    let position_amount = input.user_position_amount.checked_add(input.amount).ok_or(PitStopError::Overflow)?;
-    let vault_amount = input.vault_amount.checked_add(input.amount).ok_or(PitStopError::Overflow)?;
+    let market_total_pool = input.market_total_pool.checked_add(stake).ok_or(PitStopError::Overflow)?;
+    let outcome_pool_amount = input.outcome_pool_amount.checked_add(stake).ok_or(PitStopError::Overflow)?;
+    let position_amount = input.user_position_amount.checked_add(stake).ok_or(PitStopError::Overflow)?;
+    let vault_amount = input.vault_amount.checked_add(stake).ok_or(PitStopError::Overflow)?;
 
     let mut market = input.market_state;
     market.total_pool = market_total_pool;
@@ -137,23 +228,31 @@ pub fn place_bet(
     let mut position = input.position_state;
     position.amount = position_amount;
 
+    let mut user_market_stats = input.user_market_stats_state;
+    user_market_stats.total_staked = user_market_stats
+        .total_staked
+        .checked_add(stake)
+        .ok_or(PitStopError::Overflow)?;
+    user_market_stats.record_outcome(input.outcome_id);
+
     // Event emitted only after successful state/effect updates (EVT-MTX alignment).
     let evt = BetPlaced {
         market: input.market,
         user: input.user,
         outcome_id: input.outcome_id,
-        amount: input.amount,
+        amount: stake,
         market_total_pool,
         outcome_pool_amount,
         timestamp: input.now_ts,
     };
 
-    Ok((market, outcome_pool, position, vault_amount, evt))
+    Ok((market, outcome_pool, position, user_market_stats, vault_amount, evt))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
 
     fn base_market() -> Market {
         Market {
@@ -170,6 +269,17 @@ mod tests {
             vault: "VaultA".to_string(),
             market_type: 0,
             rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
         }
     }
 
@@ -183,6 +293,9 @@ mod tests {
             market_outcome_count: 3,
             market_max_outcomes: 3,
             amount: 100,
+            transfer_fee: 0,
+            max_outcome_share_bps: None,
+            min_implied_payout: None,
             token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
             outcome_pool_exists: true,
             outcome_pool_market: "MarketA".to_string(),
@@ -209,21 +322,131 @@ mod tests {
                 claimed: false,
                 payout: 0,
             },
+            // 200 on outcome 1 (this position) plus 300 on outcome 0.
+            user_market_stats_state: UserMarketStats {
+                market: "MarketA".to_string(),
+                user: "UserA".to_string(),
+                total_staked: 500,
+                outcome_ids: vec![0, 1],
+            },
         }
     }
 
     #[test]
     fn pbt_hp_updates_balances_and_event() {
         // PBT-HP-001/002 baseline: successful transfer/effect/event modeling.
-        let (m, o, p, vault, e) = place_bet(base_input()).expect("place_bet should pass");
+        let (m, o, p, stats, vault, e) = place_bet(base_input()).expect("place_bet should pass");
         assert_eq!(m.total_pool, 1100);
         assert_eq!(o.pool_amount, 500);
         assert_eq!(p.amount, 300);
+        assert_eq!(stats.total_staked, 600);
+        assert_eq!(stats.outcome_ids, vec![0, 1]);
         assert_eq!(vault, 1100);
         assert_eq!(e.market_total_pool, 1100);
         assert_eq!(e.outcome_pool_amount, 500);
     }
 
+    #[test]
+    fn pbt_hp_003_transfer_fee_is_not_staked() {
+        // 100 sent, 3 withheld by a Token-2022 fee mint: 97 reaches the vault.
+        let mut input = base_input();
+        input.transfer_fee = 3;
+        let (m, o, p, stats, vault, e) = place_bet(input).expect("fee bet should pass");
+        assert_eq!(m.total_pool, 1097);
+        assert_eq!(o.pool_amount, 497);
+        assert_eq!(p.amount, 297);
+        assert_eq!(stats.total_staked, 597);
+        assert_eq!(vault, 1097);
+        assert_eq!(e.amount, 97);
+
+        // Caps count the stake, not the gross amount: 1000 cap, 500 held, 503 sent, 3 fee.
+        let mut input = base_input();
+        input.amount = 503;
+        input.transfer_fee = 3;
+        assert!(place_bet(input).is_ok());
+    }
+
+    #[test]
+    fn pbt_hp_004_slippage_guards_pass_at_quoted_price() {
+        // Post-bet: outcome 500 of total 1100 = 4545.45 bps; payout = 100 * 1100 / 500 = 220.
+        let mut input = base_input();
+        input.max_outcome_share_bps = Some(4546);
+        input.min_implied_payout = Some(220);
+        assert!(place_bet(input).is_ok());
+    }
+
+    #[test]
+    fn pbt_rej_012_slippage_guards_reject_worse_price() {
+        let mut bad = base_input();
+        bad.max_outcome_share_bps = Some(4545);
+        assert_eq!(place_bet(bad).unwrap_err(), PitStopError::SlippageExceeded);
+
+        let mut bad = base_input();
+        bad.min_implied_payout = Some(221);
+        assert_eq!(place_bet(bad).unwrap_err(), PitStopError::SlippageExceeded);
+
+        // The market fee counts: prize = 1100 - 22 = 1078, payout = floor(100 * 1078 / 500) = 215.
+        let mut bad = base_input();
+        bad.market_state.fee_bps = 200;
+        bad.min_implied_payout = Some(216);
+        assert_eq!(place_bet(bad).unwrap_err(), PitStopError::SlippageExceeded);
+
+        // Guards see the staked amount, after any Token-2022 transfer fee: 97 -> 97 * 1097 / 497 = 214.
+        let mut bad = base_input();
+        bad.transfer_fee = 3;
+        bad.min_implied_payout = Some(215);
+        assert_eq!(place_bet(bad).unwrap_err(), PitStopError::SlippageExceeded);
+    }
+
+    #[test]
+    fn pbt_cap_001_market_overrides_bounded_by_config() {
+        let mut market = base_market();
+        assert_eq!(effective_caps(&market, 10_000, 1_000), (10_000, 1_000));
+
+        market.max_total_pool_per_market = Some(2_000);
+        market.max_bet_per_user_per_market = Some(250);
+        assert_eq!(effective_caps(&market, 10_000, 1_000), (2_000, 250));
+
+        // Config lowered below the overrides after creation: the lower cap wins.
+        assert_eq!(effective_caps(&market, 1_500, 200), (1_500, 200));
+
+        // 500 held + 100 > 250: the market's own user cap rejects the bet.
+        let mut bad = base_input();
+        let (max_total, max_bet) = effective_caps(&market, 10_000, 1_000);
+        bad.max_total_pool_per_market = max_total;
+        bad.max_bet_per_user_per_market = max_bet;
+        assert_eq!(place_bet(bad).unwrap_err(), PitStopError::UserBetCapExceeded);
+    }
+
+    #[test]
+    fn pbt_cap_002_user_cap_spans_all_outcomes() {
+        // A fresh position on outcome 2 still counts the 500 already staked on 0 and 1.
+        let mut input = base_input();
+        input.outcome_id = 2;
+        input.outcome_pool_outcome_id = 2;
+        input.outcome_pool_state.outcome_id = 2;
+        input.user_position_amount = 0;
+        input.position_state.outcome_id = 2;
+        input.position_state.amount = 0;
+
+        let mut bad = input.clone();
+        bad.amount = 501;
+        assert_eq!(place_bet(bad).unwrap_err(), PitStopError::UserBetCapExceeded);
+
+        input.amount = 500;
+        let (_m, _o, p, stats, _vault, _e) = place_bet(input).expect("cap is inclusive");
+        assert_eq!(p.amount, 500);
+        assert_eq!(stats.total_staked, 1000);
+        assert_eq!(stats.outcome_ids, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn pbt_rej_011_fee_consuming_whole_amount_is_zero_amount() {
+        let mut bad = base_input();
+        bad.transfer_fee = bad.amount;
+        assert_eq!(place_bet(bad).unwrap_err(), PitStopError::ZeroAmount);
+    }
+
     #[test]
     fn pbt_rej_matrix() {
         // PBT-REJ-001..010 deterministic error mapping coverage.
@@ -256,7 +479,7 @@ mod tests {
         assert_eq!(place_bet(bad).unwrap_err(), PitStopError::MarketCapExceeded);
 
         let mut bad = base_input();
-        bad.user_position_amount = 950;
+        bad.user_market_stats_state.total_staked = 950;
         assert_eq!(place_bet(bad).unwrap_err(), PitStopError::UserBetCapExceeded);
 
         let mut bad = base_input();
@@ -0,0 +1,108 @@
+//! propose_authority Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/propose_authority.md
+//!
+//! First half of the two-step authority transfer. Only records the proposed key;
+//! `config.authority` does not move until that key signs `accept_authority`.
+
+use crate::{error::PitStopError, events::AuthorityTransferProposed, state::Config};
+
+#[derive(Debug, Clone)]
+pub struct ProposeAuthorityInput {
+    pub authority: String,
+    pub new_authority: String,
+    pub now_ts: i64,
+    pub config_state: Config,
+}
+
+fn validate_propose_authority_preconditions(
+    input: &ProposeAuthorityInput,
+) -> Result<(), PitStopError> {
+    // PRA-REJ-001: only config authority can propose a successor.
+    if input.authority != input.config_state.authority {
+        return Err(PitStopError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - config.pending_authority = Some(new_authority) (replaces any earlier proposal)
+/// - emit AuthorityTransferProposed
+pub fn propose_authority(
+    input: ProposeAuthorityInput,
+) -> Result<(Config, AuthorityTransferProposed), PitStopError> {
+    validate_propose_authority_preconditions(&input)?;
+
+    let mut config = input.config_state;
+    config.pending_authority = Some(input.new_authority.clone());
+
+    let evt = AuthorityTransferProposed {
+        authority: input.authority,
+        pending_authority: input.new_authority,
+        timestamp: input.now_ts,
+    };
+
+    Ok((config, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
+
+    fn base_config() -> Config {
+        Config {
+            authority: "AuthA".to_string(),
+            oracle: "OracleA".to_string(),
+            usdc_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            treasury_authority: "TreasuryOwnerA".to_string(),
+            fee_bps: 0,
+            paused: false,
+            max_total_pool_per_market: 1_000_000,
+            max_bet_per_user_per_market: 100_000,
+            claim_window_secs: 3600,
+            dispute_window_secs: 0,
+            resolution_deadline_secs: 0,
+            early_exit_fee_bps: 0,
+            token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+            pending_authority: None,
+            payout_keeper: None,
+        }
+    }
+
+    fn base_input() -> ProposeAuthorityInput {
+        ProposeAuthorityInput {
+            authority: "AuthA".to_string(),
+            new_authority: "AuthB".to_string(),
+            now_ts: 1_800_000_000,
+            config_state: base_config(),
+        }
+    }
+
+    #[test]
+    fn pra_hp_001_records_pending_without_moving_authority() {
+        let (cfg, evt) = propose_authority(base_input()).expect("propose should pass");
+        assert_eq!(cfg.authority, "AuthA");
+        assert_eq!(cfg.pending_authority.as_deref(), Some("AuthB"));
+
+        assert_eq!(evt.authority, "AuthA");
+        assert_eq!(evt.pending_authority, "AuthB");
+        assert_eq!(evt.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn pra_hp_002_new_proposal_replaces_previous() {
+        let mut input = base_input();
+        input.config_state.pending_authority = Some("Typo".to_string());
+        let (cfg, _evt) = propose_authority(input).expect("re-propose should pass");
+        assert_eq!(cfg.pending_authority.as_deref(), Some("AuthB"));
+    }
+
+    #[test]
+    fn pra_rej_001_non_authority_rejected() {
+        let mut bad = base_input();
+        bad.authority = "AuthB".to_string();
+        assert_eq!(propose_authority(bad).unwrap_err(), PitStopError::Unauthorized);
+    }
+}
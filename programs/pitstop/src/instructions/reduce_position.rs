@@ -0,0 +1,259 @@
+//! reduce_position Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/reduce_position.md
+//!
+//! Inverse of place_bet while betting is open: removes part or all of a position's
+//! stake from the pools and refunds it, less `Config.early_exit_fee_bps`. The fee
+//! goes to the treasury at once so `vault.amount == market.total_pool` still holds.
+
+use crate::{
+    error::PitStopError,
+    events::BetReduced,
+    math,
+    state::{Market, MarketStatus, OutcomePool, Position, UserMarketStats},
+};
+
+#[derive(Debug, Clone)]
+pub struct ReducePositionInput {
+    pub market: String,
+    pub user: String,
+    pub outcome_id: u8,
+    /// Stake to remove from the position (refund + fee).
+    pub amount: u64,
+    /// `Config.early_exit_fee_bps`, read at call time.
+    pub early_exit_fee_bps: u16,
+    pub now_ts: i64,
+    pub vault_amount: u64,
+    /// Token account receiving the fee; must be `market.treasury`.
+    pub treasury: String,
+    pub market_state: Market,
+    pub outcome_pool_state: OutcomePool,
+    pub position_state: Position,
+    pub user_market_stats_state: UserMarketStats,
+}
+
+fn validate_reduce_position_preconditions(input: &ReducePositionInput) -> Result<(), PitStopError> {
+    // RDP-REJ-001: only while betting is open, same window as place_bet.
+    if input.market_state.status != MarketStatus::Open {
+        return Err(PitStopError::MarketNotOpen);
+    }
+    // RDP-REJ-002: the lock timestamp closes exits as well as bets.
+    if input.now_ts >= input.market_state.lock_timestamp {
+        return Err(PitStopError::BettingClosed);
+    }
+    // RDP-REJ-003
+    if input.amount == 0 {
+        return Err(PitStopError::ZeroAmount);
+    }
+    // RDP-REJ-004: pool and position must belong to (market, outcome_id, user).
+    if input.outcome_pool_state.market != input.market
+        || input.outcome_pool_state.outcome_id != input.outcome_id
+        || input.position_state.market != input.market
+        || input.position_state.outcome_id != input.outcome_id
+    {
+        return Err(PitStopError::OutcomeMismatch);
+    }
+    if input.position_state.user != input.user {
+        return Err(PitStopError::Unauthorized);
+    }
+    // RDP-REJ-005: cannot remove more than the position holds.
+    if input.amount > input.position_state.amount {
+        return Err(PitStopError::ReductionExceedsPosition);
+    }
+    // RDP-REJ-006: the fee goes to the market's own treasury, as in sweep_remaining.
+    // Note: JS parity maps treasury address mismatch to InvalidTreasuryOwner.
+    if input.treasury != input.market_state.treasury {
+        return Err(PitStopError::InvalidTreasuryOwner);
+    }
+    Ok(())
+}
+
+/// Effects (fee = ceil(amount * early_exit_fee_bps / 10_000), refund = amount - fee):
+/// - position.amount, outcome_pool.pool_amount, market.total_pool -= amount
+/// - user_market_stats.total_staked -= amount (outcome_ids unchanged: the position stays open)
+/// - vault -= amount: refund to the user, fee to the treasury
+/// - emit BetReduced
+pub fn reduce_position(
+    input: ReducePositionInput,
+) -> Result<(Market, OutcomePool, Position, UserMarketStats, u64, BetReduced), PitStopError> {
+    validate_reduce_position_preconditions(&input)?;
+
+    let fee = math::exit_fee_amount(input.amount, input.early_exit_fee_bps)?;
+    let refund = math::debit(input.amount, fee)?;
+
+    let mut market = input.market_state;
+    market.total_pool = math::debit(market.total_pool, input.amount)?;
+    let mut outcome_pool = input.outcome_pool_state;
+    outcome_pool.pool_amount = math::debit(outcome_pool.pool_amount, input.amount)?;
+    let mut position = input.position_state;
+    position.amount = math::debit(position.amount, input.amount)?;
+    let mut user_market_stats = input.user_market_stats_state;
+    user_market_stats.total_staked = math::debit(user_market_stats.total_staked, input.amount)?;
+    let vault_amount = math::debit(input.vault_amount, input.amount)?;
+
+    let evt = BetReduced {
+        market: input.market,
+        user: input.user,
+        outcome_id: input.outcome_id,
+        amount: input.amount,
+        fee,
+        refund,
+        position_amount: position.amount,
+        market_total_pool: market.total_pool,
+        outcome_pool_amount: outcome_pool.pool_amount,
+        timestamp: input.now_ts,
+    };
+
+    Ok((market, outcome_pool, position, user_market_stats, vault_amount, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_market() -> Market {
+        Market {
+            market_id: [1u8; 32],
+            event_id: [2u8; 32],
+            lock_timestamp: 1_800_000_100,
+            outcome_count: 3,
+            max_outcomes: 3,
+            total_pool: 1_000,
+            status: MarketStatus::Open,
+            resolved_outcome: None,
+            resolution_payload_hash: [0u8; 32],
+            resolution_timestamp: 0,
+            vault: "VaultA".to_string(),
+            market_type: 0,
+            rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
+        }
+    }
+
+    fn base_input() -> ReducePositionInput {
+        ReducePositionInput {
+            market: "MarketA".to_string(),
+            user: "UserA".to_string(),
+            outcome_id: 1,
+            amount: 300,
+            early_exit_fee_bps: 250,
+            now_ts: 1_800_000_000,
+            vault_amount: 1_000,
+            treasury: "TreasuryA".to_string(),
+            market_state: base_market(),
+            outcome_pool_state: OutcomePool {
+                market: "MarketA".to_string(),
+                outcome_id: 1,
+                pool_amount: 600,
+            },
+            position_state: Position {
+                market: "MarketA".to_string(),
+                user: "UserA".to_string(),
+                outcome_id: 1,
+                amount: 400,
+                claimed: false,
+                payout: 0,
+            },
+            user_market_stats_state: UserMarketStats {
+                market: "MarketA".to_string(),
+                user: "UserA".to_string(),
+                total_staked: 700,
+                outcome_ids: vec![0, 1],
+            },
+        }
+    }
+
+    #[test]
+    fn rdp_hp_001_partial_reduction_refunds_amount_less_fee() {
+        let (m, pool, pos, stats, vault, e) =
+            reduce_position(base_input()).expect("reduce should pass");
+        // fee = ceil(300 * 250 / 10_000) = 8
+        assert_eq!((e.amount, e.fee, e.refund), (300, 8, 292));
+        assert_eq!(m.total_pool, 700);
+        assert_eq!(pool.pool_amount, 300);
+        assert_eq!(pos.amount, 100);
+        // The reduction frees per-user cap room across the market.
+        assert_eq!(stats.total_staked, 400);
+        assert_eq!(vault, 700);
+        assert_eq!(vault, m.total_pool);
+        assert_eq!(
+            (e.position_amount, e.market_total_pool, e.outcome_pool_amount),
+            (100, 700, 300)
+        );
+        assert_eq!(e.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn rdp_hp_002_full_exit_without_fee() {
+        let mut input = base_input();
+        input.amount = 400;
+        input.early_exit_fee_bps = 0;
+        let (_m, _pool, pos, stats, _vault, e) =
+            reduce_position(input).expect("full exit should pass");
+        assert_eq!(pos.amount, 0);
+        assert_eq!((stats.total_staked, stats.outcome_ids), (300, vec![0, 1]));
+        assert_eq!((e.fee, e.refund), (0, 400));
+    }
+
+    #[test]
+    fn rdp_hp_003_dust_reduction_still_pays_fee() {
+        let mut input = base_input();
+        input.amount = 1;
+        let (_m, _pool, pos, _stats, _vault, e) =
+            reduce_position(input).expect("dust reduce should pass");
+        assert_eq!((e.fee, e.refund), (1, 0));
+        assert_eq!(pos.amount, 399);
+    }
+
+    #[test]
+    fn rdp_rej_001_to_006_error_matrix() {
+        let mut bad = base_input();
+        bad.market_state.status = MarketStatus::Locked;
+        assert_eq!(reduce_position(bad).unwrap_err(), PitStopError::MarketNotOpen);
+
+        let mut bad = base_input();
+        bad.now_ts = bad.market_state.lock_timestamp;
+        assert_eq!(reduce_position(bad).unwrap_err(), PitStopError::BettingClosed);
+
+        let mut bad = base_input();
+        bad.amount = 0;
+        assert_eq!(reduce_position(bad).unwrap_err(), PitStopError::ZeroAmount);
+
+        let mut bad = base_input();
+        bad.outcome_pool_state.outcome_id = 2;
+        assert_eq!(reduce_position(bad).unwrap_err(), PitStopError::OutcomeMismatch);
+
+        let mut bad = base_input();
+        bad.position_state.user = "UserB".to_string();
+        assert_eq!(reduce_position(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        let mut bad = base_input();
+        bad.amount = 401;
+        assert_eq!(
+            reduce_position(bad).unwrap_err(),
+            PitStopError::ReductionExceedsPosition
+        );
+
+        let mut bad = base_input();
+        bad.treasury = "TreasuryB".to_string();
+        assert_eq!(reduce_position(bad).unwrap_err(), PitStopError::InvalidTreasuryOwner);
+    }
+
+    #[test]
+    fn rdp_ord_001_status_gate_before_amount_checks() {
+        let mut bad = base_input();
+        bad.market_state.status = MarketStatus::Seeding;
+        bad.amount = 0;
+        assert_eq!(reduce_position(bad).unwrap_err(), PitStopError::MarketNotOpen);
+    }
+}
@@ -0,0 +1,122 @@
+//! remove_collateral_mint Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/remove_collateral_mint.md
+//!
+//! Delists a collateral mint so no new market can use it. Markets already created
+//! with it keep their snapshotted mint and treasury and settle normally.
+
+use crate::{
+    error::PitStopError,
+    events::CollateralMintRemoved,
+    state::{CollateralAllowlist, Config},
+};
+
+#[derive(Debug, Clone)]
+pub struct RemoveCollateralMintInput {
+    pub authority: String,
+    pub mint: String,
+    pub now_ts: i64,
+    pub config_state: Config,
+    pub allowlist_state: CollateralAllowlist,
+}
+
+fn validate_remove_collateral_mint_preconditions(
+    input: &RemoveCollateralMintInput,
+) -> Result<(), PitStopError> {
+    // RCM-REJ-001: only config authority manages the allowlist.
+    if input.authority != input.config_state.authority {
+        return Err(PitStopError::Unauthorized);
+    }
+    // RCM-REJ-002: mint must be listed (config.usdc_mint never is).
+    if !input.allowlist_state.entries.iter().any(|entry| entry.mint == input.mint) {
+        return Err(PitStopError::CollateralMintNotAllowed);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - allowlist.entries -= mint (remaining order preserved)
+/// - emit CollateralMintRemoved
+pub fn remove_collateral_mint(
+    input: RemoveCollateralMintInput,
+) -> Result<(CollateralAllowlist, CollateralMintRemoved), PitStopError> {
+    validate_remove_collateral_mint_preconditions(&input)?;
+
+    let mut allowlist = input.allowlist_state;
+    allowlist.entries.retain(|entry| entry.mint != input.mint);
+
+    let evt = CollateralMintRemoved {
+        authority: input.authority,
+        mint: input.mint,
+        timestamp: input.now_ts,
+    };
+
+    Ok((allowlist, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
+    use crate::state::CollateralMint;
+
+    fn entry(mint: &str) -> CollateralMint {
+        CollateralMint {
+            mint: mint.to_string(),
+            treasury: format!("{mint}Treasury"),
+        }
+    }
+
+    fn base_input() -> RemoveCollateralMintInput {
+        RemoveCollateralMintInput {
+            authority: "AuthA".to_string(),
+            mint: "PyusdMint".to_string(),
+            now_ts: 1_800_000_000,
+            config_state: Config {
+                authority: "AuthA".to_string(),
+                oracle: "OracleA".to_string(),
+                usdc_mint: "MintA".to_string(),
+                treasury: "TreasuryA".to_string(),
+                treasury_authority: "TreasuryOwnerA".to_string(),
+                fee_bps: 0,
+                paused: false,
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+                dispute_window_secs: 0,
+                resolution_deadline_secs: 0,
+                early_exit_fee_bps: 0,
+                token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+                pending_authority: None,
+                payout_keeper: None,
+            },
+            allowlist_state: CollateralAllowlist {
+                entries: vec![entry("EurcMint"), entry("PyusdMint"), entry("UsdtMint")],
+            },
+        }
+    }
+
+    #[test]
+    fn rcm_hp_001_removes_entry_and_keeps_order() {
+        let (allowlist, evt) = remove_collateral_mint(base_input()).expect("remove should pass");
+        assert_eq!(allowlist.entries, vec![entry("EurcMint"), entry("UsdtMint")]);
+
+        assert_eq!(evt.authority, "AuthA");
+        assert_eq!(evt.mint, "PyusdMint");
+        assert_eq!(evt.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn rcm_rej_001_to_002_error_mapping() {
+        let mut bad = base_input();
+        bad.authority = "Other".to_string();
+        assert_eq!(remove_collateral_mint(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        let mut bad = base_input();
+        bad.mint = "MintA".to_string();
+        assert_eq!(
+            remove_collateral_mint(bad).unwrap_err(),
+            PitStopError::CollateralMintNotAllowed
+        );
+    }
+}
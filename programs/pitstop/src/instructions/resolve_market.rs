@@ -1,25 +1,78 @@
+use std::collections::BTreeMap;
+
 use crate::{
+    canonical,
+    constants::{MARKET_TYPE_PODIUM, PODIUM_SIZE},
     error::PitStopError,
-    events::MarketResolved,
-    state::{Market, MarketStatus, OutcomePool},
+    events::{MarketResolved, ResolutionFinalized},
+    instructions::submit_resolution_vote::quorum_reached,
+    math,
+    state::{Market, MarketStatus, OracleCommittee, OutcomePool, ResolutionDecision, ResolutionVote},
 };
 
 #[derive(Debug, Clone)]
 pub struct ResolveMarketInput {
+    /// Transaction signer: the oracle itself, or a keeper relaying an attestation.
     pub oracle: String,
     pub config_oracle: String,
+    /// Keys whose Ed25519 precompile signature over `attestation::resolve_message`
+    /// is in the transaction.
+    pub attesters: Vec<String>,
+    /// Committee mode: set when `config.oracle` is the OracleCommittee PDA. The signer
+    /// and attesters are then ignored and `votes` must reach quorum.
+    pub committee: Option<OracleCommittee>,
+    /// Votes recorded for this market (committee mode).
+    pub votes: Vec<ResolutionVote>,
     pub market: String,
     pub market_state: Market,
     pub winning_outcome_id: u8,
     pub payload_hash: [u8; 32],
     pub winning_outcome_pool_state: Option<OutcomePool>,
+    /// Podium only: the remaining winners after `winning_outcome_id`, in finishing order.
+    pub additional_winning_outcome_ids: Vec<u8>,
+    /// Pools for `additional_winning_outcome_ids`, same order.
+    pub additional_winning_pool_states: Vec<OutcomePool>,
+    /// Verified mode: the result record whose canonical hash must equal `payload_hash`
+    /// and whose winner set must equal the resolved one. `None` keeps the hash opaque
+    /// (recorded, not checked).
+    pub result_payload: Option<Vec<(String, String)>>,
+    /// `Config.dispute_window_secs`; > 0 leaves the market in PendingResolution.
+    pub dispute_window_secs: i64,
     pub now_ts: i64,
 }
 
+/// Number of winners a market must be resolved with: 1 for Winner, the full podium
+/// (or every outcome, if fewer were seeded) for Podium.
+fn required_winner_count(market: &Market) -> usize {
+    if market.market_type == MARKET_TYPE_PODIUM {
+        usize::from(PODIUM_SIZE.min(market.outcome_count))
+    } else {
+        1
+    }
+}
+
 fn validate_resolve_market_preconditions(input: &ResolveMarketInput) -> Result<(), PitStopError> {
-    // RSM-REJ-001: only config oracle can resolve markets.
-    if input.oracle != input.config_oracle {
-        return Err(PitStopError::UnauthorizedOracle);
+    // RSM-REJ-001: config oracle must sign the transaction or attest offline.
+    // RSM-REJ-010: in committee mode, `threshold` members must have voted for exactly
+    // this winner set and payload hash.
+    match &input.committee {
+        Some(committee) => {
+            let decision = ResolutionDecision::Resolve {
+                winning_outcomes: std::iter::once(input.winning_outcome_id)
+                    .chain(input.additional_winning_outcome_ids.iter().copied())
+                    .collect(),
+            };
+            if !quorum_reached(committee, &input.votes, &decision, &input.payload_hash) {
+                return Err(PitStopError::QuorumNotReached);
+            }
+        }
+        None => {
+            if input.oracle != input.config_oracle
+                && !input.attesters.contains(&input.config_oracle)
+            {
+                return Err(PitStopError::UnauthorizedOracle);
+            }
+        }
     }
 
     // RSM-REJ-002: market must be locked before resolution.
@@ -27,26 +80,135 @@ fn validate_resolve_market_preconditions(input: &ResolveMarketInput) -> Result<(
         return Err(PitStopError::MarketNotLocked);
     }
 
+    validate_winner_set(
+        &input.market,
+        &input.market_state,
+        input.winning_outcome_id,
+        input.winning_outcome_pool_state.as_ref(),
+        &input.additional_winning_outcome_ids,
+        &input.additional_winning_pool_states,
+    )?;
+
+    // RSM-REJ-007..009, 011: a submitted payload must reference this market's event,
+    // state this winner set and hash to payload_hash.
+    if let Some(fields) = &input.result_payload {
+        let winning_outcomes: Vec<u8> = std::iter::once(input.winning_outcome_id)
+            .chain(input.additional_winning_outcome_ids.iter().copied())
+            .collect();
+        verify_result_payload(
+            fields,
+            &input.market_state.event_id,
+            &winning_outcomes,
+            &input.payload_hash,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// RSM-REJ-003..006: the winner set and its pools fit `market_state`. Shared with
+/// `overturn_resolution`, which replaces a pending result under the same rules.
+pub(crate) fn validate_winner_set(
+    market: &str,
+    market_state: &Market,
+    winning_outcome_id: u8,
+    winning_pool: Option<&OutcomePool>,
+    additional_ids: &[u8],
+    additional_pools: &[OutcomePool],
+) -> Result<(), PitStopError> {
     // RSM-REJ-003: winning_outcome_id must be in [0, 99].
-    if input.winning_outcome_id > 99 {
+    if winning_outcome_id > 99 {
         return Err(PitStopError::InvalidOutcomeId);
     }
 
     // RSM-REJ-004: winning outcome must exist in seeded outcome range.
-    if input.winning_outcome_id >= input.market_state.outcome_count {
+    if winning_outcome_id >= market_state.outcome_count {
         return Err(PitStopError::InvalidOutcomeId);
     }
 
     // RSM-REJ-004 / RSM-ADV-001: missing or mismatched outcome pool => OutcomeMismatch.
-    let winning_pool = input
-        .winning_outcome_pool_state
-        .as_ref()
-        .ok_or(PitStopError::OutcomeMismatch)?;
+    let winning_pool = winning_pool.ok_or(PitStopError::OutcomeMismatch)?;
+
+    if winning_pool.market != market || winning_pool.outcome_id != winning_outcome_id {
+        return Err(PitStopError::OutcomeMismatch);
+    }
+
+    // RSM-REJ-005: winner set size is fixed by market_type; ids are distinct seeded outcomes.
+    let extra = additional_ids;
+    if 1 + extra.len() != required_winner_count(market_state) {
+        return Err(PitStopError::InvalidOutcomeId);
+    }
+    for (i, id) in extra.iter().enumerate() {
+        if *id >= market_state.outcome_count
+            || *id == winning_outcome_id
+            || extra[..i].contains(id)
+        {
+            return Err(PitStopError::InvalidOutcomeId);
+        }
+    }
 
-    if winning_pool.market != input.market || winning_pool.outcome_id != input.winning_outcome_id {
+    // RSM-REJ-006: one matching pool per additional winner.
+    if additional_pools.len() != extra.len() {
         return Err(PitStopError::OutcomeMismatch);
     }
+    for (pool, id) in additional_pools.iter().zip(extra) {
+        if pool.market != market || pool.outcome_id != *id {
+            return Err(PitStopError::OutcomeMismatch);
+        }
+    }
+
+    Ok(())
+}
+
+/// Records a validated winner set on `market`. Pools are frozen after lock, so the
+/// combined winner pool can be snapshotted here.
+pub(crate) fn set_winners(
+    market: &mut Market,
+    winning_outcome_id: u8,
+    winning_pool: Option<&OutcomePool>,
+    additional_ids: &[u8],
+    additional_pools: &[OutcomePool],
+) -> Result<(), PitStopError> {
+    let mut winner_pool = winning_pool.map_or(0, |pool| pool.pool_amount);
+    for pool in additional_pools {
+        winner_pool = math::credit(winner_pool, pool.pool_amount)?;
+    }
+    market.resolved_outcome = Some(winning_outcome_id);
+    market.winning_outcomes = std::iter::once(winning_outcome_id)
+        .chain(additional_ids.iter().copied())
+        .collect();
+    market.winner_pool = winner_pool;
+    Ok(())
+}
 
+/// Checks a result payload against the market's `event_id`, the resolved winner set and
+/// the claimed hash, using the same canonicalization as `event_id` (SPEC_CANONICAL.md).
+pub(crate) fn verify_result_payload(
+    fields: &[(String, String)],
+    event_id: &[u8; 32],
+    winning_outcomes: &[u8],
+    payload_hash: &[u8; 32],
+) -> Result<(), PitStopError> {
+    let mut payload = BTreeMap::new();
+    for (key, value) in fields {
+        if payload.insert(key.as_str(), value.as_str()).is_some() {
+            return Err(PitStopError::InvalidResolutionPayload);
+        }
+    }
+    if payload.get(canonical::PAYLOAD_EVENT_ID_KEY).copied()
+        != Some(canonical::hex(event_id).as_str())
+    {
+        return Err(PitStopError::PayloadEventMismatch);
+    }
+    // RSM-REJ-011: the oracle cannot sign one result and resolve another.
+    if payload.get(canonical::PAYLOAD_WINNING_OUTCOMES_KEY).copied()
+        != Some(canonical::winning_outcomes_field(winning_outcomes).as_str())
+    {
+        return Err(PitStopError::PayloadOutcomeMismatch);
+    }
+    if canonical::payload_hash(&payload) != *payload_hash {
+        return Err(PitStopError::PayloadHashMismatch);
+    }
     Ok(())
 }
 
@@ -54,21 +216,65 @@ pub fn resolve_market(input: ResolveMarketInput) -> Result<(Market, MarketResolv
     validate_resolve_market_preconditions(&input)?;
 
     let mut market = input.market_state;
-    market.status = MarketStatus::Resolved;
-    market.resolved_outcome = Some(input.winning_outcome_id);
+    // With a dispute window the result stays overturnable until dispute_deadline.
+    if input.dispute_window_secs > 0 {
+        market.status = MarketStatus::PendingResolution;
+        market.dispute_deadline = input
+            .now_ts
+            .checked_add(input.dispute_window_secs)
+            .ok_or(PitStopError::Overflow)?;
+    } else {
+        market.status = MarketStatus::Resolved;
+        market.dispute_deadline = 0;
+    }
     market.resolution_payload_hash = input.payload_hash;
     market.resolution_timestamp = input.now_ts;
+    set_winners(
+        &mut market,
+        input.winning_outcome_id,
+        input.winning_outcome_pool_state.as_ref(),
+        &input.additional_winning_outcome_ids,
+        &input.additional_winning_pool_states,
+    )?;
 
     let evt = MarketResolved {
         market: input.market,
         winning_outcome: input.winning_outcome_id,
+        winning_outcomes: market.winning_outcomes.clone(),
         payload_hash: input.payload_hash,
+        payload_verified: input.result_payload.is_some(),
         resolution_timestamp: input.now_ts,
+        dispute_deadline: market.dispute_deadline,
     };
 
     Ok((market, evt))
 }
 
+/// PendingResolution -> Resolved once `now_ts` is past the dispute deadline (overturn
+/// is allowed up to and including it). Applied by the first claim_resolved,
+/// distribute_payouts, claim_many (writable market) or sweep_remaining after the
+/// deadline; `None` when the market is not pending or the window is still open.
+pub fn finalize_resolution(
+    market: &str,
+    market_state: &Market,
+    now_ts: i64,
+) -> Option<(Market, ResolutionFinalized)> {
+    if market_state.status != MarketStatus::PendingResolution
+        || now_ts <= market_state.dispute_deadline
+    {
+        return None;
+    }
+    let mut finalized = market_state.clone();
+    finalized.status = MarketStatus::Resolved;
+    let evt = ResolutionFinalized {
+        market: market.to_string(),
+        winning_outcomes: finalized.winning_outcomes.clone(),
+        dispute_deadline: finalized.dispute_deadline,
+        timestamp: now_ts,
+    };
+    Some((finalized, evt))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,6 +294,17 @@ mod tests {
             vault: "VaultA".to_string(),
             market_type: 0,
             rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
         }
     }
 
@@ -103,15 +320,68 @@ mod tests {
         ResolveMarketInput {
             oracle: "OracleA".to_string(),
             config_oracle: "OracleA".to_string(),
+            attesters: Vec::new(),
+            committee: None,
+            votes: Vec::new(),
             market: "MarketA".to_string(),
             market_state: base_market(),
             winning_outcome_id: 1,
             payload_hash: [0xabu8; 32],
             winning_outcome_pool_state: Some(base_pool()),
+            additional_winning_outcome_ids: Vec::new(),
+            additional_winning_pool_states: Vec::new(),
+            result_payload: None,
+            dispute_window_secs: 0,
             now_ts: 1_800_000_500,
         }
     }
 
+    /// Locked market for the SPEC_CANONICAL vectorA event, resolved with the vectorC payload.
+    fn verified_input() -> ResolveMarketInput {
+        let mut input = base_input();
+        input.market_state.event_id = canonical::event_id(&BTreeMap::from([
+            ("round", "05"),
+            ("season", "2026"),
+            ("session", "race"),
+            ("sport", "f1"),
+        ]));
+        let fields = vec![
+            ("winner".to_string(), "car_44".to_string()),
+            (
+                "event_id".to_string(),
+                "5621e7f82cd0b15b457944898ab557629067d4256eaa5b7dc6cec414d5c66a7f".to_string(),
+            ),
+            ("source".to_string(), "fia_classification_v1".to_string()),
+            ("winning_outcomes".to_string(), "1".to_string()),
+        ];
+        input.payload_hash =
+            canonical::payload_hash(&fields.iter().cloned().collect::<BTreeMap<_, _>>());
+        input.result_payload = Some(fields);
+        input
+    }
+
+    fn podium_input() -> ResolveMarketInput {
+        let mut input = base_input();
+        input.market_state.market_type = MARKET_TYPE_PODIUM;
+        input.market_state.rules_version = 2;
+        input.market_state.outcome_count = 5;
+        input.market_state.max_outcomes = 5;
+        input.additional_winning_outcome_ids = vec![4, 0];
+        input.additional_winning_pool_states = vec![
+            OutcomePool {
+                outcome_id: 4,
+                pool_amount: 100,
+                ..base_pool()
+            },
+            OutcomePool {
+                outcome_id: 0,
+                pool_amount: 0,
+                ..base_pool()
+            },
+        ];
+        input
+    }
+
     #[test]
     fn rsm_hp_001_transitions_market_and_emits_event() {
         let (m, e) = resolve_market(base_input()).expect("resolve_market should pass");
@@ -123,7 +393,204 @@ mod tests {
         assert_eq!(e.market, "MarketA");
         assert_eq!(e.winning_outcome, 1);
         assert_eq!(e.payload_hash, [0xabu8; 32]);
+        assert!(!e.payload_verified);
         assert_eq!(e.resolution_timestamp, 1_800_000_500);
+        assert_eq!(e.dispute_deadline, 0);
+    }
+
+    #[test]
+    fn rsm_hp_006_dispute_window_leaves_result_pending() {
+        let mut input = base_input();
+        input.dispute_window_secs = 3600;
+        let (m, e) = resolve_market(input).expect("resolve_market should pass");
+        assert_eq!(m.status, MarketStatus::PendingResolution);
+        assert_eq!(m.resolved_outcome, Some(1));
+        assert_eq!(m.dispute_deadline, 1_800_004_100);
+        assert_eq!(e.dispute_deadline, 1_800_004_100);
+    }
+
+    #[test]
+    fn rsm_fin_001_pending_result_finalizes_after_deadline() {
+        let mut input = base_input();
+        input.dispute_window_secs = 3600;
+        let (pending, _) = resolve_market(input).expect("resolve_market should pass");
+
+        // Still inside the window (inclusive deadline): nothing to finalize.
+        assert!(finalize_resolution("MarketA", &pending, 1_800_004_100).is_none());
+
+        let (m, e) = finalize_resolution("MarketA", &pending, 1_800_004_101)
+            .expect("past the deadline the result is final");
+        assert_eq!(m.status, MarketStatus::Resolved);
+        assert_eq!((m.resolved_outcome, m.dispute_deadline), (Some(1), 1_800_004_100));
+        assert_eq!(e.winning_outcomes, vec![1]);
+        assert_eq!((e.dispute_deadline, e.timestamp), (1_800_004_100, 1_800_004_101));
+
+        // Only PendingResolution is finalized; a second pass is a no-op.
+        assert!(finalize_resolution("MarketA", &m, 1_800_004_101).is_none());
+    }
+
+    #[test]
+    fn rsm_hp_005_verified_payload_records_recomputed_hash() {
+        let input = verified_input();
+        assert_eq!(
+            canonical::hex(&input.payload_hash),
+            "4e349a59a5fd877278db8ff0a8b3d115793be3c1577e59e894609e8e905fa9f2"
+        );
+        let (m, e) = resolve_market(input).expect("verified resolve should pass");
+        assert_eq!(m.resolution_payload_hash, e.payload_hash);
+        assert!(e.payload_verified);
+    }
+
+    #[test]
+    fn rsm_rej_007_to_009_payload_error_mapping() {
+        let mut bad = verified_input();
+        bad.result_payload
+            .as_mut()
+            .unwrap()
+            .push(("winner".to_string(), "car_1".to_string()));
+        assert_eq!(
+            resolve_market(bad).unwrap_err(),
+            PitStopError::InvalidResolutionPayload
+        );
+
+        let mut bad = verified_input();
+        bad.market_state.event_id = [2u8; 32];
+        assert_eq!(
+            resolve_market(bad).unwrap_err(),
+            PitStopError::PayloadEventMismatch
+        );
+
+        let mut bad = verified_input();
+        bad.result_payload
+            .as_mut()
+            .unwrap()
+            .retain(|(k, _)| k != "event_id");
+        assert_eq!(
+            resolve_market(bad).unwrap_err(),
+            PitStopError::PayloadEventMismatch
+        );
+
+        let mut bad = verified_input();
+        bad.payload_hash = [0xabu8; 32];
+        assert_eq!(
+            resolve_market(bad).unwrap_err(),
+            PitStopError::PayloadHashMismatch
+        );
+
+        // Uppercase hex is a different canonical string, not the same event.
+        let mut bad = verified_input();
+        for (k, v) in bad.result_payload.as_mut().unwrap() {
+            if k == "event_id" {
+                *v = v.to_uppercase();
+            }
+        }
+        assert_eq!(
+            resolve_market(bad).unwrap_err(),
+            PitStopError::PayloadEventMismatch
+        );
+    }
+
+    #[test]
+    fn rsm_rej_011_payload_winner_set_must_match() {
+        // Self-consistent hash, but the payload names another winner.
+        let with_winners = |mut input: ResolveMarketInput, value: &str| {
+            let fields = input.result_payload.as_mut().unwrap();
+            fields.retain(|(k, _)| k != "winning_outcomes");
+            if !value.is_empty() {
+                fields.push(("winning_outcomes".to_string(), value.to_string()));
+            }
+            input.payload_hash =
+                canonical::payload_hash(&fields.iter().cloned().collect::<BTreeMap<_, _>>());
+            input
+        };
+
+        for value in ["2", "", "01", "1,4"] {
+            assert_eq!(
+                resolve_market(with_winners(verified_input(), value)).unwrap_err(),
+                PitStopError::PayloadOutcomeMismatch,
+                "{value:?}"
+            );
+        }
+
+        // Podium: the full winner set in finishing order.
+        let mut podium = podium_input();
+        podium.market_state.event_id = verified_input().market_state.event_id;
+        podium.result_payload = verified_input().result_payload;
+        let (_, e) = resolve_market(with_winners(podium.clone(), "1,4,0"))
+            .expect("matching podium payload should pass");
+        assert!(e.payload_verified);
+        assert_eq!(
+            resolve_market(with_winners(podium, "1,0,4")).unwrap_err(),
+            PitStopError::PayloadOutcomeMismatch
+        );
+    }
+
+    #[test]
+    fn rsm_hp_002_podium_records_winner_set_and_combined_pool() {
+        let (m, e) = resolve_market(podium_input()).expect("podium resolve should pass");
+        assert_eq!(m.status, MarketStatus::Resolved);
+        assert_eq!(m.resolved_outcome, Some(1));
+        assert_eq!(m.winning_outcomes, vec![1, 4, 0]);
+        assert_eq!(m.winner_pool, 600);
+        assert_eq!(e.winning_outcome, 1);
+        assert_eq!(e.winning_outcomes, vec![1, 4, 0]);
+    }
+
+    #[test]
+    fn rsm_hp_003_winner_market_records_single_winner_pool() {
+        let (m, e) = resolve_market(base_input()).expect("resolve_market should pass");
+        assert_eq!(m.winning_outcomes, vec![1]);
+        assert_eq!(m.winner_pool, 500);
+        assert_eq!(e.winning_outcomes, vec![1]);
+    }
+
+    #[test]
+    fn rsm_rej_005_006_winner_set_error_mapping() {
+        // Winner markets take exactly one winner.
+        let mut bad = base_input();
+        bad.additional_winning_outcome_ids = vec![2];
+        bad.additional_winning_pool_states = vec![OutcomePool {
+            outcome_id: 2,
+            ..base_pool()
+        }];
+        assert_eq!(resolve_market(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
+
+        // Podium needs the full podium.
+        let mut bad = podium_input();
+        bad.additional_winning_outcome_ids.pop();
+        bad.additional_winning_pool_states.pop();
+        assert_eq!(resolve_market(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
+
+        let mut bad = podium_input();
+        bad.additional_winning_outcome_ids[1] = 1;
+        assert_eq!(resolve_market(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
+
+        let mut bad = podium_input();
+        bad.additional_winning_outcome_ids[1] = 4;
+        assert_eq!(resolve_market(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
+
+        let mut bad = podium_input();
+        bad.additional_winning_outcome_ids[1] = 5;
+        assert_eq!(resolve_market(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
+
+        let mut bad = podium_input();
+        bad.additional_winning_pool_states.pop();
+        assert_eq!(resolve_market(bad).unwrap_err(), PitStopError::OutcomeMismatch);
+
+        let mut bad = podium_input();
+        bad.additional_winning_pool_states.swap(0, 1);
+        assert_eq!(resolve_market(bad).unwrap_err(), PitStopError::OutcomeMismatch);
+    }
+
+    #[test]
+    fn rsm_hp_004_podium_with_fewer_outcomes_than_podium_size() {
+        let mut input = podium_input();
+        input.market_state.outcome_count = 2;
+        input.additional_winning_outcome_ids = vec![0];
+        input.additional_winning_pool_states.remove(0);
+        let (m, _e) = resolve_market(input).expect("two-outcome podium should pass");
+        assert_eq!(m.winning_outcomes, vec![1, 0]);
+        assert_eq!(m.winner_pool, 500);
     }
 
     #[test]
@@ -177,6 +644,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rsm_adv_003_keeper_relays_oracle_attestation() {
+        let mut input = base_input();
+        input.oracle = "KeeperA".to_string();
+        input.attesters = vec!["OracleA".to_string()];
+        let (m, _e) = resolve_market(input).expect("attested resolve should pass");
+        assert_eq!(m.status, MarketStatus::Resolved);
+
+        // Attestations by anyone but the current oracle do not count.
+        let mut bad = base_input();
+        bad.oracle = "KeeperA".to_string();
+        bad.attesters = vec!["KeeperA".to_string(), "OracleB".to_string()];
+        assert_eq!(
+            resolve_market(bad).unwrap_err(),
+            PitStopError::UnauthorizedOracle
+        );
+    }
+
+    #[test]
+    fn rsm_adv_004_committee_mode_requires_matching_quorum() {
+        let vote = |member: &str, winning_outcomes: Vec<u8>, hash: u8| ResolutionVote {
+            member: member.to_string(),
+            decision: ResolutionDecision::Resolve { winning_outcomes },
+            payload_hash: [hash; 32],
+        };
+        let committee_input = |votes: Vec<ResolutionVote>| {
+            let mut input = podium_input();
+            // Any keeper finalizes; the old single oracle has no say.
+            input.oracle = "KeeperA".to_string();
+            input.config_oracle = "CommitteePda".to_string();
+            input.committee = Some(OracleCommittee {
+                members: vec!["M1".to_string(), "M2".to_string(), "M3".to_string()],
+                threshold: 2,
+            });
+            input.votes = votes;
+            input
+        };
+
+        let input = committee_input(vec![vote("M1", vec![1, 4, 0], 0xab), vote("M3", vec![1, 4, 0], 0xab)]);
+        let (m, _e) = resolve_market(input).expect("quorum resolve should pass");
+        assert_eq!(m.winning_outcomes, vec![1, 4, 0]);
+
+        // Same winners in a different finishing order, a different hash, or too few votes.
+        for votes in [
+            vec![vote("M1", vec![1, 4, 0], 0xab), vote("M3", vec![1, 0, 4], 0xab)],
+            vec![vote("M1", vec![1, 4, 0], 0xab), vote("M3", vec![1, 4, 0], 0xac)],
+            vec![vote("M1", vec![1, 4, 0], 0xab)],
+        ] {
+            assert_eq!(
+                resolve_market(committee_input(votes)).unwrap_err(),
+                PitStopError::QuorumNotReached
+            );
+        }
+
+        // A quorum does not bypass outcome validation.
+        let mut bad = committee_input(vec![vote("M1", vec![1], 0xab), vote("M2", vec![1], 0xab)]);
+        bad.additional_winning_outcome_ids.clear();
+        bad.additional_winning_pool_states.clear();
+        assert_eq!(resolve_market(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
+    }
+
+    #[test]
+    fn rsm_adv_002_oracle_rotation_while_locked_switches_signer() {
+        use crate::{
+            constants::REQUIRED_TOKEN_PROGRAM,
+            instructions::set_oracle::{set_oracle, SetOracleInput},
+            state::Config,
+        };
+
+        let config = Config {
+            authority: "AuthA".to_string(),
+            oracle: "OracleA".to_string(),
+            usdc_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            treasury_authority: "TreasuryOwnerA".to_string(),
+            fee_bps: 0,
+            paused: false,
+            max_total_pool_per_market: 1_000_000,
+            max_bet_per_user_per_market: 100_000,
+            claim_window_secs: 3600,
+            dispute_window_secs: 0,
+            resolution_deadline_secs: 0,
+            early_exit_fee_bps: 0,
+            token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+            pending_authority: None,
+            payout_keeper: None,
+        };
+        let (rotated, _evt) = set_oracle(SetOracleInput {
+            authority: "AuthA".to_string(),
+            new_oracle: "OracleB".to_string(),
+            now_ts: 1_800_000_200,
+            config_state: config,
+        })
+        .expect("rotation should pass");
+
+        // Market was locked under OracleA; after rotation only OracleB may resolve it.
+        let mut stale = base_input();
+        stale.config_oracle = rotated.oracle.clone();
+        assert_eq!(
+            resolve_market(stale).unwrap_err(),
+            PitStopError::UnauthorizedOracle
+        );
+
+        let mut fresh = base_input();
+        fresh.oracle = "OracleB".to_string();
+        fresh.config_oracle = rotated.oracle;
+        let (m, _e) = resolve_market(fresh).expect("new oracle should resolve");
+        assert_eq!(m.status, MarketStatus::Resolved);
+    }
+
     #[test]
     fn rsm_adv_001_missing_outcome_pool_maps_to_outcome_mismatch() {
         let mut bad = base_input();
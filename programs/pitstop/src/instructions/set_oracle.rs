@@ -0,0 +1,98 @@
+//! set_oracle Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/set_oracle.md
+//!
+//! Rotates the resolution signer (`config.oracle`). Markets do not snapshot the
+//! oracle, so a rotation applies to every market that is not yet resolved/voided.
+
+use crate::{error::PitStopError, events::OracleRotated, state::Config};
+
+#[derive(Debug, Clone)]
+pub struct SetOracleInput {
+    pub authority: String,
+    pub new_oracle: String,
+    pub now_ts: i64,
+    pub config_state: Config,
+}
+
+fn validate_set_oracle_preconditions(input: &SetOracleInput) -> Result<(), PitStopError> {
+    // SOR-REJ-001: only config authority can rotate the oracle.
+    if input.authority != input.config_state.authority {
+        return Err(PitStopError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - config.oracle = new_oracle
+/// - emit OracleRotated { old_oracle, new_oracle }
+pub fn set_oracle(input: SetOracleInput) -> Result<(Config, OracleRotated), PitStopError> {
+    validate_set_oracle_preconditions(&input)?;
+
+    let mut config = input.config_state;
+    let old_oracle = std::mem::replace(&mut config.oracle, input.new_oracle.clone());
+
+    let evt = OracleRotated {
+        authority: input.authority,
+        old_oracle,
+        new_oracle: input.new_oracle,
+        timestamp: input.now_ts,
+    };
+
+    Ok((config, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
+
+    fn base_config() -> Config {
+        Config {
+            authority: "AuthA".to_string(),
+            oracle: "OracleA".to_string(),
+            usdc_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            treasury_authority: "TreasuryOwnerA".to_string(),
+            fee_bps: 0,
+            paused: false,
+            max_total_pool_per_market: 1_000_000,
+            max_bet_per_user_per_market: 100_000,
+            claim_window_secs: 3600,
+            dispute_window_secs: 0,
+            resolution_deadline_secs: 0,
+            early_exit_fee_bps: 0,
+            token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+            pending_authority: None,
+            payout_keeper: None,
+        }
+    }
+
+    fn base_input() -> SetOracleInput {
+        SetOracleInput {
+            authority: "AuthA".to_string(),
+            new_oracle: "OracleB".to_string(),
+            now_ts: 1_800_000_000,
+            config_state: base_config(),
+        }
+    }
+
+    #[test]
+    fn sor_hp_001_rotates_oracle_and_emits_event() {
+        let (cfg, evt) = set_oracle(base_input()).expect("set_oracle should pass");
+        assert_eq!(cfg.oracle, "OracleB");
+        assert_eq!(cfg.authority, "AuthA");
+
+        assert_eq!(evt.authority, "AuthA");
+        assert_eq!(evt.old_oracle, "OracleA");
+        assert_eq!(evt.new_oracle, "OracleB");
+        assert_eq!(evt.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn sor_rej_001_oracle_cannot_rotate_itself() {
+        let mut bad = base_input();
+        bad.authority = "OracleA".to_string();
+        assert_eq!(set_oracle(bad).unwrap_err(), PitStopError::Unauthorized);
+    }
+}
@@ -0,0 +1,159 @@
+//! set_oracle_committee Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/set_oracle_committee.md
+//!
+//! Installs (or replaces) the M-of-N oracle committee and points `config.oracle` at
+//! the committee PDA. No key can sign as that PDA, so from here on markets resolve or
+//! void only through member votes; `set_oracle` hands resolution back to a single key.
+
+use std::collections::BTreeSet;
+
+use crate::{
+    constants::MAX_COMMITTEE_MEMBERS,
+    error::PitStopError,
+    events::OracleCommitteeSet,
+    state::{Config, OracleCommittee},
+};
+
+#[derive(Debug, Clone)]
+pub struct SetOracleCommitteeInput {
+    pub authority: String,
+    pub members: Vec<String>,
+    pub threshold: u8,
+    /// OracleCommittee PDA address; becomes `config.oracle`.
+    pub committee: String,
+    pub now_ts: i64,
+    pub config_state: Config,
+}
+
+fn validate_set_oracle_committee_preconditions(
+    input: &SetOracleCommitteeInput,
+) -> Result<(), PitStopError> {
+    // SOC-REJ-001: only config authority manages the committee.
+    if input.authority != input.config_state.authority {
+        return Err(PitStopError::Unauthorized);
+    }
+    // SOC-REJ-002: 1..=MAX_COMMITTEE_MEMBERS distinct members.
+    let n = input.members.len();
+    if n == 0
+        || n > usize::from(MAX_COMMITTEE_MEMBERS)
+        || input.members.iter().collect::<BTreeSet<_>>().len() != n
+    {
+        return Err(PitStopError::InvalidCommittee);
+    }
+    // SOC-REJ-003: threshold <= n and a strict majority, so two different decisions can
+    // never both reach quorum.
+    let threshold = usize::from(input.threshold);
+    if threshold > n || threshold * 2 <= n {
+        return Err(PitStopError::InvalidCommittee);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - committee = { members, threshold }
+/// - config.oracle = committee PDA
+/// - emit OracleCommitteeSet
+pub fn set_oracle_committee(
+    input: SetOracleCommitteeInput,
+) -> Result<(Config, OracleCommittee, OracleCommitteeSet), PitStopError> {
+    validate_set_oracle_committee_preconditions(&input)?;
+
+    let mut config = input.config_state;
+    let old_oracle = std::mem::replace(&mut config.oracle, input.committee.clone());
+
+    let committee = OracleCommittee {
+        members: input.members.clone(),
+        threshold: input.threshold,
+    };
+
+    let evt = OracleCommitteeSet {
+        authority: input.authority,
+        old_oracle,
+        committee: input.committee,
+        members: input.members,
+        threshold: input.threshold,
+        timestamp: input.now_ts,
+    };
+
+    Ok((config, committee, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
+
+    fn base_input() -> SetOracleCommitteeInput {
+        SetOracleCommitteeInput {
+            authority: "AuthA".to_string(),
+            members: vec!["M1".to_string(), "M2".to_string(), "M3".to_string()],
+            threshold: 2,
+            committee: "CommitteePda".to_string(),
+            now_ts: 1_800_000_000,
+            config_state: Config {
+                authority: "AuthA".to_string(),
+                oracle: "OracleA".to_string(),
+                usdc_mint: "MintA".to_string(),
+                treasury: "TreasuryA".to_string(),
+                treasury_authority: "TreasuryOwnerA".to_string(),
+                fee_bps: 0,
+                paused: false,
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+                dispute_window_secs: 0,
+                resolution_deadline_secs: 0,
+                early_exit_fee_bps: 0,
+                token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+                pending_authority: None,
+                payout_keeper: None,
+            },
+        }
+    }
+
+    #[test]
+    fn soc_hp_001_installs_committee_as_oracle() {
+        let (cfg, committee, evt) =
+            set_oracle_committee(base_input()).expect("set_oracle_committee should pass");
+        assert_eq!(cfg.oracle, "CommitteePda");
+        assert_eq!(committee.members, vec!["M1", "M2", "M3"]);
+        assert_eq!(committee.threshold, 2);
+
+        assert_eq!(evt.authority, "AuthA");
+        assert_eq!(evt.old_oracle, "OracleA");
+        assert_eq!(evt.committee, "CommitteePda");
+        assert_eq!(evt.threshold, 2);
+        assert_eq!(evt.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn soc_rej_001_to_003_error_mapping() {
+        let mut bad = base_input();
+        bad.authority = "OracleA".to_string();
+        assert_eq!(set_oracle_committee(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        let mut bad = base_input();
+        bad.members.clear();
+        bad.threshold = 0;
+        assert_eq!(set_oracle_committee(bad).unwrap_err(), PitStopError::InvalidCommittee);
+
+        let mut bad = base_input();
+        bad.members = (0..=MAX_COMMITTEE_MEMBERS).map(|i| format!("M{i}")).collect();
+        bad.threshold = MAX_COMMITTEE_MEMBERS;
+        assert_eq!(set_oracle_committee(bad).unwrap_err(), PitStopError::InvalidCommittee);
+
+        let mut bad = base_input();
+        bad.members[2] = "M1".to_string();
+        assert_eq!(set_oracle_committee(bad).unwrap_err(), PitStopError::InvalidCommittee);
+
+        let mut bad = base_input();
+        bad.threshold = 4;
+        assert_eq!(set_oracle_committee(bad).unwrap_err(), PitStopError::InvalidCommittee);
+
+        // 2 of 4 is not a majority: a 2-2 split would give both sides quorum.
+        let mut bad = base_input();
+        bad.members.push("M4".to_string());
+        assert_eq!(set_oracle_committee(bad).unwrap_err(), PitStopError::InvalidCommittee);
+    }
+}
@@ -0,0 +1,109 @@
+//! set_paused Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/set_paused.md
+//!
+//! Toggles the protocol circuit breaker (`config.paused`). Which instructions
+//! honor the flag is defined by the pause policy in SPEC_PROTOCOL.md.
+
+use crate::{error::PitStopError, events::ProtocolPauseChanged, state::Config};
+
+#[derive(Debug, Clone)]
+pub struct SetPausedInput {
+    pub authority: String,
+    pub paused: bool,
+    pub now_ts: i64,
+    pub config_state: Config,
+}
+
+fn validate_set_paused_preconditions(input: &SetPausedInput) -> Result<(), PitStopError> {
+    // SPA-REJ-001: only config authority can flip the breaker.
+    if input.authority != input.config_state.authority {
+        return Err(PitStopError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - config.paused = paused (idempotent; re-setting the current value is allowed)
+/// - emit ProtocolPauseChanged
+pub fn set_paused(input: SetPausedInput) -> Result<(Config, ProtocolPauseChanged), PitStopError> {
+    validate_set_paused_preconditions(&input)?;
+
+    let mut config = input.config_state;
+    config.paused = input.paused;
+
+    let evt = ProtocolPauseChanged {
+        authority: input.authority,
+        paused: input.paused,
+        timestamp: input.now_ts,
+    };
+
+    Ok((config, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
+
+    fn base_config() -> Config {
+        Config {
+            authority: "AuthA".to_string(),
+            oracle: "AuthA".to_string(),
+            usdc_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            treasury_authority: "TreasuryOwnerA".to_string(),
+            fee_bps: 0,
+            paused: false,
+            max_total_pool_per_market: 1_000_000,
+            max_bet_per_user_per_market: 100_000,
+            claim_window_secs: 3600,
+            dispute_window_secs: 0,
+            resolution_deadline_secs: 0,
+            early_exit_fee_bps: 0,
+            token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+            pending_authority: None,
+            payout_keeper: None,
+        }
+    }
+
+    fn base_input() -> SetPausedInput {
+        SetPausedInput {
+            authority: "AuthA".to_string(),
+            paused: true,
+            now_ts: 1_800_000_000,
+            config_state: base_config(),
+        }
+    }
+
+    #[test]
+    fn spa_hp_001_pause_then_unpause() {
+        let (cfg, evt) = set_paused(base_input()).expect("pause should pass");
+        assert!(cfg.paused);
+        assert_eq!(evt.authority, "AuthA");
+        assert!(evt.paused);
+        assert_eq!(evt.timestamp, 1_800_000_000);
+
+        let mut input = base_input();
+        input.config_state = cfg;
+        input.paused = false;
+        let (cfg, evt) = set_paused(input).expect("unpause should pass");
+        assert!(!cfg.paused);
+        assert!(!evt.paused);
+    }
+
+    #[test]
+    fn spa_hp_002_setting_current_value_is_idempotent() {
+        let mut input = base_input();
+        input.paused = false;
+        let (cfg, _evt) = set_paused(input).expect("no-op set should pass");
+        assert_eq!(cfg, base_config());
+    }
+
+    #[test]
+    fn spa_rej_001_non_authority_rejected() {
+        let mut bad = base_input();
+        bad.authority = "Other".to_string();
+        assert_eq!(set_paused(bad).unwrap_err(), PitStopError::Unauthorized);
+    }
+}
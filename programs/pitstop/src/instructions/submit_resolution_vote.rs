@@ -0,0 +1,263 @@
+//! submit_resolution_vote Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/submit_resolution_vote.md
+//!
+//! Records an oracle committee member's vote on a Locked market. Votes only tally;
+//! `resolve_market` / `void_market` finalize once `threshold` current members agree on
+//! the same decision and payload hash (`quorum_reached`).
+
+use std::collections::BTreeSet;
+
+use crate::{
+    constants::PODIUM_SIZE,
+    error::PitStopError,
+    events::ResolutionVoteSubmitted,
+    state::{Market, MarketStatus, OracleCommittee, ResolutionDecision, ResolutionVote},
+};
+
+#[derive(Debug, Clone)]
+pub struct SubmitResolutionVoteInput {
+    pub member: String,
+    pub config_oracle: String,
+    /// OracleCommittee PDA address; the committee is active while it is `config.oracle`.
+    pub committee_address: String,
+    pub committee: OracleCommittee,
+    pub market: String,
+    pub market_state: Market,
+    pub decision: ResolutionDecision,
+    pub payload_hash: [u8; 32],
+    /// Votes already recorded for this market.
+    pub votes: Vec<ResolutionVote>,
+    pub now_ts: i64,
+}
+
+/// Distinct current committee members whose vote is exactly `decision` + `payload_hash`.
+/// Votes by keys no longer on the committee do not count.
+pub fn matching_votes(
+    committee: &OracleCommittee,
+    votes: &[ResolutionVote],
+    decision: &ResolutionDecision,
+    payload_hash: &[u8; 32],
+) -> usize {
+    votes
+        .iter()
+        .filter(|vote| {
+            committee.members.contains(&vote.member)
+                && vote.decision == *decision
+                && vote.payload_hash == *payload_hash
+        })
+        .map(|vote| vote.member.as_str())
+        .collect::<BTreeSet<_>>()
+        .len()
+}
+
+pub fn quorum_reached(
+    committee: &OracleCommittee,
+    votes: &[ResolutionVote],
+    decision: &ResolutionDecision,
+    payload_hash: &[u8; 32],
+) -> bool {
+    committee.threshold > 0
+        && matching_votes(committee, votes, decision, payload_hash)
+            >= usize::from(committee.threshold)
+}
+
+fn validate_submit_resolution_vote_preconditions(
+    input: &SubmitResolutionVoteInput,
+) -> Result<(), PitStopError> {
+    // SRV-REJ-001: votes only matter while the committee is the resolution signer.
+    if input.config_oracle != input.committee_address {
+        return Err(PitStopError::UnauthorizedOracle);
+    }
+    // SRV-REJ-002
+    if !input.committee.members.contains(&input.member) {
+        return Err(PitStopError::NotCommitteeMember);
+    }
+    // SRV-REJ-003: voting happens between lock and finalization.
+    if input.market_state.status != MarketStatus::Locked {
+        return Err(PitStopError::MarketNotLocked);
+    }
+    // SRV-REJ-004: winner set must fit the market's storage; full outcome validation
+    // happens when resolve_market finalizes.
+    if let ResolutionDecision::Resolve { winning_outcomes } = &input.decision {
+        if winning_outcomes.is_empty() || winning_outcomes.len() > usize::from(PODIUM_SIZE) {
+            return Err(PitStopError::InvalidOutcomeId);
+        }
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - votes -= member's previous vote and votes of former members
+/// - votes += { member, decision, payload_hash }
+/// - emit ResolutionVoteSubmitted
+pub fn submit_resolution_vote(
+    input: SubmitResolutionVoteInput,
+) -> Result<(Vec<ResolutionVote>, ResolutionVoteSubmitted), PitStopError> {
+    validate_submit_resolution_vote_preconditions(&input)?;
+
+    // Pruning former members keeps the tally within MAX_COMMITTEE_MEMBERS entries.
+    let mut votes = input.votes;
+    votes.retain(|vote| vote.member != input.member && input.committee.members.contains(&vote.member));
+    votes.push(ResolutionVote {
+        member: input.member.clone(),
+        decision: input.decision.clone(),
+        payload_hash: input.payload_hash,
+    });
+
+    let matching = matching_votes(&input.committee, &votes, &input.decision, &input.payload_hash);
+    let (void, winning_outcomes) = match input.decision {
+        ResolutionDecision::Resolve { winning_outcomes } => (false, winning_outcomes),
+        ResolutionDecision::Void => (true, Vec::new()),
+    };
+
+    let evt = ResolutionVoteSubmitted {
+        market: input.market,
+        member: input.member,
+        void,
+        winning_outcomes,
+        payload_hash: input.payload_hash,
+        matching_votes: matching as u8,
+        threshold: input.committee.threshold,
+        timestamp: input.now_ts,
+    };
+
+    Ok((votes, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_market() -> Market {
+        Market {
+            market_id: [1u8; 32],
+            event_id: [2u8; 32],
+            lock_timestamp: 1_800_000_000,
+            outcome_count: 3,
+            max_outcomes: 3,
+            total_pool: 1000,
+            status: MarketStatus::Locked,
+            resolved_outcome: None,
+            resolution_payload_hash: [0u8; 32],
+            resolution_timestamp: 0,
+            vault: "VaultA".to_string(),
+            market_type: 0,
+            rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
+        }
+    }
+
+    fn committee() -> OracleCommittee {
+        OracleCommittee {
+            members: vec!["M1".to_string(), "M2".to_string(), "M3".to_string()],
+            threshold: 2,
+        }
+    }
+
+    fn resolve(id: u8) -> ResolutionDecision {
+        ResolutionDecision::Resolve {
+            winning_outcomes: vec![id],
+        }
+    }
+
+    fn vote(member: &str, decision: ResolutionDecision, hash: u8) -> ResolutionVote {
+        ResolutionVote {
+            member: member.to_string(),
+            decision,
+            payload_hash: [hash; 32],
+        }
+    }
+
+    fn base_input() -> SubmitResolutionVoteInput {
+        SubmitResolutionVoteInput {
+            member: "M2".to_string(),
+            config_oracle: "CommitteePda".to_string(),
+            committee_address: "CommitteePda".to_string(),
+            committee: committee(),
+            market: "MarketA".to_string(),
+            market_state: base_market(),
+            decision: resolve(1),
+            payload_hash: [7u8; 32],
+            votes: vec![vote("M1", resolve(1), 7)],
+            now_ts: 1_800_000_100,
+        }
+    }
+
+    #[test]
+    fn srv_hp_001_records_vote_and_reports_tally() {
+        let (votes, evt) = submit_resolution_vote(base_input()).expect("vote should pass");
+        assert_eq!(votes.len(), 2);
+        assert!(quorum_reached(&committee(), &votes, &resolve(1), &[7u8; 32]));
+
+        assert_eq!(evt.market, "MarketA");
+        assert_eq!(evt.member, "M2");
+        assert!(!evt.void);
+        assert_eq!(evt.winning_outcomes, vec![1]);
+        assert_eq!(evt.matching_votes, 2);
+        assert_eq!(evt.threshold, 2);
+        assert_eq!(evt.timestamp, 1_800_000_100);
+    }
+
+    #[test]
+    fn srv_hp_002_revote_replaces_and_prunes_former_members() {
+        let mut input = base_input();
+        input.member = "M1".to_string();
+        input.decision = ResolutionDecision::Void;
+        input.votes = vec![vote("M1", resolve(1), 7), vote("Gone", ResolutionDecision::Void, 7)];
+        let (votes, evt) = submit_resolution_vote(input).expect("revote should pass");
+        assert_eq!(votes, vec![vote("M1", ResolutionDecision::Void, 7)]);
+        assert!(evt.void);
+        assert!(evt.winning_outcomes.is_empty());
+        assert_eq!(evt.matching_votes, 1);
+    }
+
+    #[test]
+    fn srv_rej_001_to_004_error_mapping() {
+        let mut bad = base_input();
+        bad.config_oracle = "OracleA".to_string();
+        assert_eq!(submit_resolution_vote(bad).unwrap_err(), PitStopError::UnauthorizedOracle);
+
+        let mut bad = base_input();
+        bad.member = "Outsider".to_string();
+        assert_eq!(submit_resolution_vote(bad).unwrap_err(), PitStopError::NotCommitteeMember);
+
+        let mut bad = base_input();
+        bad.market_state.status = MarketStatus::Open;
+        assert_eq!(submit_resolution_vote(bad).unwrap_err(), PitStopError::MarketNotLocked);
+
+        let mut bad = base_input();
+        bad.market_state.status = MarketStatus::Resolved;
+        assert_eq!(submit_resolution_vote(bad).unwrap_err(), PitStopError::MarketNotLocked);
+
+        let mut bad = base_input();
+        bad.decision = ResolutionDecision::Resolve {
+            winning_outcomes: vec![0, 1, 2, 3],
+        };
+        assert_eq!(submit_resolution_vote(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
+    }
+
+    #[test]
+    fn srv_adv_001_quorum_needs_same_decision_hash_and_current_members() {
+        let c = committee();
+        let hash = [7u8; 32];
+        // Split on outcome, on hash, or counting a removed member: no quorum.
+        assert!(!quorum_reached(&c, &[vote("M1", resolve(1), 7), vote("M2", resolve(2), 7)], &resolve(1), &hash));
+        assert!(!quorum_reached(&c, &[vote("M1", resolve(1), 7), vote("M2", resolve(1), 8)], &resolve(1), &hash));
+        assert!(!quorum_reached(&c, &[vote("M1", resolve(1), 7), vote("Gone", resolve(1), 7)], &resolve(1), &hash));
+        // Duplicate entries for one member count once.
+        assert!(!quorum_reached(&c, &[vote("M1", resolve(1), 7), vote("M1", resolve(1), 7)], &resolve(1), &hash));
+        assert!(quorum_reached(&c, &[vote("M3", resolve(1), 7), vote("M1", resolve(1), 7)], &resolve(1), &hash));
+        assert!(!quorum_reached(&OracleCommittee::default(), &[], &ResolutionDecision::Void, &hash));
+    }
+}
@@ -14,9 +14,11 @@
 /// integration pass. We still model the required close semantics as booleans to
 /// keep conformance assertions explicit.
 use crate::{
-    constants::REQUIRED_TOKEN_PROGRAM,
+    constants::is_supported_token_program,
     error::PitStopError,
     events::MarketSweptEvent,
+    instructions::claim_resolved::claim_window_end,
+    math,
     state::{Market, MarketStatus},
 };
 
@@ -32,9 +34,7 @@ pub struct SweepRemainingInput {
     pub token_program: String,
 
     pub treasury: String,
-    pub config_treasury: String,
     pub treasury_mint: String,
-    pub usdc_mint: String,
     pub treasury_owner: String,
     pub treasury_authority: String,
 
@@ -53,33 +53,37 @@ fn validate_sweep_remaining_preconditions(input: &SweepRemainingInput) -> Result
     }
 
     // Token program pinned.
-    if input.token_program != REQUIRED_TOKEN_PROGRAM {
+    if !is_supported_token_program(&input.token_program) {
         return Err(PitStopError::InvalidTokenProgram);
     }
 
-    // SWP-REJ-002: market must be in {Resolved, Voided}. (Includes Swept -> deterministic gate)
-    if input.market_state.status != MarketStatus::Resolved
-        && input.market_state.status != MarketStatus::Voided
-    {
+    // SWP-REJ-002: market must be in {PendingResolution, Resolved, Voided}.
+    // (Includes Swept -> deterministic gate)
+    if !matches!(
+        input.market_state.status,
+        MarketStatus::PendingResolution | MarketStatus::Resolved | MarketStatus::Voided
+    ) {
         return Err(PitStopError::MarketNotResolved);
     }
 
-    // SWP-WIN-001: claim window must be expired.
-    let claim_window_end = input
-        .market_state
-        .resolution_timestamp
-        .checked_add(input.claim_window_secs)
-        .ok_or(PitStopError::Overflow)?;
+    // SWP-WIN-001: claim window must be expired. It starts after any dispute window,
+    // which also keeps a still-disputable market from being swept.
+    let claim_window_end = claim_window_end(
+        input.market_state.resolution_timestamp,
+        input.market_state.dispute_deadline,
+        input.claim_window_secs,
+    )?;
     if input.now_ts <= claim_window_end {
         return Err(PitStopError::ClaimWindowNotExpired);
     }
 
-    // Treasury constraints (mint + owner) must match config.
+    // Treasury must be the one recorded for the market's collateral mint at create_market;
+    // its owner must still be config.treasury_authority.
     // Note: JS parity maps treasury address mismatch to InvalidTreasuryOwner.
-    if input.treasury != input.config_treasury {
+    if input.treasury != input.market_state.treasury {
         return Err(PitStopError::InvalidTreasuryOwner);
     }
-    if input.treasury_mint != input.usdc_mint {
+    if input.treasury_mint != input.market_state.collateral_mint {
         return Err(PitStopError::InvalidTreasuryMint);
     }
     if input.treasury_owner != input.treasury_authority {
@@ -104,10 +108,7 @@ pub fn sweep_remaining(
     validate_sweep_remaining_preconditions(&input)?;
 
     let swept_amount = input.vault_amount;
-    let treasury_amount = input
-        .treasury_amount
-        .checked_add(swept_amount)
-        .ok_or(PitStopError::Overflow)?;
+    let treasury_amount = math::credit(input.treasury_amount, swept_amount)?;
 
     // Lifecycle terminal transition.
     let mut market = input.market_state;
@@ -140,6 +141,7 @@ pub fn sweep_remaining(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM;
 
     fn base_market(status: MarketStatus) -> Market {
         Market {
@@ -156,6 +158,17 @@ mod tests {
             vault: "VaultA".to_string(),
             market_type: 0,
             rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
         }
     }
 
@@ -172,9 +185,7 @@ mod tests {
             claim_window_secs,
             token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
             treasury: "TreasuryA".to_string(),
-            config_treasury: "TreasuryA".to_string(),
             treasury_mint: "MintA".to_string(),
-            usdc_mint: "MintA".to_string(),
             treasury_owner: "TreasuryAuthA".to_string(),
             treasury_authority: "TreasuryAuthA".to_string(),
             vault_amount: 123,
@@ -245,7 +256,7 @@ mod tests {
 
         // SWP-REJ-004 treasury constraints
         let mut bad = base_input();
-        bad.config_treasury = "OtherTreasury".to_string();
+        bad.market_state.treasury = "OtherTreasury".to_string();
         assert_eq!(
             sweep_remaining(bad).unwrap_err(),
             PitStopError::InvalidTreasuryOwner
@@ -273,4 +284,26 @@ mod tests {
             PitStopError::InvalidTokenProgram
         );
     }
+
+    #[test]
+    fn swp_win_002_claim_window_starts_after_dispute_deadline() {
+        let pending = || {
+            let mut input = base_input();
+            input.market_state.status = MarketStatus::PendingResolution;
+            input.market_state.dispute_deadline = input.market_state.resolution_timestamp + 3600;
+            input
+        };
+
+        let mut bad = pending();
+        bad.now_ts = bad.market_state.dispute_deadline + bad.claim_window_secs;
+        assert_eq!(
+            sweep_remaining(bad).unwrap_err(),
+            PitStopError::ClaimWindowNotExpired
+        );
+
+        let mut input = pending();
+        input.now_ts = input.market_state.dispute_deadline + input.claim_window_secs + 1;
+        let (m, ..) = sweep_remaining(input).expect("sweep after delayed window should pass");
+        assert_eq!(m.status, MarketStatus::Swept);
+    }
 }
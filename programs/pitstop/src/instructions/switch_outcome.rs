@@ -0,0 +1,281 @@
+//! switch_outcome Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/switch_outcome.md
+//!
+//! Moves stake from the user's position on one outcome to their position on another
+//! outcome of the same market while betting is open. Only the two outcome pools and
+//! positions change: `market.total_pool` and the vault stay as they are, so no token
+//! transfer is needed. The user's total stake in the market is unchanged too, so the
+//! per-user cap (which covers every outcome) has nothing new to check.
+
+use crate::{
+    error::PitStopError,
+    events::OutcomeSwitched,
+    math,
+    state::{Market, MarketStatus, OutcomePool, Position, UserMarketStats},
+};
+
+#[derive(Debug, Clone)]
+pub struct SwitchOutcomeInput {
+    pub market: String,
+    pub user: String,
+    pub from_outcome_id: u8,
+    pub to_outcome_id: u8,
+    /// Stake to move from the `from` position to the `to` position.
+    pub amount: u64,
+    pub config_paused: bool,
+    pub now_ts: i64,
+    pub market_state: Market,
+    pub from_pool_state: OutcomePool,
+    pub to_pool_state: OutcomePool,
+    pub from_position_state: Position,
+    /// Fresh (amount 0) when the user had no stake on `to_outcome_id` yet.
+    pub to_position_state: Position,
+    pub user_market_stats_state: UserMarketStats,
+}
+
+fn validate_switch_outcome_preconditions(input: &SwitchOutcomeInput) -> Result<(), PitStopError> {
+    // SWO-REJ-001: moving stake onto an outcome is new betting exposure, halted by pause.
+    if input.config_paused {
+        return Err(PitStopError::ProtocolPaused);
+    }
+    // SWO-REJ-002: same betting window as place_bet.
+    if input.market_state.status != MarketStatus::Open {
+        return Err(PitStopError::MarketNotOpen);
+    }
+    if input.now_ts >= input.market_state.lock_timestamp {
+        return Err(PitStopError::BettingClosed);
+    }
+    // SWO-REJ-003
+    if input.to_outcome_id > 99 {
+        return Err(PitStopError::InvalidOutcomeId);
+    }
+    if input.from_outcome_id == input.to_outcome_id {
+        return Err(PitStopError::SameOutcomeSwitch);
+    }
+    // SWO-REJ-004
+    if input.amount == 0 {
+        return Err(PitStopError::ZeroAmount);
+    }
+    // SWO-REJ-005: both pools and positions must belong to this market and their outcome.
+    if input.from_pool_state.market != input.market
+        || input.from_pool_state.outcome_id != input.from_outcome_id
+        || input.to_pool_state.market != input.market
+        || input.to_pool_state.outcome_id != input.to_outcome_id
+        || input.from_position_state.market != input.market
+        || input.from_position_state.outcome_id != input.from_outcome_id
+        || input.to_position_state.market != input.market
+        || input.to_position_state.outcome_id != input.to_outcome_id
+    {
+        return Err(PitStopError::OutcomeMismatch);
+    }
+    if input.from_position_state.user != input.user || input.to_position_state.user != input.user
+    {
+        return Err(PitStopError::Unauthorized);
+    }
+    // SWO-REJ-006: cannot move more than the source position holds.
+    if input.amount > input.from_position_state.amount {
+        return Err(PitStopError::ReductionExceedsPosition);
+    }
+    Ok(())
+}
+
+/// Effects:
+/// - from_position.amount, from_pool.pool_amount -= amount
+/// - to_position.amount, to_pool.pool_amount += amount
+/// - market.total_pool, the vault and user_market_stats.total_staked unchanged
+/// - to_outcome_id recorded in user_market_stats.outcome_ids
+/// - emit OutcomeSwitched
+pub fn switch_outcome(
+    input: SwitchOutcomeInput,
+) -> Result<(OutcomePool, OutcomePool, Position, Position, UserMarketStats, OutcomeSwitched), PitStopError> {
+    validate_switch_outcome_preconditions(&input)?;
+
+    let mut from_pool = input.from_pool_state;
+    from_pool.pool_amount = math::debit(from_pool.pool_amount, input.amount)?;
+    let mut to_pool = input.to_pool_state;
+    to_pool.pool_amount = math::credit(to_pool.pool_amount, input.amount)?;
+    let mut from_position = input.from_position_state;
+    from_position.amount = math::debit(from_position.amount, input.amount)?;
+    let mut to_position = input.to_position_state;
+    to_position.amount = math::credit(to_position.amount, input.amount)?;
+    let mut user_market_stats = input.user_market_stats_state;
+    user_market_stats.record_outcome(input.to_outcome_id);
+
+    let evt = OutcomeSwitched {
+        market: input.market,
+        user: input.user,
+        from_outcome_id: input.from_outcome_id,
+        to_outcome_id: input.to_outcome_id,
+        amount: input.amount,
+        from_position_amount: from_position.amount,
+        to_position_amount: to_position.amount,
+        from_outcome_pool_amount: from_pool.pool_amount,
+        to_outcome_pool_amount: to_pool.pool_amount,
+        timestamp: input.now_ts,
+    };
+
+    Ok((from_pool, to_pool, from_position, to_position, user_market_stats, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_market() -> Market {
+        Market {
+            market_id: [1u8; 32],
+            event_id: [2u8; 32],
+            lock_timestamp: 1_800_000_100,
+            outcome_count: 3,
+            max_outcomes: 3,
+            total_pool: 1_000,
+            status: MarketStatus::Open,
+            resolved_outcome: None,
+            resolution_payload_hash: [0u8; 32],
+            resolution_timestamp: 0,
+            vault: "VaultA".to_string(),
+            market_type: 0,
+            rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
+        }
+    }
+
+    fn pool(outcome_id: u8, pool_amount: u64) -> OutcomePool {
+        OutcomePool {
+            market: "MarketA".to_string(),
+            outcome_id,
+            pool_amount,
+        }
+    }
+
+    fn position(outcome_id: u8, amount: u64) -> Position {
+        Position {
+            market: "MarketA".to_string(),
+            user: "UserA".to_string(),
+            outcome_id,
+            amount,
+            claimed: false,
+            payout: 0,
+        }
+    }
+
+    fn base_input() -> SwitchOutcomeInput {
+        SwitchOutcomeInput {
+            market: "MarketA".to_string(),
+            user: "UserA".to_string(),
+            from_outcome_id: 0,
+            to_outcome_id: 2,
+            amount: 300,
+            config_paused: false,
+            now_ts: 1_800_000_000,
+            market_state: base_market(),
+            from_pool_state: pool(0, 600),
+            to_pool_state: pool(2, 100),
+            from_position_state: position(0, 400),
+            to_position_state: position(2, 100),
+            user_market_stats_state: UserMarketStats {
+                market: "MarketA".to_string(),
+                user: "UserA".to_string(),
+                total_staked: 500,
+                outcome_ids: vec![0, 2],
+            },
+        }
+    }
+
+    #[test]
+    fn swo_hp_001_moves_stake_between_pools_and_positions() {
+        let (from_pool, to_pool, from_pos, to_pos, stats, e) =
+            switch_outcome(base_input()).expect("switch should pass");
+        assert_eq!((from_pool.pool_amount, to_pool.pool_amount), (300, 400));
+        assert_eq!((from_pos.amount, to_pos.amount), (100, 400));
+        assert_eq!((e.from_outcome_id, e.to_outcome_id, e.amount), (0, 2, 300));
+        assert_eq!(
+            (
+                e.from_position_amount,
+                e.to_position_amount,
+                e.from_outcome_pool_amount,
+                e.to_outcome_pool_amount
+            ),
+            (100, 400, 300, 400)
+        );
+        // Pools move by the same amount in opposite directions: total_pool is unchanged.
+        assert_eq!(from_pool.pool_amount + to_pool.pool_amount, 600 + 100);
+        assert_eq!((stats.total_staked, stats.outcome_ids), (500, vec![0, 2]));
+    }
+
+    #[test]
+    fn swo_hp_002_full_switch_into_fresh_position_records_outcome() {
+        let mut input = base_input();
+        input.amount = 400;
+        input.to_outcome_id = 1;
+        input.to_pool_state = pool(1, 0);
+        input.to_position_state = position(1, 0);
+        let (_fp, _tp, from_pos, to_pos, stats, _e) =
+            switch_outcome(input).expect("full switch should pass");
+        assert_eq!((from_pos.amount, to_pos.amount), (0, 400));
+        // The emptied source position stays open, so outcome 0 stays listed.
+        assert_eq!((stats.total_staked, stats.outcome_ids), (500, vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn swo_rej_001_to_006_error_matrix() {
+        let mut bad = base_input();
+        bad.config_paused = true;
+        assert_eq!(switch_outcome(bad).unwrap_err(), PitStopError::ProtocolPaused);
+
+        let mut bad = base_input();
+        bad.market_state.status = MarketStatus::Locked;
+        assert_eq!(switch_outcome(bad).unwrap_err(), PitStopError::MarketNotOpen);
+
+        let mut bad = base_input();
+        bad.now_ts = bad.market_state.lock_timestamp;
+        assert_eq!(switch_outcome(bad).unwrap_err(), PitStopError::BettingClosed);
+
+        let mut bad = base_input();
+        bad.to_outcome_id = 100;
+        assert_eq!(switch_outcome(bad).unwrap_err(), PitStopError::InvalidOutcomeId);
+
+        let mut bad = base_input();
+        bad.to_outcome_id = 0;
+        assert_eq!(switch_outcome(bad).unwrap_err(), PitStopError::SameOutcomeSwitch);
+
+        let mut bad = base_input();
+        bad.amount = 0;
+        assert_eq!(switch_outcome(bad).unwrap_err(), PitStopError::ZeroAmount);
+
+        let mut bad = base_input();
+        bad.to_pool_state.outcome_id = 1;
+        assert_eq!(switch_outcome(bad).unwrap_err(), PitStopError::OutcomeMismatch);
+
+        let mut bad = base_input();
+        bad.to_position_state.user = "UserB".to_string();
+        assert_eq!(switch_outcome(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        let mut bad = base_input();
+        bad.amount = 401;
+        assert_eq!(
+            switch_outcome(bad).unwrap_err(),
+            PitStopError::ReductionExceedsPosition
+        );
+    }
+
+    #[test]
+    fn swo_ord_001_pause_before_status_and_amount_checks() {
+        let mut bad = base_input();
+        bad.config_paused = true;
+        bad.market_state.status = MarketStatus::Locked;
+        bad.amount = 0;
+        assert_eq!(switch_outcome(bad).unwrap_err(), PitStopError::ProtocolPaused);
+    }
+}
@@ -0,0 +1,307 @@
+//! update_config Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/update_config.md
+//!
+//! Lets config.authority retune fee and operational limits after `initialize`.
+//! Every written value is held to the same rules `initialize` enforces.
+
+use crate::{
+    constants::MAX_FEE_BPS,
+    error::PitStopError,
+    events::ConfigUpdated,
+    instructions::initialize::{
+        validate_caps, validate_claim_window, validate_dispute_window, validate_resolution_deadline,
+    },
+    state::Config,
+};
+
+#[derive(Debug, Clone)]
+pub struct UpdateConfigInput {
+    pub authority: String,
+
+    // `None` keeps the current config value.
+    pub fee_bps: Option<u16>,
+    pub max_total_pool_per_market: Option<u64>,
+    pub max_bet_per_user_per_market: Option<u64>,
+    pub claim_window_secs: Option<i64>,
+    pub dispute_window_secs: Option<i64>,
+    pub resolution_deadline_secs: Option<i64>,
+    pub early_exit_fee_bps: Option<u16>,
+    /// `Some(None)` removes the keeper.
+    pub payout_keeper: Option<Option<String>>,
+
+    pub now_ts: i64,
+    pub config_state: Config,
+}
+
+fn validate_update_config_preconditions(input: &UpdateConfigInput) -> Result<Config, PitStopError> {
+    // UPC-REJ-001: only config authority can update parameters.
+    if input.authority != input.config_state.authority {
+        return Err(PitStopError::Unauthorized);
+    }
+
+    let mut next = input.config_state.clone();
+    if let Some(fee_bps) = input.fee_bps {
+        next.fee_bps = fee_bps;
+    }
+    if let Some(max_total) = input.max_total_pool_per_market {
+        next.max_total_pool_per_market = max_total;
+    }
+    if let Some(max_bet) = input.max_bet_per_user_per_market {
+        next.max_bet_per_user_per_market = max_bet;
+    }
+    if let Some(window) = input.claim_window_secs {
+        next.claim_window_secs = window;
+    }
+    if let Some(window) = input.dispute_window_secs {
+        next.dispute_window_secs = window;
+    }
+    if let Some(deadline) = input.resolution_deadline_secs {
+        next.resolution_deadline_secs = deadline;
+    }
+    if let Some(fee_bps) = input.early_exit_fee_bps {
+        next.early_exit_fee_bps = fee_bps;
+    }
+    if let Some(keeper) = &input.payout_keeper {
+        next.payout_keeper = keeper.clone();
+    }
+
+    // UPC-REJ-002: both fees bounded by the protocol maximum.
+    if next.fee_bps > MAX_FEE_BPS || next.early_exit_fee_bps > MAX_FEE_BPS {
+        return Err(PitStopError::FeeTooHigh);
+    }
+    // UPC-REJ-003: caps are validated on the resulting pair, so updating one side
+    // cannot leave max_bet > max_total.
+    validate_caps(next.max_total_pool_per_market, next.max_bet_per_user_per_market)?;
+    // UPC-REJ-004
+    validate_claim_window(next.claim_window_secs)?;
+    // UPC-REJ-005: 0 (disabled) up to MAX_DISPUTE_WINDOW_SECS. Only markets resolved
+    // afterwards use the new value; existing deadlines are fixed at resolve time.
+    validate_dispute_window(next.dispute_window_secs)?;
+    // UPC-REJ-006: 0 (disabled) up to MAX_RESOLUTION_DEADLINE_SECS. Read at
+    // void_stale_market time, so it also applies to markets already Locked.
+    validate_resolution_deadline(next.resolution_deadline_secs)?;
+
+    Ok(next)
+}
+
+pub fn update_config(input: UpdateConfigInput) -> Result<(Config, ConfigUpdated), PitStopError> {
+    let config = validate_update_config_preconditions(&input)?;
+    let old = input.config_state;
+
+    let evt = ConfigUpdated {
+        authority: input.authority,
+        old_fee_bps: old.fee_bps,
+        new_fee_bps: config.fee_bps,
+        old_max_total_pool_per_market: old.max_total_pool_per_market,
+        new_max_total_pool_per_market: config.max_total_pool_per_market,
+        old_max_bet_per_user_per_market: old.max_bet_per_user_per_market,
+        new_max_bet_per_user_per_market: config.max_bet_per_user_per_market,
+        old_claim_window_secs: old.claim_window_secs,
+        new_claim_window_secs: config.claim_window_secs,
+        old_dispute_window_secs: old.dispute_window_secs,
+        new_dispute_window_secs: config.dispute_window_secs,
+        old_resolution_deadline_secs: old.resolution_deadline_secs,
+        new_resolution_deadline_secs: config.resolution_deadline_secs,
+        old_early_exit_fee_bps: old.early_exit_fee_bps,
+        new_early_exit_fee_bps: config.early_exit_fee_bps,
+        old_payout_keeper: old.payout_keeper,
+        new_payout_keeper: config.payout_keeper.clone(),
+        timestamp: input.now_ts,
+    };
+
+    Ok((config, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        MAX_CLAIM_WINDOW_SECS, MAX_DISPUTE_WINDOW_SECS, MAX_RESOLUTION_DEADLINE_SECS,
+        REQUIRED_TOKEN_PROGRAM,
+    };
+
+    fn base_config() -> Config {
+        Config {
+            authority: "AuthA".to_string(),
+            oracle: "AuthA".to_string(),
+            usdc_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            treasury_authority: "TreasuryOwnerA".to_string(),
+            fee_bps: 0,
+            paused: false,
+            max_total_pool_per_market: 1_000_000,
+            max_bet_per_user_per_market: 100_000,
+            claim_window_secs: 3600,
+            dispute_window_secs: 0,
+            resolution_deadline_secs: 0,
+            early_exit_fee_bps: 0,
+            token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+            pending_authority: None,
+            payout_keeper: None,
+        }
+    }
+
+    fn base_input() -> UpdateConfigInput {
+        UpdateConfigInput {
+            authority: "AuthA".to_string(),
+            fee_bps: Some(250),
+            max_total_pool_per_market: Some(2_000_000),
+            max_bet_per_user_per_market: Some(50_000),
+            claim_window_secs: Some(7200),
+            dispute_window_secs: Some(86_400),
+            resolution_deadline_secs: Some(172_800),
+            early_exit_fee_bps: Some(100),
+            payout_keeper: Some(Some("KeeperA".to_string())),
+            now_ts: 1_800_000_000,
+            config_state: base_config(),
+        }
+    }
+
+    #[test]
+    fn upc_hp_001_updates_fields_and_emits_old_and_new_values() {
+        let (cfg, evt) = update_config(base_input()).expect("update_config should pass");
+
+        assert_eq!(cfg.fee_bps, 250);
+        assert_eq!(cfg.max_total_pool_per_market, 2_000_000);
+        assert_eq!(cfg.max_bet_per_user_per_market, 50_000);
+        assert_eq!(cfg.claim_window_secs, 7200);
+        assert_eq!(cfg.dispute_window_secs, 86_400);
+        assert_eq!(cfg.resolution_deadline_secs, 172_800);
+        assert_eq!(cfg.early_exit_fee_bps, 100);
+        assert_eq!(cfg.payout_keeper.as_deref(), Some("KeeperA"));
+        assert_eq!(cfg.authority, "AuthA");
+        assert!(!cfg.paused);
+
+        assert_eq!(evt.authority, "AuthA");
+        assert_eq!((evt.old_fee_bps, evt.new_fee_bps), (0, 250));
+        assert_eq!(
+            (evt.old_max_total_pool_per_market, evt.new_max_total_pool_per_market),
+            (1_000_000, 2_000_000)
+        );
+        assert_eq!(
+            (evt.old_max_bet_per_user_per_market, evt.new_max_bet_per_user_per_market),
+            (100_000, 50_000)
+        );
+        assert_eq!((evt.old_claim_window_secs, evt.new_claim_window_secs), (3600, 7200));
+        assert_eq!((evt.old_dispute_window_secs, evt.new_dispute_window_secs), (0, 86_400));
+        assert_eq!(
+            (evt.old_resolution_deadline_secs, evt.new_resolution_deadline_secs),
+            (0, 172_800)
+        );
+        assert_eq!((evt.old_early_exit_fee_bps, evt.new_early_exit_fee_bps), (0, 100));
+        assert_eq!(
+            (evt.old_payout_keeper, evt.new_payout_keeper.as_deref()),
+            (None, Some("KeeperA"))
+        );
+        assert_eq!(evt.timestamp, 1_800_000_000);
+    }
+
+    #[test]
+    fn upc_hp_002_none_fields_keep_current_values() {
+        let mut input = base_input();
+        input.fee_bps = None;
+        input.max_total_pool_per_market = None;
+        input.claim_window_secs = None;
+
+        let (cfg, evt) = update_config(input).expect("partial update should pass");
+        assert_eq!(cfg.fee_bps, 0);
+        assert_eq!(cfg.max_total_pool_per_market, 1_000_000);
+        assert_eq!(cfg.max_bet_per_user_per_market, 50_000);
+        assert_eq!(cfg.claim_window_secs, 3600);
+        assert_eq!(evt.old_fee_bps, evt.new_fee_bps);
+    }
+
+    #[test]
+    fn upc_hp_003_fee_bound_is_inclusive() {
+        let mut input = base_input();
+        input.fee_bps = Some(MAX_FEE_BPS);
+        assert!(update_config(input).is_ok());
+    }
+
+    #[test]
+    fn upc_rej_001_to_006_error_mapping() {
+        let mut bad = base_input();
+        bad.authority = "Other".to_string();
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::Unauthorized);
+
+        let mut bad = base_input();
+        bad.fee_bps = Some(MAX_FEE_BPS + 1);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::FeeTooHigh);
+
+        let mut bad = base_input();
+        bad.early_exit_fee_bps = Some(MAX_FEE_BPS + 1);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::FeeTooHigh);
+
+        let mut bad = base_input();
+        bad.max_total_pool_per_market = Some(0);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::InvalidCap);
+
+        let mut bad = base_input();
+        bad.max_bet_per_user_per_market = Some(0);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::InvalidCap);
+
+        let mut bad = base_input();
+        bad.claim_window_secs = Some(0);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::InvalidClaimWindow);
+
+        let mut bad = base_input();
+        bad.claim_window_secs = Some(MAX_CLAIM_WINDOW_SECS + 1);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::InvalidClaimWindow);
+
+        let mut bad = base_input();
+        bad.dispute_window_secs = Some(-1);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::InvalidDisputeWindow);
+
+        let mut bad = base_input();
+        bad.dispute_window_secs = Some(MAX_DISPUTE_WINDOW_SECS + 1);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::InvalidDisputeWindow);
+
+        let mut bad = base_input();
+        bad.resolution_deadline_secs = Some(-1);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::InvalidResolutionDeadline);
+
+        let mut bad = base_input();
+        bad.resolution_deadline_secs = Some(MAX_RESOLUTION_DEADLINE_SECS + 1);
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::InvalidResolutionDeadline);
+    }
+
+    #[test]
+    fn upc_hp_004_dispute_window_can_be_disabled() {
+        let mut config = base_config();
+        config.dispute_window_secs = 86_400;
+        let mut input = base_input();
+        input.config_state = config;
+        input.dispute_window_secs = Some(0);
+        let (cfg, _evt) = update_config(input).expect("disabling disputes should pass");
+        assert_eq!(cfg.dispute_window_secs, 0);
+    }
+
+    #[test]
+    fn upc_hp_005_payout_keeper_kept_or_removed() {
+        let mut config = base_config();
+        config.payout_keeper = Some("KeeperA".to_string());
+
+        let mut input = base_input();
+        input.config_state = config.clone();
+        input.payout_keeper = None;
+        let (cfg, _evt) = update_config(input).expect("keeping the keeper should pass");
+        assert_eq!(cfg.payout_keeper.as_deref(), Some("KeeperA"));
+
+        let mut input = base_input();
+        input.config_state = config;
+        input.payout_keeper = Some(None);
+        let (cfg, evt) = update_config(input).expect("removing the keeper should pass");
+        assert_eq!(cfg.payout_keeper, None);
+        assert_eq!(evt.old_payout_keeper.as_deref(), Some("KeeperA"));
+    }
+
+    #[test]
+    fn upc_rej_003_cap_pair_checked_after_merge() {
+        // Lowering only the market cap below the stored user cap must fail.
+        let mut bad = base_input();
+        bad.max_total_pool_per_market = Some(99_999);
+        bad.max_bet_per_user_per_market = None;
+        assert_eq!(update_config(bad).unwrap_err(), PitStopError::InvalidCap);
+    }
+}
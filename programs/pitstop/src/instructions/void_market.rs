@@ -1,13 +1,22 @@
 use crate::{
     error::PitStopError,
     events::MarketVoided,
-    state::{Market, MarketStatus},
+    instructions::submit_resolution_vote::quorum_reached,
+    state::{Market, MarketStatus, OracleCommittee, ResolutionDecision, ResolutionVote},
 };
 
 #[derive(Debug, Clone)]
 pub struct VoidMarketInput {
+    /// Transaction signer: the oracle itself, or a keeper relaying an attestation.
     pub oracle: String,
     pub config_oracle: String,
+    /// Keys whose Ed25519 precompile signature over `attestation::void_message` is in
+    /// the transaction.
+    pub attesters: Vec<String>,
+    /// Committee mode: set when `config.oracle` is the OracleCommittee PDA; `votes`
+    /// must then reach quorum for a void with this payload hash.
+    pub committee: Option<OracleCommittee>,
+    pub votes: Vec<ResolutionVote>,
 
     pub market: String,
     pub payload_hash: [u8; 32],
@@ -18,9 +27,26 @@ pub struct VoidMarketInput {
 }
 
 fn validate_void_market_preconditions(input: &VoidMarketInput) -> Result<(), PitStopError> {
-    // VDM-REJ-001: oracle signer must match config.oracle.
-    if input.oracle != input.config_oracle {
-        return Err(PitStopError::UnauthorizedOracle);
+    // VDM-REJ-001: config.oracle must sign the transaction or attest offline.
+    // VDM-REJ-004: in committee mode a void needs the same quorum as a resolution.
+    match &input.committee {
+        Some(committee) => {
+            if !quorum_reached(
+                committee,
+                &input.votes,
+                &ResolutionDecision::Void,
+                &input.payload_hash,
+            ) {
+                return Err(PitStopError::QuorumNotReached);
+            }
+        }
+        None => {
+            if input.oracle != input.config_oracle
+                && !input.attesters.contains(&input.config_oracle)
+            {
+                return Err(PitStopError::UnauthorizedOracle);
+            }
+        }
     }
 
     // VDM-REJ-002/003: only Locked markets can transition to Voided.
@@ -69,6 +95,17 @@ mod tests {
             vault: "VaultA".to_string(),
             market_type: 0,
             rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 0,
         }
     }
 
@@ -76,6 +113,9 @@ mod tests {
         VoidMarketInput {
             oracle: "OracleA".to_string(),
             config_oracle: "OracleA".to_string(),
+            attesters: Vec::new(),
+            committee: None,
+            votes: Vec::new(),
             market: "MarketA".to_string(),
             payload_hash: [7u8; 32],
             now_ts: 1_800_000_100,
@@ -103,6 +143,99 @@ mod tests {
         let mut bad = base_input();
         bad.oracle = "Other".to_string();
         assert_eq!(void_market(bad).unwrap_err(), PitStopError::UnauthorizedOracle);
+
+        let mut bad = base_input();
+        bad.oracle = "KeeperA".to_string();
+        bad.attesters = vec!["Other".to_string()];
+        assert_eq!(void_market(bad).unwrap_err(), PitStopError::UnauthorizedOracle);
+    }
+
+    #[test]
+    fn vdm_adv_002_keeper_relays_oracle_attestation() {
+        let mut input = base_input();
+        input.oracle = "KeeperA".to_string();
+        input.attesters = vec!["OracleA".to_string()];
+        let (m, _e) = void_market(input).expect("attested void should pass");
+        assert_eq!(m.status, MarketStatus::Voided);
+    }
+
+    #[test]
+    fn vdm_adv_003_committee_mode_requires_void_quorum() {
+        let vote = |member: &str, decision: ResolutionDecision| ResolutionVote {
+            member: member.to_string(),
+            decision,
+            payload_hash: [7u8; 32],
+        };
+        let committee_input = |votes: Vec<ResolutionVote>| {
+            let mut input = base_input();
+            input.oracle = "KeeperA".to_string();
+            input.config_oracle = "CommitteePda".to_string();
+            input.committee = Some(OracleCommittee {
+                members: vec!["M1".to_string(), "M2".to_string(), "M3".to_string()],
+                threshold: 2,
+            });
+            input.votes = votes;
+            input
+        };
+
+        let input = committee_input(vec![
+            vote("M1", ResolutionDecision::Void),
+            vote("M2", ResolutionDecision::Void),
+        ]);
+        let (m, _e) = void_market(input).expect("quorum void should pass");
+        assert_eq!(m.status, MarketStatus::Voided);
+
+        // Resolve votes never count toward a void.
+        let bad = committee_input(vec![
+            vote("M1", ResolutionDecision::Void),
+            vote("M2", ResolutionDecision::Resolve { winning_outcomes: vec![1] }),
+        ]);
+        assert_eq!(void_market(bad).unwrap_err(), PitStopError::QuorumNotReached);
+    }
+
+    #[test]
+    fn vdm_adv_001_oracle_rotation_while_locked_switches_signer() {
+        use crate::{
+            constants::REQUIRED_TOKEN_PROGRAM,
+            instructions::set_oracle::{set_oracle, SetOracleInput},
+            state::Config,
+        };
+
+        let config = Config {
+            authority: "AuthA".to_string(),
+            oracle: "OracleA".to_string(),
+            usdc_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            treasury_authority: "TreasuryOwnerA".to_string(),
+            fee_bps: 0,
+            paused: false,
+            max_total_pool_per_market: 1_000_000,
+            max_bet_per_user_per_market: 100_000,
+            claim_window_secs: 3600,
+            dispute_window_secs: 0,
+            resolution_deadline_secs: 0,
+            early_exit_fee_bps: 0,
+            token_program: REQUIRED_TOKEN_PROGRAM.to_string(),
+            pending_authority: None,
+            payout_keeper: None,
+        };
+        let (rotated, _evt) = set_oracle(SetOracleInput {
+            authority: "AuthA".to_string(),
+            new_oracle: "OracleB".to_string(),
+            now_ts: 1_800_000_050,
+            config_state: config,
+        })
+        .expect("rotation should pass");
+
+        let mut stale = base_input();
+        stale.config_oracle = rotated.oracle.clone();
+        assert_eq!(void_market(stale).unwrap_err(), PitStopError::UnauthorizedOracle);
+
+        let mut fresh = base_input();
+        fresh.oracle = "OracleB".to_string();
+        fresh.config_oracle = rotated.oracle;
+        let (m, _e) = void_market(fresh).expect("new oracle should void");
+        assert_eq!(m.status, MarketStatus::Voided);
     }
 
     #[test]
@@ -0,0 +1,179 @@
+//! void_stale_market Rust parity model.
+//!
+//! Spec: SPEC_INSTRUCTIONS/void_stale_market.md
+//!
+//! Permissionless liveness fallback: once a Locked market is past
+//! `lock_timestamp + market.resolution_deadline_secs` without a result, any signer
+//! can void it so positions are refundable through claim_voided.
+
+use crate::{
+    error::PitStopError,
+    events::StaleMarketVoided,
+    state::{Market, MarketStatus},
+};
+
+#[derive(Debug, Clone)]
+pub struct VoidStaleMarketInput {
+    /// Any signer; the caller gains nothing beyond moving the market to Voided.
+    pub caller: String,
+    pub market: String,
+    pub market_state: Market,
+    /// `Market.resolution_deadline_secs`, frozen at create_market; 0 disables the fallback.
+    pub resolution_deadline_secs: i64,
+    pub now_ts: i64,
+}
+
+/// Last second the oracle has to resolve or void the market.
+fn resolution_deadline(
+    lock_timestamp: i64,
+    resolution_deadline_secs: i64,
+) -> Result<i64, PitStopError> {
+    lock_timestamp
+        .checked_add(resolution_deadline_secs)
+        .ok_or(PitStopError::Overflow)
+}
+
+fn validate_void_stale_market_preconditions(
+    input: &VoidStaleMarketInput,
+) -> Result<i64, PitStopError> {
+    // VSM-REJ-001: only markets still waiting for the oracle are stale.
+    if input.market_state.status != MarketStatus::Locked {
+        return Err(PitStopError::MarketNotLocked);
+    }
+    // VSM-REJ-002: the deadline must be enabled and strictly in the past.
+    if input.resolution_deadline_secs == 0 {
+        return Err(PitStopError::ResolutionDeadlineNotReached);
+    }
+    let deadline = resolution_deadline(
+        input.market_state.lock_timestamp,
+        input.resolution_deadline_secs,
+    )?;
+    if input.now_ts <= deadline {
+        return Err(PitStopError::ResolutionDeadlineNotReached);
+    }
+    Ok(deadline)
+}
+
+/// Effects:
+/// - market.status = Voided, resolved_outcome = None
+/// - market.resolution_timestamp = now (claim_voided window starts here)
+/// - resolution_payload_hash stays zero: there is no oracle result to record
+/// - emit StaleMarketVoided
+pub fn void_stale_market(
+    input: VoidStaleMarketInput,
+) -> Result<(Market, StaleMarketVoided), PitStopError> {
+    let deadline = validate_void_stale_market_preconditions(&input)?;
+
+    let mut market = input.market_state;
+    market.status = MarketStatus::Voided;
+    market.resolved_outcome = None;
+    market.resolution_timestamp = input.now_ts;
+
+    let evt = StaleMarketVoided {
+        market: input.market,
+        caller: input.caller,
+        resolution_deadline: deadline,
+        timestamp: input.now_ts,
+    };
+
+    Ok((market, evt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_market() -> Market {
+        Market {
+            market_id: [1u8; 32],
+            event_id: [2u8; 32],
+            lock_timestamp: 1_800_000_000,
+            outcome_count: 3,
+            max_outcomes: 3,
+            total_pool: 1000,
+            status: MarketStatus::Locked,
+            resolved_outcome: None,
+            resolution_payload_hash: [0u8; 32],
+            resolution_timestamp: 0,
+            vault: "VaultA".to_string(),
+            market_type: 0,
+            rules_version: 1,
+            fee_bps: 0,
+            winning_outcomes: Vec::new(),
+            winner_pool: 0,
+            collateral_mint: "MintA".to_string(),
+            treasury: "TreasuryA".to_string(),
+            dispute_deadline: 0,
+            locked_at: 0,
+            max_total_pool_per_market: None,
+            max_bet_per_user_per_market: None,
+            claim_window_secs: 3600,
+            resolution_deadline_secs: 86_400,
+        }
+    }
+
+    fn base_input() -> VoidStaleMarketInput {
+        VoidStaleMarketInput {
+            caller: "KeeperA".to_string(),
+            market: "MarketA".to_string(),
+            market_state: base_market(),
+            resolution_deadline_secs: 86_400,
+            now_ts: 1_800_086_401,
+        }
+    }
+
+    #[test]
+    fn vsm_hp_001_anyone_voids_after_deadline() {
+        let (m, e) = void_stale_market(base_input()).expect("stale void should pass");
+        assert_eq!(m.status, MarketStatus::Voided);
+        assert_eq!(m.resolved_outcome, None);
+        assert_eq!(m.resolution_payload_hash, [0u8; 32]);
+        assert_eq!(m.resolution_timestamp, 1_800_086_401);
+
+        assert_eq!(e.market, "MarketA");
+        assert_eq!(e.caller, "KeeperA");
+        assert_eq!(e.resolution_deadline, 1_800_086_400);
+        assert_eq!(e.timestamp, 1_800_086_401);
+    }
+
+    #[test]
+    fn vsm_rej_001_only_locked_markets() {
+        for status in [
+            MarketStatus::Open,
+            MarketStatus::PendingResolution,
+            MarketStatus::Resolved,
+            MarketStatus::Voided,
+            MarketStatus::Swept,
+        ] {
+            let mut bad = base_input();
+            bad.market_state.status = status;
+            assert_eq!(void_stale_market(bad).unwrap_err(), PitStopError::MarketNotLocked);
+        }
+    }
+
+    #[test]
+    fn vsm_rej_002_deadline_disabled_or_not_passed() {
+        // The deadline second itself still belongs to the oracle.
+        let mut bad = base_input();
+        bad.now_ts = 1_800_086_400;
+        assert_eq!(
+            void_stale_market(bad).unwrap_err(),
+            PitStopError::ResolutionDeadlineNotReached
+        );
+
+        let mut bad = base_input();
+        bad.resolution_deadline_secs = 0;
+        bad.now_ts = i64::MAX;
+        assert_eq!(
+            void_stale_market(bad).unwrap_err(),
+            PitStopError::ResolutionDeadlineNotReached
+        );
+    }
+
+    #[test]
+    fn vsm_adv_001_deadline_overflow_is_rejected() {
+        let mut bad = base_input();
+        bad.market_state.lock_timestamp = i64::MAX;
+        assert_eq!(void_stale_market(bad).unwrap_err(), PitStopError::Overflow);
+    }
+}
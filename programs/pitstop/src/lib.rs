@@ -11,12 +11,15 @@ pub mod anchor_accounts;
 pub mod anchor_errors;
 pub mod anchor_events;
 
+pub mod attestation;
+pub mod canonical;
 pub mod constants;
 pub mod error;
 pub mod events;
 pub mod math;
 pub mod pda;
 pub mod state;
+pub mod token_policy;
 pub mod instructions;
 
 pub use anchor_accounts::*;
@@ -33,6 +36,48 @@ pub mod pitstop {
         handlers::initialize(ctx, args)
     }
 
+    pub fn update_config(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
+        handlers::update_config(ctx, args)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, args: SetPausedArgs) -> Result<()> {
+        handlers::set_paused(ctx, args)
+    }
+
+    pub fn set_oracle(ctx: Context<SetOracle>, args: SetOracleArgs) -> Result<()> {
+        handlers::set_oracle(ctx, args)
+    }
+
+    pub fn set_oracle_committee(
+        ctx: Context<SetOracleCommittee>,
+        args: SetOracleCommitteeArgs,
+    ) -> Result<()> {
+        handlers::set_oracle_committee(ctx, args)
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, args: ProposeAuthorityArgs) -> Result<()> {
+        handlers::propose_authority(ctx, args)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        handlers::accept_authority(ctx)
+    }
+
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        handlers::cancel_authority_transfer(ctx)
+    }
+
+    pub fn add_collateral_mint(ctx: Context<AddCollateralMint>) -> Result<()> {
+        handlers::add_collateral_mint(ctx)
+    }
+
+    pub fn remove_collateral_mint(
+        ctx: Context<RemoveCollateralMint>,
+        args: RemoveCollateralMintArgs,
+    ) -> Result<()> {
+        handlers::remove_collateral_mint(ctx, args)
+    }
+
     pub fn create_market(ctx: Context<CreateMarket>, args: CreateMarketArgs) -> Result<()> {
         handlers::create_market(ctx, args)
     }
@@ -49,10 +94,25 @@ pub mod pitstop {
         handlers::place_bet(ctx, args)
     }
 
+    pub fn reduce_position(ctx: Context<ReducePosition>, args: ReducePositionArgs) -> Result<()> {
+        handlers::reduce_position(ctx, args)
+    }
+
+    pub fn switch_outcome(ctx: Context<SwitchOutcome>, args: SwitchOutcomeArgs) -> Result<()> {
+        handlers::switch_outcome(ctx, args)
+    }
+
     pub fn lock_market(ctx: Context<LockMarket>) -> Result<()> {
         handlers::lock_market(ctx)
     }
 
+    pub fn submit_resolution_vote(
+        ctx: Context<SubmitResolutionVote>,
+        args: SubmitResolutionVoteArgs,
+    ) -> Result<()> {
+        handlers::submit_resolution_vote(ctx, args)
+    }
+
     pub fn resolve_market(ctx: Context<ResolveMarket>, args: ResolveMarketArgs) -> Result<()> {
         handlers::resolve_market(ctx, args)
     }
@@ -61,10 +121,31 @@ pub mod pitstop {
         handlers::void_market(ctx, args)
     }
 
+    pub fn void_stale_market(ctx: Context<VoidStaleMarket>) -> Result<()> {
+        handlers::void_stale_market(ctx)
+    }
+
+    pub fn overturn_resolution(
+        ctx: Context<OverturnResolution>,
+        args: OverturnResolutionArgs,
+    ) -> Result<()> {
+        handlers::overturn_resolution(ctx, args)
+    }
+
     pub fn claim_resolved(ctx: Context<ClaimResolved>, args: ClaimResolvedArgs) -> Result<()> {
         handlers::claim_resolved(ctx, args)
     }
 
+    pub fn claim_many<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>) -> Result<()> {
+        handlers::claim_many(ctx)
+    }
+
+    pub fn distribute_payouts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributePayouts<'info>>,
+    ) -> Result<()> {
+        handlers::distribute_payouts(ctx)
+    }
+
     pub fn claim_voided(ctx: Context<ClaimVoided>, args: ClaimVoidedArgs) -> Result<()> {
         handlers::claim_voided(ctx, args)
     }
@@ -76,6 +157,14 @@ pub mod pitstop {
     pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
         handlers::cancel_market(ctx)
     }
+
+    pub fn close_position(ctx: Context<ClosePosition>, args: ClosePositionArgs) -> Result<()> {
+        handlers::close_position(ctx, args)
+    }
+
+    pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+        handlers::close_market(ctx)
+    }
 }
 
 mod handlers {
@@ -83,6 +172,13 @@ mod handlers {
 
     use std::str::FromStr;
 
+    use anchor_lang::solana_program::{
+        ed25519_program, sysvar::instructions::load_instruction_at_checked,
+    };
+    use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+    use anchor_spl::token_2022_extensions::transfer_fee::{
+        harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint,
+    };
     use anchor_spl::token_interface::{
         close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TransferChecked,
     };
@@ -101,6 +197,86 @@ mod handlers {
             .map_err(|_| error!(PitStopAnchorError::InvalidTokenProgram))
     }
 
+    /// Keys that signed `message` in an Ed25519 precompile instruction of this
+    /// transaction. Empty when no instructions sysvar was passed (direct oracle signer).
+    fn oracle_attesters(
+        instructions_sysvar: Option<&UncheckedAccount>,
+        message: &[u8],
+    ) -> Vec<String> {
+        let Some(sysvar) = instructions_sysvar else {
+            return Vec::new();
+        };
+        let sysvar = sysvar.to_account_info();
+        let mut ed25519_datas = Vec::new();
+        let mut index = 0;
+        while let Ok(ix) = load_instruction_at_checked(index, &sysvar) {
+            if ix.program_id == ed25519_program::ID {
+                ed25519_datas.push(ix.data);
+            }
+            index += 1;
+        }
+        attestation::attesters(ed25519_datas.iter().map(Vec::as_slice), message)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Committee and votes for resolve/void parity input. `Some` only when the passed
+    /// committee is `config.oracle`; omitting it then leaves the single-signer check,
+    /// which no key can pass for a PDA oracle.
+    fn committee_tally(
+        config: &Config,
+        oracle_committee: Option<&Account<OracleCommittee>>,
+        resolution_votes: Option<&Account<ResolutionVotes>>,
+    ) -> (Option<crate::state::OracleCommittee>, Vec<crate::state::ResolutionVote>) {
+        match oracle_committee {
+            Some(committee) if committee.key() == config.oracle => (
+                Some(committee.to_parity()),
+                resolution_votes.map(|votes| votes.to_parity()).unwrap_or_default(),
+            ),
+            _ => (None, Vec::new()),
+        }
+    }
+
+    /// After a committee quorum resolved or voided the market its votes are spent:
+    /// close the ResolutionVotes account and return its rent to the member who paid.
+    fn close_resolution_votes<'info>(
+        resolution_votes: Option<&Account<'info, ResolutionVotes>>,
+        votes_payer: Option<&UncheckedAccount<'info>>,
+    ) -> Result<()> {
+        let Some(votes) = resolution_votes else {
+            return Ok(());
+        };
+        let payer = votes_payer.ok_or(PitStopAnchorError::VotesPayerMismatch)?;
+        require_keys_eq!(
+            payer.key(),
+            votes.payer,
+            PitStopAnchorError::VotesPayerMismatch
+        );
+        votes.close(payer.to_account_info())
+    }
+
+    /// PendingResolution -> Resolved for a market past its dispute deadline, with its
+    /// ResolutionFinalized event. Returns whether the market changed.
+    fn finalize_resolution_if_due(market: &mut Account<Market>, now_ts: i64) -> bool {
+        let market_key = market.key();
+        let Some((finalized, evt)) = instructions::resolve_market::finalize_resolution(
+            &market_key.to_string(),
+            &market.to_parity(),
+            now_ts,
+        ) else {
+            return false;
+        };
+        market.apply_parity(&finalized);
+        emit!(anchor_events::ResolutionFinalized {
+            market: market_key,
+            winning_outcomes: evt.winning_outcomes,
+            dispute_deadline: evt.dispute_deadline,
+            timestamp: evt.timestamp,
+        });
+        true
+    }
+
     /// Canonical OutcomePool loader used by all handlers that need deterministic
     /// `OutcomeMismatch` mapping for missing/wrong/malformed pool accounts.
     fn load_outcome_pool_checked(
@@ -108,14 +284,16 @@ mod handlers {
         market: Pubkey,
         outcome_id: u8,
     ) -> Result<OutcomePool> {
-        let expected_pool = Pubkey::find_program_address(
-            &[OUTCOME_SEED, market.as_ref(), &[outcome_id]],
-            &crate::id(),
-        )
-        .0;
-        if pool_account.key() != expected_pool {
+        let pool = load_market_outcome_pool(pool_account, market)?;
+        if pool.outcome_id != outcome_id {
             return Err(error!(PitStopAnchorError::OutcomeMismatch));
         }
+        Ok(pool)
+    }
+
+    /// Same checks as `load_outcome_pool_checked` for callers that take the
+    /// outcome id from the pool itself (e.g. `close_market` remaining accounts).
+    fn load_market_outcome_pool(pool_account: &AccountInfo, market: Pubkey) -> Result<OutcomePool> {
         if pool_account.owner != &crate::id() {
             return Err(error!(PitStopAnchorError::OutcomeMismatch));
         }
@@ -129,28 +307,84 @@ mod handlers {
         let pool = OutcomePool::try_deserialize(&mut slice)
             .map_err(|_| error!(PitStopAnchorError::OutcomeMismatch))?;
 
-        if pool.market != market || pool.outcome_id != outcome_id {
+        if pool.market != market {
+            return Err(error!(PitStopAnchorError::OutcomeMismatch));
+        }
+
+        // Address check uses the stored bump: one create_program_address instead of a search.
+        let expected_pool = pda::outcome_pool_address_with_bump(&market, pool.outcome_id, pool.bump);
+        if expected_pool != Some(pool_account.key()) {
             return Err(error!(PitStopAnchorError::OutcomeMismatch));
         }
 
         Ok(pool)
     }
+
+    /// Closes a program-owned account that is not held as a typed `Account`
+    /// (mirrors Anchor's `close` constraint).
+    fn close_program_account(info: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+        let lamports = info.lamports();
+        **destination.try_borrow_mut_lamports()? = destination
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(PitStopAnchorError::Overflow)?;
+        **info.try_borrow_mut_lamports()? = 0;
+        info.assign(&anchor_lang::solana_program::system_program::ID);
+        info.realloc(0, false)?;
+        Ok(())
+    }
+
+    /// Runs the close_position parity checks and emits `PositionClosed`; the caller
+    /// closes the account (Anchor `close = user`, or `AccountsClose` after a claim).
+    fn close_position_checked(
+        market: Pubkey,
+        market_status: crate::state::MarketStatus,
+        position: &Account<Position>,
+        user: &Signer,
+        now_ts: i64,
+    ) -> Result<()> {
+        let input = instructions::close_position::ClosePositionInput {
+            market: market.to_string(),
+            user: user.key().to_string(),
+            market_status,
+            now_ts,
+            position_state: position.to_parity(),
+        };
+
+        let evt = instructions::close_position::close_position(input)
+            .map_err(PitStopAnchorError::from)?;
+
+        emit!(anchor_events::PositionClosed {
+            market,
+            user: user.key(),
+            outcome_id: evt.outcome_id,
+            claimed: evt.claimed,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
     pub fn initialize(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
         // Layer 1 validation (Anchor handler level):
         // perform explicit protocol-mapped guards before invoking parity logic.
         //
         // We intentionally map to PitStopAnchorError here so callers see the same
         // deterministic error taxonomy expected by LOCKED specs.
-        require_keys_eq!(
-            ctx.accounts.token_program.key(),
-            constants::REQUIRED_TOKEN_PROGRAM_ID,
+        let token_program = ctx.accounts.token_program.key();
+        require!(
+            constants::is_supported_token_program(&token_program.to_string()),
             PitStopAnchorError::InvalidTokenProgram
         );
 
         // Anchor token_interface lets us read decimals regardless of token flavor,
         // but protocol is currently pinned to USDC(6) + required token program.
         let usdc_mint: &InterfaceAccount<Mint> = &ctx.accounts.usdc_mint;
+        let mint_info = usdc_mint.to_account_info();
+        require_keys_eq!(*mint_info.owner, token_program, PitStopAnchorError::InvalidTokenProgram);
         require!(usdc_mint.decimals == 6, PitStopAnchorError::InvalidMintDecimals);
+        // The mint is fixed for the life of the config, so its extensions are screened once.
+        token_policy::check_mint_extensions(mint_info.owner, &mint_info.try_borrow_data()?)
+            .map_err(PitStopAnchorError::from)?;
 
         let treasury: &InterfaceAccount<TokenAccount> = &ctx.accounts.treasury;
         require_keys_eq!(treasury.mint, usdc_mint.key(), PitStopAnchorError::InvalidTreasuryMint);
@@ -166,6 +400,7 @@ mod handlers {
         let now_ts = clock_unix_timestamp()?;
         let input = instructions::initialize::InitializeInput {
             authority: ctx.accounts.authority.key().to_string(),
+            oracle: args.oracle.to_string(),
             treasury_authority: args.treasury_authority.to_string(),
             usdc_mint: usdc_mint.key().to_string(),
             treasury: treasury.key().to_string(),
@@ -185,7 +420,7 @@ mod handlers {
         // parity returned canonical config values; persist those onto Anchor account.
         let config = &mut ctx.accounts.config;
         config.authority = ctx.accounts.authority.key();
-        config.oracle = ctx.accounts.authority.key();
+        config.oracle = args.oracle;
         config.usdc_mint = usdc_mint.key();
         config.treasury = treasury.key();
         config.treasury_authority = args.treasury_authority;
@@ -194,13 +429,18 @@ mod handlers {
         config.max_total_pool_per_market = cfg.max_total_pool_per_market;
         config.max_bet_per_user_per_market = cfg.max_bet_per_user_per_market;
         config.claim_window_secs = cfg.claim_window_secs;
-        config.token_program = constants::REQUIRED_TOKEN_PROGRAM_ID;
+        config.dispute_window_secs = cfg.dispute_window_secs;
+        config.resolution_deadline_secs = cfg.resolution_deadline_secs;
+        config.early_exit_fee_bps = cfg.early_exit_fee_bps;
+        config.token_program = token_program;
+        config.pending_authority = None;
+        config.payout_keeper = None;
 
         // Event emission:
         // emit after successful state write so off-chain observers see committed transitions.
         emit!(anchor_events::ConfigInitialized {
             authority: ctx.accounts.authority.key(),
-            oracle: ctx.accounts.authority.key(),
+            oracle: args.oracle,
             usdc_mint: usdc_mint.key(),
             treasury: treasury.key(),
             fee_bps: evt.fee_bps,
@@ -210,16 +450,316 @@ mod handlers {
         Ok(())
     }
 
+    pub fn update_config(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::update_config::UpdateConfigInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            fee_bps: args.fee_bps,
+            max_total_pool_per_market: args.max_total_pool_per_market,
+            max_bet_per_user_per_market: args.max_bet_per_user_per_market,
+            claim_window_secs: args.claim_window_secs,
+            dispute_window_secs: args.dispute_window_secs,
+            resolution_deadline_secs: args.resolution_deadline_secs,
+            early_exit_fee_bps: args.early_exit_fee_bps,
+            payout_keeper: args.payout_keeper.map(|k| k.map(|k| k.to_string())),
+            now_ts,
+            config_state: ctx.accounts.config.to_parity(),
+        };
+
+        let (new_config, evt) =
+            instructions::update_config::update_config(input).map_err(PitStopAnchorError::from)?;
+        let old_payout_keeper = ctx.accounts.config.payout_keeper;
+        ctx.accounts.config.apply_parity(&new_config);
+        if let Some(keeper) = args.payout_keeper {
+            ctx.accounts.config.payout_keeper = keeper;
+        }
+
+        emit!(anchor_events::ConfigUpdated {
+            authority: ctx.accounts.authority.key(),
+            old_fee_bps: evt.old_fee_bps,
+            new_fee_bps: evt.new_fee_bps,
+            old_max_total_pool_per_market: evt.old_max_total_pool_per_market,
+            new_max_total_pool_per_market: evt.new_max_total_pool_per_market,
+            old_max_bet_per_user_per_market: evt.old_max_bet_per_user_per_market,
+            new_max_bet_per_user_per_market: evt.new_max_bet_per_user_per_market,
+            old_claim_window_secs: evt.old_claim_window_secs,
+            new_claim_window_secs: evt.new_claim_window_secs,
+            old_dispute_window_secs: evt.old_dispute_window_secs,
+            new_dispute_window_secs: evt.new_dispute_window_secs,
+            old_resolution_deadline_secs: evt.old_resolution_deadline_secs,
+            new_resolution_deadline_secs: evt.new_resolution_deadline_secs,
+            old_early_exit_fee_bps: evt.old_early_exit_fee_bps,
+            new_early_exit_fee_bps: evt.new_early_exit_fee_bps,
+            old_payout_keeper,
+            new_payout_keeper: ctx.accounts.config.payout_keeper,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, args: SetPausedArgs) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::set_paused::SetPausedInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            paused: args.paused,
+            now_ts,
+            config_state: ctx.accounts.config.to_parity(),
+        };
+
+        let (new_config, evt) =
+            instructions::set_paused::set_paused(input).map_err(PitStopAnchorError::from)?;
+        ctx.accounts.config.apply_parity(&new_config);
+
+        emit!(anchor_events::ProtocolPauseChanged {
+            authority: ctx.accounts.authority.key(),
+            paused: evt.paused,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_oracle(ctx: Context<SetOracle>, args: SetOracleArgs) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::set_oracle::SetOracleInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            new_oracle: args.new_oracle.to_string(),
+            now_ts,
+            config_state: ctx.accounts.config.to_parity(),
+        };
+
+        let (_new_config, evt) =
+            instructions::set_oracle::set_oracle(input).map_err(PitStopAnchorError::from)?;
+
+        let old_oracle = ctx.accounts.config.oracle;
+        ctx.accounts.config.oracle = args.new_oracle;
+
+        emit!(anchor_events::OracleRotated {
+            authority: ctx.accounts.authority.key(),
+            old_oracle,
+            new_oracle: args.new_oracle,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_oracle_committee(
+        ctx: Context<SetOracleCommittee>,
+        args: SetOracleCommitteeArgs,
+    ) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let committee_key = ctx.accounts.oracle_committee.key();
+        let input = instructions::set_oracle_committee::SetOracleCommitteeInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            members: args.members.iter().map(ToString::to_string).collect(),
+            threshold: args.threshold,
+            committee: committee_key.to_string(),
+            now_ts,
+            config_state: ctx.accounts.config.to_parity(),
+        };
+
+        let (_new_config, _new_committee, evt) =
+            instructions::set_oracle_committee::set_oracle_committee(input)
+                .map_err(PitStopAnchorError::from)?;
+
+        let old_oracle = ctx.accounts.config.oracle;
+        ctx.accounts.config.oracle = committee_key;
+
+        // Parity bounds the member count by MAX_COMMITTEE_MEMBERS.
+        let committee = &mut ctx.accounts.oracle_committee;
+        committee.members = Default::default();
+        committee.members[..args.members.len()].copy_from_slice(&args.members);
+        committee.member_count = args.members.len() as u8;
+        committee.threshold = args.threshold;
+        committee.bump = ctx.bumps.oracle_committee;
+
+        emit!(anchor_events::OracleCommitteeSet {
+            authority: ctx.accounts.authority.key(),
+            old_oracle,
+            committee: committee_key,
+            members: args.members,
+            threshold: evt.threshold,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, args: ProposeAuthorityArgs) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::propose_authority::ProposeAuthorityInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            new_authority: args.new_authority.to_string(),
+            now_ts,
+            config_state: ctx.accounts.config.to_parity(),
+        };
+
+        let (_new_config, evt) = instructions::propose_authority::propose_authority(input)
+            .map_err(PitStopAnchorError::from)?;
+
+        ctx.accounts.config.pending_authority = Some(args.new_authority);
+
+        emit!(anchor_events::AuthorityTransferProposed {
+            authority: ctx.accounts.authority.key(),
+            pending_authority: args.new_authority,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::accept_authority::AcceptAuthorityInput {
+            new_authority: ctx.accounts.new_authority.key().to_string(),
+            now_ts,
+            config_state: ctx.accounts.config.to_parity(),
+        };
+
+        let (_new_config, evt) = instructions::accept_authority::accept_authority(input)
+            .map_err(PitStopAnchorError::from)?;
+
+        let config = &mut ctx.accounts.config;
+        let old_authority = config.authority;
+        config.authority = ctx.accounts.new_authority.key();
+        config.pending_authority = None;
+
+        emit!(anchor_events::AuthorityTransferred {
+            old_authority,
+            new_authority: ctx.accounts.new_authority.key(),
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::cancel_authority_transfer::CancelAuthorityTransferInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            now_ts,
+            config_state: ctx.accounts.config.to_parity(),
+        };
+
+        let (_new_config, evt) =
+            instructions::cancel_authority_transfer::cancel_authority_transfer(input)
+                .map_err(PitStopAnchorError::from)?;
+
+        // Parity guarantees a pending key exists.
+        let cancelled_authority = ctx
+            .accounts
+            .config
+            .pending_authority
+            .take()
+            .ok_or(PitStopAnchorError::NoPendingAuthority)?;
+
+        emit!(anchor_events::AuthorityTransferCancelled {
+            authority: ctx.accounts.authority.key(),
+            cancelled_authority,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn add_collateral_mint(ctx: Context<AddCollateralMint>) -> Result<()> {
+        // Same mint screening as initialize: supported token program + no blocked
+        // extensions. The program need not match config's; markets record their own.
+        let mint_info = ctx.accounts.mint.to_account_info();
+        require!(
+            constants::is_supported_token_program(&mint_info.owner.to_string()),
+            PitStopAnchorError::InvalidTokenProgram
+        );
+        token_policy::check_mint_extensions(mint_info.owner, &mint_info.try_borrow_data()?)
+            .map_err(PitStopAnchorError::from)?;
+
+        let now_ts = clock_unix_timestamp()?;
+        let mint = ctx.accounts.mint.key();
+        let treasury = &ctx.accounts.treasury;
+        let input = instructions::add_collateral_mint::AddCollateralMintInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            mint: mint.to_string(),
+            mint_decimals: ctx.accounts.mint.decimals,
+            treasury: treasury.key().to_string(),
+            treasury_mint: treasury.mint.to_string(),
+            treasury_owner: treasury.owner.to_string(),
+            now_ts,
+            config_state: ctx.accounts.config.to_parity(),
+            allowlist_state: ctx.accounts.collateral_allowlist.to_parity(),
+        };
+
+        let (_new_allowlist, evt) = instructions::add_collateral_mint::add_collateral_mint(input)
+            .map_err(PitStopAnchorError::from)?;
+
+        // Parity guarantees a free slot.
+        let allowlist = &mut ctx.accounts.collateral_allowlist;
+        let slot = usize::from(allowlist.count);
+        allowlist.entries[slot] = CollateralMint {
+            mint,
+            treasury: treasury.key(),
+        };
+        allowlist.count += 1;
+        allowlist.bump = ctx.bumps.collateral_allowlist;
+
+        emit!(anchor_events::CollateralMintAdded {
+            authority: ctx.accounts.authority.key(),
+            mint,
+            treasury: treasury.key(),
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn remove_collateral_mint(
+        ctx: Context<RemoveCollateralMint>,
+        args: RemoveCollateralMintArgs,
+    ) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::remove_collateral_mint::RemoveCollateralMintInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            mint: args.mint.to_string(),
+            now_ts,
+            config_state: ctx.accounts.config.to_parity(),
+            allowlist_state: ctx.accounts.collateral_allowlist.to_parity(),
+        };
+
+        let (_new_allowlist, evt) =
+            instructions::remove_collateral_mint::remove_collateral_mint(input)
+                .map_err(PitStopAnchorError::from)?;
+
+        // Same order-preserving removal as parity; unused slots stay zeroed.
+        let allowlist = &mut ctx.accounts.collateral_allowlist;
+        let count = usize::from(allowlist.count);
+        let kept: Vec<CollateralMint> = allowlist.entries[..count]
+            .iter()
+            .filter(|entry| entry.mint != args.mint)
+            .copied()
+            .collect();
+        allowlist.entries = Default::default();
+        allowlist.entries[..kept.len()].copy_from_slice(&kept);
+        allowlist.count = kept.len() as u8;
+
+        emit!(anchor_events::CollateralMintRemoved {
+            authority: ctx.accounts.authority.key(),
+            mint: args.mint,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
     pub fn create_market(ctx: Context<CreateMarket>, args: CreateMarketArgs) -> Result<()> {
-        // Pre-flight account compatibility checks at Anchor boundary.
+        // Pre-flight account compatibility checks at Anchor boundary. The collateral
+        // mint's owner decides the market's token program, so a v1 and a Token-2022
+        // collateral can be live side by side.
         require_keys_eq!(
             ctx.accounts.token_program.key(),
-            ctx.accounts.config.token_program,
+            *ctx.accounts.usdc_mint.to_account_info().owner,
             PitStopAnchorError::InvalidTokenProgram
         );
-        // Taxonomy note: current LOCKED error surface reuses InvalidTreasuryMint
-        // for config-usdc mint mismatches at this boundary.
-        require_keys_eq!(ctx.accounts.usdc_mint.key(), ctx.accounts.config.usdc_mint, PitStopAnchorError::InvalidTreasuryMint);
 
         // Build parity input from Anchor accounts/args.
         // This keeps one authoritative implementation for business rules.
@@ -227,6 +767,7 @@ mod handlers {
         let input = instructions::create_market::CreateMarketInput {
             authority: ctx.accounts.authority.key().to_string(),
             config_authority: ctx.accounts.config.authority.to_string(),
+            config_paused: ctx.accounts.config.paused,
             token_program: ctx.accounts.token_program.key().to_string(),
             market: ctx.accounts.market.key().to_string(),
             vault: ctx.accounts.vault.key().to_string(),
@@ -237,6 +778,23 @@ mod handlers {
             max_outcomes: args.max_outcomes,
             market_type: args.market_type,
             rules_version: args.rules_version,
+            config_fee_bps: ctx.accounts.config.fee_bps,
+            config_claim_window_secs: ctx.accounts.config.claim_window_secs,
+            config_resolution_deadline_secs: ctx.accounts.config.resolution_deadline_secs,
+            collateral_mint: ctx.accounts.usdc_mint.key().to_string(),
+            config_usdc_mint: ctx.accounts.config.usdc_mint.to_string(),
+            config_treasury: ctx.accounts.config.treasury.to_string(),
+            collateral_allowlist: ctx
+                .accounts
+                .collateral_allowlist
+                .as_ref()
+                .map(|allowlist| allowlist.to_parity())
+                .unwrap_or_default(),
+            max_total_pool_per_market: args.max_total_pool_per_market,
+            max_bet_per_user_per_market: args.max_bet_per_user_per_market,
+            config_max_total_pool_per_market: ctx.accounts.config.max_total_pool_per_market,
+            config_max_bet_per_user_per_market: ctx.accounts.config.max_bet_per_user_per_market,
+            market_tombstone_exists: !ctx.accounts.market_tombstone.data_is_empty(),
         };
 
         let (mkt, evt) = instructions::create_market::create_market(input).map_err(PitStopAnchorError::from)?;
@@ -256,6 +814,18 @@ mod handlers {
         market.vault = ctx.accounts.vault.key();
         market.market_type = mkt.market_type;
         market.rules_version = mkt.rules_version;
+        market.fee_bps = mkt.fee_bps;
+        market.bump = ctx.bumps.market;
+        market.pools_closed = 0;
+        market.collateral_mint = ctx.accounts.usdc_mint.key();
+        // Parity resolved the paired treasury (config's, or the allowlist entry's).
+        market.treasury = Pubkey::from_str(&mkt.treasury)
+            .map_err(|_| error!(PitStopAnchorError::CollateralMintNotAllowed))?;
+        market.max_total_pool_per_market = mkt.max_total_pool_per_market;
+        market.max_bet_per_user_per_market = mkt.max_bet_per_user_per_market;
+        market.claim_window_secs = mkt.claim_window_secs;
+        market.resolution_deadline_secs = mkt.resolution_deadline_secs;
+        market.token_program = ctx.accounts.token_program.key();
 
         emit!(anchor_events::MarketCreated {
             market: ctx.accounts.market.key(),
@@ -265,6 +835,9 @@ mod handlers {
             max_outcomes: evt.max_outcomes,
             market_type: evt.market_type,
             rules_version: evt.rules_version,
+            collateral_mint: ctx.accounts.usdc_mint.key(),
+            max_total_pool_per_market: evt.max_total_pool_per_market,
+            max_bet_per_user_per_market: evt.max_bet_per_user_per_market,
             timestamp: now_ts,
         });
 
@@ -280,6 +853,7 @@ mod handlers {
         let input = instructions::add_outcome::AddOutcomeInput {
             authority: ctx.accounts.authority.key().to_string(),
             config_authority: ctx.accounts.config.authority.to_string(),
+            config_paused: ctx.accounts.config.paused,
             market: ctx.accounts.market.key().to_string(),
             market_status: market_state.status,
             market_outcome_count: market_state.outcome_count,
@@ -299,6 +873,7 @@ mod handlers {
         outcome_pool.market = ctx.accounts.market.key();
         outcome_pool.outcome_id = args.outcome_id;
         outcome_pool.pool_amount = 0;
+        outcome_pool.bump = ctx.bumps.outcome_pool;
 
         ctx.accounts.market.apply_parity(&new_market);
 
@@ -319,6 +894,7 @@ mod handlers {
         let input = instructions::finalize_seeding::FinalizeSeedingInput {
             authority: ctx.accounts.authority.key().to_string(),
             config_authority: ctx.accounts.config.authority.to_string(),
+            config_paused: ctx.accounts.config.paused,
             market: ctx.accounts.market.key().to_string(),
             market_status: market_state.status,
             market_outcome_count: market_state.outcome_count,
@@ -346,12 +922,12 @@ mod handlers {
         // Anchor boundary checks for pinned token program + mint/vault relations.
         require_keys_eq!(
             ctx.accounts.token_program.key(),
-            ctx.accounts.config.token_program,
+            ctx.accounts.market.token_program,
             PitStopAnchorError::InvalidTokenProgram
         );
         require_keys_eq!(
             ctx.accounts.usdc_mint.key(),
-            ctx.accounts.config.usdc_mint,
+            ctx.accounts.market.collateral_mint,
             PitStopAnchorError::InvalidTreasuryMint
         );
         require_keys_eq!(
@@ -386,10 +962,31 @@ mod handlers {
             pos.amount = 0;
             pos.claimed = false;
             pos.payout = 0;
+            pos.bump = ctx.bumps.position;
+        }
+        if ctx.accounts.user_stats.market == Pubkey::default() {
+            let stats = &mut ctx.accounts.user_stats;
+            stats.market = ctx.accounts.market.key();
+            stats.user = ctx.accounts.user.key();
+            stats.bump = ctx.bumps.user_stats;
         }
 
-        let now_ts = clock_unix_timestamp()?;
+        let clock = Clock::get()?;
+        let now_ts = clock.unix_timestamp;
+        // Token-2022 fee mints withhold part of the transfer in the vault; only the rest is staked.
+        let transfer_fee = {
+            let mint_info = ctx.accounts.usdc_mint.to_account_info();
+            let mint_data = mint_info.try_borrow_data()?;
+            token_policy::transfer_fee(mint_info.owner, &mint_data, clock.epoch, args.amount)
+                .map_err(PitStopAnchorError::from)?
+        };
         let market_state = ctx.accounts.market.to_parity();
+        let (max_total_pool_per_market, max_bet_per_user_per_market) =
+            instructions::place_bet::effective_caps(
+                &market_state,
+                ctx.accounts.config.max_total_pool_per_market,
+                ctx.accounts.config.max_bet_per_user_per_market,
+            );
         let input = instructions::place_bet::PlaceBetInput {
             config_paused: ctx.accounts.config.paused,
             market_status: market_state.status,
@@ -399,6 +996,9 @@ mod handlers {
             market_outcome_count: market_state.outcome_count,
             market_max_outcomes: market_state.max_outcomes,
             amount: args.amount,
+            transfer_fee,
+            max_outcome_share_bps: args.max_outcome_share_bps,
+            min_implied_payout: args.min_implied_payout,
             token_program: ctx.accounts.token_program.key().to_string(),
             outcome_pool_exists: true,
             outcome_pool_market: outcome_pool.market.to_string(),
@@ -406,9 +1006,9 @@ mod handlers {
             market: ctx.accounts.market.key().to_string(),
             user: ctx.accounts.user.key().to_string(),
             market_total_pool: market_state.total_pool,
-            max_total_pool_per_market: ctx.accounts.config.max_total_pool_per_market,
+            max_total_pool_per_market,
             user_position_amount: ctx.accounts.position.amount,
-            max_bet_per_user_per_market: ctx.accounts.config.max_bet_per_user_per_market,
+            max_bet_per_user_per_market,
             outcome_pool_amount: outcome_pool.pool_amount,
             vault_amount: ctx.accounts.vault.amount,
             market_state,
@@ -418,9 +1018,10 @@ mod handlers {
                 pool_amount: outcome_pool.pool_amount,
             },
             position_state: ctx.accounts.position.to_parity(),
+            user_market_stats_state: ctx.accounts.user_stats.to_parity(),
         };
 
-        let (new_market, new_pool, new_pos, _new_vault_amount, evt) =
+        let (new_market, new_pool, new_pos, new_stats, _new_vault_amount, evt) =
             instructions::place_bet::place_bet(input).map_err(PitStopAnchorError::from)?;
 
         // Funds move (CPI) happens only after deterministic preconditions pass.
@@ -442,6 +1043,7 @@ mod handlers {
             outcome_pool.try_serialize(&mut dst)?;
         }
         ctx.accounts.position.apply_parity(&new_pos);
+        ctx.accounts.user_stats.apply_parity(&new_stats);
 
         emit!(anchor_events::BetPlaced {
             market: ctx.accounts.market.key(),
@@ -456,31 +1058,306 @@ mod handlers {
         Ok(())
     }
 
-    pub fn lock_market(ctx: Context<LockMarket>) -> Result<()> {
-        let now_ts = clock_unix_timestamp()?;
-        let market_state = ctx.accounts.market.to_parity();
-        let input = instructions::lock_market::LockMarketInput {
-            authority: ctx.accounts.authority.key().to_string(),
-            config_authority: ctx.accounts.config.authority.to_string(),
-            market: ctx.accounts.market.key().to_string(),
-            market_status: market_state.status,
-            now_ts,
-            lock_timestamp: market_state.lock_timestamp,
-            market_state,
-        };
-
-        let (new_market, evt) =
-            instructions::lock_market::lock_market(input).map_err(PitStopAnchorError::from)?;
-        ctx.accounts.market.apply_parity(&new_market);
-
-        emit!(anchor_events::MarketLocked {
-            market: ctx.accounts.market.key(),
-            timestamp: evt.timestamp,
-        });
-
-        Ok(())
-    }
-
+    pub fn reduce_position(ctx: Context<ReducePosition>, args: ReducePositionArgs) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.token_program.key(),
+            ctx.accounts.market.token_program,
+            PitStopAnchorError::InvalidTokenProgram
+        );
+        require_keys_eq!(
+            ctx.accounts.usdc_mint.key(),
+            ctx.accounts.market.collateral_mint,
+            PitStopAnchorError::InvalidTreasuryMint
+        );
+        require_keys_eq!(
+            ctx.accounts.vault.key(),
+            ctx.accounts.market.vault,
+            PitStopAnchorError::OutcomeMismatch
+        );
+        require_keys_eq!(
+            ctx.accounts.user_usdc.mint,
+            ctx.accounts.usdc_mint.key(),
+            PitStopAnchorError::InvalidTreasuryMint
+        );
+        require_keys_eq!(
+            ctx.accounts.user_usdc.owner,
+            ctx.accounts.user.key(),
+            PitStopAnchorError::Unauthorized
+        );
+        require_keys_eq!(
+            ctx.accounts.treasury.mint,
+            ctx.accounts.usdc_mint.key(),
+            PitStopAnchorError::InvalidTreasuryMint
+        );
+        require_keys_eq!(
+            ctx.accounts.treasury.owner,
+            ctx.accounts.config.treasury_authority,
+            PitStopAnchorError::InvalidTreasuryOwner
+        );
+
+        let mut outcome_pool = load_outcome_pool_checked(
+            &ctx.accounts.outcome_pool,
+            ctx.accounts.market.key(),
+            args.outcome_id,
+        )?;
+
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::reduce_position::ReducePositionInput {
+            market: ctx.accounts.market.key().to_string(),
+            user: ctx.accounts.user.key().to_string(),
+            outcome_id: args.outcome_id,
+            amount: args.amount,
+            early_exit_fee_bps: ctx.accounts.config.early_exit_fee_bps,
+            now_ts,
+            vault_amount: ctx.accounts.vault.amount,
+            treasury: ctx.accounts.treasury.key().to_string(),
+            market_state: ctx.accounts.market.to_parity(),
+            outcome_pool_state: crate::state::OutcomePool {
+                market: outcome_pool.market.to_string(),
+                outcome_id: outcome_pool.outcome_id,
+                pool_amount: outcome_pool.pool_amount,
+            },
+            position_state: ctx.accounts.position.to_parity(),
+            user_market_stats_state: ctx.accounts.user_stats.to_parity(),
+        };
+
+        let (new_market, new_pool, new_pos, new_stats, _new_vault_amount, evt) =
+            instructions::reduce_position::reduce_position(input)
+                .map_err(PitStopAnchorError::from)?;
+
+        let market_bump = [ctx.accounts.market.bump];
+        let signer_seeds = pda::market_signer_seeds(&ctx.accounts.market.market_id, &market_bump);
+        let signer: &[&[&[u8]]] = &[&signer_seeds];
+        for (to, amount) in [
+            (ctx.accounts.user_usdc.to_account_info(), evt.refund),
+            (ctx.accounts.treasury.to_account_info(), evt.fee),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to,
+                authority: ctx.accounts.market.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
+        }
+
+        ctx.accounts.market.apply_parity(&new_market);
+        outcome_pool.pool_amount = new_pool.pool_amount;
+        {
+            let mut data_mut = ctx.accounts.outcome_pool.try_borrow_mut_data()?;
+            let mut dst: &mut [u8] = &mut data_mut;
+            outcome_pool.try_serialize(&mut dst)?;
+        }
+        ctx.accounts.position.apply_parity(&new_pos);
+        ctx.accounts.user_stats.apply_parity(&new_stats);
+
+        emit!(anchor_events::BetReduced {
+            market: ctx.accounts.market.key(),
+            user: ctx.accounts.user.key(),
+            outcome_id: evt.outcome_id,
+            amount: evt.amount,
+            fee: evt.fee,
+            refund: evt.refund,
+            position_amount: evt.position_amount,
+            market_total_pool: evt.market_total_pool,
+            outcome_pool_amount: evt.outcome_pool_amount,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn switch_outcome(ctx: Context<SwitchOutcome>, args: SwitchOutcomeArgs) -> Result<()> {
+        let mut from_pool = load_outcome_pool_checked(
+            &ctx.accounts.from_outcome_pool,
+            ctx.accounts.market.key(),
+            args.from_outcome_id,
+        )?;
+        let mut to_pool = load_outcome_pool_checked(
+            &ctx.accounts.to_outcome_pool,
+            ctx.accounts.market.key(),
+            args.to_outcome_id,
+        )?;
+
+        // Initialize destination position metadata on first creation, as in place_bet.
+        if ctx.accounts.to_position.market == Pubkey::default() {
+            let pos = &mut ctx.accounts.to_position;
+            pos.market = ctx.accounts.market.key();
+            pos.user = ctx.accounts.user.key();
+            pos.outcome_id = args.to_outcome_id;
+            pos.amount = 0;
+            pos.claimed = false;
+            pos.payout = 0;
+            pos.bump = ctx.bumps.to_position;
+        }
+
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::switch_outcome::SwitchOutcomeInput {
+            market: ctx.accounts.market.key().to_string(),
+            user: ctx.accounts.user.key().to_string(),
+            from_outcome_id: args.from_outcome_id,
+            to_outcome_id: args.to_outcome_id,
+            amount: args.amount,
+            config_paused: ctx.accounts.config.paused,
+            now_ts,
+            market_state: ctx.accounts.market.to_parity(),
+            from_pool_state: crate::state::OutcomePool {
+                market: from_pool.market.to_string(),
+                outcome_id: from_pool.outcome_id,
+                pool_amount: from_pool.pool_amount,
+            },
+            to_pool_state: crate::state::OutcomePool {
+                market: to_pool.market.to_string(),
+                outcome_id: to_pool.outcome_id,
+                pool_amount: to_pool.pool_amount,
+            },
+            from_position_state: ctx.accounts.from_position.to_parity(),
+            to_position_state: ctx.accounts.to_position.to_parity(),
+            user_market_stats_state: ctx.accounts.user_stats.to_parity(),
+        };
+
+        let (new_from_pool, new_to_pool, new_from_pos, new_to_pos, new_stats, evt) =
+            instructions::switch_outcome::switch_outcome(input)
+                .map_err(PitStopAnchorError::from)?;
+
+        from_pool.pool_amount = new_from_pool.pool_amount;
+        to_pool.pool_amount = new_to_pool.pool_amount;
+        for (account, pool) in [
+            (&ctx.accounts.from_outcome_pool, &from_pool),
+            (&ctx.accounts.to_outcome_pool, &to_pool),
+        ] {
+            let mut data_mut = account.try_borrow_mut_data()?;
+            let mut dst: &mut [u8] = &mut data_mut;
+            pool.try_serialize(&mut dst)?;
+        }
+        ctx.accounts.from_position.apply_parity(&new_from_pos);
+        ctx.accounts.to_position.apply_parity(&new_to_pos);
+        ctx.accounts.user_stats.apply_parity(&new_stats);
+
+        emit!(anchor_events::OutcomeSwitched {
+            market: ctx.accounts.market.key(),
+            user: ctx.accounts.user.key(),
+            from_outcome_id: evt.from_outcome_id,
+            to_outcome_id: evt.to_outcome_id,
+            amount: evt.amount,
+            from_position_amount: evt.from_position_amount,
+            to_position_amount: evt.to_position_amount,
+            from_outcome_pool_amount: evt.from_outcome_pool_amount,
+            to_outcome_pool_amount: evt.to_outcome_pool_amount,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn lock_market(ctx: Context<LockMarket>) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let market_state = ctx.accounts.market.to_parity();
+        let input = instructions::lock_market::LockMarketInput {
+            caller: ctx.accounts.caller.key().to_string(),
+            market: ctx.accounts.market.key().to_string(),
+            market_status: market_state.status,
+            now_ts,
+            lock_timestamp: market_state.lock_timestamp,
+            market_state,
+        };
+
+        let (new_market, evt) =
+            instructions::lock_market::lock_market(input).map_err(PitStopAnchorError::from)?;
+        ctx.accounts.market.apply_parity(&new_market);
+
+        emit!(anchor_events::MarketLocked {
+            market: ctx.accounts.market.key(),
+            locked_by: ctx.accounts.caller.key(),
+            lock_timestamp: evt.lock_timestamp,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn submit_resolution_vote(
+        ctx: Context<SubmitResolutionVote>,
+        args: SubmitResolutionVoteArgs,
+    ) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let decision = if args.void {
+            crate::state::ResolutionDecision::Void
+        } else {
+            crate::state::ResolutionDecision::Resolve {
+                winning_outcomes: std::iter::once(args.winning_outcome_id)
+                    .chain(args.additional_winning_outcome_ids.iter().copied())
+                    .collect(),
+            }
+        };
+
+        let input = instructions::submit_resolution_vote::SubmitResolutionVoteInput {
+            member: ctx.accounts.member.key().to_string(),
+            config_oracle: ctx.accounts.config.oracle.to_string(),
+            committee_address: ctx.accounts.oracle_committee.key().to_string(),
+            committee: ctx.accounts.oracle_committee.to_parity(),
+            market: ctx.accounts.market.key().to_string(),
+            market_state: ctx.accounts.market.to_parity(),
+            decision,
+            payload_hash: args.payload_hash,
+            votes: ctx.accounts.resolution_votes.to_parity(),
+            now_ts,
+        };
+
+        let (_new_votes, evt) = instructions::submit_resolution_vote::submit_resolution_vote(input)
+            .map_err(PitStopAnchorError::from)?;
+
+        // Same pruning as parity: the member's previous vote and votes of former members
+        // go, so at most one vote per current member remains.
+        let member = ctx.accounts.member.key();
+        let members = ctx.accounts.oracle_committee.members().to_vec();
+        let votes = &mut ctx.accounts.resolution_votes;
+        let mut kept: Vec<CommitteeVote> = votes.votes[..usize::from(votes.count)]
+            .iter()
+            .filter(|vote| vote.member != member && members.contains(&vote.member))
+            .copied()
+            .collect();
+        // Parity caps a resolve vote at PODIUM_SIZE winners.
+        let mut winning_outcomes = [0u8; constants::PODIUM_SIZE as usize];
+        winning_outcomes[..evt.winning_outcomes.len()].copy_from_slice(&evt.winning_outcomes);
+        kept.push(CommitteeVote {
+            member,
+            void: evt.void,
+            winning_outcomes,
+            winning_outcome_count: evt.winning_outcomes.len() as u8,
+            payload_hash: args.payload_hash,
+        });
+        votes.votes = Default::default();
+        votes.votes[..kept.len()].copy_from_slice(&kept);
+        votes.count = kept.len() as u8;
+        votes.market = ctx.accounts.market.key();
+        if votes.payer == Pubkey::default() {
+            votes.payer = member;
+        }
+        votes.bump = ctx.bumps.resolution_votes;
+
+        emit!(anchor_events::ResolutionVoteSubmitted {
+            market: ctx.accounts.market.key(),
+            member,
+            void: evt.void,
+            winning_outcomes: evt.winning_outcomes,
+            payload_hash: evt.payload_hash,
+            matching_votes: evt.matching_votes,
+            threshold: evt.threshold,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
     pub fn resolve_market(ctx: Context<ResolveMarket>, args: ResolveMarketArgs) -> Result<()> {
         let now_ts = clock_unix_timestamp()?;
 
@@ -490,10 +1367,45 @@ mod handlers {
             args.winning_outcome_id,
         )?;
 
+        // Count mismatches are left to the parity layer (OutcomeMismatch).
+        let additional_winning_pool_states = ctx
+            .remaining_accounts
+            .iter()
+            .zip(&args.additional_winning_outcome_ids)
+            .map(|(account, id)| {
+                load_outcome_pool_checked(account, ctx.accounts.market.key(), *id).map(|pool| {
+                    crate::state::OutcomePool {
+                        market: pool.market.to_string(),
+                        outcome_id: pool.outcome_id,
+                        pool_amount: pool.pool_amount,
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let attesters = oracle_attesters(
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &attestation::resolve_message(
+                &ctx.accounts.market.key(),
+                args.winning_outcome_id,
+                &args.additional_winning_outcome_ids,
+                &args.payload_hash,
+            ),
+        );
+        let (committee, votes) = committee_tally(
+            &ctx.accounts.config,
+            ctx.accounts.oracle_committee.as_ref(),
+            ctx.accounts.resolution_votes.as_ref(),
+        );
+        let committee_mode = committee.is_some();
+
         let market_state = ctx.accounts.market.to_parity();
         let input = instructions::resolve_market::ResolveMarketInput {
             oracle: ctx.accounts.oracle.key().to_string(),
             config_oracle: ctx.accounts.config.oracle.to_string(),
+            attesters,
+            committee,
+            votes,
             market: ctx.accounts.market.key().to_string(),
             market_state,
             winning_outcome_id: args.winning_outcome_id,
@@ -503,18 +1415,33 @@ mod handlers {
                 outcome_id: winning_pool.outcome_id,
                 pool_amount: winning_pool.pool_amount,
             }),
+            additional_winning_outcome_ids: args.additional_winning_outcome_ids,
+            additional_winning_pool_states,
+            result_payload: args.result_payload.map(|fields| {
+                fields.into_iter().map(|field| (field.key, field.value)).collect()
+            }),
+            dispute_window_secs: ctx.accounts.config.dispute_window_secs,
             now_ts,
         };
 
         let (new_market, evt) = instructions::resolve_market::resolve_market(input)
             .map_err(PitStopAnchorError::from)?;
         ctx.accounts.market.apply_parity(&new_market);
+        if committee_mode {
+            close_resolution_votes(
+                ctx.accounts.resolution_votes.as_ref(),
+                ctx.accounts.votes_payer.as_ref(),
+            )?;
+        }
 
         emit!(anchor_events::MarketResolved {
             market: ctx.accounts.market.key(),
             winning_outcome: evt.winning_outcome,
+            winning_outcomes: evt.winning_outcomes,
             payload_hash: evt.payload_hash,
+            payload_verified: evt.payload_verified,
             resolution_timestamp: evt.resolution_timestamp,
+            dispute_deadline: evt.dispute_deadline,
         });
 
         Ok(())
@@ -522,10 +1449,24 @@ mod handlers {
 
     pub fn void_market(ctx: Context<VoidMarket>, args: VoidMarketArgs) -> Result<()> {
         let now_ts = clock_unix_timestamp()?;
+        let attesters = oracle_attesters(
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &attestation::void_message(&ctx.accounts.market.key(), &args.payload_hash),
+        );
+        let (committee, votes) = committee_tally(
+            &ctx.accounts.config,
+            ctx.accounts.oracle_committee.as_ref(),
+            ctx.accounts.resolution_votes.as_ref(),
+        );
+        let committee_mode = committee.is_some();
+
         let market_state = ctx.accounts.market.to_parity();
         let input = instructions::void_market::VoidMarketInput {
             oracle: ctx.accounts.oracle.key().to_string(),
             config_oracle: ctx.accounts.config.oracle.to_string(),
+            attesters,
+            committee,
+            votes,
             market: ctx.accounts.market.key().to_string(),
             payload_hash: args.payload_hash,
             now_ts,
@@ -535,6 +1476,12 @@ mod handlers {
         let (new_market, evt) =
             instructions::void_market::void_market(input).map_err(PitStopAnchorError::from)?;
         ctx.accounts.market.apply_parity(&new_market);
+        if committee_mode {
+            close_resolution_votes(
+                ctx.accounts.resolution_votes.as_ref(),
+                ctx.accounts.votes_payer.as_ref(),
+            )?;
+        }
 
         emit!(anchor_events::MarketVoided {
             market: ctx.accounts.market.key(),
@@ -545,15 +1492,108 @@ mod handlers {
         Ok(())
     }
 
+    pub fn void_stale_market(ctx: Context<VoidStaleMarket>) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::void_stale_market::VoidStaleMarketInput {
+            caller: ctx.accounts.caller.key().to_string(),
+            market: ctx.accounts.market.key().to_string(),
+            market_state: ctx.accounts.market.to_parity(),
+            resolution_deadline_secs: ctx.accounts.market.resolution_deadline_secs,
+            now_ts,
+        };
+
+        let (new_market, evt) = instructions::void_stale_market::void_stale_market(input)
+            .map_err(PitStopAnchorError::from)?;
+        ctx.accounts.market.apply_parity(&new_market);
+
+        emit!(anchor_events::StaleMarketVoided {
+            market: ctx.accounts.market.key(),
+            caller: ctx.accounts.caller.key(),
+            resolution_deadline: evt.resolution_deadline,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn overturn_resolution(
+        ctx: Context<OverturnResolution>,
+        args: OverturnResolutionArgs,
+    ) -> Result<()> {
+        let now_ts = clock_unix_timestamp()?;
+        let market_key = ctx.accounts.market.key();
+
+        // Voiding needs no pools; a missing pool for a new winner maps to OutcomeMismatch.
+        let (winning_outcome_pool_state, additional_winning_pool_states) = if args.void {
+            (None, Vec::new())
+        } else {
+            let to_parity = |pool: OutcomePool| crate::state::OutcomePool {
+                market: pool.market.to_string(),
+                outcome_id: pool.outcome_id,
+                pool_amount: pool.pool_amount,
+            };
+            let winning = ctx
+                .accounts
+                .winning_outcome_pool
+                .as_ref()
+                .map(|account| {
+                    load_outcome_pool_checked(account, market_key, args.winning_outcome_id)
+                })
+                .transpose()?
+                .map(to_parity);
+            let additional = ctx
+                .remaining_accounts
+                .iter()
+                .zip(&args.additional_winning_outcome_ids)
+                .map(|(account, id)| load_outcome_pool_checked(account, market_key, *id).map(to_parity))
+                .collect::<Result<Vec<_>>>()?;
+            (winning, additional)
+        };
+
+        let input = instructions::overturn_resolution::OverturnResolutionInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            config_authority: ctx.accounts.config.authority.to_string(),
+            market: market_key.to_string(),
+            market_state: ctx.accounts.market.to_parity(),
+            void: args.void,
+            winning_outcome_id: args.winning_outcome_id,
+            winning_outcome_pool_state,
+            additional_winning_outcome_ids: args.additional_winning_outcome_ids,
+            additional_winning_pool_states,
+            payload_hash: args.payload_hash,
+            result_payload: args.result_payload.map(|fields| {
+                fields.into_iter().map(|field| (field.key, field.value)).collect()
+            }),
+            now_ts,
+        };
+
+        let (new_market, evt) = instructions::overturn_resolution::overturn_resolution(input)
+            .map_err(PitStopAnchorError::from)?;
+        ctx.accounts.market.apply_parity(&new_market);
+
+        emit!(anchor_events::ResolutionOverturned {
+            market: market_key,
+            authority: ctx.accounts.authority.key(),
+            void: evt.void,
+            old_winning_outcomes: evt.old_winning_outcomes,
+            winning_outcomes: evt.winning_outcomes,
+            payload_hash: evt.payload_hash,
+            payload_verified: evt.payload_verified,
+            timestamp: evt.timestamp,
+        });
+
+        Ok(())
+    }
+
     pub fn claim_resolved(ctx: Context<ClaimResolved>, args: ClaimResolvedArgs) -> Result<()> {
         require_keys_eq!(
             ctx.accounts.token_program.key(),
-            ctx.accounts.config.token_program,
+            ctx.accounts.market.token_program,
             PitStopAnchorError::InvalidTokenProgram
         );
         require_keys_eq!(
             ctx.accounts.usdc_mint.key(),
-            ctx.accounts.config.usdc_mint,
+            ctx.accounts.market.collateral_mint,
             PitStopAnchorError::InvalidTreasuryMint
         );
         require_keys_eq!(
@@ -579,6 +1619,7 @@ mod handlers {
         )?;
 
         let now_ts = clock_unix_timestamp()?;
+        finalize_resolution_if_due(&mut ctx.accounts.market, now_ts);
         let market_state = ctx.accounts.market.to_parity();
         let input = instructions::claim_resolved::ClaimResolvedInput {
             market: ctx.accounts.market.key().to_string(),
@@ -586,8 +1627,9 @@ mod handlers {
             market_status: market_state.status,
             now_ts,
             resolution_timestamp: market_state.resolution_timestamp,
-            claim_window_secs: ctx.accounts.config.claim_window_secs,
-            fee_bps: ctx.accounts.config.fee_bps,
+            dispute_deadline: market_state.dispute_deadline,
+            claim_window_secs: market_state.claim_window_secs,
+            fee_bps: market_state.fee_bps,
             resolved_outcome: market_state.resolved_outcome,
             outcome_id: args.outcome_id,
             position_claimed: ctx.accounts.position.claimed,
@@ -617,16 +1659,10 @@ mod handlers {
                 to: ctx.accounts.user_usdc.to_account_info(),
                 authority: ctx.accounts.market.to_account_info(),
             };
-            let (_market_pda, market_bump) = Pubkey::find_program_address(
-                &[MARKET_SEED, ctx.accounts.market.market_id.as_ref()],
-                &crate::id(),
-            );
-            let signer_seeds: &[&[u8]] = &[
-                MARKET_SEED,
-                ctx.accounts.market.market_id.as_ref(),
-                &[market_bump],
-            ];
-            let signer = &[signer_seeds];
+            let market_bump = [ctx.accounts.market.bump];
+            let signer_seeds =
+                pda::market_signer_seeds(&ctx.accounts.market.market_id, &market_bump);
+            let signer: &[&[&[u8]]] = &[&signer_seeds];
             let cpi_ctx = CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 cpi_accounts,
@@ -643,20 +1679,338 @@ mod handlers {
             outcome_id: evt.outcome_id,
             payout: evt.payout,
             claimed_at: evt.claimed_at,
+            initiator: ctx.accounts.user.key(),
         });
 
+        if args.close_position {
+            close_position_checked(
+                ctx.accounts.market.key(),
+                ctx.accounts.market.to_parity().status,
+                &ctx.accounts.position,
+                &ctx.accounts.user,
+                evt.claimed_at,
+            )?;
+            ctx.accounts.position.close(ctx.accounts.user.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    /// Accounts per claim_many group: market, outcome_pool, position, vault.
+    const CLAIM_MANY_GROUP_LEN: usize = 4;
+
+    pub fn claim_many<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.user_usdc.mint,
+            ctx.accounts.usdc_mint.key(),
+            PitStopAnchorError::InvalidTreasuryMint
+        );
+        require_keys_eq!(
+            ctx.accounts.user_usdc.owner,
+            ctx.accounts.user.key(),
+            PitStopAnchorError::Unauthorized
+        );
+
+        let groups = ctx.remaining_accounts;
+        if !groups.len().is_multiple_of(CLAIM_MANY_GROUP_LEN) {
+            return Err(error!(PitStopAnchorError::InvalidClaimBatch));
+        }
+
+        let user_key = ctx.accounts.user.key();
+        let now_ts = clock_unix_timestamp()?;
+        let mut markets = Vec::with_capacity(groups.len() / CLAIM_MANY_GROUP_LEN);
+        let mut positions = Vec::with_capacity(groups.len() / CLAIM_MANY_GROUP_LEN);
+        let mut vaults = Vec::with_capacity(groups.len() / CLAIM_MANY_GROUP_LEN);
+        let mut entries = Vec::with_capacity(groups.len() / CLAIM_MANY_GROUP_LEN);
+        let mut finalized = Vec::new();
+
+        for group in groups.chunks_exact(CLAIM_MANY_GROUP_LEN) {
+            let mut market = Account::<Market>::try_from(&group[0])?;
+            let position = Account::<Position>::try_from(&group[2])?;
+            let vault = &group[3];
+
+            // Same relations claim_resolved gets from its account constraints.
+            require_keys_eq!(
+                ctx.accounts.token_program.key(),
+                market.token_program,
+                PitStopAnchorError::InvalidTokenProgram
+            );
+            require_keys_eq!(
+                ctx.accounts.usdc_mint.key(),
+                market.collateral_mint,
+                PitStopAnchorError::InvalidTreasuryMint
+            );
+            require_keys_eq!(vault.key(), market.vault, PitStopAnchorError::OutcomeMismatch);
+            let expected_position = pda::position_address_with_bump(
+                &market.key(),
+                &position.user,
+                position.outcome_id,
+                position.bump,
+            );
+            if expected_position != Some(position.key()) {
+                return Err(error!(PitStopAnchorError::OutcomeMismatch));
+            }
+
+            let outcome_pool =
+                load_outcome_pool_checked(&group[1], market.key(), position.outcome_id)?;
+            let vault_amount = InterfaceAccount::<TokenAccount>::try_from(vault)?.amount;
+
+            // Markets are read-only here unless passed writable; finalize each one once.
+            if group[0].is_writable
+                && !markets.iter().any(|m: &Account<Market>| m.key() == market.key())
+                && finalize_resolution_if_due(&mut market, now_ts)
+            {
+                finalized.push(markets.len());
+            }
+
+            let market_state = market.to_parity();
+            entries.push(instructions::claim_many::ClaimManyEntry {
+                position: position.key().to_string(),
+                vault: vault.key().to_string(),
+                claim: instructions::claim_resolved::ClaimResolvedInput {
+                    market: market.key().to_string(),
+                    user: user_key.to_string(),
+                    market_status: market_state.status,
+                    now_ts,
+                    resolution_timestamp: market_state.resolution_timestamp,
+                    dispute_deadline: market_state.dispute_deadline,
+                    claim_window_secs: market_state.claim_window_secs,
+                    fee_bps: market_state.fee_bps,
+                    resolved_outcome: market_state.resolved_outcome,
+                    outcome_id: position.outcome_id,
+                    position_claimed: position.claimed,
+                    position_amount: position.amount,
+                    outcome_pool_exists: true,
+                    outcome_pool_market: outcome_pool.market.to_string(),
+                    outcome_pool_outcome_id: outcome_pool.outcome_id,
+                    outcome_pool_amount: outcome_pool.pool_amount,
+                    vault_amount,
+                    user_usdc_amount: ctx.accounts.user_usdc.amount,
+                    market_state,
+                    outcome_pool_state: crate::state::OutcomePool {
+                        market: outcome_pool.market.to_string(),
+                        outcome_id: outcome_pool.outcome_id,
+                        pool_amount: outcome_pool.pool_amount,
+                    },
+                    position_state: position.to_parity(),
+                },
+            });
+            markets.push(market);
+            positions.push(position);
+            vaults.push(vault);
+        }
+
+        let input = instructions::claim_many::ClaimManyInput {
+            user: user_key.to_string(),
+            entries,
+        };
+        let out = instructions::claim_many::claim_many(input).map_err(PitStopAnchorError::from)?;
+
+        // One transfer per vault, signed by the market of the first group using it.
+        for (vault_key, payout) in &out.vault_payouts {
+            if *payout == 0 {
+                continue;
+            }
+            let Some(i) = vaults.iter().position(|v| v.key().to_string() == *vault_key) else {
+                return Err(error!(PitStopAnchorError::OutcomeMismatch));
+            };
+            let cpi_accounts = TransferChecked {
+                from: vaults[i].clone(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.user_usdc.to_account_info(),
+                authority: markets[i].to_account_info(),
+            };
+            let market_bump = [markets[i].bump];
+            let signer_seeds = pda::market_signer_seeds(&markets[i].market_id, &market_bump);
+            let signer: &[&[&[u8]]] = &[&signer_seeds];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            transfer_checked(cpi_ctx, *payout, ctx.accounts.usdc_mint.decimals)?;
+        }
+
+        // Remaining accounts are not persisted by Anchor; write each position back.
+        for (position, new_pos) in positions.iter_mut().zip(&out.positions) {
+            position.apply_parity(new_pos);
+            position.exit(&crate::ID)?;
+        }
+        for i in finalized {
+            markets[i].exit(&crate::ID)?;
+        }
+
+        for (evt, market) in out.events.iter().zip(&markets) {
+            emit!(anchor_events::Claimed {
+                market: market.key(),
+                user: user_key,
+                outcome_id: evt.outcome_id,
+                payout: evt.payout,
+                claimed_at: evt.claimed_at,
+                initiator: user_key,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Accounts per distribute_payouts group: position, outcome_pool, destination.
+    const DISTRIBUTE_GROUP_LEN: usize = 3;
+
+    pub fn distribute_payouts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributePayouts<'info>>,
+    ) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.token_program.key(),
+            ctx.accounts.market.token_program,
+            PitStopAnchorError::InvalidTokenProgram
+        );
+        require_keys_eq!(
+            ctx.accounts.usdc_mint.key(),
+            ctx.accounts.market.collateral_mint,
+            PitStopAnchorError::InvalidTreasuryMint
+        );
+        require_keys_eq!(
+            ctx.accounts.vault.key(),
+            ctx.accounts.market.vault,
+            PitStopAnchorError::OutcomeMismatch
+        );
+
+        let groups = ctx.remaining_accounts;
+        if !groups.len().is_multiple_of(DISTRIBUTE_GROUP_LEN) {
+            return Err(error!(PitStopAnchorError::InvalidClaimBatch));
+        }
+
+        let market_key = ctx.accounts.market.key();
+        let now_ts = clock_unix_timestamp()?;
+        finalize_resolution_if_due(&mut ctx.accounts.market, now_ts);
+        let mut positions = Vec::with_capacity(groups.len() / DISTRIBUTE_GROUP_LEN);
+        let mut destinations = Vec::with_capacity(groups.len() / DISTRIBUTE_GROUP_LEN);
+        let mut entries = Vec::with_capacity(groups.len() / DISTRIBUTE_GROUP_LEN);
+
+        for group in groups.chunks_exact(DISTRIBUTE_GROUP_LEN) {
+            let position = Account::<Position>::try_from(&group[0])?;
+            let destination = InterfaceAccount::<TokenAccount>::try_from(&group[2])?;
+
+            let expected_position = pda::position_address_with_bump(
+                &market_key,
+                &position.user,
+                position.outcome_id,
+                position.bump,
+            );
+            if expected_position != Some(position.key()) {
+                return Err(error!(PitStopAnchorError::OutcomeMismatch));
+            }
+            require_keys_eq!(
+                destination.mint,
+                ctx.accounts.usdc_mint.key(),
+                PitStopAnchorError::InvalidTreasuryMint
+            );
+
+            let outcome_pool = load_outcome_pool_checked(&group[1], market_key, position.outcome_id)?;
+
+            let market_state = ctx.accounts.market.to_parity();
+            entries.push(instructions::distribute_payouts::DistributePayoutsEntry {
+                position: position.key().to_string(),
+                destination_owner: destination.owner.to_string(),
+                destination_is_owner_ata: destination.key()
+                    == get_associated_token_address_with_program_id(
+                        &position.user,
+                        &ctx.accounts.usdc_mint.key(),
+                        &ctx.accounts.market.token_program,
+                    ),
+                claim: instructions::claim_resolved::ClaimResolvedInput {
+                    market: market_key.to_string(),
+                    user: position.user.to_string(),
+                    market_status: market_state.status,
+                    now_ts,
+                    resolution_timestamp: market_state.resolution_timestamp,
+                    dispute_deadline: market_state.dispute_deadline,
+                    claim_window_secs: market_state.claim_window_secs,
+                    fee_bps: market_state.fee_bps,
+                    resolved_outcome: market_state.resolved_outcome,
+                    outcome_id: position.outcome_id,
+                    position_claimed: position.claimed,
+                    position_amount: position.amount,
+                    outcome_pool_exists: true,
+                    outcome_pool_market: outcome_pool.market.to_string(),
+                    outcome_pool_outcome_id: outcome_pool.outcome_id,
+                    outcome_pool_amount: outcome_pool.pool_amount,
+                    vault_amount: ctx.accounts.vault.amount,
+                    user_usdc_amount: destination.amount,
+                    market_state,
+                    outcome_pool_state: crate::state::OutcomePool {
+                        market: outcome_pool.market.to_string(),
+                        outcome_id: outcome_pool.outcome_id,
+                        pool_amount: outcome_pool.pool_amount,
+                    },
+                    position_state: position.to_parity(),
+                },
+            });
+            positions.push(position);
+            destinations.push(&group[2]);
+        }
+
+        let input = instructions::distribute_payouts::DistributePayoutsInput {
+            caller: ctx.accounts.caller.key().to_string(),
+            config_authority: ctx.accounts.config.authority.to_string(),
+            config_payout_keeper: ctx.accounts.config.payout_keeper.map(|k| k.to_string()),
+            market: market_key.to_string(),
+            entries,
+        };
+        let out = instructions::distribute_payouts::distribute_payouts(input)
+            .map_err(PitStopAnchorError::from)?;
+
+        let market_bump = [ctx.accounts.market.bump];
+        let signer_seeds = pda::market_signer_seeds(&ctx.accounts.market.market_id, &market_bump);
+        let signer: &[&[&[u8]]] = &[&signer_seeds];
+        for (evt, destination) in out.events.iter().zip(&destinations) {
+            if evt.payout == 0 {
+                continue;
+            }
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: (*destination).clone(),
+                authority: ctx.accounts.market.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            transfer_checked(cpi_ctx, evt.payout, ctx.accounts.usdc_mint.decimals)?;
+        }
+
+        // Remaining accounts are not persisted by Anchor; write each position back.
+        for (position, new_pos) in positions.iter_mut().zip(&out.positions) {
+            position.apply_parity(new_pos);
+            position.exit(&crate::ID)?;
+        }
+
+        for (evt, position) in out.events.iter().zip(&positions) {
+            emit!(anchor_events::Claimed {
+                market: market_key,
+                user: position.user,
+                outcome_id: evt.outcome_id,
+                payout: evt.payout,
+                claimed_at: evt.claimed_at,
+                initiator: ctx.accounts.caller.key(),
+            });
+        }
+
         Ok(())
     }
 
     pub fn claim_voided(ctx: Context<ClaimVoided>, args: ClaimVoidedArgs) -> Result<()> {
         require_keys_eq!(
             ctx.accounts.token_program.key(),
-            ctx.accounts.config.token_program,
+            ctx.accounts.market.token_program,
             PitStopAnchorError::InvalidTokenProgram
         );
         require_keys_eq!(
             ctx.accounts.usdc_mint.key(),
-            ctx.accounts.config.usdc_mint,
+            ctx.accounts.market.collateral_mint,
             PitStopAnchorError::InvalidTreasuryMint
         );
         require_keys_eq!(
@@ -681,7 +2035,7 @@ mod handlers {
             user: ctx.accounts.user.key().to_string(),
             market_status: ctx.accounts.market.to_parity().status,
             resolution_timestamp: ctx.accounts.market.resolution_timestamp,
-            claim_window_secs: ctx.accounts.config.claim_window_secs,
+            claim_window_secs: ctx.accounts.market.claim_window_secs,
             now_ts,
             outcome_id: args.outcome_id,
             user_usdc_amount: ctx.accounts.user_usdc.amount,
@@ -699,16 +2053,10 @@ mod handlers {
                 to: ctx.accounts.user_usdc.to_account_info(),
                 authority: ctx.accounts.market.to_account_info(),
             };
-            let (_market_pda, market_bump) = Pubkey::find_program_address(
-                &[MARKET_SEED, ctx.accounts.market.market_id.as_ref()],
-                &crate::id(),
-            );
-            let signer_seeds: &[&[u8]] = &[
-                MARKET_SEED,
-                ctx.accounts.market.market_id.as_ref(),
-                &[market_bump],
-            ];
-            let signer = &[signer_seeds];
+            let market_bump = [ctx.accounts.market.bump];
+            let signer_seeds =
+                pda::market_signer_seeds(&ctx.accounts.market.market_id, &market_bump);
+            let signer: &[&[&[u8]]] = &[&signer_seeds];
             let cpi_ctx = CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 cpi_accounts,
@@ -725,20 +2073,32 @@ mod handlers {
             outcome_id: evt.outcome_id,
             payout: evt.payout,
             claimed_at: evt.claimed_at,
+            initiator: ctx.accounts.user.key(),
         });
 
+        if args.close_position {
+            close_position_checked(
+                ctx.accounts.market.key(),
+                ctx.accounts.market.to_parity().status,
+                &ctx.accounts.position,
+                &ctx.accounts.user,
+                evt.claimed_at,
+            )?;
+            ctx.accounts.position.close(ctx.accounts.user.to_account_info())?;
+        }
+
         Ok(())
     }
 
     pub fn sweep_remaining(ctx: Context<SweepRemaining>) -> Result<()> {
         require_keys_eq!(
             ctx.accounts.token_program.key(),
-            ctx.accounts.config.token_program,
+            ctx.accounts.market.token_program,
             PitStopAnchorError::InvalidTokenProgram
         );
         require_keys_eq!(
             ctx.accounts.usdc_mint.key(),
-            ctx.accounts.config.usdc_mint,
+            ctx.accounts.market.collateral_mint,
             PitStopAnchorError::InvalidTreasuryMint
         );
         require_keys_eq!(
@@ -758,18 +2118,18 @@ mod handlers {
         );
 
         let now_ts = clock_unix_timestamp()?;
+        // A result that was never finalized by a claim is final by now if sweep can pass.
+        finalize_resolution_if_due(&mut ctx.accounts.market, now_ts);
         let market_state = ctx.accounts.market.to_parity();
         let input = instructions::sweep_remaining::SweepRemainingInput {
             authority: ctx.accounts.authority.key().to_string(),
             config_authority: ctx.accounts.config.authority.to_string(),
             market: ctx.accounts.market.key().to_string(),
             now_ts,
-            claim_window_secs: ctx.accounts.config.claim_window_secs,
+            claim_window_secs: market_state.claim_window_secs,
             token_program: ctx.accounts.token_program.key().to_string(),
             treasury: ctx.accounts.treasury.key().to_string(),
-            config_treasury: ctx.accounts.config.treasury.to_string(),
             treasury_mint: ctx.accounts.treasury.mint.to_string(),
-            usdc_mint: ctx.accounts.usdc_mint.key().to_string(),
             treasury_owner: ctx.accounts.treasury.owner.to_string(),
             treasury_authority: ctx.accounts.config.treasury_authority.to_string(),
             vault_amount: ctx.accounts.vault.amount,
@@ -780,16 +2140,9 @@ mod handlers {
         let (new_market, _new_treasury_amount, swept_amount, _vault_closed, _vault_exists, _used_seeds, evt) =
             instructions::sweep_remaining::sweep_remaining(input).map_err(PitStopAnchorError::from)?;
 
-        let (_market_pda, market_bump) = Pubkey::find_program_address(
-            &[MARKET_SEED, ctx.accounts.market.market_id.as_ref()],
-            &crate::id(),
-        );
-        let signer_seeds: &[&[u8]] = &[
-            MARKET_SEED,
-            ctx.accounts.market.market_id.as_ref(),
-            &[market_bump],
-        ];
-        let signer = &[signer_seeds];
+        let market_bump = [ctx.accounts.market.bump];
+        let signer_seeds = pda::market_signer_seeds(&ctx.accounts.market.market_id, &market_bump);
+        let signer: &[&[&[u8]]] = &[&signer_seeds];
 
         if swept_amount > 0 {
             let transfer_accounts = TransferChecked {
@@ -806,6 +2159,23 @@ mod handlers {
             transfer_checked(transfer_ctx, swept_amount, ctx.accounts.usdc_mint.decimals)?;
         }
 
+        // Token-2022 will not close an account holding withheld transfer fees; move them
+        // to the mint (permissionless) where the mint's withdraw authority can collect them.
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let withheld = token_policy::withheld_amount(vault_info.owner, &vault_info.try_borrow_data()?);
+        if withheld > 0 {
+            harvest_withheld_tokens_to_mint(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    HarvestWithheldTokensToMint {
+                        token_program_id: ctx.accounts.token_program.to_account_info(),
+                        mint: ctx.accounts.usdc_mint.to_account_info(),
+                    },
+                ),
+                vec![vault_info],
+            )?;
+        }
+
         let close_accounts = CloseAccount {
             account: ctx.accounts.vault.to_account_info(),
             destination: ctx.accounts.close_destination.to_account_info(),
@@ -833,7 +2203,7 @@ mod handlers {
     pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
         require_keys_eq!(
             ctx.accounts.token_program.key(),
-            ctx.accounts.config.token_program,
+            ctx.accounts.market.token_program,
             PitStopAnchorError::InvalidTokenProgram
         );
         require_keys_eq!(
@@ -858,16 +2228,9 @@ mod handlers {
         let (new_market, evt) =
             instructions::cancel_market::cancel_market(input).map_err(PitStopAnchorError::from)?;
 
-        let (_market_pda, market_bump) = Pubkey::find_program_address(
-            &[MARKET_SEED, ctx.accounts.market.market_id.as_ref()],
-            &crate::id(),
-        );
-        let signer_seeds: &[&[u8]] = &[
-            MARKET_SEED,
-            ctx.accounts.market.market_id.as_ref(),
-            &[market_bump],
-        ];
-        let signer = &[signer_seeds];
+        let market_bump = [ctx.accounts.market.bump];
+        let signer_seeds = pda::market_signer_seeds(&ctx.accounts.market.market_id, &market_bump);
+        let signer: &[&[&[u8]]] = &[&signer_seeds];
         let close_accounts = CloseAccount {
             account: ctx.accounts.vault.to_account_info(),
             destination: ctx.accounts.close_destination.to_account_info(),
@@ -889,4 +2252,89 @@ mod handlers {
 
         Ok(())
     }
+
+    pub fn close_position(ctx: Context<ClosePosition>, _args: ClosePositionArgs) -> Result<()> {
+        // close_market only closes finished markets (Swept or cancelled), and a
+        // cancelled market never held positions, so a closed market reads as Swept.
+        let market_info = &ctx.accounts.market;
+        let market_status = if market_info.data_is_empty() {
+            crate::state::MarketStatus::Swept
+        } else {
+            if market_info.owner != &crate::id() {
+                return Err(error!(ErrorCode::AccountOwnedByWrongProgram));
+            }
+            let data = market_info.try_borrow_data()?;
+            Market::try_deserialize(&mut &data[..])?.to_parity().status
+        };
+
+        let now_ts = clock_unix_timestamp()?;
+        close_position_checked(
+            ctx.accounts.market.key(),
+            market_status,
+            &ctx.accounts.position,
+            &ctx.accounts.user,
+            now_ts,
+        )
+    }
+
+    pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.vault.key(),
+            ctx.accounts.market.vault,
+            PitStopAnchorError::OutcomeMismatch
+        );
+
+        let market_key = ctx.accounts.market.key();
+        let pools = ctx.remaining_accounts;
+        let pools_in_call =
+            u8::try_from(pools.len()).map_err(|_| error!(PitStopAnchorError::OutcomeMismatch))?;
+
+        let now_ts = clock_unix_timestamp()?;
+        let input = instructions::close_market::CloseMarketInput {
+            authority: ctx.accounts.authority.key().to_string(),
+            config_authority: ctx.accounts.config.authority.to_string(),
+            market: market_key.to_string(),
+            market_status: ctx.accounts.market.to_parity().status,
+            total_pool: ctx.accounts.market.total_pool,
+            vault_closed: ctx.accounts.vault.data_is_empty(),
+            outcome_count: ctx.accounts.market.outcome_count,
+            pools_closed: ctx.accounts.market.pools_closed,
+            pools_in_call,
+            now_ts,
+        };
+
+        let (pools_closed, evt) =
+            instructions::close_market::close_market(input).map_err(PitStopAnchorError::from)?;
+
+        // Each pool is validated right before it is closed, so a pool listed twice
+        // fails the owner check on its second pass.
+        let authority = ctx.accounts.authority.to_account_info();
+        for pool_account in pools {
+            load_market_outcome_pool(pool_account, market_key)?;
+            close_program_account(pool_account, &authority)?;
+        }
+
+        ctx.accounts.market.pools_closed = pools_closed;
+
+        let tombstone = &mut ctx.accounts.market_tombstone;
+        tombstone.market_id = ctx.accounts.market.market_id;
+        tombstone.bump = ctx.bumps.market_tombstone;
+        if evt.market_closed {
+            tombstone.closed_at = evt.timestamp;
+        }
+
+        emit!(anchor_events::MarketRentReclaimed {
+            market: market_key,
+            pools_closed: evt.pools_closed,
+            total_pools_closed: evt.total_pools_closed,
+            market_closed: evt.market_closed,
+            timestamp: evt.timestamp,
+        });
+
+        if evt.market_closed {
+            ctx.accounts.market.close(authority)?;
+        }
+
+        Ok(())
+    }
 }
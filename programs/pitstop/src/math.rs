@@ -1 +1,183 @@
-// Math helpers skeleton; deterministic fee/payout behavior defined in specs.
+//! Payout/fee engine.
+//!
+//! Spec: SPEC_PROTOCOL.md -> "Payout math". Golden vectors: specs/vectors/payout_vectors.json
+//!
+//! All products are taken in u128 so `amount * pool` cannot overflow for any pair of
+//! u64 inputs; only results that do not fit back into u64 surface as `Overflow`.
+//! Every division floors, so rounding always favors the vault. The one exception is
+//! `exit_fee_amount`, which rounds up so an early exit cannot be split into fee-free pieces.
+
+use crate::error::PitStopError;
+
+/// Basis-point denominator (100% == 10_000 bps).
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+fn to_u64(v: u128) -> Result<u64, PitStopError> {
+    u64::try_from(v).map_err(|_| PitStopError::Overflow)
+}
+
+/// fee = floor(total_pool * fee_bps / 10_000)
+///
+/// `fee_bps` is bounded by config validation, not here; a fee above 100% surfaces
+/// from `prize_pool` as `Underflow`.
+pub fn fee_amount(total_pool: u64, fee_bps: u16) -> Result<u64, PitStopError> {
+    let fee = u128::from(total_pool) * u128::from(fee_bps) / u128::from(BPS_DENOMINATOR);
+    to_u64(fee)
+}
+
+/// exit_fee = ceil(amount * fee_bps / 10_000)
+///
+/// Early-exit fee for reduce_position. Rounding up means any non-zero fee rate charges at
+/// least one unit, so repeated tiny reductions cannot dodge the fee.
+pub fn exit_fee_amount(amount: u64, fee_bps: u16) -> Result<u64, PitStopError> {
+    let fee = (u128::from(amount) * u128::from(fee_bps)).div_ceil(u128::from(BPS_DENOMINATOR));
+    to_u64(fee)
+}
+
+/// prize_pool = total_pool - fee
+pub fn prize_pool(total_pool: u64, fee_bps: u16) -> Result<u64, PitStopError> {
+    let fee = fee_amount(total_pool, fee_bps)?;
+    total_pool.checked_sub(fee).ok_or(PitStopError::Underflow)
+}
+
+/// payout = floor(position_amount * prize_pool / winner_pool)
+pub fn winner_payout(
+    position_amount: u64,
+    prize_pool: u64,
+    winner_pool: u64,
+) -> Result<u64, PitStopError> {
+    if winner_pool == 0 {
+        return Err(PitStopError::DivisionByZero);
+    }
+    let payout = u128::from(position_amount) * u128::from(prize_pool) / u128::from(winner_pool);
+    to_u64(payout)
+}
+
+/// Convenience wrapper: fee -> prize pool -> payout for a single winning position.
+pub fn resolved_payout(
+    total_pool: u64,
+    winner_pool: u64,
+    position_amount: u64,
+    fee_bps: u16,
+) -> Result<u64, PitStopError> {
+    let prize = prize_pool(total_pool, fee_bps)?;
+    winner_payout(position_amount, prize, winner_pool)
+}
+
+/// dust = prize_pool - sum(payouts). Floor rounding leaves at most `winners - 1`
+/// units behind; they stay in the vault until `sweep_remaining`.
+pub fn payout_dust(prize_pool: u64, total_paid: u64) -> Result<u64, PitStopError> {
+    prize_pool.checked_sub(total_paid).ok_or(PitStopError::Underflow)
+}
+
+/// balance + amount, for token-balance effects.
+pub fn credit(balance: u64, amount: u64) -> Result<u64, PitStopError> {
+    balance.checked_add(amount).ok_or(PitStopError::Overflow)
+}
+
+/// balance - amount, for token-balance effects.
+pub fn debit(balance: u64, amount: u64) -> Result<u64, PitStopError> {
+    balance.checked_sub(amount).ok_or(PitStopError::Underflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const PAYOUT_VECTORS: &str = include_str!("../../../specs/vectors/payout_vectors.json");
+
+    fn num(v: &Value, key: &str) -> u64 {
+        v[key]
+            .as_str()
+            .unwrap_or_else(|| panic!("{key} must be a decimal string"))
+            .parse()
+            .unwrap_or_else(|_| panic!("{key} must fit u64"))
+    }
+
+    fn error_named(name: &str) -> PitStopError {
+        match name {
+            "Overflow" => PitStopError::Overflow,
+            "Underflow" => PitStopError::Underflow,
+            "DivisionByZero" => PitStopError::DivisionByZero,
+            other => panic!("unknown vector error {other}"),
+        }
+    }
+
+    #[test]
+    fn math_vec_001_payout_golden_vectors() {
+        let doc: Value = serde_json::from_str(PAYOUT_VECTORS).expect("valid vector json");
+
+        for case in doc["payout"].as_array().expect("payout cases") {
+            let name = case["name"].as_str().unwrap();
+            let total = num(case, "totalPool");
+            let fee_bps = case["feeBps"].as_u64().unwrap() as u16;
+            let winner_pool = num(case, "winnerPool");
+            let position = num(case, "positionAmount");
+
+            if let Some(err) = case["error"].as_str() {
+                assert_eq!(
+                    resolved_payout(total, winner_pool, position, fee_bps).unwrap_err(),
+                    error_named(err),
+                    "{name}"
+                );
+                continue;
+            }
+
+            assert_eq!(fee_amount(total, fee_bps).unwrap(), num(case, "fee"), "{name}: fee");
+            let prize = prize_pool(total, fee_bps).unwrap();
+            assert_eq!(prize, num(case, "prizePool"), "{name}: prizePool");
+            assert_eq!(
+                winner_payout(position, prize, winner_pool).unwrap(),
+                num(case, "payout"),
+                "{name}: payout"
+            );
+        }
+
+        for case in doc["dust"].as_array().expect("dust cases") {
+            let name = case["name"].as_str().unwrap();
+            let prize = num(case, "prizePool");
+            let winner_pool = num(case, "winnerPool");
+            let paid = case["positions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| winner_payout(p.as_str().unwrap().parse().unwrap(), prize, winner_pool))
+                .try_fold(0u64, |acc, p| credit(acc, p?))
+                .unwrap();
+            assert_eq!(paid, num(case, "totalPaid"), "{name}: totalPaid");
+            assert_eq!(payout_dust(prize, paid).unwrap(), num(case, "dust"), "{name}: dust");
+        }
+    }
+
+    #[test]
+    fn math_ovf_001_u64_product_overflow_is_handled_in_u128() {
+        // 10M USDC position against a 20M USDC pool: the product is ~2e26, far beyond u64.
+        let total = 20_000_000_000_000u64;
+        let winner_pool = 10_000_000_000_000u64;
+        assert!(winner_pool.checked_mul(total).is_none());
+        assert_eq!(resolved_payout(total, winner_pool, winner_pool, 0).unwrap(), total);
+    }
+
+    #[test]
+    fn math_fee_001_exit_fee_rounds_up() {
+        assert_eq!(exit_fee_amount(300, 250).unwrap(), 8);
+        assert_eq!(exit_fee_amount(4_000, 250).unwrap(), 100);
+        assert_eq!(exit_fee_amount(1, 1).unwrap(), 1);
+        assert_eq!(exit_fee_amount(1, 0).unwrap(), 0);
+        assert_eq!(exit_fee_amount(0, 250).unwrap(), 0);
+    }
+
+    #[test]
+    fn math_rej_001_error_mapping() {
+        assert_eq!(prize_pool(1, 65_535).unwrap_err(), PitStopError::Underflow);
+        assert_eq!(fee_amount(u64::MAX, 65_535).unwrap_err(), PitStopError::Overflow);
+        assert_eq!(exit_fee_amount(u64::MAX, 65_535).unwrap_err(), PitStopError::Overflow);
+        assert_eq!(winner_payout(1, 1, 0).unwrap_err(), PitStopError::DivisionByZero);
+        // Position larger than its own pool is a corrupted input; the result cannot fit u64.
+        assert_eq!(winner_payout(u64::MAX, u64::MAX, 1).unwrap_err(), PitStopError::Overflow);
+        assert_eq!(payout_dust(10, 11).unwrap_err(), PitStopError::Underflow);
+        assert_eq!(credit(u64::MAX, 1).unwrap_err(), PitStopError::Overflow);
+        assert_eq!(debit(0, 1).unwrap_err(), PitStopError::Underflow);
+    }
+}
@@ -1 +1,167 @@
-// PDA helper skeleton; canonical seeds are defined in locked specs.
+//! Canonical PDA derivations.
+//!
+//! Spec: SPEC_PROTOCOL.md -> "Locked PDA derivations"
+//!
+//! `*_address` helpers run the canonical bump search (`find_program_address`) and are
+//! meant for account creation and off-chain clients. Once an account exists its bump
+//! is stored on-chain, and `*_address_with_bump` / `*_signer_seeds` rebuild the address
+//! with a single `create_program_address` instead of repeating the search.
+
+use anchor_lang::prelude::Pubkey;
+
+/// Canonical PDA seed for the singleton Config account.
+pub const CONFIG_SEED: &[u8] = b"config";
+/// Canonical PDA seed for Market accounts.
+pub const MARKET_SEED: &[u8] = b"market";
+/// Canonical PDA seed for OutcomePool accounts.
+pub const OUTCOME_SEED: &[u8] = b"outcome";
+/// Canonical PDA seed for Position accounts.
+pub const POSITION_SEED: &[u8] = b"position";
+/// Canonical PDA seed for the singleton CollateralAllowlist account.
+pub const COLLATERAL_SEED: &[u8] = b"collateral";
+/// Canonical PDA seed for the singleton OracleCommittee account.
+pub const COMMITTEE_SEED: &[u8] = b"committee";
+/// Canonical PDA seed for per-market ResolutionVotes accounts.
+pub const VOTES_SEED: &[u8] = b"votes";
+/// Canonical PDA seed for per-(market, user) UserMarketStats accounts.
+pub const USER_STATS_SEED: &[u8] = b"user_stats";
+/// Canonical PDA seed for per-market MarketTombstone accounts.
+pub const TOMBSTONE_SEED: &[u8] = b"tombstone";
+
+/// `["config"]`
+pub fn config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &crate::id())
+}
+
+/// `["market", market_id]`
+pub fn market_address(market_id: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MARKET_SEED, market_id.as_ref()], &crate::id())
+}
+
+/// `["outcome", market, outcome_id]`
+pub fn outcome_pool_address(market: &Pubkey, outcome_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OUTCOME_SEED, market.as_ref(), &[outcome_id]], &crate::id())
+}
+
+/// `["position", market, user, outcome_id]`
+pub fn position_address(market: &Pubkey, user: &Pubkey, outcome_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POSITION_SEED, market.as_ref(), user.as_ref(), &[outcome_id]],
+        &crate::id(),
+    )
+}
+
+/// `["user_stats", market, user]`
+pub fn user_market_stats_address(market: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[USER_STATS_SEED, market.as_ref(), user.as_ref()],
+        &crate::id(),
+    )
+}
+
+/// `["tombstone", market]`
+pub fn market_tombstone_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOMBSTONE_SEED, market.as_ref()], &crate::id())
+}
+
+/// `["collateral"]`
+pub fn collateral_allowlist_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COLLATERAL_SEED], &crate::id())
+}
+
+/// `["committee"]`
+pub fn oracle_committee_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMMITTEE_SEED], &crate::id())
+}
+
+/// `["votes", market]`
+pub fn resolution_votes_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOTES_SEED, market.as_ref()], &crate::id())
+}
+
+/// Rebuilds an outcome pool address from its stored bump. `None` if the bump is
+/// not valid for these seeds.
+pub fn outcome_pool_address_with_bump(market: &Pubkey, outcome_id: u8, bump: u8) -> Option<Pubkey> {
+    Pubkey::create_program_address(
+        &[OUTCOME_SEED, market.as_ref(), &[outcome_id], &[bump]],
+        &crate::id(),
+    )
+    .ok()
+}
+
+/// Rebuilds a position address from its stored bump. `None` if the bump is not
+/// valid for these seeds.
+pub fn position_address_with_bump(
+    market: &Pubkey,
+    user: &Pubkey,
+    outcome_id: u8,
+    bump: u8,
+) -> Option<Pubkey> {
+    Pubkey::create_program_address(
+        &[POSITION_SEED, market.as_ref(), user.as_ref(), &[outcome_id], &[bump]],
+        &crate::id(),
+    )
+    .ok()
+}
+
+/// Signer seeds for the market PDA (vault authority), using the stored bump.
+pub fn market_signer_seeds<'a>(market_id: &'a [u8; 32], bump: &'a [u8; 1]) -> [&'a [u8]; 3] {
+    [MARKET_SEED, market_id.as_ref(), bump.as_ref()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pda_001_stored_bump_rebuilds_searched_address() {
+        let market_id = [7u8; 32];
+        let (market, market_bump) = market_address(&market_id);
+        let bump = [market_bump];
+        let seeds = market_signer_seeds(&market_id, &bump);
+        assert_eq!(
+            Pubkey::create_program_address(&seeds, &crate::id()).unwrap(),
+            market
+        );
+
+        let (pool, pool_bump) = outcome_pool_address(&market, 3);
+        assert_eq!(outcome_pool_address_with_bump(&market, 3, pool_bump), Some(pool));
+        assert_ne!(outcome_pool_address_with_bump(&market, 4, pool_bump), Some(pool));
+
+        let user = Pubkey::new_unique();
+        let (position, position_bump) = position_address(&market, &user, 3);
+        assert_eq!(
+            position_address_with_bump(&market, &user, 3, position_bump),
+            Some(position)
+        );
+    }
+
+    #[test]
+    fn pda_002_seed_layout_matches_spec() {
+        let market = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        assert_eq!(
+            config_address(),
+            Pubkey::find_program_address(&[b"config"], &crate::id())
+        );
+        assert_eq!(
+            collateral_allowlist_address(),
+            Pubkey::find_program_address(&[b"collateral"], &crate::id())
+        );
+        assert_eq!(
+            oracle_committee_address(),
+            Pubkey::find_program_address(&[b"committee"], &crate::id())
+        );
+        assert_eq!(
+            resolution_votes_address(&market),
+            Pubkey::find_program_address(&[b"votes", market.as_ref()], &crate::id())
+        );
+        assert_eq!(
+            position_address(&market, &user, 1),
+            Pubkey::find_program_address(
+                &[b"position", market.as_ref(), user.as_ref(), &[1]],
+                &crate::id()
+            )
+        );
+    }
+}
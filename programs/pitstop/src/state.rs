@@ -10,7 +10,20 @@ pub struct Config {
     pub max_total_pool_per_market: u64,
     pub max_bet_per_user_per_market: u64,
     pub claim_window_secs: i64,
+    /// Delay between resolve_market and claims during which the authority can
+    /// overturn the result; 0 makes resolutions final immediately.
+    pub dispute_window_secs: i64,
+    /// Time after `Market.lock_timestamp` by which the oracle must resolve or void;
+    /// afterwards anyone may void the market. 0 disables the deadline.
+    pub resolution_deadline_secs: i64,
+    /// Fee withheld from reduce_position refunds and sent to the market treasury,
+    /// in bps of the reduced amount. 0 refunds in full.
+    pub early_exit_fee_bps: u16,
     pub token_program: String,
+    /// Proposed successor for `authority`; only takes effect once that key accepts.
+    pub pending_authority: Option<String>,
+    /// Extra signer allowed to run distribute_payouts besides `authority`.
+    pub payout_keeper: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,6 +31,9 @@ pub enum MarketStatus {
     Seeding,
     Open,
     Locked,
+    /// Resolved but still disputable until `Market.dispute_deadline`; final (and
+    /// claimable) once that time has passed.
+    PendingResolution,
     Resolved,
     Voided,
     Swept,
@@ -38,6 +54,33 @@ pub struct Market {
     pub vault: String,
     pub market_type: u8,
     pub rules_version: u16,
+    /// Config.fee_bps snapshot taken at create_market; later config updates do not
+    /// change the fee of an existing market.
+    pub fee_bps: u16,
+    /// Every winning outcome once Resolved (Winner: one id, Podium: up to PODIUM_SIZE).
+    pub winning_outcomes: Vec<u8>,
+    /// Sum of the winning outcome pools, snapshotted at resolution.
+    pub winner_pool: u64,
+    /// Collateral mint chosen at create_market; every transfer for the market uses it.
+    pub collateral_mint: String,
+    /// Treasury paired with `collateral_mint` at create_market; sweep destination.
+    pub treasury: String,
+    /// When the resolution became final: end of the dispute window, or the overturn
+    /// time. 0 when the market was resolved without a dispute window.
+    pub dispute_deadline: i64,
+    /// Time lock_market actually ran (>= lock_timestamp); 0 while Seeding/Open.
+    pub locked_at: i64,
+    /// create_market override of `Config.max_total_pool_per_market`; `None` uses the
+    /// config cap. place_bet applies the lower of the two.
+    pub max_total_pool_per_market: Option<u64>,
+    /// create_market override of `Config.max_bet_per_user_per_market`, same rule.
+    pub max_bet_per_user_per_market: Option<u64>,
+    /// Config.claim_window_secs snapshot taken at create_market; later config updates
+    /// do not shorten or extend the claim window of an existing market.
+    pub claim_window_secs: i64,
+    /// Config.resolution_deadline_secs snapshot taken at create_market; 0 disables
+    /// void_stale_market for this market.
+    pub resolution_deadline_secs: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -58,3 +101,61 @@ pub struct Position {
     /// Payout recorded at claim time (base units). For resolved losers this is 0.
     pub payout: u64,
 }
+
+/// A user's stake across every outcome of one market; the per-user cap applies to
+/// `total_staked`, not to each Position.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserMarketStats {
+    pub market: String,
+    pub user: String,
+    /// Sum of the user's Position amounts in this market.
+    pub total_staked: u64,
+    /// Outcomes the user holds a Position on, ascending.
+    pub outcome_ids: Vec<u8>,
+}
+
+impl UserMarketStats {
+    /// Adds `outcome_id` to `outcome_ids` unless it is already there.
+    pub fn record_outcome(&mut self, outcome_id: u8) {
+        if let Err(at) = self.outcome_ids.binary_search(&outcome_id) {
+            self.outcome_ids.insert(at, outcome_id);
+        }
+    }
+}
+
+/// Allowlisted collateral mint and the treasury that receives its sweeps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollateralMint {
+    pub mint: String,
+    pub treasury: String,
+}
+
+/// Authority-managed collateral mints usable by new markets besides `Config.usdc_mint`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollateralAllowlist {
+    pub entries: Vec<CollateralMint>,
+}
+
+/// M-of-N resolution signers. Active while `Config.oracle` is the committee PDA.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OracleCommittee {
+    pub members: Vec<String>,
+    /// Matching votes needed to resolve or void; a strict majority of `members`.
+    pub threshold: u8,
+}
+
+/// Outcome a committee member votes for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolutionDecision {
+    /// Winner set in finishing order (`winning_outcome_id` first).
+    Resolve { winning_outcomes: Vec<u8> },
+    Void,
+}
+
+/// A member's current vote on one market; a later vote by the same member replaces it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionVote {
+    pub member: String,
+    pub decision: ResolutionDecision,
+    pub payload_hash: [u8; 32],
+}
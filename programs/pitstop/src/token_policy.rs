@@ -0,0 +1,224 @@
+//! Collateral mint policy for SPL Token v1 and (opt-in) Token-2022.
+//!
+//! Spec: SPEC_PROTOCOL.md -> "Token custody"
+//!
+//! Token-2022 is accepted only when built with the `token-2022` feature
+//! (`constants::is_supported_token_program`). Mints are screened once, when they
+//! become collateral: the extensions in `BLOCKED_MINT_EXTENSIONS`, which let someone
+//! other than the vault authority move, freeze or veto vault transfers, are refused.
+//! A classic mint freeze authority is not screened (USDC has one). Transfer-fee mints are
+//! allowed; only the amount that reaches the vault is staked, and fees withheld in
+//! the vault are harvested to the mint before the vault is closed.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    state::{Account as TokenAccountState, Mint as MintState},
+};
+
+use crate::{constants::TOKEN_2022_PROGRAM_ID, error::PitStopError};
+
+/// Mint extensions that make a mint unusable as collateral:
+/// - TransferHook: an external program can fail (or observe) every vault transfer
+/// - PermanentDelegate: a third party can transfer or burn vault tokens
+/// - NonTransferable: payouts could never leave the vault
+/// - DefaultAccountState: the vault ATA can be created frozen (the default state is
+///   updatable, so an Initialized default is refused too)
+pub const BLOCKED_MINT_EXTENSIONS: [ExtensionType; 4] = [
+    ExtensionType::TransferHook,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::NonTransferable,
+    ExtensionType::DefaultAccountState,
+];
+
+/// Rejects Token-2022 mints that carry a blocked extension. SPL Token v1 mints
+/// have no extensions and always pass.
+pub fn check_mint_extensions(mint_owner: &Pubkey, mint_data: &[u8]) -> Result<(), PitStopError> {
+    if *mint_owner != TOKEN_2022_PROGRAM_ID {
+        return Ok(());
+    }
+
+    let mint = StateWithExtensions::<MintState>::unpack(mint_data)
+        .map_err(|_| PitStopError::UnsupportedMintExtension)?;
+    let extensions = mint
+        .get_extension_types()
+        .map_err(|_| PitStopError::UnsupportedMintExtension)?;
+    if extensions.iter().any(|ext| BLOCKED_MINT_EXTENSIONS.contains(ext)) {
+        return Err(PitStopError::UnsupportedMintExtension);
+    }
+
+    Ok(())
+}
+
+/// Fee Token-2022 withholds from a transfer of `amount` in `epoch`; 0 for SPL Token
+/// v1 and for mints without a transfer-fee config.
+pub fn transfer_fee(
+    mint_owner: &Pubkey,
+    mint_data: &[u8],
+    epoch: u64,
+    amount: u64,
+) -> Result<u64, PitStopError> {
+    if *mint_owner != TOKEN_2022_PROGRAM_ID {
+        return Ok(0);
+    }
+
+    let mint = StateWithExtensions::<MintState>::unpack(mint_data)
+        .map_err(|_| PitStopError::UnsupportedMintExtension)?;
+    match mint.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(epoch, amount)
+            .ok_or(PitStopError::Overflow),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Transfer fees withheld in a token account. Token-2022 refuses to close an account
+/// while this is non-zero, so it must be harvested to the mint first.
+pub fn withheld_amount(account_owner: &Pubkey, account_data: &[u8]) -> u64 {
+    if *account_owner != TOKEN_2022_PROGRAM_ID {
+        return 0;
+    }
+
+    StateWithExtensions::<TokenAccountState>::unpack(account_data)
+        .ok()
+        .and_then(|account| {
+            account
+                .get_extension::<TransferFeeAmount>()
+                .ok()
+                .map(|fee| u64::from(fee.withheld_amount))
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::REQUIRED_TOKEN_PROGRAM_ID;
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        default_account_state::DefaultAccountState, non_transferable::NonTransferable,
+        permanent_delegate::PermanentDelegate, transfer_hook::TransferHook,
+        BaseStateWithExtensionsMut, StateWithExtensionsMut,
+    };
+    use anchor_spl::token_2022::spl_token_2022::state::AccountState;
+    use anchor_lang::solana_program::program_pack::Pack;
+
+    fn mint_with(extensions: &[ExtensionType], init: impl FnOnce(&mut StateWithExtensionsMut<MintState>)) -> Vec<u8> {
+        let len = ExtensionType::try_calculate_account_len::<MintState>(extensions).unwrap();
+        let mut data = vec![0u8; len];
+        let mut state = StateWithExtensionsMut::<MintState>::unpack_uninitialized(&mut data).unwrap();
+        init(&mut state);
+        state.base = MintState {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    fn fee_mint(bps: u16, maximum_fee: u64) -> Vec<u8> {
+        mint_with(&[ExtensionType::TransferFeeConfig], |state| {
+            let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+            for fee in [&mut config.older_transfer_fee, &mut config.newer_transfer_fee] {
+                fee.transfer_fee_basis_points = bps.into();
+                fee.maximum_fee = maximum_fee.into();
+            }
+        })
+    }
+
+    #[test]
+    fn tkp_hp_001_v1_and_plain_token_2022_mints_pass() {
+        let mut v1 = vec![0u8; MintState::LEN];
+        MintState {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut v1);
+        assert_eq!(check_mint_extensions(&REQUIRED_TOKEN_PROGRAM_ID, &v1), Ok(()));
+        assert_eq!(transfer_fee(&REQUIRED_TOKEN_PROGRAM_ID, &v1, 0, 1_000), Ok(0));
+
+        let plain = mint_with(&[], |_| {});
+        assert_eq!(check_mint_extensions(&TOKEN_2022_PROGRAM_ID, &plain), Ok(()));
+        assert_eq!(transfer_fee(&TOKEN_2022_PROGRAM_ID, &plain, 0, 1_000), Ok(0));
+
+        // Transfer fees are handled, not refused.
+        assert_eq!(check_mint_extensions(&TOKEN_2022_PROGRAM_ID, &fee_mint(100, 50)), Ok(()));
+    }
+
+    #[test]
+    fn tkp_rej_001_blocked_extensions_are_refused() {
+        let hook = mint_with(&[ExtensionType::TransferHook], |state| {
+            state.init_extension::<TransferHook>(true).unwrap().program_id =
+                Some(Pubkey::new_unique()).try_into().unwrap();
+        });
+        let delegate = mint_with(&[ExtensionType::PermanentDelegate], |state| {
+            state.init_extension::<PermanentDelegate>(true).unwrap().delegate =
+                Some(Pubkey::new_unique()).try_into().unwrap();
+        });
+        let non_transferable = mint_with(&[ExtensionType::NonTransferable], |state| {
+            state.init_extension::<NonTransferable>(true).unwrap();
+        });
+
+        let default_state = |state: AccountState| {
+            mint_with(&[ExtensionType::DefaultAccountState], |mint| {
+                mint.init_extension::<DefaultAccountState>(true).unwrap().state = state.into();
+            })
+        };
+
+        for data in [
+            hook,
+            delegate,
+            non_transferable,
+            default_state(AccountState::Frozen),
+            default_state(AccountState::Initialized),
+        ] {
+            assert_eq!(
+                check_mint_extensions(&TOKEN_2022_PROGRAM_ID, &data),
+                Err(PitStopError::UnsupportedMintExtension)
+            );
+        }
+        assert_eq!(
+            check_mint_extensions(&TOKEN_2022_PROGRAM_ID, &[0u8; 3]),
+            Err(PitStopError::UnsupportedMintExtension)
+        );
+    }
+
+    #[test]
+    fn tkp_fee_001_transfer_fee_rounds_up_and_caps_at_maximum() {
+        let mint = fee_mint(100, 50);
+        // 1% of 1_000 = 10; 1% of 1_001 = 10.01 -> 11 (Token-2022 rounds up); cap 50.
+        assert_eq!(transfer_fee(&TOKEN_2022_PROGRAM_ID, &mint, 0, 1_000), Ok(10));
+        assert_eq!(transfer_fee(&TOKEN_2022_PROGRAM_ID, &mint, 0, 1_001), Ok(11));
+        assert_eq!(transfer_fee(&TOKEN_2022_PROGRAM_ID, &mint, 0, 1_000_000), Ok(50));
+        // Same bytes under SPL Token v1 ownership are never charged.
+        assert_eq!(transfer_fee(&REQUIRED_TOKEN_PROGRAM_ID, &mint, 0, 1_000), Ok(0));
+    }
+
+    #[test]
+    fn tkp_fee_002_withheld_amount_reads_fee_account_extension() {
+        let len =
+            ExtensionType::try_calculate_account_len::<TokenAccountState>(&[ExtensionType::TransferFeeAmount])
+                .unwrap();
+        let mut data = vec![0u8; len];
+        {
+            let mut state =
+                StateWithExtensionsMut::<TokenAccountState>::unpack_uninitialized(&mut data).unwrap();
+            state.init_extension::<TransferFeeAmount>(true).unwrap().withheld_amount = 42u64.into();
+            state.base = TokenAccountState {
+                state: AccountState::Initialized,
+                ..Default::default()
+            };
+            state.pack_base();
+            state.init_account_type().unwrap();
+        }
+
+        assert_eq!(withheld_amount(&TOKEN_2022_PROGRAM_ID, &data), 42);
+        assert_eq!(withheld_amount(&REQUIRED_TOKEN_PROGRAM_ID, &data), 0);
+        assert_eq!(withheld_amount(&TOKEN_2022_PROGRAM_ID, &[0u8; 3]), 0);
+    }
+}
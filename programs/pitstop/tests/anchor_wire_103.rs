@@ -142,6 +142,7 @@ async fn issue_103_anchor_happy_path_initialize_create_market_add_outcome_finali
         .to_account_metas(None),
         data: pitstop::instruction::Initialize {
             args: InitializeArgs {
+                oracle: authority.pubkey(),
                 treasury_authority: treasury_authority.pubkey(),
                 max_total_pool_per_market: 1_000_000,
                 max_bet_per_user_per_market: 100_000,
@@ -186,7 +187,9 @@ async fn issue_103_anchor_happy_path_initialize_create_market_add_outcome_finali
             config: config_pda,
             market: market_pda,
             vault: vault_ata,
+            market_tombstone: pitstop::pda::market_tombstone_address(&market_pda).0,
             usdc_mint: usdc_mint.pubkey(),
+            collateral_allowlist: None,
             token_program: spl_token::id(),
             associated_token_program: spl_associated_token_account::id(),
             system_program: solana_sdk::system_program::id(),
@@ -200,6 +203,8 @@ async fn issue_103_anchor_happy_path_initialize_create_market_add_outcome_finali
                 max_outcomes: 2,
                 market_type,
                 rules_version,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
             },
         }
         .data(),
@@ -319,6 +324,7 @@ async fn issue_103_anchor_create_market_rejects_wrong_authority() {
         .to_account_metas(None),
         data: pitstop::instruction::Initialize {
             args: InitializeArgs {
+                oracle: authority.pubkey(),
                 treasury_authority: treasury_authority.pubkey(),
                 max_total_pool_per_market: 1_000_000,
                 max_bet_per_user_per_market: 100_000,
@@ -376,7 +382,9 @@ async fn issue_103_anchor_create_market_rejects_wrong_authority() {
             config: config_pda,
             market: market_pda,
             vault: vault_ata,
+            market_tombstone: pitstop::pda::market_tombstone_address(&market_pda).0,
             usdc_mint: usdc_mint.pubkey(),
+            collateral_allowlist: None,
             token_program: spl_token::id(),
             associated_token_program: spl_associated_token_account::id(),
             system_program: solana_sdk::system_program::id(),
@@ -390,6 +398,8 @@ async fn issue_103_anchor_create_market_rejects_wrong_authority() {
                 max_outcomes: 2,
                 market_type,
                 rules_version,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
             },
         }
         .data(),
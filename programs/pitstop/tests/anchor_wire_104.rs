@@ -7,7 +7,7 @@ use solana_sdk::{
     transaction::Transaction,
 };
 
-use pitstop::{self, anchor_accounts::*};
+use pitstop::{self, anchor_accounts::*, canonical};
 
 fn pitstop_entry<'a, 'b, 'c, 'd>(
     program_id: &'a Pubkey,
@@ -125,18 +125,6 @@ async fn mint_to(
     ctx.banks_client.process_transaction(tx).await.unwrap();
 }
 
-fn canonical_market_id(event_id: [u8; 32], market_type: u8, rules_version: u16) -> [u8; 32] {
-    use sha2::{Digest, Sha256};
-    let mut bytes = [0u8; 35];
-    bytes[0..32].copy_from_slice(&event_id);
-    bytes[32] = market_type;
-    bytes[33..35].copy_from_slice(&rules_version.to_le_bytes());
-    let digest = Sha256::digest(bytes);
-    let mut out = [0u8; 32];
-    out.copy_from_slice(&digest);
-    out
-}
-
 #[tokio::test]
 async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
     let mut ctx = program_test().start_with_context().await;
@@ -181,6 +169,7 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
         .to_account_metas(None),
         data: pitstop::instruction::Initialize {
             args: InitializeArgs {
+                oracle: authority.pubkey(),
                 treasury_authority: treasury_authority.pubkey(),
                 max_total_pool_per_market: 1_000_000,
                 max_bet_per_user_per_market: 100_000,
@@ -204,7 +193,7 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
     let event_id = [7u8; 32];
     let market_type = 0u8;
     let rules_version = 1u16;
-    let market_id = canonical_market_id(event_id, market_type, rules_version);
+    let market_id = canonical::market_id(event_id, market_type, rules_version);
 
     let (market_pda, _) =
         Pubkey::find_program_address(&[b"market", market_id.as_ref()], &pitstop::id());
@@ -220,7 +209,9 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
             config: config_pda,
             market: market_pda,
             vault: vault_ata,
+            market_tombstone: pitstop::pda::market_tombstone_address(&market_pda).0,
             usdc_mint: usdc_mint.pubkey(),
+            collateral_allowlist: None,
             token_program: spl_token::id(),
             associated_token_program: spl_associated_token_account::id(),
             system_program: solana_sdk::system_program::id(),
@@ -234,6 +225,8 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
                 max_outcomes: 2,
                 market_type,
                 rules_version,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
             },
         }
         .data(),
@@ -315,6 +308,11 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
             market: market_pda,
             outcome_pool: pool_pda,
             position: pos_pda,
+            user_stats: Pubkey::find_program_address(
+                &[USER_STATS_SEED, market_pda.as_ref(), user.pubkey().as_ref()],
+                &pitstop::id(),
+            )
+            .0,
             user_usdc: user_usdc.pubkey(),
             vault: vault_ata,
             usdc_mint: usdc_mint.pubkey(),
@@ -323,7 +321,12 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
         }
         .to_account_metas(None),
         data: pitstop::instruction::PlaceBet {
-            args: PlaceBetArgs { outcome_id, amount },
+            args: PlaceBetArgs {
+                outcome_id,
+                amount,
+                max_outcome_share_bps: None,
+                min_implied_payout: None,
+            },
         }
         .data(),
     };
@@ -363,8 +366,7 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
     let ix = solana_sdk::instruction::Instruction {
         program_id: pitstop::id(),
         accounts: pitstop::accounts::LockMarket {
-            authority: authority.pubkey(),
-            config: config_pda,
+            caller: authority.pubkey(),
             market: market_pda,
         }
         .to_account_metas(None),
@@ -378,7 +380,7 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
     );
     ctx.banks_client.process_transaction(tx).await.unwrap();
 
-    // resolve market (authority is oracle by default)
+    // resolve market (authority was initialized as oracle)
     let payload_hash = [0xabu8; 32];
     let ix = solana_sdk::instruction::Instruction {
         program_id: pitstop::id(),
@@ -387,6 +389,10 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
             config: config_pda,
             market: market_pda,
             winning_outcome_pool: pool_pda,
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
             system_program: solana_sdk::system_program::id(),
         }
         .to_account_metas(None),
@@ -394,6 +400,8 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
             args: ResolveMarketArgs {
                 winning_outcome_id: outcome_id,
                 payload_hash,
+                additional_winning_outcome_ids: vec![],
+                result_payload: None,
             },
         }
         .data(),
@@ -419,7 +427,7 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
 
     // create + void a second market (locked -> voided)
     let event_id2 = [9u8; 32];
-    let market_id2 = canonical_market_id(event_id2, market_type, rules_version);
+    let market_id2 = canonical::market_id(event_id2, market_type, rules_version);
     let (market2_pda, _) =
         Pubkey::find_program_address(&[b"market", market_id2.as_ref()], &pitstop::id());
     let vault2_ata = spl_associated_token_account::get_associated_token_address(
@@ -436,7 +444,9 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
             config: config_pda,
             market: market2_pda,
             vault: vault2_ata,
+            market_tombstone: pitstop::pda::market_tombstone_address(&market2_pda).0,
             usdc_mint: usdc_mint.pubkey(),
+            collateral_allowlist: None,
             token_program: spl_token::id(),
             associated_token_program: spl_associated_token_account::id(),
             system_program: solana_sdk::system_program::id(),
@@ -450,6 +460,8 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
                 max_outcomes: 1,
                 market_type,
                 rules_version,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
             },
         }
         .data(),
@@ -525,8 +537,7 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
     let ix = solana_sdk::instruction::Instruction {
         program_id: pitstop::id(),
         accounts: pitstop::accounts::LockMarket {
-            authority: authority.pubkey(),
-            config: config_pda,
+            caller: authority.pubkey(),
             market: market2_pda,
         }
         .to_account_metas(None),
@@ -547,6 +558,10 @@ async fn issue_104_anchor_happy_path_bet_lock_resolve_void() {
             oracle: authority.pubkey(),
             config: config_pda,
             market: market2_pda,
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
         }
         .to_account_metas(None),
         data: pitstop::instruction::VoidMarket {
@@ -619,6 +634,7 @@ async fn issue_104_anchor_place_bet_rejects_outcome_mismatch() {
         .to_account_metas(None),
         data: pitstop::instruction::Initialize {
             args: InitializeArgs {
+                oracle: authority.pubkey(),
                 treasury_authority: treasury_authority.pubkey(),
                 max_total_pool_per_market: 1_000_000,
                 max_bet_per_user_per_market: 100_000,
@@ -641,7 +657,7 @@ async fn issue_104_anchor_place_bet_rejects_outcome_mismatch() {
     let event_id = [7u8; 32];
     let market_type = 0u8;
     let rules_version = 1u16;
-    let market_id = canonical_market_id(event_id, market_type, rules_version);
+    let market_id = canonical::market_id(event_id, market_type, rules_version);
 
     let (market_pda, _) =
         Pubkey::find_program_address(&[b"market", market_id.as_ref()], &pitstop::id());
@@ -657,7 +673,9 @@ async fn issue_104_anchor_place_bet_rejects_outcome_mismatch() {
             config: config_pda,
             market: market_pda,
             vault: vault_ata,
+            market_tombstone: pitstop::pda::market_tombstone_address(&market_pda).0,
             usdc_mint: usdc_mint.pubkey(),
+            collateral_allowlist: None,
             token_program: spl_token::id(),
             associated_token_program: spl_associated_token_account::id(),
             system_program: solana_sdk::system_program::id(),
@@ -671,6 +689,8 @@ async fn issue_104_anchor_place_bet_rejects_outcome_mismatch() {
                 max_outcomes: 1,
                 market_type,
                 rules_version,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
             },
         }
         .data(),
@@ -743,6 +763,11 @@ async fn issue_104_anchor_place_bet_rejects_outcome_mismatch() {
             market: market_pda,
             outcome_pool: treasury.pubkey(),
             position: pos_pda,
+            user_stats: Pubkey::find_program_address(
+                &[USER_STATS_SEED, market_pda.as_ref(), user.pubkey().as_ref()],
+                &pitstop::id(),
+            )
+            .0,
             user_usdc: user_usdc.pubkey(),
             vault: vault_ata,
             usdc_mint: usdc_mint.pubkey(),
@@ -754,6 +779,8 @@ async fn issue_104_anchor_place_bet_rejects_outcome_mismatch() {
             args: PlaceBetArgs {
                 outcome_id,
                 amount: 1,
+                max_outcome_share_bps: None,
+                min_implied_payout: None,
             },
         }
         .data(),
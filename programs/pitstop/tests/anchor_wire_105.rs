@@ -7,7 +7,7 @@ use solana_sdk::{
     transaction::Transaction,
 };
 
-use pitstop::{self, anchor_accounts::*};
+use pitstop::{self, anchor_accounts::*, canonical};
 
 fn pitstop_entry<'a, 'b, 'c, 'd>(
     program_id: &'a Pubkey,
@@ -125,18 +125,6 @@ async fn mint_to(
     ctx.banks_client.process_transaction(tx).await.unwrap();
 }
 
-fn canonical_market_id(event_id: [u8; 32], market_type: u8, rules_version: u16) -> [u8; 32] {
-    use sha2::{Digest, Sha256};
-    let mut bytes = [0u8; 35];
-    bytes[0..32].copy_from_slice(&event_id);
-    bytes[32] = market_type;
-    bytes[33..35].copy_from_slice(&rules_version.to_le_bytes());
-    let digest = Sha256::digest(bytes);
-    let mut out = [0u8; 32];
-    out.copy_from_slice(&digest);
-    out
-}
-
 #[tokio::test]
 async fn issue_105_anchor_claim_and_sweep_status_guards() {
     let mut ctx = program_test().start_with_context().await;
@@ -179,6 +167,7 @@ async fn issue_105_anchor_claim_and_sweep_status_guards() {
         .to_account_metas(None),
         data: pitstop::instruction::Initialize {
             args: InitializeArgs {
+                oracle: authority.pubkey(),
                 treasury_authority: treasury_authority.pubkey(),
                 max_total_pool_per_market: 1_000_000,
                 max_bet_per_user_per_market: 500_000,
@@ -198,7 +187,7 @@ async fn issue_105_anchor_claim_and_sweep_status_guards() {
     let event_id = [7u8; 32];
     let market_type = 0u8;
     let rules_version = 1u16;
-    let market_id = canonical_market_id(event_id, market_type, rules_version);
+    let market_id = canonical::market_id(event_id, market_type, rules_version);
     let (market_pda, _) = Pubkey::find_program_address(&[MARKET_SEED, market_id.as_ref()], &pitstop::id());
     let vault_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
         &market_pda,
@@ -213,7 +202,9 @@ async fn issue_105_anchor_claim_and_sweep_status_guards() {
             config: config_pda,
             market: market_pda,
             vault: vault_ata,
+            market_tombstone: pitstop::pda::market_tombstone_address(&market_pda).0,
             usdc_mint: usdc_mint.pubkey(),
+            collateral_allowlist: None,
             token_program: spl_token::id(),
             associated_token_program: spl_associated_token_account::id(),
             system_program: solana_sdk::system_program::id(),
@@ -227,6 +218,8 @@ async fn issue_105_anchor_claim_and_sweep_status_guards() {
                 max_outcomes: 2,
                 market_type,
                 rules_version,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
             },
         }
         .data(),
@@ -304,6 +297,11 @@ async fn issue_105_anchor_claim_and_sweep_status_guards() {
             market: market_pda,
             outcome_pool: pool_pda,
             position: pos_pda,
+            user_stats: Pubkey::find_program_address(
+                &[USER_STATS_SEED, market_pda.as_ref(), user.pubkey().as_ref()],
+                &pitstop::id(),
+            )
+            .0,
             user_usdc: user_usdc.pubkey(),
             vault: vault_ata,
             usdc_mint: usdc_mint.pubkey(),
@@ -315,6 +313,8 @@ async fn issue_105_anchor_claim_and_sweep_status_guards() {
             args: PlaceBetArgs {
                 outcome_id,
                 amount: 100_000,
+                max_outcome_share_bps: None,
+                min_implied_payout: None,
             },
         }
         .data(),
@@ -343,7 +343,10 @@ async fn issue_105_anchor_claim_and_sweep_status_guards() {
         }
         .to_account_metas(None),
         data: pitstop::instruction::ClaimResolved {
-            args: ClaimResolvedArgs { outcome_id },
+            args: ClaimResolvedArgs {
+                outcome_id,
+                close_position: false,
+            },
         }
         .data(),
     };
@@ -415,6 +418,7 @@ async fn issue_105_anchor_cancel_market_closes_vault_and_sets_voided() {
         .to_account_metas(None),
         data: pitstop::instruction::Initialize {
             args: InitializeArgs {
+                oracle: authority.pubkey(),
                 treasury_authority: treasury_authority.pubkey(),
                 max_total_pool_per_market: 1_000_000,
                 max_bet_per_user_per_market: 500_000,
@@ -434,7 +438,7 @@ async fn issue_105_anchor_cancel_market_closes_vault_and_sets_voided() {
     let event_id = [8u8; 32];
     let market_type = 0u8;
     let rules_version = 1u16;
-    let market_id = canonical_market_id(event_id, market_type, rules_version);
+    let market_id = canonical::market_id(event_id, market_type, rules_version);
     let (market_pda, _) = Pubkey::find_program_address(&[MARKET_SEED, market_id.as_ref()], &pitstop::id());
     let vault_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
         &market_pda,
@@ -449,7 +453,9 @@ async fn issue_105_anchor_cancel_market_closes_vault_and_sets_voided() {
             config: config_pda,
             market: market_pda,
             vault: vault_ata,
+            market_tombstone: pitstop::pda::market_tombstone_address(&market_pda).0,
             usdc_mint: usdc_mint.pubkey(),
+            collateral_allowlist: None,
             token_program: spl_token::id(),
             associated_token_program: spl_associated_token_account::id(),
             system_program: solana_sdk::system_program::id(),
@@ -463,6 +469,8 @@ async fn issue_105_anchor_cancel_market_closes_vault_and_sets_voided() {
                 max_outcomes: 2,
                 market_type,
                 rules_version,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
             },
         }
         .data(),
@@ -0,0 +1,340 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, attestation, canonical, pda};
+
+async fn send_all(
+    ctx: &mut ProgramTestContext,
+    ixs: &[solana_sdk::instruction::Instruction],
+    signer: &Keypair,
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = ctx.banks_client.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&signer.pubkey()), &[signer], blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+/// Ed25519 precompile instruction carrying `signer`'s offline signature over `message`.
+fn attestation_ix(signer: &Keypair, message: &[u8]) -> solana_sdk::instruction::Instruction {
+    let signature: [u8; 64] = signer.sign_message(message).into();
+    solana_sdk::instruction::Instruction {
+        program_id: solana_sdk::ed25519_program::id(),
+        accounts: vec![],
+        data: attestation::ed25519_instruction_data(&signer.pubkey(), &signature, message),
+    }
+}
+
+/// create -> add one outcome -> open; returns (market, outcome pool).
+async fn open_market(
+    ctx: &mut ProgramTestContext,
+    authority: &Keypair,
+    usdc_mint: &Pubkey,
+    event_byte: u8,
+    lock_timestamp: i64,
+) -> (Pubkey, Pubkey) {
+    let (config_pda, _) = pda::config_address();
+    let event_id = [event_byte; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market_pda, _) = pda::market_address(&market_id);
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            vault: spl_associated_token_account::get_associated_token_address(
+                &market_pda,
+                usdc_mint,
+            ),
+            market_tombstone: pda::market_tombstone_address(&market_pda).0,
+            usdc_mint: *usdc_mint,
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 1,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(ctx, ix, authority).await.unwrap();
+
+    let (pool_pda, _) = pda::outcome_pool_address(&market_pda, 0);
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddOutcome {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            outcome_pool: pool_pda,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddOutcome {
+            args: AddOutcomeArgs { outcome_id: 0 },
+        }
+        .data(),
+    };
+    send(ctx, ix, authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(ctx, ix, authority).await.unwrap();
+
+    (market_pda, pool_pda)
+}
+
+async fn market_status(ctx: &mut ProgramTestContext, market: Pubkey) -> MarketStatus {
+    let acct = ctx.banks_client.get_account(market).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    m.status
+}
+
+#[tokio::test]
+async fn keeper_relays_offline_oracle_attestations_for_resolve_and_void() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    // The oracle never signs a transaction or holds lamports in this test.
+    let oracle = Keypair::new();
+    let keeper = Keypair::new();
+    let impostor = Keypair::new();
+    for kp in [&authority, &keeper] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: oracle.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let (resolved, resolved_pool) = open_market(
+        &mut ctx,
+        &authority,
+        &usdc_mint.pubkey(),
+        61,
+        lock_timestamp,
+    )
+    .await;
+    let (voided, _) = open_market(
+        &mut ctx,
+        &authority,
+        &usdc_mint.pubkey(),
+        62,
+        lock_timestamp,
+    )
+    .await;
+
+    loop {
+        let c: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+        if c.unix_timestamp >= lock_timestamp {
+            break;
+        }
+        let slot = ctx.banks_client.get_root_slot().await.unwrap() + 10;
+        ctx.warp_to_slot(slot).unwrap();
+    }
+    for market in [resolved, voided] {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::LockMarket {
+                caller: authority.pubkey(),
+                market,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::LockMarket {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let payload_hash = [0x5au8; 32];
+    let resolve_ix = |instructions_sysvar: Option<Pubkey>| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ResolveMarket {
+            oracle: keeper.pubkey(),
+            config: config_pda,
+            market: resolved,
+            winning_outcome_pool: resolved_pool,
+            instructions_sysvar,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ResolveMarket {
+            args: ResolveMarketArgs {
+                winning_outcome_id: 0,
+                payload_hash,
+                additional_winning_outcome_ids: vec![],
+                result_payload: None,
+            },
+        }
+        .data(),
+    };
+    let sysvar = Some(solana_sdk::sysvar::instructions::id());
+    let resolve_msg = attestation::resolve_message(&resolved, 0, &[], &payload_hash);
+
+    // A keeper alone is not the oracle.
+    let err = send(&mut ctx, resolve_ix(None), &keeper).await.unwrap_err();
+    assert_custom(err, 6001, "UnauthorizedOracle");
+
+    // Attestation without the sysvar is never read.
+    let err = send_all(
+        &mut ctx,
+        &[attestation_ix(&oracle, &resolve_msg), resolve_ix(None)],
+        &keeper,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6001, "UnauthorizedOracle");
+
+    // Right message, wrong key.
+    let err = send_all(
+        &mut ctx,
+        &[attestation_ix(&impostor, &resolve_msg), resolve_ix(sysvar)],
+        &keeper,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6001, "UnauthorizedOracle");
+
+    // Oracle key, but the attestation is for a different payload.
+    let other_msg = attestation::resolve_message(&resolved, 0, &[], &[0x5bu8; 32]);
+    let err = send_all(
+        &mut ctx,
+        &[attestation_ix(&oracle, &other_msg), resolve_ix(sysvar)],
+        &keeper,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6001, "UnauthorizedOracle");
+
+    // A void attestation cannot be replayed as a resolution.
+    let void_msg = attestation::void_message(&resolved, &payload_hash);
+    let err = send_all(
+        &mut ctx,
+        &[attestation_ix(&oracle, &void_msg), resolve_ix(sysvar)],
+        &keeper,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6001, "UnauthorizedOracle");
+
+    send_all(
+        &mut ctx,
+        &[attestation_ix(&oracle, &resolve_msg), resolve_ix(sysvar)],
+        &keeper,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        market_status(&mut ctx, resolved).await,
+        MarketStatus::Resolved
+    );
+
+    let void_ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::VoidMarket {
+            oracle: keeper.pubkey(),
+            config: config_pda,
+            market: voided,
+            instructions_sysvar: sysvar,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::VoidMarket {
+            args: VoidMarketArgs { payload_hash },
+        }
+        .data(),
+    };
+
+    // An attestation for one market does not void another.
+    let err = send_all(
+        &mut ctx,
+        &[
+            attestation_ix(
+                &oracle,
+                &attestation::void_message(&resolved, &payload_hash),
+            ),
+            void_ix.clone(),
+        ],
+        &keeper,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6001, "UnauthorizedOracle");
+
+    send_all(
+        &mut ctx,
+        &[
+            attestation_ix(&oracle, &attestation::void_message(&voided, &payload_hash)),
+            void_ix,
+        ],
+        &keeper,
+    )
+    .await
+    .unwrap();
+    assert_eq!(market_status(&mut ctx, voided).await, MarketStatus::Voided);
+}
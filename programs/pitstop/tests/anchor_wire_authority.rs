@@ -0,0 +1,414 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical};
+
+#[tokio::test]
+async fn authority_gated_instructions_follow_accepted_authority() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let old_authority = Keypair::new();
+    let new_authority = Keypair::new();
+    let oracle = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let stranger = Keypair::new();
+    for kp in [&old_authority, &new_authority, &oracle, &treasury_authority, &stranger] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &old_authority.pubkey()).await;
+
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &pitstop::id());
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: old_authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: oracle.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 1,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &old_authority).await.unwrap();
+
+    // --- instruction builders -------------------------------------------------
+    let market_type = 0u8;
+    let rules_version = 1u16;
+    let market_pda_for = |event_id: [u8; 32]| {
+        let market_id = canonical::market_id(event_id, market_type, rules_version);
+        Pubkey::find_program_address(&[b"market", market_id.as_ref()], &pitstop::id()).0
+    };
+    let vault_for = |market: &Pubkey| {
+        spl_associated_token_account::get_associated_token_address(market, &usdc_mint.pubkey())
+    };
+
+    let create_market_ix = |signer: &Pubkey, event_id: [u8; 32], lock_timestamp: i64| {
+        let market_id = canonical::market_id(event_id, market_type, rules_version);
+        let market = market_pda_for(event_id);
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::CreateMarket {
+                authority: *signer,
+                config: config_pda,
+                market,
+                vault: vault_for(&market),
+                market_tombstone: pitstop::pda::market_tombstone_address(&market).0,
+                usdc_mint: usdc_mint.pubkey(),
+                collateral_allowlist: None,
+                token_program: spl_token::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::CreateMarket {
+                args: CreateMarketArgs {
+                    market_id,
+                    event_id,
+                    lock_timestamp,
+                    max_outcomes: 1,
+                    market_type,
+                    rules_version,
+                    max_total_pool_per_market: None,
+                    max_bet_per_user_per_market: None,
+                },
+            }
+            .data(),
+        }
+    };
+    let lock_market_ix = |signer: &Pubkey, market: Pubkey| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::LockMarket {
+            caller: *signer,
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::LockMarket {}.data(),
+    };
+    let cancel_market_ix = |signer: &Pubkey, market: Pubkey| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CancelMarket {
+            authority: *signer,
+            config: config_pda,
+            market,
+            vault: vault_for(&market),
+            close_destination: *signer,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CancelMarket {}.data(),
+    };
+    let sweep_ix = |signer: &Pubkey, market: Pubkey| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SweepRemaining {
+            authority: *signer,
+            config: config_pda,
+            market,
+            vault: vault_for(&market),
+            treasury: treasury.pubkey(),
+            close_destination: *signer,
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SweepRemaining {}.data(),
+    };
+
+    // --- two-step transfer ----------------------------------------------------
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ProposeAuthority {
+            authority: old_authority.pubkey(),
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ProposeAuthority {
+            args: ProposeAuthorityArgs {
+                new_authority: new_authority.pubkey(),
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &old_authority).await.unwrap();
+
+    let acct = ctx.banks_client.get_account(config_pda).await.unwrap().unwrap();
+    let cfg: Config = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(cfg.authority, old_authority.pubkey());
+    assert_eq!(cfg.pending_authority, Some(new_authority.pubkey()));
+
+    // a proposal alone grants nothing
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let err = send(
+        &mut ctx,
+        create_market_ix(&new_authority.pubkey(), [1u8; 32], lock_timestamp),
+        &new_authority,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+
+    let accept_ix = |signer: &Pubkey| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AcceptAuthority {
+            new_authority: *signer,
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AcceptAuthority {}.data(),
+    };
+    let err = send(&mut ctx, accept_ix(&stranger.pubkey()), &stranger)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+
+    send(&mut ctx, accept_ix(&new_authority.pubkey()), &new_authority)
+        .await
+        .unwrap();
+
+    let acct = ctx.banks_client.get_account(config_pda).await.unwrap().unwrap();
+    let cfg: Config = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(cfg.authority, new_authority.pubkey());
+    assert_eq!(cfg.pending_authority, None);
+    assert_eq!(cfg.oracle, oracle.pubkey());
+
+    // --- create_market ----------------------------------------------------------
+    let err = send(
+        &mut ctx,
+        create_market_ix(&old_authority.pubkey(), [1u8; 32], lock_timestamp),
+        &old_authority,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+
+    let live = market_pda_for([1u8; 32]);
+    send(
+        &mut ctx,
+        create_market_ix(&new_authority.pubkey(), [1u8; 32], lock_timestamp),
+        &new_authority,
+    )
+    .await
+    .unwrap();
+
+    // --- cancel_market ----------------------------------------------------------
+    let doomed = market_pda_for([2u8; 32]);
+    send(
+        &mut ctx,
+        create_market_ix(&new_authority.pubkey(), [2u8; 32], lock_timestamp),
+        &new_authority,
+    )
+    .await
+    .unwrap();
+    let err = send(&mut ctx, cancel_market_ix(&old_authority.pubkey(), doomed), &old_authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+    send(&mut ctx, cancel_market_ix(&new_authority.pubkey(), doomed), &new_authority)
+        .await
+        .unwrap();
+
+    // open the live market under the new authority
+    let outcome_id = 0u8;
+    let (pool_pda, _) = Pubkey::find_program_address(
+        &[b"outcome", live.as_ref(), &[outcome_id]],
+        &pitstop::id(),
+    );
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddOutcome {
+            authority: new_authority.pubkey(),
+            config: config_pda,
+            market: live,
+            outcome_pool: pool_pda,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddOutcome {
+            args: AddOutcomeArgs { outcome_id },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &new_authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: new_authority.pubkey(),
+            config: config_pda,
+            market: live,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &new_authority).await.unwrap();
+
+    // --- lock_market ------------------------------------------------------------
+    // Permissionless crank: the retired authority locks like any other keeper.
+    warp_past(&mut ctx, lock_timestamp).await;
+    send(&mut ctx, lock_market_ix(&old_authority.pubkey(), live), &old_authority)
+        .await
+        .unwrap();
+
+    // oracle is untouched by the transfer
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::VoidMarket {
+            oracle: oracle.pubkey(),
+            config: config_pda,
+            market: live,
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::VoidMarket {
+            args: VoidMarketArgs {
+                payload_hash: [0xeeu8; 32],
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &oracle).await.unwrap();
+
+    // --- sweep_remaining --------------------------------------------------------
+    let acct = ctx.banks_client.get_account(live).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    warp_past(&mut ctx, m.resolution_timestamp + 1).await;
+
+    let err = send(&mut ctx, sweep_ix(&old_authority.pubkey(), live), &old_authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+    send(&mut ctx, sweep_ix(&new_authority.pubkey(), live), &new_authority)
+        .await
+        .unwrap();
+
+    let acct = ctx.banks_client.get_account(live).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(m.status, MarketStatus::Swept);
+}
+
+#[tokio::test]
+async fn cancel_authority_transfer_voids_pending_proposal() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let typo = Keypair::new();
+    let treasury_authority = Keypair::new();
+    for kp in [&authority, &typo, &treasury_authority] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &pitstop::id());
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let cancel_ix = || solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CancelAuthorityTransfer {
+            authority: authority.pubkey(),
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CancelAuthorityTransfer {}.data(),
+    };
+
+    // nothing pending yet -> NoPendingAuthority (6041).
+    let err = send(&mut ctx, cancel_ix(), &authority).await.unwrap_err();
+    assert_custom(err, 6041, "NoPendingAuthority");
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ProposeAuthority {
+            authority: authority.pubkey(),
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ProposeAuthority {
+            args: ProposeAuthorityArgs {
+                new_authority: typo.pubkey(),
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+    send(&mut ctx, cancel_ix(), &authority).await.unwrap();
+
+    let acct = ctx.banks_client.get_account(config_pda).await.unwrap().unwrap();
+    let cfg: Config = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(cfg.authority, authority.pubkey());
+    assert_eq!(cfg.pending_authority, None);
+
+    // the withdrawn key can no longer accept
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AcceptAuthority {
+            new_authority: typo.pubkey(),
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AcceptAuthority {}.data(),
+    };
+    let err = send(&mut ctx, ix, &typo).await.unwrap_err();
+    assert_custom(err, 6041, "NoPendingAuthority");
+}
@@ -0,0 +1,325 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+struct TestMarket {
+    market: Pubkey,
+    vault: Pubkey,
+}
+
+/// One claim_many group: `[market, outcome_pool, position (mut), vault (mut)]`.
+fn group(m: &TestMarket, user: &Pubkey, outcome_id: u8) -> Vec<AccountMeta> {
+    let (pool, _) = pda::outcome_pool_address(&m.market, outcome_id);
+    let (position, _) = pda::position_address(&m.market, user, outcome_id);
+    vec![
+        AccountMeta::new_readonly(m.market, false),
+        AccountMeta::new_readonly(pool, false),
+        AccountMeta::new(position, false),
+        AccountMeta::new(m.vault, false),
+    ]
+}
+
+
+#[tokio::test]
+async fn claim_many_pays_each_vault_once_and_fails_whole_batch_on_any_group() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &usdc_mint.pubkey(), &user.pubkey()).await;
+    mint_to(&mut ctx, &usdc_mint.pubkey(), &authority, &user_usdc.pubkey(), 500_000).await;
+
+    let (config_pda, _) = pda::config_address();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3_600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Three two-outcome markets. The user bets on both outcomes of market A and on
+    // outcome 0 of markets B and C; A and B resolve to outcome 0, C stays Locked.
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let bets: [&[(u8, u64)]; 3] = [&[(0, 50_000), (1, 30_000)], &[(0, 20_000)], &[(0, 10_000)]];
+
+    let mut markets = Vec::new();
+    for (event_id, market_bets) in [[31u8; 32], [32u8; 32], [33u8; 32]].into_iter().zip(bets) {
+        let market_id = canonical::market_id(event_id, 0, 1);
+        let (market_pda, _) = pda::market_address(&market_id);
+        let vault_ata = spl_associated_token_account::get_associated_token_address(
+            &market_pda,
+            &usdc_mint.pubkey(),
+        );
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::CreateMarket {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                vault: vault_ata,
+                market_tombstone: pda::market_tombstone_address(&market_pda).0,
+                usdc_mint: usdc_mint.pubkey(),
+                collateral_allowlist: None,
+                token_program: spl_token::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::CreateMarket {
+                args: CreateMarketArgs {
+                    market_id,
+                    event_id,
+                    lock_timestamp,
+                    max_outcomes: 2,
+                    market_type: 0,
+                    rules_version: 1,
+                    max_total_pool_per_market: None,
+                    max_bet_per_user_per_market: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        for outcome_id in 0..2u8 {
+            let (pool_pda, _) = pda::outcome_pool_address(&market_pda, outcome_id);
+            let ix = solana_sdk::instruction::Instruction {
+                program_id: pitstop::id(),
+                accounts: pitstop::accounts::AddOutcome {
+                    authority: authority.pubkey(),
+                    config: config_pda,
+                    market: market_pda,
+                    outcome_pool: pool_pda,
+                    system_program: solana_sdk::system_program::id(),
+                }
+                .to_account_metas(None),
+                data: pitstop::instruction::AddOutcome {
+                    args: AddOutcomeArgs { outcome_id },
+                }
+                .data(),
+            };
+            send(&mut ctx, ix, &authority).await.unwrap();
+        }
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::FinalizeSeeding {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::FinalizeSeeding {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        for &(outcome_id, amount) in market_bets {
+            let (pool_pda, _) = pda::outcome_pool_address(&market_pda, outcome_id);
+            let (pos_pda, _) = pda::position_address(&market_pda, &user.pubkey(), outcome_id);
+            let ix = solana_sdk::instruction::Instruction {
+                program_id: pitstop::id(),
+                accounts: pitstop::accounts::PlaceBet {
+                    user: user.pubkey(),
+                    config: config_pda,
+                    market: market_pda,
+                    outcome_pool: pool_pda,
+                    position: pos_pda,
+                    user_stats: pda::user_market_stats_address(&market_pda, &user.pubkey()).0,
+                    user_usdc: user_usdc.pubkey(),
+                    vault: vault_ata,
+                    usdc_mint: usdc_mint.pubkey(),
+                    token_program: spl_token::id(),
+                    system_program: solana_sdk::system_program::id(),
+                }
+                .to_account_metas(None),
+                data: pitstop::instruction::PlaceBet {
+                    args: PlaceBetArgs {
+                        outcome_id,
+                        amount,
+                        max_outcome_share_bps: None,
+                        min_implied_payout: None,
+                    },
+                }
+                .data(),
+            };
+            send(&mut ctx, ix, &user).await.unwrap();
+        }
+
+        markets.push(TestMarket {
+            market: market_pda,
+            vault: vault_ata,
+        });
+    }
+
+    warp_past(&mut ctx, lock_timestamp).await;
+    for m in &markets {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::LockMarket {
+                caller: authority.pubkey(),
+                market: m.market,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::LockMarket {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    for m in &markets[..2] {
+        let (pool_pda, _) = pda::outcome_pool_address(&m.market, 0);
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::ResolveMarket {
+                oracle: authority.pubkey(),
+                config: config_pda,
+                market: m.market,
+                winning_outcome_pool: pool_pda,
+                instructions_sysvar: None,
+                oracle_committee: None,
+                resolution_votes: None,
+                votes_payer: None,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::ResolveMarket {
+                args: ResolveMarketArgs {
+                    winning_outcome_id: 0,
+                    payload_hash: [1u8; 32],
+                    additional_winning_outcome_ids: vec![],
+                    result_payload: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let claim_many_ix = |groups: Vec<Vec<AccountMeta>>| {
+        let mut accounts = pitstop::accounts::ClaimMany {
+            user: user.pubkey(),
+            config: config_pda,
+            user_usdc: user_usdc.pubkey(),
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None);
+        accounts.extend(groups.into_iter().flatten());
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts,
+            data: pitstop::instruction::ClaimMany {}.data(),
+        }
+    };
+    let (a, b, c) = (&markets[0], &markets[1], &markets[2]);
+    let user_key = user.pubkey();
+
+    // CLM-REJ-001: empty batch and a trailing partial group.
+    let err = send(&mut ctx, claim_many_ix(vec![]), &user).await.unwrap_err();
+    assert_custom(err, 6060, "InvalidClaimBatch");
+    let mut partial = group(a, &user_key, 0);
+    partial.pop();
+    let err = send(&mut ctx, claim_many_ix(vec![partial]), &user)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6060, "InvalidClaimBatch");
+
+    // One unclaimable group (C is still Locked) aborts the whole batch.
+    let ix = claim_many_ix(vec![group(a, &user_key, 0), group(c, &user_key, 0)]);
+    let err = send(&mut ctx, ix, &user).await.unwrap_err();
+    assert_custom(err, 6017, "MarketNotResolved");
+    let (a_winner, _) = pda::position_address(&a.market, &user_key, 0);
+    assert!(!position_state(&mut ctx, a_winner).await.claimed);
+
+    // The same position twice is a double claim.
+    let ix = claim_many_ix(vec![group(a, &user_key, 0), group(a, &user_key, 0)]);
+    let err = send(&mut ctx, ix, &user).await.unwrap_err();
+    assert_custom(err, 6020, "AlreadyClaimed");
+
+    // A vault from another market is rejected.
+    let mut wrong_vault = group(a, &user_key, 0);
+    wrong_vault[3] = AccountMeta::new(b.vault, false);
+    let err = send(&mut ctx, claim_many_ix(vec![wrong_vault]), &user)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6027, "OutcomeMismatch");
+
+    // A: winner 50_000 takes the 80_000 pool, loser gets 0; B: 20_000 back.
+    let ix = claim_many_ix(vec![
+        group(a, &user_key, 0),
+        group(a, &user_key, 1),
+        group(b, &user_key, 0),
+    ]);
+    let blockhash = ctx.banks_client.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(std::slice::from_ref(&ix), Some(&user_key), &[&user], blockhash);
+    let sim = ctx.banks_client.simulate_transaction(tx).await.unwrap();
+    assert!(sim.result.unwrap().is_ok(), "claim_many simulation failed");
+    let transfers = sim
+        .simulation_details
+        .unwrap()
+        .logs
+        .iter()
+        .filter(|l| l.contains("Instruction: TransferChecked"))
+        .count();
+    assert_eq!(transfers, 2, "one transfer per vault");
+    send(&mut ctx, ix, &user).await.unwrap();
+
+    assert_eq!(token_amount(&mut ctx, user_usdc.pubkey()).await, 490_000);
+    assert_eq!(token_amount(&mut ctx, a.vault).await, 0);
+    assert_eq!(token_amount(&mut ctx, b.vault).await, 0);
+    let expected = [(a, 0u8, 80_000u64), (a, 1, 0), (b, 0, 20_000)];
+    for (m, outcome_id, payout) in expected {
+        let (pos, _) = pda::position_address(&m.market, &user_key, outcome_id);
+        let p = position_state(&mut ctx, pos).await;
+        assert!(p.claimed);
+        assert_eq!(p.payout, payout);
+    }
+
+    // Claimed positions cannot be batched again.
+    let ix = claim_many_ix(vec![group(b, &user_key, 0)]);
+    let err = send(&mut ctx, ix, &user).await.unwrap_err();
+    assert_custom(err, 6020, "AlreadyClaimed");
+}
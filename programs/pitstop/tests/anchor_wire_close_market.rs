@@ -0,0 +1,370 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+async fn lamports(ctx: &mut ProgramTestContext, key: Pubkey) -> u64 {
+    ctx.banks_client.get_balance(key).await.unwrap()
+}
+
+#[tokio::test]
+async fn swept_and_cancelled_markets_return_pool_and_market_rent() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &usdc_mint.pubkey(), &user.pubkey()).await;
+    mint_to(&mut ctx, &usdc_mint.pubkey(), &authority, &user_usdc.pubkey(), 500_000).await;
+
+    let (config_pda, _) = pda::config_address();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 1,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+
+    // market 0: three outcomes, one bet, later resolved and swept
+    // market 1: one outcome, cancelled during seeding
+    let create_ix = |event_id: [u8; 32], outcomes: u8, lock_timestamp: i64| {
+        let market_id = canonical::market_id(event_id, 0, 1);
+        let (market_pda, _) = pda::market_address(&market_id);
+        let vault_ata = spl_associated_token_account::get_associated_token_address(
+            &market_pda,
+            &usdc_mint.pubkey(),
+        );
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::CreateMarket {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                vault: vault_ata,
+                market_tombstone: pda::market_tombstone_address(&market_pda).0,
+                usdc_mint: usdc_mint.pubkey(),
+                collateral_allowlist: None,
+                token_program: spl_token::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::CreateMarket {
+                args: CreateMarketArgs {
+                    market_id,
+                    event_id,
+                    lock_timestamp,
+                    max_outcomes: outcomes,
+                    market_type: 0,
+                    rules_version: 1,
+                    max_total_pool_per_market: None,
+                    max_bet_per_user_per_market: None,
+                },
+            }
+            .data(),
+        };
+        (ix, market_pda, vault_ata)
+    };
+
+    let mut markets = Vec::new();
+    for (event_id, outcomes) in [([31u8; 32], 3u8), ([32u8; 32], 1u8)] {
+        let (ix, market_pda, vault_ata) = create_ix(event_id, outcomes, lock_timestamp);
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        let mut pools = Vec::new();
+        for outcome_id in 0..outcomes {
+            let (pool_pda, _) = pda::outcome_pool_address(&market_pda, outcome_id);
+            let ix = solana_sdk::instruction::Instruction {
+                program_id: pitstop::id(),
+                accounts: pitstop::accounts::AddOutcome {
+                    authority: authority.pubkey(),
+                    config: config_pda,
+                    market: market_pda,
+                    outcome_pool: pool_pda,
+                    system_program: solana_sdk::system_program::id(),
+                }
+                .to_account_metas(None),
+                data: pitstop::instruction::AddOutcome {
+                    args: AddOutcomeArgs { outcome_id },
+                }
+                .data(),
+            };
+            send(&mut ctx, ix, &authority).await.unwrap();
+            pools.push(pool_pda);
+        }
+
+        markets.push((market_pda, vault_ata, pools));
+    }
+
+    let close_ix = |signer: &Pubkey, market: Pubkey, vault: Pubkey, pools: &[Pubkey]| {
+        let mut accounts = pitstop::accounts::CloseMarket {
+            authority: *signer,
+            config: config_pda,
+            market,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market).0,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.extend(pools.iter().map(|p| AccountMeta::new(*p, false)));
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts,
+            data: pitstop::instruction::CloseMarket {}.data(),
+        }
+    };
+
+    // --- cancelled market: one call closes pool and market ----------------------
+    let (cancelled, cancelled_vault, cancelled_pools) = markets[1].clone();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CancelMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: cancelled,
+            vault: cancelled_vault,
+            close_destination: authority.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CancelMarket {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let err = send(
+        &mut ctx,
+        close_ix(&user.pubkey(), cancelled, cancelled_vault, &cancelled_pools),
+        &user,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+
+    let reclaimed =
+        lamports(&mut ctx, cancelled).await + lamports(&mut ctx, cancelled_pools[0]).await;
+    let before = lamports(&mut ctx, authority.pubkey()).await;
+    send(
+        &mut ctx,
+        close_ix(&authority.pubkey(), cancelled, cancelled_vault, &cancelled_pools),
+        &authority,
+    )
+    .await
+    .unwrap();
+    assert!(ctx.banks_client.get_account(cancelled).await.unwrap().is_none());
+    assert!(ctx.banks_client.get_account(cancelled_pools[0]).await.unwrap().is_none());
+    // The tombstone is the only rent left behind.
+    let (cancelled_tombstone, _) = pda::market_tombstone_address(&cancelled);
+    let tombstone_rent = lamports(&mut ctx, cancelled_tombstone).await;
+    assert_eq!(
+        lamports(&mut ctx, authority.pubkey()).await,
+        before + reclaimed - tombstone_rent - 5_000
+    );
+    let acct = ctx.banks_client.get_account(cancelled_tombstone).await.unwrap().unwrap();
+    let t: MarketTombstone = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(t.market_id, canonical::market_id([32u8; 32], 0, 1));
+    assert!(t.closed_at > 0);
+
+    // --- resolved market: batches after sweep -----------------------------------
+    let (live, live_vault, live_pools) = markets[0].clone();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: live,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let (pos_pda, _) = pda::position_address(&live, &user.pubkey(), 0);
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::PlaceBet {
+            user: user.pubkey(),
+            config: config_pda,
+            market: live,
+            outcome_pool: live_pools[0],
+            position: pos_pda,
+            user_stats: pda::user_market_stats_address(&live, &user.pubkey()).0,
+            user_usdc: user_usdc.pubkey(),
+            vault: live_vault,
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::PlaceBet {
+            args: PlaceBetArgs {
+                outcome_id: 0,
+                amount: 50_000,
+                max_outcome_share_bps: None,
+                min_implied_payout: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &user).await.unwrap();
+
+    warp_past(&mut ctx, lock_timestamp).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::LockMarket {
+            caller: authority.pubkey(),
+            market: live,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::LockMarket {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ResolveMarket {
+            oracle: authority.pubkey(),
+            config: config_pda,
+            market: live,
+            winning_outcome_pool: live_pools[0],
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ResolveMarket {
+            args: ResolveMarketArgs {
+                winning_outcome_id: 0,
+                payload_hash: [1u8; 32],
+                additional_winning_outcome_ids: vec![],
+                result_payload: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // not swept yet: vault still holds the unclaimed pool
+    let err = send(
+        &mut ctx,
+        close_ix(&authority.pubkey(), live, live_vault, &live_pools),
+        &authority,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6043, "MarketNotClosable");
+
+    let acct = ctx.banks_client.get_account(live).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    warp_past(&mut ctx, m.resolution_timestamp + 1).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SweepRemaining {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: live,
+            vault: live_vault,
+            treasury: treasury.pubkey(),
+            close_destination: authority.pubkey(),
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SweepRemaining {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // first batch: two pools, market stays
+    send(
+        &mut ctx,
+        close_ix(&authority.pubkey(), live, live_vault, &live_pools[..2]),
+        &authority,
+    )
+    .await
+    .unwrap();
+    for pool in &live_pools[..2] {
+        assert!(ctx.banks_client.get_account(*pool).await.unwrap().is_none());
+    }
+    let acct = ctx.banks_client.get_account(live).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(m.pools_closed, 2);
+
+    // last batch closes the final pool and the market
+    send(
+        &mut ctx,
+        close_ix(&authority.pubkey(), live, live_vault, &live_pools[2..]),
+        &authority,
+    )
+    .await
+    .unwrap();
+    assert!(ctx.banks_client.get_account(live_pools[2]).await.unwrap().is_none());
+    assert!(ctx.banks_client.get_account(live).await.unwrap().is_none());
+
+    // The freed market address cannot be created again over the stale position.
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    for event_id in [[31u8; 32], [32u8; 32]] {
+        let (ix, _, _) = create_ix(event_id, 3, clock.unix_timestamp + 1000);
+        let err = send(&mut ctx, ix, &authority).await.unwrap_err();
+        assert_custom(err, 6064, "MarketIdRetired");
+    }
+
+    // the never-claimed position is still closable by its owner
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClosePosition {
+            user: user.pubkey(),
+            market: live,
+            position: pos_pda,
+            user_stats: None,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClosePosition {
+            args: ClosePositionArgs { outcome_id: 0 },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &user).await.unwrap();
+    assert!(ctx.banks_client.get_account(pos_pda).await.unwrap().is_none());
+}
@@ -0,0 +1,321 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+async fn lamports(ctx: &mut ProgramTestContext, key: Pubkey) -> u64 {
+    ctx.banks_client.get_balance(key).await.unwrap()
+}
+
+#[tokio::test]
+async fn positions_close_after_claim_or_sweep_and_refund_rent() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &usdc_mint.pubkey(), &user.pubkey()).await;
+    mint_to(&mut ctx, &usdc_mint.pubkey(), &authority, &user_usdc.pubkey(), 500_000).await;
+
+    let (config_pda, _) = pda::config_address();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 1,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Two single-outcome markets with one bet each: one is resolved, one voided.
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let outcome_id = 0u8;
+
+    let mut markets = Vec::new();
+    for event_id in [[21u8; 32], [22u8; 32]] {
+        let market_id = canonical::market_id(event_id, 0, 1);
+        let (market_pda, _) = pda::market_address(&market_id);
+        let vault_ata = spl_associated_token_account::get_associated_token_address(
+            &market_pda,
+            &usdc_mint.pubkey(),
+        );
+        let (pool_pda, _) = pda::outcome_pool_address(&market_pda, outcome_id);
+        let (pos_pda, _) = pda::position_address(&market_pda, &user.pubkey(), outcome_id);
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::CreateMarket {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                vault: vault_ata,
+                market_tombstone: pda::market_tombstone_address(&market_pda).0,
+                usdc_mint: usdc_mint.pubkey(),
+                collateral_allowlist: None,
+                token_program: spl_token::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::CreateMarket {
+                args: CreateMarketArgs {
+                    market_id,
+                    event_id,
+                    lock_timestamp,
+                    max_outcomes: 1,
+                    market_type: 0,
+                    rules_version: 1,
+                    max_total_pool_per_market: None,
+                    max_bet_per_user_per_market: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                outcome_pool: pool_pda,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs { outcome_id },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::FinalizeSeeding {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::FinalizeSeeding {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::PlaceBet {
+                user: user.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                outcome_pool: pool_pda,
+                position: pos_pda,
+                user_stats: pda::user_market_stats_address(&market_pda, &user.pubkey()).0,
+                user_usdc: user_usdc.pubkey(),
+                vault: vault_ata,
+                usdc_mint: usdc_mint.pubkey(),
+                token_program: spl_token::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::PlaceBet {
+                args: PlaceBetArgs {
+                    outcome_id,
+                    amount: 50_000,
+                    max_outcome_share_bps: None,
+                    min_implied_payout: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &user).await.unwrap();
+
+        markets.push((market_pda, vault_ata, pool_pda, pos_pda));
+    }
+
+    let close_ix = |market: Pubkey, position: Pubkey| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClosePosition {
+            user: user.pubkey(),
+            market,
+            position,
+            user_stats: None,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClosePosition {
+            args: ClosePositionArgs { outcome_id },
+        }
+        .data(),
+    };
+
+    warp_past(&mut ctx, lock_timestamp).await;
+    for (market_pda, ..) in &markets {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::LockMarket {
+                caller: authority.pubkey(),
+                market: *market_pda,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::LockMarket {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let (resolved, resolved_vault, resolved_pool, resolved_pos) = markets[0];
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ResolveMarket {
+            oracle: authority.pubkey(),
+            config: config_pda,
+            market: resolved,
+            winning_outcome_pool: resolved_pool,
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ResolveMarket {
+            args: ResolveMarketArgs {
+                winning_outcome_id: outcome_id,
+                payload_hash: [1u8; 32],
+                additional_winning_outcome_ids: vec![],
+                result_payload: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let (voided, voided_vault, _voided_pool, voided_pos) = markets[1];
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::VoidMarket {
+            oracle: authority.pubkey(),
+            config: config_pda,
+            market: voided,
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::VoidMarket {
+            args: VoidMarketArgs {
+                payload_hash: [2u8; 32],
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Snapshot before the rejected close: its fee is charged too, and reading the
+    // balance right after a failed transaction can miss it.
+    let rent = lamports(&mut ctx, resolved_pos).await;
+    let before = lamports(&mut ctx, user.pubkey()).await;
+
+    // unclaimed position in a Resolved market may still be owed a payout
+    let err = send(&mut ctx, close_ix(resolved, resolved_pos), &user)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6042, "PositionNotClosable");
+
+    // claim + close atomically: payout and rent both land with the user
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClaimResolved {
+            user: user.pubkey(),
+            config: config_pda,
+            market: resolved,
+            position: resolved_pos,
+            outcome_pool: resolved_pool,
+            user_usdc: user_usdc.pubkey(),
+            vault: resolved_vault,
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClaimResolved {
+            args: ClaimResolvedArgs {
+                outcome_id,
+                close_position: true,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &user).await.unwrap();
+    assert!(ctx.banks_client.get_account(resolved_pos).await.unwrap().is_none());
+    assert_eq!(lamports(&mut ctx, user.pubkey()).await, before + rent - 2 * 5_000);
+
+    let ua = ctx.banks_client.get_account(user_usdc.pubkey()).await.unwrap().unwrap();
+    let ustate = spl_token::state::Account::unpack(&ua.data).unwrap();
+    assert_eq!(ustate.amount, 450_000);
+
+    // voided position is never claimed; after the sweep it can only be closed
+    let acct = ctx.banks_client.get_account(voided).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    warp_past(&mut ctx, m.resolution_timestamp + 1).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SweepRemaining {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: voided,
+            vault: voided_vault,
+            treasury: treasury.pubkey(),
+            close_destination: authority.pubkey(),
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SweepRemaining {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let rent = lamports(&mut ctx, voided_pos).await;
+    let before = lamports(&mut ctx, user.pubkey()).await;
+    send(&mut ctx, close_ix(voided, voided_pos), &user).await.unwrap();
+    assert!(ctx.banks_client.get_account(voided_pos).await.unwrap().is_none());
+    assert_eq!(lamports(&mut ctx, user.pubkey()).await, before + rent - 5_000);
+}
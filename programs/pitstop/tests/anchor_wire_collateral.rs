@@ -0,0 +1,381 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+fn create_market_ix(
+    authority: &Keypair,
+    event_id: [u8; 32],
+    lock_timestamp: i64,
+    mint: Pubkey,
+    collateral_allowlist: Option<Pubkey>,
+) -> (solana_sdk::instruction::Instruction, Pubkey, Pubkey) {
+    let (config_pda, _) = pda::config_address();
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market_pda, _) = pda::market_address(&market_id);
+    let vault = spl_associated_token_account::get_associated_token_address(&market_pda, &mint);
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market_pda).0,
+            usdc_mint: mint,
+            collateral_allowlist,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 2,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    (ix, market_pda, vault)
+}
+
+fn add_collateral_mint_ix(
+    authority: &Keypair,
+    mint: Pubkey,
+    treasury: Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddCollateralMint {
+            authority: authority.pubkey(),
+            config: pda::config_address().0,
+            collateral_allowlist: pda::collateral_allowlist_address().0,
+            mint,
+            treasury,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddCollateralMint {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn usdc_and_pyusd_markets_settle_side_by_side() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let outsider = Keypair::new();
+    fund(&mut ctx, &authority, 2_000_000_000).await;
+    fund(&mut ctx, &outsider, 1_000_000_000).await;
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let usdc_treasury = Keypair::new();
+    create_token_account(&mut ctx, &usdc_treasury, &usdc_mint.pubkey(), &treasury_authority.pubkey())
+        .await;
+    let pyusd_mint = Keypair::new();
+    create_mint(&mut ctx, &pyusd_mint, &authority.pubkey()).await;
+    let pyusd_treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &pyusd_treasury,
+        &pyusd_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = pda::config_address();
+    let (allowlist_pda, _) = pda::collateral_allowlist_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: usdc_treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 1,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+
+    // PYUSD is not usable until the authority allowlists it.
+    let (ix, _, _) =
+        create_market_ix(&authority, [51u8; 32], lock_timestamp, pyusd_mint.pubkey(), None);
+    let err = send(&mut ctx, ix, &authority).await.unwrap_err();
+    assert_custom(err, 6046, "CollateralMintNotAllowed");
+
+    // Only the authority manages the allowlist; a treasury must hold the mint it pairs with.
+    let outsider_add = solana_sdk::instruction::Instruction {
+        accounts: pitstop::accounts::AddCollateralMint {
+            authority: outsider.pubkey(),
+            config: config_pda,
+            collateral_allowlist: allowlist_pda,
+            mint: pyusd_mint.pubkey(),
+            treasury: pyusd_treasury.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        ..add_collateral_mint_ix(&authority, pyusd_mint.pubkey(), pyusd_treasury.pubkey())
+    };
+    let err = send(&mut ctx, outsider_add, &outsider).await.unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+
+    let ix = add_collateral_mint_ix(&authority, pyusd_mint.pubkey(), usdc_treasury.pubkey());
+    let err = send(&mut ctx, ix, &authority).await.unwrap_err();
+    assert_custom(err, 6005, "InvalidTreasuryMint");
+
+    let ix = add_collateral_mint_ix(&authority, pyusd_mint.pubkey(), pyusd_treasury.pubkey());
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // The config mint is implicitly allowed and cannot be listed.
+    let ix = add_collateral_mint_ix(&authority, usdc_mint.pubkey(), usdc_treasury.pubkey());
+    let err = send(&mut ctx, ix, &authority).await.unwrap_err();
+    assert_custom(err, 6047, "CollateralMintAlreadyAllowed");
+
+    let acct = ctx.banks_client.get_account(allowlist_pda).await.unwrap().unwrap();
+    let allowlist: CollateralAllowlist =
+        AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(allowlist.count, 1);
+    assert_eq!(allowlist.entries[0].mint, pyusd_mint.pubkey());
+    assert_eq!(allowlist.entries[0].treasury, pyusd_treasury.pubkey());
+
+    // One market per collateral; each vault is the ATA for its own mint.
+    let mut markets = Vec::new();
+    for (event_byte, mint, treasury, allowlist) in [
+        (52u8, &usdc_mint, &usdc_treasury, None),
+        (53u8, &pyusd_mint, &pyusd_treasury, Some(allowlist_pda)),
+    ] {
+        let (ix, market_pda, vault) =
+            create_market_ix(&authority, [event_byte; 32], lock_timestamp, mint.pubkey(), allowlist);
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        let acct = ctx.banks_client.get_account(market_pda).await.unwrap().unwrap();
+        let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+        assert_eq!(m.collateral_mint, mint.pubkey());
+        assert_eq!(m.treasury, treasury.pubkey());
+        assert_eq!(
+            vault,
+            spl_associated_token_account::get_associated_token_address(&market_pda, &mint.pubkey())
+        );
+
+        let pools: Vec<Pubkey> = (0..2u8)
+            .map(|id| pda::outcome_pool_address(&market_pda, id).0)
+            .collect();
+        for (outcome_id, pool) in pools.iter().enumerate() {
+            let ix = solana_sdk::instruction::Instruction {
+                program_id: pitstop::id(),
+                accounts: pitstop::accounts::AddOutcome {
+                    authority: authority.pubkey(),
+                    config: config_pda,
+                    market: market_pda,
+                    outcome_pool: *pool,
+                    system_program: solana_sdk::system_program::id(),
+                }
+                .to_account_metas(None),
+                data: pitstop::instruction::AddOutcome {
+                    args: AddOutcomeArgs {
+                        outcome_id: outcome_id as u8,
+                    },
+                }
+                .data(),
+            };
+            send(&mut ctx, ix, &authority).await.unwrap();
+        }
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::FinalizeSeeding {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::FinalizeSeeding {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+        markets.push((market_pda, vault, pools, mint, treasury));
+    }
+
+    // Same bettor, one funded account per mint; a market only takes its own mint.
+    let user = Keypair::new();
+    fund(&mut ctx, &user, 1_000_000_000).await;
+    let mut user_accounts = Vec::new();
+    for mint in [&usdc_mint, &pyusd_mint] {
+        let acct = Keypair::new();
+        create_token_account(&mut ctx, &acct, &mint.pubkey(), &user.pubkey()).await;
+        mint_to(&mut ctx, &mint.pubkey(), &authority, &acct.pubkey(), 50_000).await;
+        user_accounts.push(acct.pubkey());
+    }
+
+    let place_bet_ix = |market: usize, mint: Pubkey, user_usdc: Pubkey, amount: u64| {
+        let (market_pda, vault, pools, _, _) = &markets[market];
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::PlaceBet {
+                user: user.pubkey(),
+                config: config_pda,
+                market: *market_pda,
+                outcome_pool: pools[0],
+                position: pda::position_address(market_pda, &user.pubkey(), 0).0,
+                user_stats: pda::user_market_stats_address(market_pda, &user.pubkey()).0,
+                user_usdc,
+                vault: *vault,
+                usdc_mint: mint,
+                token_program: spl_token::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::PlaceBet {
+                args: PlaceBetArgs {
+                    outcome_id: 0,
+                    amount,
+                    max_outcome_share_bps: None,
+                    min_implied_payout: None,
+                },
+            }
+            .data(),
+        }
+    };
+
+    let ix = place_bet_ix(1, usdc_mint.pubkey(), user_accounts[0], 10_000);
+    let err = send(&mut ctx, ix, &user).await.unwrap_err();
+    assert_custom(err, 6005, "InvalidTreasuryMint");
+
+    send(&mut ctx, place_bet_ix(0, usdc_mint.pubkey(), user_accounts[0], 10_000), &user)
+        .await
+        .unwrap();
+    send(&mut ctx, place_bet_ix(1, pyusd_mint.pubkey(), user_accounts[1], 20_000), &user)
+        .await
+        .unwrap();
+    assert_eq!(token_amount(&mut ctx, markets[0].1).await, 10_000);
+    assert_eq!(token_amount(&mut ctx, markets[1].1).await, 20_000);
+
+    // Delisting stops new PYUSD markets but leaves the existing one untouched.
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::RemoveCollateralMint {
+            authority: authority.pubkey(),
+            config: config_pda,
+            collateral_allowlist: allowlist_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::RemoveCollateralMint {
+            args: RemoveCollateralMintArgs {
+                mint: pyusd_mint.pubkey(),
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+    let (ix, _, _) = create_market_ix(
+        &authority,
+        [54u8; 32],
+        lock_timestamp,
+        pyusd_mint.pubkey(),
+        Some(allowlist_pda),
+    );
+    let err = send(&mut ctx, ix, &authority).await.unwrap_err();
+    assert_custom(err, 6046, "CollateralMintNotAllowed");
+
+    warp_past(&mut ctx, lock_timestamp).await;
+    for (market_pda, _, pools, _, _) in &markets {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::LockMarket {
+                caller: authority.pubkey(),
+                market: *market_pda,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::LockMarket {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        // Outcome 1 wins and has no bettors: the whole pool is swept to treasury.
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::ResolveMarket {
+                oracle: authority.pubkey(),
+                config: config_pda,
+                market: *market_pda,
+                winning_outcome_pool: pools[1],
+                instructions_sysvar: None,
+                oracle_committee: None,
+                resolution_votes: None,
+                votes_payer: None,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::ResolveMarket {
+                args: ResolveMarketArgs {
+                    winning_outcome_id: 1,
+                    payload_hash: [5u8; 32],
+                    additional_winning_outcome_ids: vec![],
+                    result_payload: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let acct = ctx.banks_client.get_account(markets[1].0).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    warp_past(&mut ctx, m.resolution_timestamp + 1).await;
+
+    let sweep_ix = |market: usize, treasury: Pubkey| {
+        let (market_pda, vault, _, mint, _) = &markets[market];
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::SweepRemaining {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: *market_pda,
+                vault: *vault,
+                treasury,
+                close_destination: authority.pubkey(),
+                usdc_mint: mint.pubkey(),
+                token_program: spl_token::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::SweepRemaining {}.data(),
+        }
+    };
+
+    // Each market sweeps only to the treasury recorded for its mint.
+    let err = send(&mut ctx, sweep_ix(1, usdc_treasury.pubkey()), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6005, "InvalidTreasuryMint");
+
+    send(&mut ctx, sweep_ix(0, usdc_treasury.pubkey()), &authority).await.unwrap();
+    send(&mut ctx, sweep_ix(1, pyusd_treasury.pubkey()), &authority).await.unwrap();
+    assert_eq!(token_amount(&mut ctx, usdc_treasury.pubkey()).await, 10_000);
+    assert_eq!(token_amount(&mut ctx, pyusd_treasury.pubkey()).await, 20_000);
+    assert!(ctx.banks_client.get_account(markets[1].1).await.unwrap().is_none());
+}
@@ -0,0 +1,442 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+/// create -> add one outcome -> open; returns (market, outcome pool).
+async fn open_market(
+    ctx: &mut ProgramTestContext,
+    authority: &Keypair,
+    usdc_mint: &Pubkey,
+    event_byte: u8,
+    lock_timestamp: i64,
+) -> (Pubkey, Pubkey) {
+    let (config_pda, _) = pda::config_address();
+    let event_id = [event_byte; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market_pda, _) = pda::market_address(&market_id);
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            vault: spl_associated_token_account::get_associated_token_address(
+                &market_pda,
+                usdc_mint,
+            ),
+            market_tombstone: pda::market_tombstone_address(&market_pda).0,
+            usdc_mint: *usdc_mint,
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 1,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(ctx, ix, authority).await.unwrap();
+
+    let (pool_pda, _) = pda::outcome_pool_address(&market_pda, 0);
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddOutcome {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            outcome_pool: pool_pda,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddOutcome {
+            args: AddOutcomeArgs { outcome_id: 0 },
+        }
+        .data(),
+    };
+    send(ctx, ix, authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(ctx, ix, authority).await.unwrap();
+
+    (market_pda, pool_pda)
+}
+
+async fn market_status(ctx: &mut ProgramTestContext, market: Pubkey) -> MarketStatus {
+    let acct = ctx.banks_client.get_account(market).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    m.status
+}
+
+fn vote_ix(
+    member: &Keypair,
+    market: Pubkey,
+    void: bool,
+    payload_hash: [u8; 32],
+) -> solana_sdk::instruction::Instruction {
+    let (config, _) = pda::config_address();
+    let (oracle_committee, _) = pda::oracle_committee_address();
+    let (resolution_votes, _) = pda::resolution_votes_address(&market);
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SubmitResolutionVote {
+            member: member.pubkey(),
+            config,
+            oracle_committee,
+            market,
+            resolution_votes,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SubmitResolutionVote {
+            args: SubmitResolutionVoteArgs {
+                void,
+                winning_outcome_id: 0,
+                additional_winning_outcome_ids: vec![],
+                payload_hash,
+            },
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn committee_quorum_gates_resolve_and_void() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let oracle = Keypair::new();
+    let keeper = Keypair::new();
+    let outsider = Keypair::new();
+    let members = [Keypair::new(), Keypair::new(), Keypair::new()];
+    for kp in [&authority, &oracle, &keeper, &outsider]
+        .into_iter()
+        .chain(members.iter())
+    {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: oracle.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let (committee_pda, _) = pda::oracle_committee_address();
+    let set_committee_ix = |threshold: u8| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SetOracleCommittee {
+            authority: authority.pubkey(),
+            config: config_pda,
+            oracle_committee: committee_pda,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SetOracleCommittee {
+            args: SetOracleCommitteeArgs {
+                members: members.iter().map(Keypair::pubkey).collect(),
+                threshold,
+            },
+        }
+        .data(),
+    };
+
+    // 1-of-3 is not a majority.
+    let err = send(&mut ctx, set_committee_ix(1), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6052, "InvalidCommittee");
+    send(&mut ctx, set_committee_ix(2), &authority)
+        .await
+        .unwrap();
+
+    let acct = ctx
+        .banks_client
+        .get_account(config_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let cfg: Config = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(cfg.oracle, committee_pda);
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let (resolved, resolved_pool) = open_market(
+        &mut ctx,
+        &authority,
+        &usdc_mint.pubkey(),
+        71,
+        lock_timestamp,
+    )
+    .await;
+    let (voided, _) = open_market(
+        &mut ctx,
+        &authority,
+        &usdc_mint.pubkey(),
+        72,
+        lock_timestamp,
+    )
+    .await;
+
+    // Votes are only taken once the market is Locked.
+    let err = send(
+        &mut ctx,
+        vote_ix(&members[0], resolved, false, [1u8; 32]),
+        &members[0],
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6016, "MarketNotLocked");
+
+    loop {
+        let c: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+        if c.unix_timestamp >= lock_timestamp {
+            break;
+        }
+        let slot = ctx.banks_client.get_root_slot().await.unwrap() + 10;
+        ctx.warp_to_slot(slot).unwrap();
+    }
+    for market in [resolved, voided] {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::LockMarket {
+                caller: authority.pubkey(),
+                market,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::LockMarket {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let payload_hash = [0x11u8; 32];
+    // `Some(votes_payer)` passes the committee accounts.
+    let resolve_ix =
+        |signer: &Keypair, votes_payer: Option<Pubkey>| solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::ResolveMarket {
+                oracle: signer.pubkey(),
+                config: config_pda,
+                market: resolved,
+                winning_outcome_pool: resolved_pool,
+                instructions_sysvar: None,
+                oracle_committee: votes_payer.map(|_| committee_pda),
+                resolution_votes: votes_payer.map(|_| pda::resolution_votes_address(&resolved).0),
+                votes_payer,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::ResolveMarket {
+                args: ResolveMarketArgs {
+                    winning_outcome_id: 0,
+                    payload_hash,
+                    additional_winning_outcome_ids: vec![],
+                    result_payload: None,
+                },
+            }
+            .data(),
+        };
+
+    // The former single oracle lost its say.
+    let err = send(&mut ctx, resolve_ix(&oracle, None), &oracle)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6001, "UnauthorizedOracle");
+
+    let err = send(
+        &mut ctx,
+        vote_ix(&outsider, resolved, false, payload_hash),
+        &outsider,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6053, "NotCommitteeMember");
+
+    send(
+        &mut ctx,
+        vote_ix(&members[0], resolved, false, payload_hash),
+        &members[0],
+    )
+    .await
+    .unwrap();
+    // Disagreeing on the payload hash is not agreement.
+    send(
+        &mut ctx,
+        vote_ix(&members[1], resolved, false, [0x22u8; 32]),
+        &members[1],
+    )
+    .await
+    .unwrap();
+    let err = send(&mut ctx, resolve_ix(&keeper, Some(members[0].pubkey())), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6054, "QuorumNotReached");
+
+    // Omitting the committee accounts falls back to the single-signer check.
+    let err = send(&mut ctx, resolve_ix(&keeper, None), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6001, "UnauthorizedOracle");
+
+    // A revote replaces the member's earlier vote.
+    send(
+        &mut ctx,
+        vote_ix(&members[1], resolved, false, payload_hash),
+        &members[1],
+    )
+    .await
+    .unwrap();
+    let votes_pda = pda::resolution_votes_address(&resolved).0;
+    let acct = ctx
+        .banks_client
+        .get_account(votes_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let votes: ResolutionVotes =
+        AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(votes.count, 2);
+    assert_eq!(votes.payer, members[0].pubkey());
+
+    // The votes rent only goes back to the member who paid for the account.
+    let err = send(&mut ctx, resolve_ix(&keeper, Some(keeper.pubkey())), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6065, "VotesPayerMismatch");
+
+    let votes_rent = acct.lamports;
+    let payer_before = ctx
+        .banks_client
+        .get_balance(members[0].pubkey())
+        .await
+        .unwrap();
+    send(&mut ctx, resolve_ix(&keeper, Some(members[0].pubkey())), &keeper)
+        .await
+        .unwrap();
+    assert_eq!(
+        market_status(&mut ctx, resolved).await,
+        MarketStatus::Resolved
+    );
+    assert!(ctx
+        .banks_client
+        .get_account(votes_pda)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        ctx.banks_client
+            .get_balance(members[0].pubkey())
+            .await
+            .unwrap(),
+        payer_before + votes_rent
+    );
+
+    let err = send(
+        &mut ctx,
+        vote_ix(&members[2], resolved, false, payload_hash),
+        &members[2],
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6016, "MarketNotLocked");
+
+    // Voiding needs the same quorum.
+    let void_ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::VoidMarket {
+            oracle: keeper.pubkey(),
+            config: config_pda,
+            market: voided,
+            instructions_sysvar: None,
+            oracle_committee: Some(committee_pda),
+            resolution_votes: Some(pda::resolution_votes_address(&voided).0),
+            votes_payer: Some(members[0].pubkey()),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::VoidMarket {
+            args: VoidMarketArgs { payload_hash },
+        }
+        .data(),
+    };
+    send(
+        &mut ctx,
+        vote_ix(&members[0], voided, true, payload_hash),
+        &members[0],
+    )
+    .await
+    .unwrap();
+    let err = send(&mut ctx, void_ix.clone(), &keeper).await.unwrap_err();
+    assert_custom(err, 6054, "QuorumNotReached");
+
+    send(
+        &mut ctx,
+        vote_ix(&members[2], voided, true, payload_hash),
+        &members[2],
+    )
+    .await
+    .unwrap();
+    send(&mut ctx, void_ix, &keeper).await.unwrap();
+    assert_eq!(market_status(&mut ctx, voided).await, MarketStatus::Voided);
+    assert!(ctx
+        .banks_client
+        .get_account(pda::resolution_votes_address(&voided).0)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,227 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, pda};
+
+fn overturn_ix(
+    authority: &Keypair,
+    m: &TestMarket,
+    winner: Option<u8>,
+) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::OverturnResolution {
+            authority: authority.pubkey(),
+            config: pda::config_address().0,
+            market: m.market,
+            winning_outcome_pool: winner.map(|id| m.pools[id as usize]),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::OverturnResolution {
+            args: OverturnResolutionArgs {
+                void: winner.is_none(),
+                winning_outcome_id: winner.unwrap_or(0),
+                additional_winning_outcome_ids: vec![],
+                payload_hash: [0x22; 32],
+                result_payload: None,
+            },
+        }
+        .data(),
+    }
+}
+
+fn claim_ix(m: &TestMarket, bettor: &Bettor, usdc_mint: &Pubkey) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClaimResolved {
+            user: bettor.user.pubkey(),
+            config: pda::config_address().0,
+            market: m.market,
+            position: bettor.position,
+            outcome_pool: m.pools[bettor.outcome_id as usize],
+            user_usdc: bettor.user_usdc,
+            vault: m.vault,
+            usdc_mint: *usdc_mint,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClaimResolved {
+            args: ClaimResolvedArgs {
+                outcome_id: bettor.outcome_id,
+                close_position: false,
+            },
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn dispute_window_delays_claims_and_allows_overturn() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let oracle = Keypair::new();
+    for kp in [&authority, &treasury_authority, &oracle] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: oracle.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let dispute_window_ix = |secs: i64| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::UpdateConfig {
+            authority: authority.pubkey(),
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::UpdateConfig {
+            args: UpdateConfigArgs {
+                fee_bps: None,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+                claim_window_secs: None,
+                dispute_window_secs: Some(secs),
+                resolution_deadline_secs: None,
+                early_exit_fee_bps: None,
+                payout_keeper: None,
+            },
+        }
+        .data(),
+    };
+    let err = send(&mut ctx, dispute_window_ix(-1), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6055, "InvalidDisputeWindow");
+    send(&mut ctx, dispute_window_ix(60), &authority).await.unwrap();
+
+    // Overturned inside the window: the corrected winner is paid, the original is not.
+    let overturned = locked_market(&mut ctx, &authority, &usdc_mint.pubkey(), 81).await;
+    send(&mut ctx, resolve_ix(&oracle, &overturned, 0), &oracle)
+        .await
+        .unwrap();
+    let m = market_state(&mut ctx, overturned.market).await;
+    assert_eq!(m.status, MarketStatus::PendingResolution);
+    assert_eq!(m.dispute_deadline, m.resolution_timestamp + 60);
+
+    let err = send(
+        &mut ctx,
+        claim_ix(&overturned, &overturned.bettors[0], &usdc_mint.pubkey()),
+        &overturned.bettors[0].user,
+    )
+    .await
+    .unwrap_err();
+    assert_custom(err, 6056, "DisputeWindowActive");
+
+    // Only the authority overturns; the oracle cannot amend its own result.
+    let err = send(&mut ctx, overturn_ix(&oracle, &overturned, Some(1)), &oracle)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+
+    send(&mut ctx, overturn_ix(&authority, &overturned, Some(1)), &authority)
+        .await
+        .unwrap();
+    let m = market_state(&mut ctx, overturned.market).await;
+    assert_eq!(m.status, MarketStatus::Resolved);
+    assert_eq!(m.resolved_outcome, Some(1));
+    // No result payload backed the correction, so no hash is kept for it.
+    assert_eq!(m.resolution_payload_hash, [0u8; 32]);
+
+    let err = send(&mut ctx, overturn_ix(&authority, &overturned, None), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6057, "DisputeWindowClosed");
+
+    for bettor in &overturned.bettors {
+        send(
+            &mut ctx,
+            claim_ix(&overturned, bettor, &usdc_mint.pubkey()),
+            &bettor.user,
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(token_amount(&mut ctx, overturned.bettors[0].user_usdc).await, 0);
+    assert_eq!(token_amount(&mut ctx, overturned.bettors[1].user_usdc).await, 20_000);
+
+    // Left alone, the pending result becomes final when the window ends.
+    let pending = locked_market(&mut ctx, &authority, &usdc_mint.pubkey(), 82).await;
+    send(&mut ctx, resolve_ix(&oracle, &pending, 0), &oracle)
+        .await
+        .unwrap();
+    let deadline = market_state(&mut ctx, pending.market).await.dispute_deadline;
+    warp_past(&mut ctx, deadline).await;
+
+    let err = send(&mut ctx, overturn_ix(&authority, &pending, None), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6057, "DisputeWindowClosed");
+    assert_eq!(
+        market_state(&mut ctx, pending.market).await.status,
+        MarketStatus::PendingResolution
+    );
+
+    // The first claim after the deadline records the result as Resolved.
+    send(
+        &mut ctx,
+        claim_ix(&pending, &pending.bettors[0], &usdc_mint.pubkey()),
+        &pending.bettors[0].user,
+    )
+    .await
+    .unwrap();
+    assert_eq!(token_amount(&mut ctx, pending.bettors[0].user_usdc).await, 20_000);
+    let m = market_state(&mut ctx, pending.market).await;
+    assert_eq!(m.status, MarketStatus::Resolved);
+    assert_eq!((m.resolved_outcome, m.dispute_deadline), (Some(0), deadline));
+
+    send(
+        &mut ctx,
+        claim_ix(&pending, &pending.bettors[1], &usdc_mint.pubkey()),
+        &pending.bettors[1].user,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        market_state(&mut ctx, pending.market).await.status,
+        MarketStatus::Resolved
+    );
+}
@@ -0,0 +1,220 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, pda};
+
+/// One distribute_payouts group: `[position (mut), outcome_pool, destination (mut)]`.
+fn group(m: &TestMarket, bettor: &Bettor, destination: Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(bettor.position, false),
+        AccountMeta::new_readonly(m.pools[bettor.outcome_id as usize], false),
+        AccountMeta::new(destination, false),
+    ]
+}
+
+fn distribute_ix(
+    caller: &Keypair,
+    m: &TestMarket,
+    usdc_mint: &Pubkey,
+    groups: Vec<Vec<AccountMeta>>,
+) -> solana_sdk::instruction::Instruction {
+    let mut accounts = pitstop::accounts::DistributePayouts {
+        caller: caller.pubkey(),
+        config: pda::config_address().0,
+        market: m.market,
+        vault: m.vault,
+        usdc_mint: *usdc_mint,
+        token_program: spl_token::id(),
+    }
+    .to_account_metas(None);
+    accounts.extend(groups.into_iter().flatten());
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts,
+        data: pitstop::instruction::DistributePayouts {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn keeper_pushes_payouts_to_owner_atas_only() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let keeper = Keypair::new();
+    for kp in [&authority, &treasury_authority, &keeper] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+    let keeper_usdc = Keypair::new();
+    create_token_account(&mut ctx, &keeper_usdc, &usdc_mint.pubkey(), &keeper.pubkey()).await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let m = locked_market(&mut ctx, &authority, &usdc_mint.pubkey(), 95).await;
+    let (winner, loser) = (&m.bettors[0], &m.bettors[1]);
+    let mint = usdc_mint.pubkey();
+    let ata = |owner: &Keypair| {
+        spl_associated_token_account::get_associated_token_address(&owner.pubkey(), &mint)
+    };
+    for bettor in [winner, loser] {
+        let ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &keeper.pubkey(),
+            &bettor.user.pubkey(),
+            &mint,
+            &spl_token::id(),
+        );
+        send(&mut ctx, ix, &keeper).await.unwrap();
+    }
+    let (winner_ata, loser_ata) = (ata(&winner.user), ata(&loser.user));
+    let both = || {
+        vec![
+            group(&m, winner, winner_ata),
+            group(&m, loser, loser_ata),
+        ]
+    };
+
+    // DSP-REJ-005: an arbitrary signer cannot push payouts until it is the configured keeper.
+    let err = send(&mut ctx, distribute_ix(&keeper, &m, &mint, both()), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::UpdateConfig {
+            authority: authority.pubkey(),
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::UpdateConfig {
+            args: UpdateConfigArgs {
+                fee_bps: None,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+                claim_window_secs: None,
+                dispute_window_secs: None,
+                resolution_deadline_secs: None,
+                early_exit_fee_bps: None,
+                payout_keeper: Some(Some(keeper.pubkey())),
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Same status gate as claim_resolved.
+    let err = send(&mut ctx, distribute_ix(&keeper, &m, &mint, both()), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6017, "MarketNotResolved");
+
+    send(&mut ctx, resolve_ix(&authority, &m, 0), &authority)
+        .await
+        .unwrap();
+
+    // DSP-REJ-001: a trailing partial group.
+    let mut groups = both();
+    groups[1].pop();
+    let err = send(&mut ctx, distribute_ix(&keeper, &m, &mint, groups), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6060, "InvalidClaimBatch");
+
+    // DSP-REJ-003: the winner's payout cannot be redirected to the keeper.
+    let groups = vec![group(&m, winner, keeper_usdc.pubkey())];
+    let err = send(&mut ctx, distribute_ix(&keeper, &m, &mint, groups), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6000, "Unauthorized");
+    assert_eq!(token_amount(&mut ctx, keeper_usdc.pubkey()).await, 0);
+
+    // DSP-REJ-006: nor to another account the winner owns that is not their ATA.
+    let groups = vec![group(&m, winner, winner.user_usdc)];
+    let err = send(&mut ctx, distribute_ix(&keeper, &m, &mint, groups), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6067, "InvalidPayoutDestination");
+
+    // The winner takes the whole 20k pool; the loser is marked claimed with 0.
+    send(&mut ctx, distribute_ix(&keeper, &m, &mint, both()), &keeper)
+        .await
+        .unwrap();
+    assert_eq!(token_amount(&mut ctx, winner_ata).await, 20_000);
+    assert_eq!(token_amount(&mut ctx, loser_ata).await, 0);
+    assert_eq!(token_amount(&mut ctx, m.vault).await, 0);
+    let p = position_state(&mut ctx, winner.position).await;
+    assert!(p.claimed);
+    assert_eq!(p.payout, 20_000);
+    let p = position_state(&mut ctx, loser.position).await;
+    assert!(p.claimed);
+    assert_eq!(p.payout, 0);
+
+    // Pushed positions are claimed for good: neither path pays twice.
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClaimResolved {
+            user: winner.user.pubkey(),
+            config: config_pda,
+            market: m.market,
+            position: winner.position,
+            outcome_pool: m.pools[0],
+            user_usdc: winner.user_usdc,
+            vault: m.vault,
+            usdc_mint: mint,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClaimResolved {
+            args: ClaimResolvedArgs {
+                outcome_id: 0,
+                close_position: false,
+            },
+        }
+        .data(),
+    };
+    let err = send(&mut ctx, ix, &winner.user).await.unwrap_err();
+    assert_custom(err, 6020, "AlreadyClaimed");
+    let groups = vec![group(&m, winner, winner_ata)];
+    let err = send(&mut ctx, distribute_ix(&keeper, &m, &mint, groups), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6020, "AlreadyClaimed");
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+fn lock_ix(caller: &Keypair, market: Pubkey) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::LockMarket {
+            caller: caller.pubkey(),
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::LockMarket {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn any_keeper_locks_after_lock_timestamp() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let oracle = Keypair::new();
+    let keeper = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &oracle, &keeper, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: oracle.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 100;
+    let event_id = [101u8; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market, _) = pda::market_address(&market_id);
+    let usdc = usdc_mint.pubkey();
+    let vault = spl_associated_token_account::get_associated_token_address(&market, &usdc);
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market).0,
+            usdc_mint: usdc,
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 1,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let (pool, _) = pda::outcome_pool_address(&market, 0);
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddOutcome {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+            outcome_pool: pool,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddOutcome {
+            args: AddOutcomeArgs { outcome_id: 0 },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Before lock_timestamp nobody can lock, the operator included.
+    let err = send(&mut ctx, lock_ix(&keeper, market), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6010, "TooEarlyToLock");
+
+    warp_past(&mut ctx, lock_timestamp).await;
+
+    // Betting is already closed while the market is still Open in state.
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &usdc, &user.pubkey()).await;
+    mint_to(&mut ctx, &usdc, &authority, &user_usdc.pubkey(), 10_000).await;
+    let (position, _) = pda::position_address(&market, &user.pubkey(), 0);
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::PlaceBet {
+            user: user.pubkey(),
+            config: config_pda,
+            market,
+            outcome_pool: pool,
+            position,
+            user_stats: pda::user_market_stats_address(&market, &user.pubkey()).0,
+            user_usdc: user_usdc.pubkey(),
+            vault,
+            usdc_mint: usdc,
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::PlaceBet {
+            args: PlaceBetArgs {
+                outcome_id: 0,
+                amount: 10_000,
+                max_outcome_share_bps: None,
+                min_implied_payout: None,
+            },
+        }
+        .data(),
+    };
+    let err = send(&mut ctx, ix, &user).await.unwrap_err();
+    assert_custom(err, 6011, "BettingClosed");
+    assert_eq!(market_state(&mut ctx, market).await.status, MarketStatus::Open);
+
+    // A keeper with no protocol role cranks the lock; the actual time is recorded.
+    send(&mut ctx, lock_ix(&keeper, market), &keeper)
+        .await
+        .unwrap();
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let m = market_state(&mut ctx, market).await;
+    assert_eq!(m.status, MarketStatus::Locked);
+    assert_eq!(m.lock_timestamp, lock_timestamp);
+    assert!(m.locked_at >= lock_timestamp && m.locked_at <= clock.unix_timestamp);
+
+    let err = send(&mut ctx, lock_ix(&authority, market), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6015, "MarketNotOpen");
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+#[tokio::test]
+async fn create_market_cap_overrides_bind_place_bet() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    for kp in [&authority, &treasury_authority, &alice, &bob] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let mint = usdc_mint.pubkey();
+    let treasury = Keypair::new();
+    create_token_account(&mut ctx, &treasury, &mint, &treasury_authority.pubkey()).await;
+    let alice_usdc = Keypair::new();
+    create_token_account(&mut ctx, &alice_usdc, &mint, &alice.pubkey()).await;
+    mint_to(&mut ctx, &mint, &authority, &alice_usdc.pubkey(), 10_000).await;
+    let bob_usdc = Keypair::new();
+    create_token_account(&mut ctx, &bob_usdc, &mint, &bob.pubkey()).await;
+    mint_to(&mut ctx, &mint, &authority, &bob_usdc.pubkey(), 10_000).await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: mint,
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let event_id = [44u8; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market, _) = pda::market_address(&market_id);
+    let vault = spl_associated_token_account::get_associated_token_address(&market, &mint);
+    let pools: Vec<Pubkey> = (0..2u8)
+        .map(|id| pda::outcome_pool_address(&market, id).0)
+        .collect();
+
+    let create_ix = |max_total_pool_per_market: Option<u64>,
+                     max_bet_per_user_per_market: Option<u64>| {
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::CreateMarket {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market,
+                vault,
+                market_tombstone: pda::market_tombstone_address(&market).0,
+                usdc_mint: mint,
+                collateral_allowlist: None,
+                token_program: spl_token::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::CreateMarket {
+                args: CreateMarketArgs {
+                    market_id,
+                    event_id,
+                    lock_timestamp: clock.unix_timestamp + 1_000,
+                    max_outcomes: 2,
+                    market_type: 0,
+                    rules_version: 1,
+                    max_total_pool_per_market,
+                    max_bet_per_user_per_market,
+                },
+            }
+            .data(),
+        }
+    };
+
+    // Overrides can only tighten the config caps.
+    let err = send(&mut ctx, create_ix(Some(1_000_001), None), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6007, "InvalidCap");
+    let err = send(&mut ctx, create_ix(None, Some(100_001)), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6007, "InvalidCap");
+    let err = send(&mut ctx, create_ix(Some(1_000), Some(1_001)), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6007, "InvalidCap");
+
+    send(&mut ctx, create_ix(Some(1_500), Some(1_000)), &authority)
+        .await
+        .unwrap();
+    let m = market_state(&mut ctx, market).await;
+    assert_eq!(m.max_total_pool_per_market, Some(1_500));
+    assert_eq!(m.max_bet_per_user_per_market, Some(1_000));
+
+    for (outcome_id, pool) in pools.iter().enumerate() {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market,
+                outcome_pool: *pool,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs {
+                    outcome_id: outcome_id as u8,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let bet_ix = |bettor: &Keypair, bettor_usdc: Pubkey, outcome_id: u8, amount: u64| {
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::PlaceBet {
+                user: bettor.pubkey(),
+                config: config_pda,
+                market,
+                outcome_pool: pools[outcome_id as usize],
+                position: pda::position_address(&market, &bettor.pubkey(), outcome_id).0,
+                user_stats: pda::user_market_stats_address(&market, &bettor.pubkey()).0,
+                user_usdc: bettor_usdc,
+                vault,
+                usdc_mint: mint,
+                token_program: spl_token::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::PlaceBet {
+                args: PlaceBetArgs {
+                    outcome_id,
+                    amount,
+                    max_outcome_share_bps: None,
+                    min_implied_payout: None,
+                },
+            }
+            .data(),
+        }
+    };
+
+    // The market's per-user cap (1_000) binds well below the config's 100_000.
+    send(&mut ctx, bet_ix(&alice, alice_usdc.pubkey(), 0, 1_000), &alice)
+        .await
+        .unwrap();
+    let err = send(&mut ctx, bet_ix(&alice, alice_usdc.pubkey(), 0, 1), &alice)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6031, "UserBetCapExceeded");
+
+    // So does its total cap (1_500) below the config's 1_000_000.
+    let err = send(&mut ctx, bet_ix(&bob, bob_usdc.pubkey(), 1, 501), &bob)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6030, "MarketCapExceeded");
+    send(&mut ctx, bet_ix(&bob, bob_usdc.pubkey(), 1, 500), &bob)
+        .await
+        .unwrap();
+    assert_eq!(market_state(&mut ctx, market).await.total_pool, 1_500);
+}
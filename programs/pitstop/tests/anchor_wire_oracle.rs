@@ -0,0 +1,270 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical};
+
+fn set_oracle_ix(
+    authority: &Pubkey,
+    config: &Pubkey,
+    new_oracle: Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SetOracle {
+            authority: *authority,
+            config: *config,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SetOracle {
+            args: SetOracleArgs { new_oracle },
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn oracle_rotation_after_lock_moves_resolution_to_new_signer() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let oracle_a = Keypair::new();
+    let oracle_b = Keypair::new();
+    for kp in [&authority, &treasury_authority, &oracle_a, &oracle_b] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &pitstop::id());
+
+    // initialize with an oracle distinct from the authority
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: oracle_a.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let acct = ctx
+        .banks_client
+        .get_account(config_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let cfg: Config = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(cfg.authority, authority.pubkey());
+    assert_eq!(cfg.oracle, oracle_a.pubkey());
+
+    // create -> add outcome -> open
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let event_id = [9u8; 32];
+    let market_type = 0u8;
+    let rules_version = 1u16;
+    let market_id = canonical::market_id(event_id, market_type, rules_version);
+    let (market_pda, _) =
+        Pubkey::find_program_address(&[b"market", market_id.as_ref()], &pitstop::id());
+    let vault_ata = spl_associated_token_account::get_associated_token_address(
+        &market_pda,
+        &usdc_mint.pubkey(),
+    );
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            vault: vault_ata,
+            market_tombstone: pitstop::pda::market_tombstone_address(&market_pda).0,
+            usdc_mint: usdc_mint.pubkey(),
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 1,
+                market_type,
+                rules_version,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let outcome_id = 0u8;
+    let (pool_pda, _) = Pubkey::find_program_address(
+        &[b"outcome", market_pda.as_ref(), &[outcome_id]],
+        &pitstop::id(),
+    );
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddOutcome {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            outcome_pool: pool_pda,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddOutcome {
+            args: AddOutcomeArgs { outcome_id },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // warp until lock time, then lock
+    loop {
+        let c: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+        if c.unix_timestamp >= lock_timestamp {
+            break;
+        }
+        let slot = ctx.banks_client.get_root_slot().await.unwrap() + 10;
+        ctx.warp_to_slot(slot).unwrap();
+    }
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::LockMarket {
+            caller: authority.pubkey(),
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::LockMarket {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // the oracle cannot rotate itself
+    let err = send(
+        &mut ctx,
+        set_oracle_ix(&oracle_a.pubkey(), &config_pda, oracle_a.pubkey()),
+        &oracle_a,
+    )
+    .await
+    .unwrap_err();
+    let msg = format!("{err:?}");
+    assert!(
+        msg.contains("Custom(6000)"),
+        "expected Unauthorized (6000), got {msg}"
+    );
+
+    // rotate while the market is Locked
+    send(
+        &mut ctx,
+        set_oracle_ix(&authority.pubkey(), &config_pda, oracle_b.pubkey()),
+        &authority,
+    )
+    .await
+    .unwrap();
+    let acct = ctx
+        .banks_client
+        .get_account(config_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let cfg: Config = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(cfg.oracle, oracle_b.pubkey());
+
+    let payload_hash = [0xcdu8; 32];
+    let resolve_ix = |oracle: Pubkey| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ResolveMarket {
+            oracle,
+            config: config_pda,
+            market: market_pda,
+            winning_outcome_pool: pool_pda,
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ResolveMarket {
+            args: ResolveMarketArgs {
+                winning_outcome_id: outcome_id,
+                payload_hash,
+                additional_winning_outcome_ids: vec![],
+                result_payload: None,
+            },
+        }
+        .data(),
+    };
+
+    // UnauthorizedOracle is the second custom error (6001).
+    let err = send(&mut ctx, resolve_ix(oracle_a.pubkey()), &oracle_a)
+        .await
+        .unwrap_err();
+    let msg = format!("{err:?}");
+    assert!(
+        msg.contains("Custom(6001)"),
+        "expected UnauthorizedOracle (6001), got {msg}"
+    );
+
+    send(&mut ctx, resolve_ix(oracle_b.pubkey()), &oracle_b)
+        .await
+        .unwrap();
+
+    let acct = ctx
+        .banks_client
+        .get_account(market_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(m.status, MarketStatus::Resolved);
+    assert_eq!(m.resolved_outcome, Some(outcome_id));
+}
@@ -0,0 +1,235 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical};
+
+
+fn set_paused_ix(authority: &Pubkey, config: &Pubkey, paused: bool) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SetPaused {
+            authority: *authority,
+            config: *config,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SetPaused {
+            args: SetPausedArgs { paused },
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn set_paused_halts_betting_and_market_creation_until_unpaused() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &usdc_mint.pubkey(), &user.pubkey()).await;
+    mint_to(&mut ctx, &usdc_mint.pubkey(), &authority, &user_usdc.pubkey(), 500_000).await;
+
+    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &pitstop::id());
+
+    // initialize
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // open market with a single outcome
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let market_type = 0u8;
+    let rules_version = 1u16;
+
+    let create_market_ix = |event_id: [u8; 32]| {
+        let market_id = canonical::market_id(event_id, market_type, rules_version);
+        let (market_pda, _) =
+            Pubkey::find_program_address(&[b"market", market_id.as_ref()], &pitstop::id());
+        let vault_ata = spl_associated_token_account::get_associated_token_address(
+            &market_pda,
+            &usdc_mint.pubkey(),
+        );
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::CreateMarket {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                vault: vault_ata,
+                market_tombstone: pitstop::pda::market_tombstone_address(&market_pda).0,
+                usdc_mint: usdc_mint.pubkey(),
+                collateral_allowlist: None,
+                token_program: spl_token::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::CreateMarket {
+                args: CreateMarketArgs {
+                    market_id,
+                    event_id,
+                    lock_timestamp,
+                    max_outcomes: 1,
+                    market_type,
+                    rules_version,
+                    max_total_pool_per_market: None,
+                    max_bet_per_user_per_market: None,
+                },
+            }
+            .data(),
+        };
+        (ix, market_pda, vault_ata)
+    };
+
+    let (ix, market_pda, vault_ata) = create_market_ix([7u8; 32]);
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let outcome_id = 0u8;
+    let (pool_pda, _) = Pubkey::find_program_address(
+        &[b"outcome", market_pda.as_ref(), &[outcome_id]],
+        &pitstop::id(),
+    );
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddOutcome {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            outcome_pool: pool_pda,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddOutcome {
+            args: AddOutcomeArgs { outcome_id },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let (pos_pda, _) = Pubkey::find_program_address(
+        &[b"position", market_pda.as_ref(), user.pubkey().as_ref(), &[outcome_id]],
+        &pitstop::id(),
+    );
+    let place_bet_ix = |amount: u64| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::PlaceBet {
+            user: user.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            outcome_pool: pool_pda,
+            position: pos_pda,
+            user_stats: Pubkey::find_program_address(
+                &[USER_STATS_SEED, market_pda.as_ref(), user.pubkey().as_ref()],
+                &pitstop::id(),
+            )
+            .0,
+            user_usdc: user_usdc.pubkey(),
+            vault: vault_ata,
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::PlaceBet {
+            args: PlaceBetArgs {
+                outcome_id,
+                amount,
+                max_outcome_share_bps: None,
+                min_implied_payout: None,
+            },
+        }
+        .data(),
+    };
+
+    // pause
+    send(&mut ctx, set_paused_ix(&authority.pubkey(), &config_pda, true), &authority)
+        .await
+        .unwrap();
+    let acct = ctx.banks_client.get_account(config_pda).await.unwrap().unwrap();
+    let cfg: Config = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert!(cfg.paused);
+
+    // ProtocolPaused is the third custom error (6002).
+    let err = send(&mut ctx, place_bet_ix(10_000), &user).await.unwrap_err();
+    let msg = format!("{err:?}");
+    assert!(msg.contains("Custom(6002)"), "expected ProtocolPaused (6002), got {msg}");
+
+    let (ix, _, _) = create_market_ix([8u8; 32]);
+    let err = send(&mut ctx, ix, &authority).await.unwrap_err();
+    let msg = format!("{err:?}");
+    assert!(msg.contains("Custom(6002)"), "expected ProtocolPaused (6002), got {msg}");
+
+    // only authority can unpause
+    let err = send(&mut ctx, set_paused_ix(&user.pubkey(), &config_pda, false), &user)
+        .await
+        .unwrap_err();
+    let msg = format!("{err:?}");
+    assert!(msg.contains("Custom(6000)"), "expected Unauthorized (6000), got {msg}");
+
+    send(&mut ctx, set_paused_ix(&authority.pubkey(), &config_pda, false), &authority)
+        .await
+        .unwrap();
+
+    // betting resumes
+    send(&mut ctx, place_bet_ix(10_000), &user).await.unwrap();
+    let va = ctx.banks_client.get_account(vault_ata).await.unwrap().unwrap();
+    let vstate = spl_token::state::Account::unpack(&va.data).unwrap();
+    assert_eq!(vstate.amount, 10_000);
+}
@@ -0,0 +1,251 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+fn fields(payload: &BTreeMap<&str, String>) -> Vec<PayloadField> {
+    payload
+        .iter()
+        .map(|(k, v)| PayloadField {
+            key: k.to_string(),
+            value: v.clone(),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn oracle_result_payload_is_hashed_on_chain_and_bound_to_event_id() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let oracle = Keypair::new();
+    for kp in [&authority, &oracle] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: oracle.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Market for a real descriptor so the payload can reference its event_id.
+    let descriptor = BTreeMap::from([
+        ("round", "05"),
+        ("season", "2026"),
+        ("session", "race"),
+        ("sport", "f1"),
+    ]);
+    let (event_id, market_id) = canonical::derive_ids(&descriptor, 0, 1);
+    let (market_pda, _) = pda::market_address(&market_id);
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            vault: spl_associated_token_account::get_associated_token_address(
+                &market_pda,
+                &usdc_mint.pubkey(),
+            ),
+            market_tombstone: pda::market_tombstone_address(&market_pda).0,
+            usdc_mint: usdc_mint.pubkey(),
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 1,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let (pool_pda, _) = pda::outcome_pool_address(&market_pda, 0);
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddOutcome {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            outcome_pool: pool_pda,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddOutcome {
+            args: AddOutcomeArgs { outcome_id: 0 },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    loop {
+        let c: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+        if c.unix_timestamp >= lock_timestamp {
+            break;
+        }
+        let slot = ctx.banks_client.get_root_slot().await.unwrap() + 10;
+        ctx.warp_to_slot(slot).unwrap();
+    }
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::LockMarket {
+            caller: authority.pubkey(),
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::LockMarket {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let resolve_ix = |payload_hash: [u8; 32], result_payload: Vec<PayloadField>| {
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::ResolveMarket {
+                oracle: oracle.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                winning_outcome_pool: pool_pda,
+                instructions_sysvar: None,
+                oracle_committee: None,
+                resolution_votes: None,
+                votes_payer: None,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::ResolveMarket {
+                args: ResolveMarketArgs {
+                    winning_outcome_id: 0,
+                    payload_hash,
+                    additional_winning_outcome_ids: vec![],
+                    result_payload: Some(result_payload),
+                },
+            }
+            .data(),
+        }
+    };
+
+    let payload = BTreeMap::from([
+        ("event_id", canonical::hex(&event_id)),
+        ("source", "fia_classification_v1".to_string()),
+        ("winner", "car_44".to_string()),
+        (
+            canonical::PAYLOAD_WINNING_OUTCOMES_KEY,
+            canonical::winning_outcomes_field(&[0]),
+        ),
+    ]);
+    let payload_hash = canonical::payload_hash(&payload);
+
+    // The payload must name the outcome being resolved.
+    let mut other_winner = payload.clone();
+    other_winner.insert(
+        canonical::PAYLOAD_WINNING_OUTCOMES_KEY,
+        canonical::winning_outcomes_field(&[1]),
+    );
+    let ix = resolve_ix(canonical::payload_hash(&other_winner), fields(&other_winner));
+    let err = send(&mut ctx, ix, &oracle).await.unwrap_err();
+    assert_custom(err, 6066, "PayloadOutcomeMismatch");
+
+    // A payload for another event is rejected even when its hash is self-consistent.
+    let mut foreign = payload.clone();
+    foreign.insert("event_id", canonical::hex(&[7u8; 32]));
+    let ix = resolve_ix(canonical::payload_hash(&foreign), fields(&foreign));
+    let err = send(&mut ctx, ix, &oracle).await.unwrap_err();
+    assert_custom(err, 6050, "PayloadEventMismatch");
+
+    // The hash must be the one the payload canonicalizes to.
+    let ix = resolve_ix([0xcdu8; 32], fields(&payload));
+    let err = send(&mut ctx, ix, &oracle).await.unwrap_err();
+    assert_custom(err, 6051, "PayloadHashMismatch");
+
+    let mut duplicated = fields(&payload);
+    duplicated.push(PayloadField {
+        key: "winner".to_string(),
+        value: "car_1".to_string(),
+    });
+    let err = send(&mut ctx, resolve_ix(payload_hash, duplicated), &oracle)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6049, "InvalidResolutionPayload");
+
+    // Field order is irrelevant: the program canonicalizes before hashing.
+    let mut reversed = fields(&payload);
+    reversed.reverse();
+    send(&mut ctx, resolve_ix(payload_hash, reversed), &oracle)
+        .await
+        .unwrap();
+
+    let acct = ctx
+        .banks_client
+        .get_account(market_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(m.status, MarketStatus::Resolved);
+    assert_eq!(m.resolution_payload_hash, payload_hash);
+}
@@ -0,0 +1,368 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+use solana_program_runtime::compute_budget::ComputeBudget;
+
+
+/// Derivations `find_program_address` performs for `seeds`: one
+/// `create_program_address` per bump tried, counting down from 255.
+fn find_program_address_attempts(seeds: &[&[u8]]) -> u64 {
+    let mut attempts = 0;
+    for bump in (0..=u8::MAX).rev() {
+        attempts += 1;
+        let bump = [bump];
+        let mut with_bump = seeds.to_vec();
+        with_bump.push(&bump);
+        if Pubkey::create_program_address(&with_bump, &pitstop::id()).is_ok() {
+            return attempts;
+        }
+    }
+    unreachable!("no viable bump")
+}
+
+async fn account_state<T: AccountDeserialize>(ctx: &mut ProgramTestContext, address: Pubkey) -> T {
+    let acct = ctx.banks_client.get_account(address).await.unwrap().unwrap();
+    T::try_deserialize(&mut acct.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn claim_paths_sign_with_stored_bumps() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &usdc_mint.pubkey(), &user.pubkey()).await;
+    mint_to(&mut ctx, &usdc_mint.pubkey(), &authority, &user_usdc.pubkey(), 500_000).await;
+
+    let (config_pda, _) = pda::config_address();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Two single-outcome markets with one bet each: one is resolved, one voided.
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let market_type = 0u8;
+    let rules_version = 1u16;
+    let outcome_id = 0u8;
+
+    let mut markets = Vec::new();
+    for event_id in [[11u8; 32], [12u8; 32]] {
+        let market_id = canonical::market_id(event_id, market_type, rules_version);
+        let (market_pda, market_bump) = pda::market_address(&market_id);
+        let vault_ata = spl_associated_token_account::get_associated_token_address(
+            &market_pda,
+            &usdc_mint.pubkey(),
+        );
+        let (pool_pda, pool_bump) = pda::outcome_pool_address(&market_pda, outcome_id);
+        let (pos_pda, pos_bump) = pda::position_address(&market_pda, &user.pubkey(), outcome_id);
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::CreateMarket {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                vault: vault_ata,
+                market_tombstone: pda::market_tombstone_address(&market_pda).0,
+                usdc_mint: usdc_mint.pubkey(),
+                collateral_allowlist: None,
+                token_program: spl_token::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::CreateMarket {
+                args: CreateMarketArgs {
+                    market_id,
+                    event_id,
+                    lock_timestamp,
+                    max_outcomes: 1,
+                    market_type,
+                    rules_version,
+                    max_total_pool_per_market: None,
+                    max_bet_per_user_per_market: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                outcome_pool: pool_pda,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs { outcome_id },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::FinalizeSeeding {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::FinalizeSeeding {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::PlaceBet {
+                user: user.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                outcome_pool: pool_pda,
+                position: pos_pda,
+                user_stats: pda::user_market_stats_address(&market_pda, &user.pubkey()).0,
+                user_usdc: user_usdc.pubkey(),
+                vault: vault_ata,
+                usdc_mint: usdc_mint.pubkey(),
+                token_program: spl_token::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::PlaceBet {
+                args: PlaceBetArgs {
+                    outcome_id,
+                    amount: 50_000,
+                    max_outcome_share_bps: None,
+                    min_implied_payout: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &user).await.unwrap();
+
+        // canonical bumps are persisted at account creation
+        let acct = ctx.banks_client.get_account(market_pda).await.unwrap().unwrap();
+        let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+        assert_eq!(m.bump, market_bump);
+        let acct = ctx.banks_client.get_account(pool_pda).await.unwrap().unwrap();
+        let p: OutcomePool = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+        assert_eq!(p.bump, pool_bump);
+        let acct = ctx.banks_client.get_account(pos_pda).await.unwrap().unwrap();
+        let pos: Position = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+        assert_eq!(pos.bump, pos_bump);
+
+        markets.push((market_pda, vault_ata, pool_pda, pos_pda));
+    }
+
+    // warp until lock time, then lock both
+    loop {
+        let c: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+        if c.unix_timestamp >= lock_timestamp {
+            break;
+        }
+        let slot = ctx.banks_client.get_root_slot().await.unwrap() + 10;
+        ctx.warp_to_slot(slot).unwrap();
+    }
+    for (market_pda, ..) in &markets {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::LockMarket {
+                caller: authority.pubkey(),
+                market: *market_pda,
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::LockMarket {}.data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let (resolved, resolved_vault, resolved_pool, resolved_pos) = markets[0];
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ResolveMarket {
+            oracle: authority.pubkey(),
+            config: config_pda,
+            market: resolved,
+            winning_outcome_pool: resolved_pool,
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ResolveMarket {
+            args: ResolveMarketArgs {
+                winning_outcome_id: outcome_id,
+                payload_hash: [1u8; 32],
+                additional_winning_outcome_ids: vec![],
+                result_payload: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let (voided, voided_vault, _voided_pool, voided_pos) = markets[1];
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::VoidMarket {
+            oracle: authority.pubkey(),
+            config: config_pda,
+            market: voided,
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::VoidMarket {
+            args: VoidMarketArgs {
+                payload_hash: [2u8; 32],
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let claim_resolved_ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClaimResolved {
+            user: user.pubkey(),
+            config: config_pda,
+            market: resolved,
+            position: resolved_pos,
+            outcome_pool: resolved_pool,
+            user_usdc: user_usdc.pubkey(),
+            vault: resolved_vault,
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClaimResolved {
+            args: ClaimResolvedArgs {
+                outcome_id,
+                close_position: false,
+            },
+        }
+        .data(),
+    };
+    let claim_voided_ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClaimVoided {
+            user: user.pubkey(),
+            config: config_pda,
+            market: voided,
+            position: voided_pos,
+            user_usdc: user_usdc.pubkey(),
+            vault: voided_vault,
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClaimVoided {
+            args: ClaimVoidedArgs {
+                outcome_id,
+                close_position: false,
+            },
+        }
+        .data(),
+    };
+
+    // The native `processor!` harness does not meter this program's own work,
+    // so compare derivation cost directly: each claim re-derives the market,
+    // pool and position from their stored bumps with one `create_program_address`
+    // apiece, where the `find_program_address` path pays one per bump tried.
+    let units_per_derivation = ComputeBudget::default().create_program_address_units;
+    for (market, pos) in [(resolved, resolved_pos), (voided, voided_pos)] {
+        let m: Market = account_state(&mut ctx, market).await;
+        let (pool, _) = pda::outcome_pool_address(&market, outcome_id);
+        let p: OutcomePool = account_state(&mut ctx, pool).await;
+        let position: Position = account_state(&mut ctx, pos).await;
+
+        let market_bump = [m.bump];
+        let signer_seeds = pda::market_signer_seeds(&m.market_id, &market_bump);
+        assert_eq!(Pubkey::create_program_address(&signer_seeds, &pitstop::id()), Ok(market));
+        assert_eq!(pda::outcome_pool_address_with_bump(&market, outcome_id, p.bump), Some(pool));
+        assert_eq!(
+            pda::position_address_with_bump(&market, &user.pubkey(), outcome_id, position.bump),
+            Some(pos)
+        );
+        let stored_cu = 3 * units_per_derivation;
+
+        let find_attempts = find_program_address_attempts(&[pda::MARKET_SEED, &m.market_id])
+            + find_program_address_attempts(&[pda::OUTCOME_SEED, market.as_ref(), &[outcome_id]])
+            + find_program_address_attempts(&[
+                pda::POSITION_SEED,
+                market.as_ref(),
+                user.pubkey().as_ref(),
+                &[outcome_id],
+            ]);
+        let find_cu = find_attempts * units_per_derivation;
+        // `find_program_address` lands on the stored (canonical) bump after
+        // `256 - bump` tries, so the saving is the bumps it walks past.
+        let skipped: u64 = [m.bump, p.bump, position.bump]
+            .iter()
+            .map(|bump| u64::from(u8::MAX - bump))
+            .sum();
+        assert_eq!(find_cu - stored_cu, skipped * units_per_derivation);
+        assert!(stored_cu <= find_cu);
+    }
+
+    send(&mut ctx, claim_resolved_ix, &user).await.unwrap();
+    send(&mut ctx, claim_voided_ix, &user).await.unwrap();
+
+    let ua = ctx.banks_client.get_account(user_usdc.pubkey()).await.unwrap().unwrap();
+    let ustate = spl_token::state::Account::unpack(&ua.data).unwrap();
+    assert_eq!(ustate.amount, 500_000);
+}
@@ -0,0 +1,290 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, constants, pda};
+
+#[tokio::test]
+async fn podium_market_resolves_three_winners_and_pays_against_combined_pool() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    fund(&mut ctx, &authority, 2_000_000_000).await;
+    fund(&mut ctx, &treasury_authority, 2_000_000_000).await;
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let event_id = [31u8; 32];
+    let create_ix = |market_id: [u8; 32], rules_version: u16| {
+        let (market_pda, _) = pda::market_address(&market_id);
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::CreateMarket {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                vault: spl_associated_token_account::get_associated_token_address(
+                    &market_pda,
+                    &usdc_mint.pubkey(),
+                ),
+                market_tombstone: pda::market_tombstone_address(&market_pda).0,
+                usdc_mint: usdc_mint.pubkey(),
+                collateral_allowlist: None,
+                token_program: spl_token::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::CreateMarket {
+                args: CreateMarketArgs {
+                    market_id,
+                    event_id,
+                    lock_timestamp,
+                    max_outcomes: 4,
+                    market_type: constants::MARKET_TYPE_PODIUM,
+                    rules_version,
+                    max_total_pool_per_market: None,
+                    max_bet_per_user_per_market: None,
+                },
+            }
+            .data(),
+        }
+    };
+
+    // podium markets only exist under rules_version 2
+    let bad_id = canonical::market_id(event_id, constants::MARKET_TYPE_PODIUM, 1);
+    let err = send(&mut ctx, create_ix(bad_id, 1), &authority).await.unwrap_err();
+    assert_custom(err, 6035, "UnsupportedRulesVersion");
+
+    let market_id = canonical::market_id(
+        event_id,
+        constants::MARKET_TYPE_PODIUM,
+        constants::RULES_VERSION_PODIUM,
+    );
+    send(&mut ctx, create_ix(market_id, constants::RULES_VERSION_PODIUM), &authority)
+        .await
+        .unwrap();
+    let (market_pda, _) = pda::market_address(&market_id);
+    let vault_ata =
+        spl_associated_token_account::get_associated_token_address(&market_pda, &usdc_mint.pubkey());
+
+    let pools: Vec<Pubkey> = (0..4u8)
+        .map(|id| pda::outcome_pool_address(&market_pda, id).0)
+        .collect();
+    for (outcome_id, pool) in pools.iter().enumerate() {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                outcome_pool: *pool,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs {
+                    outcome_id: outcome_id as u8,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // one bettor per outcome; pools [30k, 10k, 20k, 40k]
+    let stakes = [30_000u64, 10_000, 20_000, 40_000];
+    let mut bettors = Vec::new();
+    for (outcome_id, amount) in stakes.iter().enumerate() {
+        let outcome_id = outcome_id as u8;
+        let user = Keypair::new();
+        fund(&mut ctx, &user, 1_000_000_000).await;
+        let user_usdc = Keypair::new();
+        create_token_account(&mut ctx, &user_usdc, &usdc_mint.pubkey(), &user.pubkey()).await;
+        mint_to(&mut ctx, &usdc_mint.pubkey(), &authority, &user_usdc.pubkey(), *amount).await;
+        let (pos_pda, _) = pda::position_address(&market_pda, &user.pubkey(), outcome_id);
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::PlaceBet {
+                user: user.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                outcome_pool: pools[outcome_id as usize],
+                position: pos_pda,
+                user_stats: pda::user_market_stats_address(&market_pda, &user.pubkey()).0,
+                user_usdc: user_usdc.pubkey(),
+                vault: vault_ata,
+                usdc_mint: usdc_mint.pubkey(),
+                token_program: spl_token::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::PlaceBet {
+                args: PlaceBetArgs {
+                    outcome_id,
+                    amount: *amount,
+                    max_outcome_share_bps: None,
+                    min_implied_payout: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &user).await.unwrap();
+        bettors.push((user, user_usdc, pos_pda, outcome_id));
+    }
+
+    warp_past(&mut ctx, lock_timestamp).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::LockMarket {
+            caller: authority.pubkey(),
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::LockMarket {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // podium [1, 3, 0]; places 2 and 3 ride in remaining accounts, in order
+    let resolve_ix = |additional: Vec<u8>, remaining: Vec<Pubkey>, hash: u8| {
+        let mut accounts = pitstop::accounts::ResolveMarket {
+            oracle: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            winning_outcome_pool: pools[1],
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.extend(remaining.into_iter().map(|k| AccountMeta::new_readonly(k, false)));
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts,
+            data: pitstop::instruction::ResolveMarket {
+                args: ResolveMarketArgs {
+                    winning_outcome_id: 1,
+                    payload_hash: [hash; 32],
+                    additional_winning_outcome_ids: additional,
+                    result_payload: None,
+                },
+            }
+            .data(),
+        }
+    };
+
+    // a single winner is not a podium
+    let err = send(&mut ctx, resolve_ix(vec![], vec![], 1), &authority).await.unwrap_err();
+    assert_custom(err, 6023, "InvalidOutcomeId");
+
+    // pools out of order with the ids
+    let err = send(&mut ctx, resolve_ix(vec![3, 0], vec![pools[0], pools[3]], 2), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6027, "OutcomeMismatch");
+
+    send(&mut ctx, resolve_ix(vec![3, 0], vec![pools[3], pools[0]], 3), &authority)
+        .await
+        .unwrap();
+
+    let acct = ctx.banks_client.get_account(market_pda).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(m.winning_outcome_count, 3);
+    assert_eq!(m.winning_outcomes, [1, 3, 0]);
+    assert_eq!(m.winner_pool, 80_000);
+    assert_eq!(m.resolved_outcome, Some(1));
+
+    // prize 100k over winner pool 80k: every podium stake is paid at 1.25x
+    let expected = [37_500u64, 12_500, 0, 50_000];
+    for (user, user_usdc, pos_pda, outcome_id) in &bettors {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::ClaimResolved {
+                user: user.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                position: *pos_pda,
+                outcome_pool: pools[*outcome_id as usize],
+                user_usdc: user_usdc.pubkey(),
+                vault: vault_ata,
+                usdc_mint: usdc_mint.pubkey(),
+                token_program: spl_token::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::ClaimResolved {
+                args: ClaimResolvedArgs {
+                    outcome_id: *outcome_id,
+                    close_position: false,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, user).await.unwrap();
+
+        let ua = ctx.banks_client.get_account(user_usdc.pubkey()).await.unwrap().unwrap();
+        let ustate = spl_token::state::Account::unpack(&ua.data).unwrap();
+        assert_eq!(ustate.amount, expected[*outcome_id as usize], "outcome {outcome_id}");
+    }
+
+    let va = ctx.banks_client.get_account(vault_ata).await.unwrap().unwrap();
+    let vstate = spl_token::state::Account::unpack(&va.data).unwrap();
+    assert_eq!(vstate.amount, 0);
+}
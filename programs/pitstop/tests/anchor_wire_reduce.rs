@@ -0,0 +1,250 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+async fn pool_amount(ctx: &mut ProgramTestContext, pool: Pubkey) -> u64 {
+    let acct = ctx.banks_client.get_account(pool).await.unwrap().unwrap();
+    let pool: OutcomePool = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    pool.pool_amount
+}
+
+#[tokio::test]
+async fn open_positions_reduce_with_early_exit_fee_until_lock() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let mint = usdc_mint.pubkey();
+    let treasury = Keypair::new();
+    create_token_account(&mut ctx, &treasury, &mint, &treasury_authority.pubkey()).await;
+    let other_treasury = Keypair::new();
+    create_token_account(&mut ctx, &other_treasury, &mint, &treasury_authority.pubkey()).await;
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &mint, &user.pubkey()).await;
+    mint_to(&mut ctx, &mint, &authority, &user_usdc.pubkey(), 10_000).await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: mint,
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::UpdateConfig {
+            authority: authority.pubkey(),
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::UpdateConfig {
+            args: UpdateConfigArgs {
+                fee_bps: None,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+                claim_window_secs: None,
+                dispute_window_secs: None,
+                resolution_deadline_secs: None,
+                early_exit_fee_bps: Some(250),
+                payout_keeper: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 100;
+    let event_id = [41u8; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market, _) = pda::market_address(&market_id);
+    let vault = spl_associated_token_account::get_associated_token_address(&market, &mint);
+    let (pool, _) = pda::outcome_pool_address(&market, 0);
+    let (position, _) = pda::position_address(&market, &user.pubkey(), 0);
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market).0,
+            usdc_mint: mint,
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 1,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddOutcome {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+            outcome_pool: pool,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddOutcome {
+            args: AddOutcomeArgs { outcome_id: 0 },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::PlaceBet {
+            user: user.pubkey(),
+            config: config_pda,
+            market,
+            outcome_pool: pool,
+            position,
+            user_stats: pda::user_market_stats_address(&market, &user.pubkey()).0,
+            user_usdc: user_usdc.pubkey(),
+            vault,
+            usdc_mint: mint,
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::PlaceBet {
+            args: PlaceBetArgs {
+                outcome_id: 0,
+                amount: 10_000,
+                max_outcome_share_bps: None,
+                min_implied_payout: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &user).await.unwrap();
+
+    let reduce_ix = |amount: u64, treasury: Pubkey| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ReducePosition {
+            user: user.pubkey(),
+            config: config_pda,
+            market,
+            outcome_pool: pool,
+            position,
+            user_stats: pda::user_market_stats_address(&market, &user.pubkey()).0,
+            user_usdc: user_usdc.pubkey(),
+            vault,
+            treasury,
+            usdc_mint: mint,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ReducePosition {
+            args: ReducePositionArgs {
+                outcome_id: 0,
+                amount,
+            },
+        }
+        .data(),
+    };
+
+    let err = send(&mut ctx, reduce_ix(10_001, treasury.pubkey()), &user)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6061, "ReductionExceedsPosition");
+
+    // The fee cannot be routed anywhere but the configured treasury owner.
+    let err = send(&mut ctx, reduce_ix(4_000, user_usdc.pubkey()), &user)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6006, "InvalidTreasuryOwner");
+    // Same owner and mint is not enough: it has to be this market's treasury.
+    let err = send(&mut ctx, reduce_ix(4_000, other_treasury.pubkey()), &user)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6006, "InvalidTreasuryOwner");
+
+    // fee = ceil(4_000 * 250 / 10_000) = 100; the vault still matches total_pool.
+    send(&mut ctx, reduce_ix(4_000, treasury.pubkey()), &user)
+        .await
+        .unwrap();
+    assert_eq!(token_amount(&mut ctx, user_usdc.pubkey()).await, 3_900);
+    assert_eq!(token_amount(&mut ctx, treasury.pubkey()).await, 100);
+    assert_eq!(token_amount(&mut ctx, vault).await, 6_000);
+    assert_eq!(market_state(&mut ctx, market).await.total_pool, 6_000);
+    assert_eq!(pool_amount(&mut ctx, pool).await, 6_000);
+    assert_eq!(position_state(&mut ctx, position).await.amount, 6_000);
+
+    // The fee rounds up, so a 1-unit exit pays the whole unit as fee.
+    send(&mut ctx, reduce_ix(1, treasury.pubkey()), &user)
+        .await
+        .unwrap();
+    assert_eq!(token_amount(&mut ctx, user_usdc.pubkey()).await, 3_900);
+    assert_eq!(token_amount(&mut ctx, treasury.pubkey()).await, 101);
+    assert_eq!(token_amount(&mut ctx, vault).await, 5_999);
+    assert_eq!(market_state(&mut ctx, market).await.total_pool, 5_999);
+
+    // Exits close with betting, even before anyone locks the market.
+    warp_past(&mut ctx, lock_timestamp).await;
+    let err = send(&mut ctx, reduce_ix(1_000, treasury.pubkey()), &user)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6011, "BettingClosed");
+}
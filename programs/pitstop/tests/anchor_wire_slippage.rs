@@ -0,0 +1,196 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+async fn pool_amount(ctx: &mut ProgramTestContext, pool: Pubkey) -> u64 {
+    let acct = ctx.banks_client.get_account(pool).await.unwrap().unwrap();
+    let pool: OutcomePool = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    pool.pool_amount
+}
+
+#[tokio::test]
+async fn place_bet_honors_quoted_share_and_payout() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let whale = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &whale, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let mint = usdc_mint.pubkey();
+    let treasury = Keypair::new();
+    create_token_account(&mut ctx, &treasury, &mint, &treasury_authority.pubkey()).await;
+    let whale_usdc = Keypair::new();
+    create_token_account(&mut ctx, &whale_usdc, &mint, &whale.pubkey()).await;
+    mint_to(&mut ctx, &mint, &authority, &whale_usdc.pubkey(), 10_000).await;
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &mint, &user.pubkey()).await;
+    mint_to(&mut ctx, &mint, &authority, &user_usdc.pubkey(), 10_000).await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: mint,
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let event_id = [43u8; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market, _) = pda::market_address(&market_id);
+    let vault = spl_associated_token_account::get_associated_token_address(&market, &mint);
+    let pools: Vec<Pubkey> = (0..2u8)
+        .map(|id| pda::outcome_pool_address(&market, id).0)
+        .collect();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market).0,
+            usdc_mint: mint,
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp: clock.unix_timestamp + 1_000,
+                max_outcomes: 2,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    for (outcome_id, pool) in pools.iter().enumerate() {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market,
+                outcome_pool: *pool,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs {
+                    outcome_id: outcome_id as u8,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let bet_ix = |bettor: &Keypair,
+                  bettor_usdc: Pubkey,
+                  outcome_id: u8,
+                  amount: u64,
+                  max_outcome_share_bps: Option<u16>,
+                  min_implied_payout: Option<u64>| {
+        solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::PlaceBet {
+                user: bettor.pubkey(),
+                config: config_pda,
+                market,
+                outcome_pool: pools[outcome_id as usize],
+                position: pda::position_address(&market, &bettor.pubkey(), outcome_id).0,
+                user_stats: pda::user_market_stats_address(&market, &bettor.pubkey()).0,
+                user_usdc: bettor_usdc,
+                vault,
+                usdc_mint: mint,
+                token_program: spl_token::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::PlaceBet {
+                args: PlaceBetArgs {
+                    outcome_id,
+                    amount,
+                    max_outcome_share_bps,
+                    min_implied_payout,
+                },
+            }
+            .data(),
+        }
+    };
+
+    for outcome_id in 0..2u8 {
+        let ix = bet_ix(&whale, whale_usdc.pubkey(), outcome_id, 1_000, None, None);
+        send(&mut ctx, ix, &whale).await.unwrap();
+    }
+
+    // Quote for 1_000 on outcome 0: share 2_000 / 3_000 (under 6_667 bps),
+    // payout 1_000 * 3_000 / 2_000 = 1_500. The whale lands another 1_000 on 0 first.
+    let ix = bet_ix(&whale, whale_usdc.pubkey(), 0, 1_000, None, None);
+    send(&mut ctx, ix, &whale).await.unwrap();
+
+    let ix = bet_ix(&user, user_usdc.pubkey(), 0, 1_000, Some(6_667), None);
+    let err = send(&mut ctx, ix, &user).await.unwrap_err();
+    assert_custom(err, 6063, "SlippageExceeded");
+
+    let ix = bet_ix(&user, user_usdc.pubkey(), 0, 1_000, None, Some(1_500));
+    let err = send(&mut ctx, ix, &user).await.unwrap_err();
+    assert_custom(err, 6063, "SlippageExceeded");
+    assert_eq!(pool_amount(&mut ctx, pools[0]).await, 2_000);
+
+    // Re-quoted at the moved pools: share 3_000 / 4_000 = 7_500 bps,
+    // payout floor(1_000 * 4_000 / 3_000) = 1_333.
+    let ix = bet_ix(&user, user_usdc.pubkey(), 0, 1_000, Some(7_500), Some(1_333));
+    send(&mut ctx, ix, &user).await.unwrap();
+    assert_eq!(pool_amount(&mut ctx, pools[0]).await, 3_000);
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, pda};
+
+fn void_stale_ix(caller: &Keypair, m: &TestMarket) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::VoidStaleMarket {
+            caller: caller.pubkey(),
+            market: m.market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::VoidStaleMarket {}.data(),
+    }
+}
+
+fn claim_voided_ix(m: &TestMarket, bettor: &Bettor, usdc_mint: &Pubkey) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClaimVoided {
+            user: bettor.user.pubkey(),
+            config: pda::config_address().0,
+            market: m.market,
+            position: bettor.position,
+            user_usdc: bettor.user_usdc,
+            vault: m.vault,
+            usdc_mint: *usdc_mint,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClaimVoided {
+            args: ClaimVoidedArgs {
+                outcome_id: bettor.outcome_id,
+                close_position: false,
+            },
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn stale_locked_market_is_voidable_by_anyone_after_deadline() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let oracle = Keypair::new();
+    let keeper = Keypair::new();
+    for kp in [&authority, &treasury_authority, &oracle, &keeper] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: oracle.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Deadline disabled (the default): a stale market cannot be voided by a keeper.
+    let undated = locked_market(&mut ctx, &authority, &usdc_mint.pubkey(), 91).await;
+    let err = send(&mut ctx, void_stale_ix(&keeper, &undated), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6059, "ResolutionDeadlineNotReached");
+
+    let resolution_deadline_ix = |secs: i64| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::UpdateConfig {
+            authority: authority.pubkey(),
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::UpdateConfig {
+            args: UpdateConfigArgs {
+                fee_bps: None,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+                claim_window_secs: None,
+                dispute_window_secs: None,
+                resolution_deadline_secs: Some(secs),
+                early_exit_fee_bps: None,
+                payout_keeper: None,
+            },
+        }
+        .data(),
+    };
+    let err = send(&mut ctx, resolution_deadline_ix(-1), &authority)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6058, "InvalidResolutionDeadline");
+    send(&mut ctx, resolution_deadline_ix(3600), &authority)
+        .await
+        .unwrap();
+
+    // Inside the deadline the market still belongs to the oracle.
+    let long = locked_market(&mut ctx, &authority, &usdc_mint.pubkey(), 92).await;
+    assert_eq!(market_state(&mut ctx, long.market).await.resolution_deadline_secs, 3600);
+    let err = send(&mut ctx, void_stale_ix(&keeper, &long), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6059, "ResolutionDeadlineNotReached");
+
+    send(&mut ctx, resolution_deadline_ix(30), &authority)
+        .await
+        .unwrap();
+    let stale = locked_market(&mut ctx, &authority, &usdc_mint.pubkey(), 93).await;
+    let lock_timestamp = market_state(&mut ctx, stale.market).await.lock_timestamp;
+    warp_past(&mut ctx, lock_timestamp + 30).await;
+
+    // The deadline is frozen at create_market: shortening or enabling it later does
+    // not reach markets that already exist.
+    for m in [&undated, &long] {
+        let err = send(&mut ctx, void_stale_ix(&keeper, m), &keeper)
+            .await
+            .unwrap_err();
+        assert_custom(err, 6059, "ResolutionDeadlineNotReached");
+    }
+
+    send(&mut ctx, void_stale_ix(&keeper, &stale), &keeper)
+        .await
+        .unwrap();
+    let m = market_state(&mut ctx, stale.market).await;
+    assert_eq!(m.status, MarketStatus::Voided);
+    assert_eq!(m.resolved_outcome, None);
+    assert_eq!(m.resolution_payload_hash, [0u8; 32]);
+
+    // The late oracle can no longer resolve, and a second stale void is rejected.
+    let err = send(&mut ctx, resolve_ix(&oracle, &stale, 0), &oracle)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6016, "MarketNotLocked");
+    let err = send(&mut ctx, void_stale_ix(&keeper, &stale), &keeper)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6016, "MarketNotLocked");
+
+    // Every bettor gets their principal back through claim_voided.
+    for bettor in &stale.bettors {
+        send(
+            &mut ctx,
+            claim_voided_ix(&stale, bettor, &usdc_mint.pubkey()),
+            &bettor.user,
+        )
+        .await
+        .unwrap();
+        assert_eq!(token_amount(&mut ctx, bettor.user_usdc).await, 10_000);
+    }
+}
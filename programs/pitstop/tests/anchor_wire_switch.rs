@@ -0,0 +1,233 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+async fn pool_amount(ctx: &mut ProgramTestContext, pool: Pubkey) -> u64 {
+    let acct = ctx.banks_client.get_account(pool).await.unwrap().unwrap();
+    let pool: OutcomePool = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    pool.pool_amount
+}
+
+#[tokio::test]
+async fn open_positions_switch_outcomes_without_moving_tokens() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let mint = usdc_mint.pubkey();
+    let treasury = Keypair::new();
+    create_token_account(&mut ctx, &treasury, &mint, &treasury_authority.pubkey()).await;
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &mint, &user.pubkey()).await;
+    mint_to(&mut ctx, &mint, &authority, &user_usdc.pubkey(), 10_000).await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: mint,
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 7_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 100;
+    let event_id = [42u8; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market, _) = pda::market_address(&market_id);
+    let vault = spl_associated_token_account::get_associated_token_address(&market, &mint);
+    let pools: Vec<Pubkey> = (0..3u8)
+        .map(|id| pda::outcome_pool_address(&market, id).0)
+        .collect();
+    let positions: Vec<Pubkey> = (0..3u8)
+        .map(|id| pda::position_address(&market, &user.pubkey(), id).0)
+        .collect();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market).0,
+            usdc_mint: mint,
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 3,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    for (outcome_id, pool) in pools.iter().enumerate() {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market,
+                outcome_pool: *pool,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs {
+                    outcome_id: outcome_id as u8,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    for (outcome_id, amount) in [(0u8, 5_000u64), (1, 2_000)] {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::PlaceBet {
+                user: user.pubkey(),
+                config: config_pda,
+                market,
+                outcome_pool: pools[outcome_id as usize],
+                position: positions[outcome_id as usize],
+                user_stats: pda::user_market_stats_address(&market, &user.pubkey()).0,
+                user_usdc: user_usdc.pubkey(),
+                vault,
+                usdc_mint: mint,
+                token_program: spl_token::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::PlaceBet {
+                args: PlaceBetArgs {
+                    outcome_id,
+                    amount,
+                    max_outcome_share_bps: None,
+                    min_implied_payout: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &user).await.unwrap();
+    }
+
+    let switch_ix = |from: u8, to: u8, amount: u64| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SwitchOutcome {
+            user: user.pubkey(),
+            config: config_pda,
+            market,
+            from_outcome_pool: pools[from as usize],
+            to_outcome_pool: pools[to as usize],
+            from_position: positions[from as usize],
+            to_position: positions[to as usize],
+            user_stats: pda::user_market_stats_address(&market, &user.pubkey()).0,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SwitchOutcome {
+            args: SwitchOutcomeArgs {
+                from_outcome_id: from,
+                to_outcome_id: to,
+                amount,
+            },
+        }
+        .data(),
+    };
+
+    let err = send(&mut ctx, switch_ix(0, 0, 1_000), &user).await.unwrap_err();
+    assert_custom(err, 6062, "SameOutcomeSwitch");
+
+    let err = send(&mut ctx, switch_ix(0, 2, 5_001), &user).await.unwrap_err();
+    assert_custom(err, 6061, "ReductionExceedsPosition");
+
+    // The user is at the 7_000 per-user cap across outcomes; a switch keeps that total.
+    send(&mut ctx, switch_ix(0, 1, 4_000), &user).await.unwrap();
+    // Moving the rest onto outcome 2 creates that position.
+    send(&mut ctx, switch_ix(0, 2, 1_000), &user).await.unwrap();
+
+    let mut pool_amounts = Vec::new();
+    for pool in &pools {
+        pool_amounts.push(pool_amount(&mut ctx, *pool).await);
+    }
+    assert_eq!(pool_amounts, vec![0, 6_000, 1_000]);
+    let mut position_amounts = Vec::new();
+    for position in &positions {
+        position_amounts.push(position_state(&mut ctx, *position).await.amount);
+    }
+    assert_eq!(position_amounts, vec![0, 6_000, 1_000]);
+    let fresh = position_state(&mut ctx, positions[2]).await;
+    assert_eq!((fresh.user, fresh.outcome_id), (user.pubkey(), 2));
+    let acct = ctx
+        .banks_client
+        .get_account(pda::user_market_stats_address(&market, &user.pubkey()).0)
+        .await
+        .unwrap()
+        .unwrap();
+    let stats: UserMarketStats =
+        AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!((stats.total_staked, stats.outcome_ids()), (7_000, &[0, 1, 2][..]));
+
+    // No tokens moved: vault and total_pool still hold the two original bets.
+    assert_eq!(token_amount(&mut ctx, vault).await, 7_000);
+    assert_eq!(token_amount(&mut ctx, user_usdc.pubkey()).await, 3_000);
+    assert_eq!(market_state(&mut ctx, market).await.total_pool, 7_000);
+
+    warp_past(&mut ctx, lock_timestamp).await;
+    let err = send(&mut ctx, switch_ix(1, 2, 1_000), &user).await.unwrap_err();
+    assert_custom(err, 6011, "BettingClosed");
+}
@@ -0,0 +1,654 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee, transfer_hook, ExtensionType},
+};
+#[cfg(feature = "token-2022")]
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id, instruction::create_associated_token_account,
+};
+
+use common::*;
+#[cfg(feature = "token-2022")]
+use pitstop::canonical;
+use pitstop::{self, anchor_accounts::*, pda};
+
+/// Token-2022 mint (6 decimals) with at most one extension initialized.
+async fn create_mint_2022(
+    ctx: &mut ProgramTestContext,
+    mint: &Keypair,
+    mint_authority: &Pubkey,
+    extension: Option<ExtensionType>,
+) {
+    let extensions: Vec<ExtensionType> = extension.into_iter().collect();
+    let space =
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions).unwrap();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+
+    let mut ixs = vec![solana_sdk::system_instruction::create_account(
+        &ctx.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(space),
+        space as u64,
+        &spl_token_2022::id(),
+    )];
+    let program = spl_token_2022::id();
+    let mint_key = mint.pubkey();
+    match extension {
+        None => {}
+        Some(ExtensionType::TransferFeeConfig) => ixs.push(
+            transfer_fee::instruction::initialize_transfer_fee_config(
+                &program,
+                &mint_key,
+                Some(mint_authority),
+                Some(mint_authority),
+                100, // 1%
+                1_000_000,
+            )
+            .unwrap(),
+        ),
+        Some(ExtensionType::TransferHook) => ixs.push(
+            transfer_hook::instruction::initialize(
+                &program,
+                &mint_key,
+                Some(*mint_authority),
+                Some(Pubkey::new_unique()),
+            )
+            .unwrap(),
+        ),
+        Some(ExtensionType::PermanentDelegate) => ixs.push(
+            spl_token_2022::instruction::initialize_permanent_delegate(
+                &program,
+                &mint_key,
+                mint_authority,
+            )
+            .unwrap(),
+        ),
+        Some(ExtensionType::NonTransferable) => ixs.push(
+            spl_token_2022::instruction::initialize_non_transferable_mint(&program, &mint_key)
+                .unwrap(),
+        ),
+        Some(other) => panic!("unsupported test extension {other:?}"),
+    }
+    ixs.push(
+        spl_token_2022::instruction::initialize_mint(&program, &mint_key, mint_authority, None, 6)
+            .unwrap(),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &ixs,
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, mint],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+}
+
+async fn create_ata_2022(ctx: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let ix = create_associated_token_account(&ctx.payer.pubkey(), owner, mint, &spl_token_2022::id());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+    get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::id())
+}
+
+fn initialize_ix(
+    authority: &Keypair,
+    mint: Pubkey,
+    treasury: Pubkey,
+    treasury_authority: Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    let (config_pda, _) = pda::config_address();
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: mint,
+            treasury,
+            token_program: spl_token_2022::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority,
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 1,
+            },
+        }
+        .data(),
+    }
+}
+
+#[cfg(not(feature = "token-2022"))]
+#[tokio::test]
+async fn token_2022_mints_need_the_token_2022_feature() {
+    let mut ctx = program_test().start_with_context().await;
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    fund(&mut ctx, &authority, 2_000_000_000).await;
+
+    let mint = Keypair::new();
+    create_mint_2022(&mut ctx, &mint, &authority.pubkey(), None).await;
+    let treasury = create_ata_2022(&mut ctx, &mint.pubkey(), &treasury_authority.pubkey()).await;
+
+    let ix = initialize_ix(&authority, mint.pubkey(), treasury, treasury_authority.pubkey());
+    let err = send(&mut ctx, ix, &authority).await.unwrap_err();
+    assert_custom(err, 6003, "InvalidTokenProgram");
+}
+
+#[cfg(feature = "token-2022")]
+async fn token_amount(ctx: &mut ProgramTestContext, account: Pubkey) -> (u64, u64) {
+    let acct = ctx.banks_client.get_account(account).await.unwrap().unwrap();
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&acct.data).unwrap();
+    let withheld = state
+        .get_extension::<TransferFeeAmount>()
+        .map(|fee| u64::from(fee.withheld_amount))
+        .unwrap_or(0);
+    (state.base.amount, withheld)
+}
+
+#[cfg(feature = "token-2022")]
+#[tokio::test]
+async fn token_2022_mints_with_blocked_extensions_are_refused() {
+    let mut ctx = program_test().start_with_context().await;
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    fund(&mut ctx, &authority, 2_000_000_000).await;
+
+    // Extension screening runs before the treasury checks; one plain treasury serves all.
+    let plain = Keypair::new();
+    create_mint_2022(&mut ctx, &plain, &authority.pubkey(), None).await;
+    let treasury = create_ata_2022(&mut ctx, &plain.pubkey(), &treasury_authority.pubkey()).await;
+
+    for extension in [
+        ExtensionType::TransferHook,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::NonTransferable,
+    ] {
+        let mint = Keypair::new();
+        create_mint_2022(&mut ctx, &mint, &authority.pubkey(), Some(extension)).await;
+        let ix = initialize_ix(&authority, mint.pubkey(), treasury, treasury_authority.pubkey());
+        let err = send(&mut ctx, ix, &authority).await.unwrap_err();
+        assert_custom(err, 6045, "UnsupportedMintExtension");
+    }
+
+    // A plain Token-2022 mint is accepted and pinned into config.
+    let ix = initialize_ix(&authority, plain.pubkey(), treasury, treasury_authority.pubkey());
+    send(&mut ctx, ix, &authority).await.unwrap();
+    let (config_pda, _) = pda::config_address();
+    let acct = ctx.banks_client.get_account(config_pda).await.unwrap().unwrap();
+    let config: Config = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(config.token_program, spl_token_2022::id());
+}
+
+#[cfg(feature = "token-2022")]
+#[tokio::test]
+async fn transfer_fee_mint_stakes_net_amounts_and_sweeps_withheld_fees() {
+    let mut ctx = program_test().start_with_context().await;
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    fund(&mut ctx, &authority, 2_000_000_000).await;
+
+    let mint = Keypair::new();
+    create_mint_2022(
+        &mut ctx,
+        &mint,
+        &authority.pubkey(),
+        Some(ExtensionType::TransferFeeConfig),
+    )
+    .await;
+    let mint_key = mint.pubkey();
+    let treasury = create_ata_2022(&mut ctx, &mint_key, &treasury_authority.pubkey()).await;
+
+    let ix = initialize_ix(&authority, mint_key, treasury, treasury_authority.pubkey());
+    send(&mut ctx, ix, &authority).await.unwrap();
+    let (config_pda, _) = pda::config_address();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 1000;
+    let event_id = [41u8; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market_pda, _) = pda::market_address(&market_id);
+    let vault = get_associated_token_address_with_program_id(
+        &market_pda,
+        &mint_key,
+        &spl_token_2022::id(),
+    );
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market_pda).0,
+            usdc_mint: mint_key,
+            collateral_allowlist: None,
+            token_program: spl_token_2022::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 2,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let pools: Vec<Pubkey> = (0..2u8)
+        .map(|id| pda::outcome_pool_address(&market_pda, id).0)
+        .collect();
+    for (outcome_id, pool) in pools.iter().enumerate() {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                outcome_pool: *pool,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs {
+                    outcome_id: outcome_id as u8,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // 1% fee: 10_000 sent stakes 9_900, 20_000 sent stakes 19_800.
+    let mut bettors = Vec::new();
+    for (outcome_id, amount) in [(0u8, 10_000u64), (1, 20_000)] {
+        let user = Keypair::new();
+        fund(&mut ctx, &user, 1_000_000_000).await;
+        let user_ata = create_ata_2022(&mut ctx, &mint_key, &user.pubkey()).await;
+        let ix = spl_token_2022::instruction::mint_to(
+            &spl_token_2022::id(),
+            &mint_key,
+            &user_ata,
+            &authority.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap();
+        send(&mut ctx, ix, &authority).await.unwrap();
+
+        let (position, _) = pda::position_address(&market_pda, &user.pubkey(), outcome_id);
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::PlaceBet {
+                user: user.pubkey(),
+                config: config_pda,
+                market: market_pda,
+                outcome_pool: pools[outcome_id as usize],
+                position,
+                user_stats: pda::user_market_stats_address(&market_pda, &user.pubkey()).0,
+                user_usdc: user_ata,
+                vault,
+                usdc_mint: mint_key,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::PlaceBet {
+                args: PlaceBetArgs {
+                    outcome_id,
+                    amount,
+                    max_outcome_share_bps: None,
+                    min_implied_payout: None,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &user).await.unwrap();
+        bettors.push((user, user_ata, position, outcome_id));
+    }
+
+    let acct = ctx.banks_client.get_account(market_pda).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(m.total_pool, 29_700);
+    let acct = ctx.banks_client.get_account(bettors[0].2).await.unwrap().unwrap();
+    let p: Position = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    assert_eq!(p.amount, 9_900);
+    // The vault's spendable balance matches the pool; fees sit withheld beside it.
+    assert_eq!(token_amount(&mut ctx, vault).await, (29_700, 300));
+
+    warp_past(&mut ctx, lock_timestamp).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::LockMarket {
+            caller: authority.pubkey(),
+            market: market_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::LockMarket {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ResolveMarket {
+            oracle: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            winning_outcome_pool: pools[0],
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ResolveMarket {
+            args: ResolveMarketArgs {
+                winning_outcome_id: 0,
+                payload_hash: [4u8; 32],
+                additional_winning_outcome_ids: vec![],
+                result_payload: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Winner takes the whole 29_700 pool; the outbound 1% (297) is withheld at the user.
+    let (winner, winner_ata, winner_pos, outcome_id) = &bettors[0];
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClaimResolved {
+            user: winner.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            position: *winner_pos,
+            outcome_pool: pools[*outcome_id as usize],
+            user_usdc: *winner_ata,
+            vault,
+            usdc_mint: mint_key,
+            token_program: spl_token_2022::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClaimResolved {
+            args: ClaimResolvedArgs {
+                outcome_id: *outcome_id,
+                close_position: false,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, winner).await.unwrap();
+    assert_eq!(token_amount(&mut ctx, *winner_ata).await, (29_403, 297));
+    assert_eq!(token_amount(&mut ctx, vault).await, (0, 300));
+
+    // Sweep harvests the vault's withheld fees to the mint, then closes the vault.
+    let acct = ctx.banks_client.get_account(market_pda).await.unwrap().unwrap();
+    let m: Market = AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap();
+    warp_past(&mut ctx, m.resolution_timestamp + 1).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SweepRemaining {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: market_pda,
+            vault,
+            treasury,
+            close_destination: authority.pubkey(),
+            usdc_mint: mint_key,
+            token_program: spl_token_2022::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SweepRemaining {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+    assert!(ctx.banks_client.get_account(vault).await.unwrap().is_none());
+
+    let acct = ctx.banks_client.get_account(mint_key).await.unwrap().unwrap();
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&acct.data).unwrap();
+    let fee_config = mint_state.get_extension::<TransferFeeConfig>().unwrap();
+    assert_eq!(u64::from(fee_config.withheld_amount), 300);
+}
+
+#[cfg(feature = "token-2022")]
+fn create_market_ix(
+    authority: &Keypair,
+    mint: Pubkey,
+    token_program: Pubkey,
+    event_byte: u8,
+) -> (solana_sdk::instruction::Instruction, Pubkey, Pubkey) {
+    let event_id = [event_byte; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market, _) = pda::market_address(&market_id);
+    let vault = get_associated_token_address_with_program_id(&market, &mint, &token_program);
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: pda::config_address().0,
+            market,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market).0,
+            usdc_mint: mint,
+            collateral_allowlist: Some(pda::collateral_allowlist_address().0),
+            token_program,
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                // Far enough out that the test never has to warp past it.
+                lock_timestamp: 4_000_000_000,
+                max_outcomes: 2,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    (ix, market, vault)
+}
+
+#[cfg(feature = "token-2022")]
+#[tokio::test]
+async fn token_2022_collateral_settles_beside_a_token_v1_config_mint() {
+    let mut ctx = program_test().start_with_context().await;
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    // Config collateral on SPL Token v1.
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let usdc_treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &usdc_treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: usdc_treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 1,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // A Token-2022 collateral is allowlisted even though config is pinned to v1.
+    let pyusd = Keypair::new();
+    create_mint_2022(&mut ctx, &pyusd, &authority.pubkey(), None).await;
+    let pyusd_treasury = create_ata_2022(&mut ctx, &pyusd.pubkey(), &treasury_authority.pubkey()).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::AddCollateralMint {
+            authority: authority.pubkey(),
+            config: config_pda,
+            collateral_allowlist: pda::collateral_allowlist_address().0,
+            mint: pyusd.pubkey(),
+            treasury: pyusd_treasury,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::AddCollateralMint {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    // Each market records the token program that owns its collateral mint.
+    let (ix, usdc_market, _) = create_market_ix(&authority, usdc_mint.pubkey(), spl_token::id(), 51);
+    send(&mut ctx, ix, &authority).await.unwrap();
+    // The vault ATA cannot even be created under config's program.
+    let (ix, _, _) = create_market_ix(&authority, pyusd.pubkey(), spl_token::id(), 52);
+    assert!(send(&mut ctx, ix, &authority).await.is_err());
+    let (ix, pyusd_market, pyusd_vault) =
+        create_market_ix(&authority, pyusd.pubkey(), spl_token_2022::id(), 52);
+    send(&mut ctx, ix, &authority).await.unwrap();
+    assert_eq!(market_state(&mut ctx, usdc_market).await.token_program, spl_token::id());
+    assert_eq!(market_state(&mut ctx, pyusd_market).await.token_program, spl_token_2022::id());
+
+    for outcome_id in 0..2u8 {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market: pyusd_market,
+                outcome_pool: pda::outcome_pool_address(&pyusd_market, outcome_id).0,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs { outcome_id },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: pyusd_market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let user_ata = create_ata_2022(&mut ctx, &pyusd.pubkey(), &user.pubkey()).await;
+    let ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::id(),
+        &pyusd.pubkey(),
+        &user_ata,
+        &authority.pubkey(),
+        &[],
+        10_000,
+    )
+    .unwrap();
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let place_bet_ix = |token_program: Pubkey| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::PlaceBet {
+            user: user.pubkey(),
+            config: config_pda,
+            market: pyusd_market,
+            outcome_pool: pda::outcome_pool_address(&pyusd_market, 0).0,
+            position: pda::position_address(&pyusd_market, &user.pubkey(), 0).0,
+            user_stats: pda::user_market_stats_address(&pyusd_market, &user.pubkey()).0,
+            user_usdc: user_ata,
+            vault: pyusd_vault,
+            usdc_mint: pyusd.pubkey(),
+            token_program,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::PlaceBet {
+            args: PlaceBetArgs {
+                outcome_id: 0,
+                amount: 10_000,
+                max_outcome_share_bps: None,
+                min_implied_payout: None,
+            },
+        }
+        .data(),
+    };
+    // Config's program is not the market's.
+    let err = send(&mut ctx, place_bet_ix(spl_token::id()), &user)
+        .await
+        .unwrap_err();
+    assert_custom(err, 6003, "InvalidTokenProgram");
+    send(&mut ctx, place_bet_ix(spl_token_2022::id()), &user)
+        .await
+        .unwrap();
+    assert_eq!(token_amount(&mut ctx, pyusd_vault).await, (10_000, 0));
+}
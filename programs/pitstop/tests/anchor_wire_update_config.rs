@@ -0,0 +1,134 @@
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, pda};
+
+#[tokio::test]
+async fn claim_window_change_does_not_reach_resolved_markets() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let oracle = Keypair::new();
+    for kp in [&authority, &treasury_authority, &oracle] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let treasury = Keypair::new();
+    create_token_account(
+        &mut ctx,
+        &treasury,
+        &usdc_mint.pubkey(),
+        &treasury_authority.pubkey(),
+    )
+    .await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: usdc_mint.pubkey(),
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: oracle.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 100_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let m = locked_market(&mut ctx, &authority, &usdc_mint.pubkey(), 71).await;
+    send(&mut ctx, resolve_ix(&oracle, &m, 0), &oracle)
+        .await
+        .unwrap();
+    let resolution_timestamp = market_state(&mut ctx, m.market).await.resolution_timestamp;
+
+    // Shrink the config window to one second once the market is already resolved.
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::UpdateConfig {
+            authority: authority.pubkey(),
+            config: config_pda,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::UpdateConfig {
+            args: UpdateConfigArgs {
+                fee_bps: None,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+                claim_window_secs: Some(1),
+                dispute_window_secs: None,
+                resolution_deadline_secs: None,
+                early_exit_fee_bps: None,
+                payout_keeper: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+    warp_past(&mut ctx, resolution_timestamp + 1).await;
+
+    // The market keeps the window it was created with, so the treasury cannot sweep yet.
+    assert_eq!(market_state(&mut ctx, m.market).await.claim_window_secs, 3600);
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::SweepRemaining {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market: m.market,
+            vault: m.vault,
+            treasury: treasury.pubkey(),
+            close_destination: authority.pubkey(),
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::SweepRemaining {}.data(),
+    };
+    let err = send(&mut ctx, ix, &authority).await.unwrap_err();
+    assert_custom(err, 6022, "ClaimWindowNotExpired");
+
+    // ...and the winner can still claim.
+    let winner = &m.bettors[0];
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClaimResolved {
+            user: winner.user.pubkey(),
+            config: config_pda,
+            market: m.market,
+            position: winner.position,
+            outcome_pool: m.pools[0],
+            user_usdc: winner.user_usdc,
+            vault: m.vault,
+            usdc_mint: usdc_mint.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClaimResolved {
+            args: ClaimResolvedArgs {
+                outcome_id: 0,
+                close_position: false,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &winner.user).await.unwrap();
+    assert_eq!(token_amount(&mut ctx, winner.user_usdc).await, 20_000);
+}
@@ -0,0 +1,296 @@
+mod common;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+use pitstop::{self, anchor_accounts::*, canonical, pda};
+
+async fn user_stats(ctx: &mut ProgramTestContext, stats: Pubkey) -> UserMarketStats {
+    let acct = ctx.banks_client.get_account(stats).await.unwrap().unwrap();
+    AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap()
+}
+
+async fn lamports(ctx: &mut ProgramTestContext, key: Pubkey) -> u64 {
+    ctx.banks_client.get_balance(key).await.unwrap()
+}
+
+#[tokio::test]
+async fn user_cap_spans_outcomes_and_stats_close_with_position() {
+    let mut ctx = program_test().start_with_context().await;
+
+    let authority = Keypair::new();
+    let treasury_authority = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&authority, &treasury_authority, &user] {
+        fund(&mut ctx, kp, 2_000_000_000).await;
+    }
+
+    let usdc_mint = Keypair::new();
+    create_mint(&mut ctx, &usdc_mint, &authority.pubkey()).await;
+    let mint = usdc_mint.pubkey();
+    let treasury = Keypair::new();
+    create_token_account(&mut ctx, &treasury, &mint, &treasury_authority.pubkey()).await;
+    let user_usdc = Keypair::new();
+    create_token_account(&mut ctx, &user_usdc, &mint, &user.pubkey()).await;
+    mint_to(&mut ctx, &mint, &authority, &user_usdc.pubkey(), 10_000).await;
+
+    let (config_pda, _) = pda::config_address();
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::Initialize {
+            authority: authority.pubkey(),
+            config: config_pda,
+            usdc_mint: mint,
+            treasury: treasury.pubkey(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::Initialize {
+            args: InitializeArgs {
+                oracle: authority.pubkey(),
+                treasury_authority: treasury_authority.pubkey(),
+                max_total_pool_per_market: 1_000_000,
+                max_bet_per_user_per_market: 1_000,
+                claim_window_secs: 3600,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 100;
+    let event_id = [45u8; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market, _) = pda::market_address(&market_id);
+    let vault = spl_associated_token_account::get_associated_token_address(&market, &mint);
+    let pools: Vec<Pubkey> = (0..3u8)
+        .map(|id| pda::outcome_pool_address(&market, id).0)
+        .collect();
+    let positions: Vec<Pubkey> = (0..3u8)
+        .map(|id| pda::position_address(&market, &user.pubkey(), id).0)
+        .collect();
+    let (stats, _) = pda::user_market_stats_address(&market, &user.pubkey());
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market).0,
+            usdc_mint: mint,
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 3,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    for (outcome_id, pool) in pools.iter().enumerate() {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market,
+                outcome_pool: *pool,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs {
+                    outcome_id: outcome_id as u8,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &authority).await.unwrap();
+    }
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let bet_ix = |outcome_id: u8, amount: u64| solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::PlaceBet {
+            user: user.pubkey(),
+            config: config_pda,
+            market,
+            outcome_pool: pools[outcome_id as usize],
+            position: positions[outcome_id as usize],
+            user_stats: stats,
+            user_usdc: user_usdc.pubkey(),
+            vault,
+            usdc_mint: mint,
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::PlaceBet {
+            args: PlaceBetArgs {
+                outcome_id,
+                amount,
+                max_outcome_share_bps: None,
+                min_implied_payout: None,
+            },
+        }
+        .data(),
+    };
+
+    send(&mut ctx, bet_ix(1, 400), &user).await.unwrap();
+    send(&mut ctx, bet_ix(0, 600), &user).await.unwrap();
+    let s = user_stats(&mut ctx, stats).await;
+    assert_eq!((s.market, s.user), (market, user.pubkey()));
+    assert_eq!((s.total_staked, s.outcome_ids()), (1_000, &[0, 1][..]));
+
+    // A fresh position on outcome 2 still counts the 1_000 staked on 0 and 1.
+    let err = send(&mut ctx, bet_ix(2, 1), &user).await.unwrap_err();
+    assert_custom(err, 6031, "UserBetCapExceeded");
+
+    // Reducing any position frees room for the whole market.
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ReducePosition {
+            user: user.pubkey(),
+            config: config_pda,
+            market,
+            outcome_pool: pools[0],
+            position: positions[0],
+            user_stats: stats,
+            user_usdc: user_usdc.pubkey(),
+            vault,
+            treasury: treasury.pubkey(),
+            usdc_mint: mint,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ReducePosition {
+            args: ReducePositionArgs {
+                outcome_id: 0,
+                amount: 300,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &user).await.unwrap();
+    assert_eq!(user_stats(&mut ctx, stats).await.total_staked, 700);
+
+    send(&mut ctx, bet_ix(2, 300), &user).await.unwrap();
+    let s = user_stats(&mut ctx, stats).await;
+    assert_eq!((s.total_staked, s.outcome_ids()), (1_000, &[0, 1, 2][..]));
+    assert_eq!(market_state(&mut ctx, market).await.total_pool, 1_000);
+    assert_eq!(token_amount(&mut ctx, vault).await, 1_000);
+
+    warp_past(&mut ctx, lock_timestamp).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::LockMarket {
+            caller: authority.pubkey(),
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::LockMarket {}.data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ResolveMarket {
+            oracle: authority.pubkey(),
+            config: config_pda,
+            market,
+            winning_outcome_pool: pools[2],
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ResolveMarket {
+            args: ResolveMarketArgs {
+                winning_outcome_id: 2,
+                payload_hash: [1u8; 32],
+                additional_winning_outcome_ids: vec![],
+                result_payload: None,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &authority).await.unwrap();
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClaimResolved {
+            user: user.pubkey(),
+            config: config_pda,
+            market,
+            position: positions[2],
+            outcome_pool: pools[2],
+            user_usdc: user_usdc.pubkey(),
+            vault,
+            usdc_mint: mint,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClaimResolved {
+            args: ClaimResolvedArgs {
+                outcome_id: 2,
+                close_position: false,
+            },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &user).await.unwrap();
+
+    // The stats account closes with the claimed position; both rents go to the user.
+    let rent = lamports(&mut ctx, positions[2]).await + lamports(&mut ctx, stats).await;
+    let before = lamports(&mut ctx, user.pubkey()).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ClosePosition {
+            user: user.pubkey(),
+            market,
+            position: positions[2],
+            user_stats: Some(stats),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ClosePosition {
+            args: ClosePositionArgs { outcome_id: 2 },
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &user).await.unwrap();
+    assert!(ctx.banks_client.get_account(stats).await.unwrap().is_none());
+    assert!(ctx.banks_client.get_account(positions[2]).await.unwrap().is_none());
+    assert_eq!(lamports(&mut ctx, user.pubkey()).await, before + rent - 5_000);
+}
@@ -0,0 +1,362 @@
+//! Helpers shared by the `anchor_wire_*` integration tests.
+//!
+//! Each test file is its own crate and only uses a subset of these.
+#![allow(dead_code)]
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use pitstop::{anchor_accounts::*, canonical, pda};
+
+pub fn pitstop_entry<'a, 'b, 'c, 'd>(
+    program_id: &'a Pubkey,
+    accounts: &'b [AccountInfo<'c>],
+    data: &'d [u8],
+) -> ProgramResult {
+    // `anchor_lang`'s generated `entry` expects the slice lifetime to match the
+    // inner AccountInfo lifetime. `solana-program-test` passes them as the same
+    // lifetime in practice, but its processor signature is more general.
+    //
+    // This shim uses an unsafe lifetime coercion for test-only execution.
+    let accounts: &'c [AccountInfo<'c>] = unsafe { std::mem::transmute(accounts) };
+    pitstop::entry(program_id, accounts, data)
+}
+
+pub fn program_test() -> ProgramTest {
+    ProgramTest::new("pitstop", pitstop::id(), processor!(pitstop_entry))
+}
+
+pub async fn fund(ctx: &mut ProgramTestContext, kp: &Keypair, lamports: u64) {
+    let tx = Transaction::new_signed_with_payer(
+        &[solana_sdk::system_instruction::transfer(
+            &ctx.payer.pubkey(),
+            &kp.pubkey(),
+            lamports,
+        )],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+}
+
+pub async fn create_mint(ctx: &mut ProgramTestContext, mint: &Keypair, mint_authority: &Pubkey) {
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let space = spl_token::state::Mint::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    let create = solana_sdk::system_instruction::create_account(
+        &ctx.payer.pubkey(),
+        &mint.pubkey(),
+        lamports,
+        space as u64,
+        &spl_token::id(),
+    );
+    let init = spl_token::instruction::initialize_mint(
+        &spl_token::id(),
+        &mint.pubkey(),
+        mint_authority,
+        None,
+        6,
+    )
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create, init],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, mint],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+}
+
+pub async fn create_token_account(
+    ctx: &mut ProgramTestContext,
+    acct: &Keypair,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) {
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let space = spl_token::state::Account::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    let create = solana_sdk::system_instruction::create_account(
+        &ctx.payer.pubkey(),
+        &acct.pubkey(),
+        lamports,
+        space as u64,
+        &spl_token::id(),
+    );
+    let init =
+        spl_token::instruction::initialize_account(&spl_token::id(), &acct.pubkey(), mint, owner)
+            .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create, init],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, acct],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+}
+
+pub async fn mint_to(
+    ctx: &mut ProgramTestContext,
+    mint: &Pubkey,
+    mint_authority: &Keypair,
+    to: &Pubkey,
+    amount: u64,
+) {
+    let ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        mint,
+        to,
+        &mint_authority.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, mint_authority],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+}
+
+pub async fn send(
+    ctx: &mut ProgramTestContext,
+    ix: solana_sdk::instruction::Instruction,
+    signer: &Keypair,
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = ctx.banks_client.get_latest_blockhash().await.unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+pub fn assert_custom(err: BanksClientError, code: u32, name: &str) {
+    let msg = format!("{err:?}");
+    assert!(
+        msg.contains(&format!("Custom({code})")),
+        "expected {name} ({code}), got {msg}"
+    );
+}
+
+pub async fn warp_past(ctx: &mut ProgramTestContext, ts: i64) {
+    loop {
+        let c: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+        if c.unix_timestamp > ts {
+            break;
+        }
+        let slot = ctx.banks_client.get_root_slot().await.unwrap() + 10;
+        ctx.warp_to_slot(slot).unwrap();
+    }
+}
+
+pub async fn market_state(ctx: &mut ProgramTestContext, market: Pubkey) -> Market {
+    let acct = ctx.banks_client.get_account(market).await.unwrap().unwrap();
+    AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap()
+}
+
+pub async fn token_amount(ctx: &mut ProgramTestContext, account: Pubkey) -> u64 {
+    let acct = ctx.banks_client.get_account(account).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&acct.data).unwrap().amount
+}
+
+pub async fn position_state(ctx: &mut ProgramTestContext, position: Pubkey) -> Position {
+    let acct = ctx.banks_client.get_account(position).await.unwrap().unwrap();
+    AccountDeserialize::try_deserialize(&mut acct.data.as_slice()).unwrap()
+}
+
+pub struct Bettor {
+    pub user: Keypair,
+    pub user_usdc: Pubkey,
+    pub position: Pubkey,
+    pub outcome_id: u8,
+}
+
+pub struct TestMarket {
+    pub market: Pubkey,
+    pub vault: Pubkey,
+    pub pools: [Pubkey; 2],
+    pub bettors: Vec<Bettor>,
+}
+
+/// Two-outcome market with one 10k bettor per outcome, locked and waiting for the oracle.
+pub async fn locked_market(
+    ctx: &mut ProgramTestContext,
+    authority: &Keypair,
+    usdc_mint: &Pubkey,
+    event_byte: u8,
+) -> TestMarket {
+    let (config_pda, _) = pda::config_address();
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    let lock_timestamp = clock.unix_timestamp + 100;
+    let event_id = [event_byte; 32];
+    let market_id = canonical::market_id(event_id, 0, 1);
+    let (market, _) = pda::market_address(&market_id);
+    let vault = spl_associated_token_account::get_associated_token_address(&market, usdc_mint);
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::CreateMarket {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+            vault,
+            market_tombstone: pda::market_tombstone_address(&market).0,
+            usdc_mint: *usdc_mint,
+            collateral_allowlist: None,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::CreateMarket {
+            args: CreateMarketArgs {
+                market_id,
+                event_id,
+                lock_timestamp,
+                max_outcomes: 2,
+                market_type: 0,
+                rules_version: 1,
+                max_total_pool_per_market: None,
+                max_bet_per_user_per_market: None,
+            },
+        }
+        .data(),
+    };
+    send(ctx, ix, authority).await.unwrap();
+
+    let pools = [0u8, 1].map(|id| pda::outcome_pool_address(&market, id).0);
+    for (outcome_id, pool) in pools.iter().enumerate() {
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::AddOutcome {
+                authority: authority.pubkey(),
+                config: config_pda,
+                market,
+                outcome_pool: *pool,
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::AddOutcome {
+                args: AddOutcomeArgs {
+                    outcome_id: outcome_id as u8,
+                },
+            }
+            .data(),
+        };
+        send(ctx, ix, authority).await.unwrap();
+    }
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::FinalizeSeeding {
+            authority: authority.pubkey(),
+            config: config_pda,
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::FinalizeSeeding {}.data(),
+    };
+    send(ctx, ix, authority).await.unwrap();
+
+    let mut bettors = Vec::new();
+    for outcome_id in [0u8, 1] {
+        let user = Keypair::new();
+        fund(ctx, &user, 1_000_000_000).await;
+        let user_usdc = Keypair::new();
+        create_token_account(ctx, &user_usdc, usdc_mint, &user.pubkey()).await;
+        mint_to(ctx, usdc_mint, authority, &user_usdc.pubkey(), 10_000).await;
+        let (position, _) = pda::position_address(&market, &user.pubkey(), outcome_id);
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id: pitstop::id(),
+            accounts: pitstop::accounts::PlaceBet {
+                user: user.pubkey(),
+                config: config_pda,
+                market,
+                outcome_pool: pools[outcome_id as usize],
+                position,
+                user_stats: pda::user_market_stats_address(&market, &user.pubkey()).0,
+                user_usdc: user_usdc.pubkey(),
+                vault,
+                usdc_mint: *usdc_mint,
+                token_program: spl_token::id(),
+                system_program: solana_sdk::system_program::id(),
+            }
+            .to_account_metas(None),
+            data: pitstop::instruction::PlaceBet {
+                args: PlaceBetArgs {
+                    outcome_id,
+                    amount: 10_000,
+                    max_outcome_share_bps: None,
+                    min_implied_payout: None,
+                },
+            }
+            .data(),
+        };
+        send(ctx, ix, &user).await.unwrap();
+        bettors.push(Bettor {
+            user,
+            user_usdc: user_usdc.pubkey(),
+            position,
+            outcome_id,
+        });
+    }
+
+    warp_past(ctx, lock_timestamp).await;
+    let ix = solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::LockMarket {
+            caller: authority.pubkey(),
+            market,
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::LockMarket {}.data(),
+    };
+    send(ctx, ix, authority).await.unwrap();
+
+    TestMarket {
+        market,
+        vault,
+        pools,
+        bettors,
+    }
+}
+
+pub fn resolve_ix(oracle: &Keypair, m: &TestMarket, outcome_id: u8) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: pitstop::id(),
+        accounts: pitstop::accounts::ResolveMarket {
+            oracle: oracle.pubkey(),
+            config: pda::config_address().0,
+            market: m.market,
+            winning_outcome_pool: m.pools[outcome_id as usize],
+            instructions_sysvar: None,
+            oracle_committee: None,
+            resolution_votes: None,
+            votes_payer: None,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: pitstop::instruction::ResolveMarket {
+            args: ResolveMarketArgs {
+                winning_outcome_id: outcome_id,
+                payload_hash: [0x11; 32],
+                additional_winning_outcome_ids: vec![],
+                result_payload: None,
+            },
+        }
+        .data(),
+    }
+}
@@ -11,7 +11,7 @@ for (const p of requiredSpecs){ if(!fs.existsSync(p)) fail(`Missing required spe
 
 const idx = fs.readFileSync('SPEC_INSTRUCTIONS/INDEX.md','utf8');
 const instructionRows = (idx.match(/^\|\s*\d+\s*\|/gm)||[]).length;
-if (instructionRows !== 12) fail(`Instruction inventory mismatch: expected 12, found ${instructionRows}`);
+if (instructionRows !== 30) fail(`Instruction inventory mismatch: expected 30, found ${instructionRows}`);
 
 for (const f of fs.readdirSync('SPEC_INSTRUCTIONS')){
   if(!f.endsWith('.md')||f==='README.md'||f==='INDEX.md') continue;
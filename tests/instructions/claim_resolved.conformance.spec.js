@@ -46,6 +46,7 @@ const { invokeClaimResolvedOnProgram } = require('../harness/claim_resolved_adap
     assert.equal(out.event.outcome_id, base.outcomeId);
     assert.equal(out.event.payout, out.position.payout);
     assert.equal(out.event.claimed_at, nowTs);
+    assert.equal(out.event.initiator, base.user);
   }
 
   // CLR-HP-002
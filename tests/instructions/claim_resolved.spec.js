@@ -1,6 +1,6 @@
 const assert = require('assert');
 const constants = require('../../specs/constants.json');
-const { validateClaimResolvedInput } = require('../../packages/core/src/claim_resolved_instruction.cjs');
+const { validateClaimResolvedInput, executeClaimResolved } = require('../../packages/core/src/claim_resolved_instruction.cjs');
 
 (function run() {
   const base = {
@@ -32,6 +32,22 @@ const { validateClaimResolvedInput } = require('../../packages/core/src/claim_re
   );
   assert.equal(validateClaimResolvedInput({ ...base, outcomePoolState: null }), 'OutcomeMismatch');
 
+  // Dispute window: claims open after disputeDeadline and the claim window counts from it.
+  const pending = { ...base.marketState, status: 'PendingResolution', disputeDeadline: base.resolutionTimestamp + 600 };
+  assert.equal(validateClaimResolvedInput({ ...base, marketState: pending, nowTs: pending.disputeDeadline }), 'DisputeWindowActive');
+  assert.equal(validateClaimResolvedInput({ ...base, marketState: pending, nowTs: pending.disputeDeadline + 1 }), null);
+  assert.equal(validateClaimResolvedInput({ ...base, marketState: pending, nowTs: pending.disputeDeadline + base.claimWindowSecs }), null);
+  assert.equal(
+    validateClaimResolvedInput({ ...base, marketState: pending, nowTs: pending.disputeDeadline + base.claimWindowSecs + 1 }),
+    'ClaimWindowExpired'
+  );
+  const late = executeClaimResolved({ ...base, marketState: pending, nowTs: pending.disputeDeadline + 1, vaultAmount: 1_000_000, userUsdcAmount: 0 });
+  assert.equal(late.market.status, 'Resolved');
+  assert.equal(late.resolutionFinalizedEvent.name, 'ResolutionFinalized');
+  const settled = executeClaimResolved({ ...base, vaultAmount: 1_000_000, userUsdcAmount: 0 });
+  assert.equal(settled.market.status, 'Resolved');
+  assert.equal(settled.resolutionFinalizedEvent, null);
+
   // Failure ordering (locked): status checked before outcome/vault usage.
   assert.equal(
     validateClaimResolvedInput({ ...base, marketState: { ...base.marketState, status: 'Swept' }, outcomePoolState: null }),
@@ -34,6 +34,7 @@ const { invokeClaimVoidedOnProgram } = require('../harness/claim_voided_adapter'
   assert.equal(ok.event.outcome_id, base.outcomeId);
   assert.equal(ok.event.payout, base.positionState.amount);
   assert.equal(ok.event.claimed_at, nowTs);
+  assert.equal(ok.event.initiator, base.user);
 
   // CLV-REJ-001..003
   const cases = [
@@ -23,6 +23,12 @@ const { invokeCreateMarketOnProgram } = require('../harness/create_market_adapte
     maxOutcomes: 20,
     marketType: 0,
     rulesVersion: 1,
+    collateralMint: 'MintA',
+    configUsdcMint: 'MintA',
+    configTreasury: 'TreasuryA',
+    collateralAllowlist: [],
+    configMaxTotalPoolPerMarket: 1_000_000,
+    configMaxBetPerUserPerMarket: 100_000,
   };
 
   // CRM-HP-001
@@ -34,11 +40,15 @@ const { invokeCreateMarketOnProgram } = require('../harness/create_market_adapte
   assert.equal(ok.market.resolvedOutcome, null);
   assert.equal(ok.market.resolutionTimestamp, 0);
   assert.equal(ok.market.vault, base.vault);
+  assert.equal(ok.market.collateralMint, 'MintA');
+  assert.equal(ok.market.treasury, 'TreasuryA');
   assert.equal(ok.event.name, 'MarketCreated');
   assert.equal(ok.event.market, base.market);
   assert.equal(ok.event.market_id, base.marketIdHex);
+  assert.equal(ok.market.maxTotalPoolPerMarket, null);
+  assert.equal(ok.event.max_bet_per_user_per_market, null);
 
-  // CRM-REJ-001..006 (+ split rows)
+  // CRM-REJ-001..006, 008..010 (+ split rows)
   const cases = [
     [{ authority: 'Other' }, 'Unauthorized'],
     [{ tokenProgram: 'TokenzFake' }, 'InvalidTokenProgram'],
@@ -48,6 +58,10 @@ const { invokeCreateMarketOnProgram } = require('../harness/create_market_adapte
     [{ marketType: 2 }, 'UnsupportedMarketType'],
     [{ rulesVersion: 2 }, 'UnsupportedRulesVersion'],
     [{ marketIdHex: 'b'.repeat(64) }, 'InvalidMarketId'],
+    [{ collateralMint: 'PyusdMint' }, 'CollateralMintNotAllowed'],
+    [{ maxTotalPoolPerMarket: 1_000_001 }, 'InvalidCap'],
+    [{ maxTotalPoolPerMarket: 1_000, maxBetPerUserPerMarket: 1_001 }, 'InvalidCap'],
+    [{ marketTombstoneExists: true }, 'MarketIdRetired'],
   ];
 
   for (const [patch, expected] of cases) {
@@ -57,5 +71,27 @@ const { invokeCreateMarketOnProgram } = require('../harness/create_market_adapte
     assert.equal(out.event, undefined, 'failed instruction must not emit event');
   }
 
+  // Allowlisted collateral records its own treasury.
+  const pyusd = await invokeCreateMarketOnProgram({
+    ...base,
+    collateralMint: 'PyusdMint',
+    collateralAllowlist: [{ mint: 'PyusdMint', treasury: 'PyusdTreasury' }],
+  });
+  assert.equal(pyusd.ok, true);
+  assert.equal(pyusd.market.treasury, 'PyusdTreasury');
+  assert.equal(pyusd.event.collateral_mint, 'PyusdMint');
+
+  // Cap overrides are stored on the market and echoed in MarketCreated.
+  const capped = await invokeCreateMarketOnProgram({
+    ...base,
+    maxTotalPoolPerMarket: 5_000,
+    maxBetPerUserPerMarket: 1_000,
+  });
+  assert.equal(capped.ok, true);
+  assert.equal(capped.market.maxTotalPoolPerMarket, 5_000);
+  assert.equal(capped.market.maxBetPerUserPerMarket, 1_000);
+  assert.equal(capped.event.max_total_pool_per_market, 5_000);
+  assert.equal(capped.event.max_bet_per_user_per_market, 1_000);
+
   console.log('create_market conformance tests ok');
 })();
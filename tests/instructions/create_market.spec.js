@@ -20,6 +20,12 @@ const {
     maxOutcomes: 20,
     marketType: 0,
     rulesVersion: 1,
+    collateralMint: 'MintA',
+    configUsdcMint: 'MintA',
+    configTreasury: 'TreasuryA',
+    collateralAllowlist: [],
+    configMaxTotalPoolPerMarket: 1_000_000,
+    configMaxBetPerUserPerMarket: 100_000,
   };
 
   assert.equal(validateCreateMarketInput(base), null);
@@ -32,5 +38,29 @@ const {
   assert.equal(validateCreateMarketInput({ ...base, rulesVersion: 2 }), 'UnsupportedRulesVersion');
   assert.equal(validateCreateMarketInput({ ...base, marketIdHex: 'b'.repeat(64) }), 'InvalidMarketId');
 
+  // Podium markets pair market_type=1 with rules_version=2 only.
+  const podium = { ...base, marketType: 1, rulesVersion: 2, marketIdHex: computeMarketIdHex(eventIdHex, 1, 2) };
+  assert.equal(validateCreateMarketInput(podium), null);
+  assert.equal(validateCreateMarketInput({ ...podium, rulesVersion: 1 }), 'UnsupportedRulesVersion');
+
+  // CRM-REJ-008: only config.usdc_mint or an allowlisted mint can back a market.
+  assert.equal(validateCreateMarketInput({ ...base, collateralMint: 'PyusdMint' }), 'CollateralMintNotAllowed');
+  const pyusd = {
+    ...base,
+    collateralMint: 'PyusdMint',
+    collateralAllowlist: [{ mint: 'PyusdMint', treasury: 'PyusdTreasury' }],
+  };
+  assert.equal(validateCreateMarketInput(pyusd), null);
+
+  // CRM-REJ-009: cap overrides may only tighten the config caps.
+  assert.equal(validateCreateMarketInput({ ...base, maxTotalPoolPerMarket: 5_000, maxBetPerUserPerMarket: 1_000 }), null);
+  assert.equal(validateCreateMarketInput({ ...base, maxTotalPoolPerMarket: 1_000_001 }), 'InvalidCap');
+  assert.equal(validateCreateMarketInput({ ...base, maxBetPerUserPerMarket: 100_001 }), 'InvalidCap');
+  assert.equal(validateCreateMarketInput({ ...base, maxBetPerUserPerMarket: 0 }), 'InvalidCap');
+  assert.equal(validateCreateMarketInput({ ...base, maxTotalPoolPerMarket: 50_000 }), 'InvalidCap');
+
+  // CRM-REJ-010: a closed market_id cannot be created again.
+  assert.equal(validateCreateMarketInput({ ...base, marketTombstoneExists: true }), 'MarketIdRetired');
+
   console.log('create_market spec tests ok');
 })();
@@ -8,6 +8,7 @@ const { invokeInitializeOnProgram } = require('../harness/initialize_adapter');
   const nowTs = 1_800_000_000;
   const base = {
     authority: 'AuthA',
+    oracle: 'OracleA',
     tokenProgram: constants.REQUIRED_TOKEN_PROGRAM,
     usdcDecimals: 6,
     usdcMint: 'MintA',
@@ -25,7 +26,7 @@ const { invokeInitializeOnProgram } = require('../harness/initialize_adapter');
   const ok = await invokeInitializeOnProgram(base);
   assert.equal(ok.ok, true);
   assert.equal(ok.config.authority, base.authority);
-  assert.equal(ok.config.oracle, base.authority);
+  assert.equal(ok.config.oracle, base.oracle);
   assert.equal(ok.config.usdcMint, base.usdcMint);
   assert.equal(ok.config.treasury, base.treasury);
   assert.equal(ok.config.treasuryAuthority, base.treasuryAuthority);
@@ -38,7 +39,7 @@ const { invokeInitializeOnProgram } = require('../harness/initialize_adapter');
 
   assert.equal(ok.event.name, 'ConfigInitialized');
   assert.equal(ok.event.authority, base.authority);
-  assert.equal(ok.event.oracle, base.authority);
+  assert.equal(ok.event.oracle, base.oracle);
   assert.equal(ok.event.usdc_mint, base.usdcMint);
   assert.equal(ok.event.treasury, base.treasury);
   assert.equal(ok.event.fee_bps, 0);
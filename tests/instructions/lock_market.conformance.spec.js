@@ -4,8 +4,7 @@ const { invokeLockMarketOnProgram } = require('../harness/lock_market_adapter');
 (async function run() {
   const nowTs = 1_800_000_100;
   const base = {
-    authority: 'AuthA',
-    configAuthority: 'AuthA',
+    caller: 'KeeperA',
     market: 'MarketA',
     nowTs,
     marketState: { status: 'Open', lockTimestamp: 1_800_000_000 },
@@ -15,13 +14,15 @@ const { invokeLockMarketOnProgram } = require('../harness/lock_market_adapter');
   const ok = await invokeLockMarketOnProgram(base);
   assert.equal(ok.ok, true);
   assert.equal(ok.market.status, 'Locked');
+  assert.equal(ok.market.lockedAt, nowTs);
   assert.equal(ok.event.name, 'MarketLocked');
   assert.equal(ok.event.market, base.market);
+  assert.equal(ok.event.locked_by, 'KeeperA');
+  assert.equal(ok.event.lock_timestamp, base.marketState.lockTimestamp);
   assert.equal(ok.event.timestamp, nowTs);
 
-  // LKM-REJ-001..003
+  // LKM-REJ-002..003 (LKM-REJ-001 retired: locking is permissionless)
   const cases = [
-    [{ authority: 'Other' }, 'Unauthorized'],
     [{ marketState: { ...base.marketState, status: 'Locked' } }, 'MarketNotOpen'],
     [{ nowTs: base.marketState.lockTimestamp - 1 }, 'TooEarlyToLock'],
   ];
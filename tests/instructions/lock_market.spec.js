@@ -3,14 +3,13 @@ const { validateLockMarketInput } = require('../../packages/core/src/lock_market
 
 (function run() {
   const base = {
-    authority: 'AuthA',
-    configAuthority: 'AuthA',
+    caller: 'KeeperA',
     nowTs: 1_800_000_100,
     marketState: { status: 'Open', lockTimestamp: 1_800_000_000 },
   };
 
   assert.equal(validateLockMarketInput(base), null);
-  assert.equal(validateLockMarketInput({ ...base, authority: 'Other' }), 'Unauthorized');
+  assert.equal(validateLockMarketInput({ ...base, nowTs: base.marketState.lockTimestamp }), null);
   assert.equal(validateLockMarketInput({ ...base, marketState: { ...base.marketState, status: 'Locked' } }), 'MarketNotOpen');
   assert.equal(validateLockMarketInput({ ...base, nowTs: base.marketState.lockTimestamp - 1 }), 'TooEarlyToLock');
 
@@ -18,6 +18,7 @@ const { invokePlaceBetOnProgram } = require('../harness/place_bet_adapter');
     marketState: { totalPool: 1000, status: 'Open', lockTimestamp: nowTs + 100, outcomeCount: 3, maxOutcomes: 3 },
     outcomePoolState: { poolAmount: 400, market: 'MarketA', outcomeId: 1 },
     positionState: { amount: 200 },
+    userMarketStatsState: { totalStaked: 500, outcomeIds: [0, 1] },
   };
 
   // PBT-HP-001/002 + invariants
@@ -26,6 +27,8 @@ const { invokePlaceBetOnProgram } = require('../harness/place_bet_adapter');
   assert.equal(ok.market.totalPool, 1100);
   assert.equal(ok.outcomePool.poolAmount, 500);
   assert.equal(ok.position.amount, 300);
+  assert.equal(ok.userMarketStats.totalStaked, 600);
+  assert.deepEqual(ok.userMarketStats.outcomeIds, [0, 1]);
   assert.equal(ok.vaultAmount, 1100);
   assert.equal(ok.market.totalPool, ok.vaultAmount, 'PBT-INV-002 pre-resolution vault == market total');
   assert.equal(ok.event.name, 'BetPlaced');
@@ -42,6 +45,7 @@ const { invokePlaceBetOnProgram } = require('../harness/place_bet_adapter');
     ...base,
     user: 'UserB',
     positionState: { amount: 0 },
+    userMarketStatsState: { totalStaked: 0, outcomeIds: [] },
     amount: 75,
     outcomePoolState: { ...base.outcomePoolState, poolAmount: 200 },
     marketState: { ...base.marketState, totalPool: 500 },
@@ -51,6 +55,8 @@ const { invokePlaceBetOnProgram } = require('../harness/place_bet_adapter');
   assert.equal(newPos.position.amount, 75);
   assert.equal(newPos.market.totalPool, 575);
   assert.equal(newPos.outcomePool.poolAmount, 275);
+  assert.equal(newPos.userMarketStats.totalStaked, 75);
+  assert.deepEqual(newPos.userMarketStats.outcomeIds, [1]);
 
   // PBT-INV-001: sum(outcome pools) == market.total_pool (modeled with multi-pool snapshot).
   const otherOutcomePoolAmount = 300;
@@ -66,7 +72,8 @@ const { invokePlaceBetOnProgram } = require('../harness/place_bet_adapter');
     [{ marketState: { ...base.marketState, outcomeCount: 2 } }, 'MarketNotReady'],
     [{ amount: 0 }, 'ZeroAmount'],
     [{ marketState: { ...base.marketState, totalPool: 9_950 } }, 'MarketCapExceeded'],
-    [{ positionState: { ...base.positionState, amount: 950 } }, 'UserBetCapExceeded'],
+    [{ userMarketStatsState: { ...base.userMarketStatsState, totalStaked: 950 } }, 'UserBetCapExceeded'],
+    [{ outcomeId: 2, outcomePoolState: { ...base.outcomePoolState, outcomeId: 2 }, amount: 501 }, 'UserBetCapExceeded'],
     [{ outcomePoolState: null }, 'OutcomeMismatch'],
     [{ tokenProgram: 'TokenzFake' }, 'InvalidTokenProgram'],
   ];
@@ -77,6 +84,20 @@ const { invokePlaceBetOnProgram } = require('../harness/place_bet_adapter');
     assert.equal(out.event, undefined);
   }
 
+  // PBT-HP-004 / PBT-REJ-012: post-bet share 500/1100 = 4545.45 bps, payout 100 * 1100 / 500 = 220.
+  const quoted = await invokePlaceBetOnProgram({ ...base, maxOutcomeShareBps: 4546, minImpliedPayout: 220 });
+  assert.equal(quoted.ok, true);
+  const slippageCases = [
+    { maxOutcomeShareBps: 4545 },
+    { minImpliedPayout: 221 },
+    { minImpliedPayout: 216, marketState: { ...base.marketState, feeBps: 200 } },
+  ];
+  for (const patch of slippageCases) {
+    const out = await invokePlaceBetOnProgram({ ...base, ...patch });
+    assert.equal(out.ok, false);
+    assert.equal(out.error, 'SlippageExceeded');
+  }
+
   // PBT-ADV-001..004 basic adversarial mismatches
   const advCases = [
     [{ outcomePoolState: { ...base.outcomePoolState, market: 'OtherMarket' } }, 'OutcomeMismatch'],
@@ -15,6 +15,7 @@ const { validatePlaceBetInput } = require('../../packages/core/src/place_bet_ins
     marketState: { status: 'Open', lockTimestamp: 1_800_000_100, outcomeCount: 3, maxOutcomes: 3, totalPool: 1000 },
     outcomePoolState: { market: 'MarketA', outcomeId: 1, poolAmount: 400 },
     positionState: { amount: 200 },
+    userMarketStatsState: { totalStaked: 500, outcomeIds: [0, 1] },
   };
 
   assert.equal(validatePlaceBetInput(base), null);
@@ -32,7 +33,18 @@ const { validatePlaceBetInput } = require('../../packages/core/src/place_bet_ins
     'MarketCapExceeded'
   );
   assert.equal(
-    validatePlaceBetInput({ ...base, positionState: { ...base.positionState, amount: 950 }, amount: 100 }),
+    validatePlaceBetInput({ ...base, userMarketStatsState: { ...base.userMarketStatsState, totalStaked: 950 }, amount: 100 }),
+    'UserBetCapExceeded'
+  );
+  // A fresh position still counts the stake already on other outcomes.
+  assert.equal(
+    validatePlaceBetInput({
+      ...base,
+      outcomeId: 2,
+      outcomePoolState: { ...base.outcomePoolState, outcomeId: 2 },
+      positionState: { amount: 0 },
+      amount: 501,
+    }),
     'UserBetCapExceeded'
   );
   assert.equal(validatePlaceBetInput({ ...base, outcomePoolState: null }), 'OutcomeMismatch');
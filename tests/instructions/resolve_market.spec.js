@@ -1,5 +1,7 @@
 const assert = require('assert');
-const { validateResolveMarketInput } = require('../../packages/core/src/resolve_market_instruction.cjs');
+const { validateResolveMarketInput, executeResolveMarket, finalizeResolution } = require('../../packages/core/src/resolve_market_instruction.cjs');
+const { computePayloadHashHex } = require('../../packages/core/src/protocol_primitives.cjs');
+const vectors = require('../../specs/vectors/canonical_vectors.json');
 
 (function run() {
   const base = {
@@ -14,6 +16,18 @@ const { validateResolveMarketInput } = require('../../packages/core/src/resolve_
 
   assert.equal(validateResolveMarketInput(base), null);
   assert.equal(validateResolveMarketInput({ ...base, oracle: 'Other' }), 'UnauthorizedOracle');
+  assert.equal(validateResolveMarketInput({ ...base, oracle: 'Keeper', attesters: ['OracleA'] }), null);
+  assert.equal(validateResolveMarketInput({ ...base, oracle: 'Keeper', attesters: ['Other'] }), 'UnauthorizedOracle');
+
+  // Committee mode: any keeper finalizes once 2 of 3 members agree on winners and hash.
+  const committee = { members: ['M1', 'M2', 'M3'], threshold: 2 };
+  const vote = (member, winningOutcomes, payloadHash = base.payloadHashHex) => ({ member, winningOutcomes, payloadHash });
+  const committeeInput = (votes) => ({ ...base, oracle: 'Keeper', configOracle: 'CommitteePda', committee, votes });
+  assert.equal(validateResolveMarketInput(committeeInput([vote('M1', [1]), vote('M3', [1])])), null);
+  assert.equal(validateResolveMarketInput(committeeInput([vote('M1', [1]), vote('M3', [2])])), 'QuorumNotReached');
+  assert.equal(validateResolveMarketInput(committeeInput([vote('M1', [1]), vote('M3', [1], 'cd'.repeat(32))])), 'QuorumNotReached');
+  assert.equal(validateResolveMarketInput(committeeInput([vote('M1', [1]), vote('Gone', [1])])), 'QuorumNotReached');
+  assert.equal(validateResolveMarketInput(committeeInput([vote('M1', [1]), { member: 'M2', void: true, payloadHash: base.payloadHashHex }])), 'QuorumNotReached');
   assert.equal(validateResolveMarketInput({ ...base, marketState: { ...base.marketState, status: 'Open' } }), 'MarketNotLocked');
   assert.equal(validateResolveMarketInput({ ...base, winningOutcomeId: 100 }), 'InvalidOutcomeId');
   assert.equal(
@@ -21,6 +35,98 @@ const { validateResolveMarketInput } = require('../../packages/core/src/resolve_
     'InvalidOutcomeId'
   );
   assert.equal(validateResolveMarketInput({ ...base, winningOutcomePoolState: null }), 'OutcomeMismatch');
+  assert.equal(validateResolveMarketInput({ ...base, additionalWinningOutcomeIds: [0] }), 'InvalidOutcomeId');
+
+  // Podium: exactly min(3, outcomeCount) distinct winners, one pool per extra winner in order.
+  const pool = (outcomeId, poolAmount) => ({ market: 'MarketA', outcomeId, poolAmount });
+  const podium = {
+    ...base,
+    marketState: { status: 'Locked', outcomeCount: 4, marketType: 1 },
+    winningOutcomePoolState: pool(1, 100),
+    additionalWinningOutcomeIds: [3, 0],
+    additionalWinningPoolStates: [pool(3, 400), pool(0, 300)],
+  };
+  assert.equal(validateResolveMarketInput(podium), null);
+  const resolved = executeResolveMarket(podium);
+  assert.deepEqual(resolved.market.winningOutcomes, [1, 3, 0]);
+  assert.equal(resolved.market.winnerPool, 800);
+  assert.deepEqual(resolved.event.winning_outcomes, [1, 3, 0]);
+  assert.equal(resolved.market.status, 'Resolved');
+  assert.equal(resolved.market.disputeDeadline, 0);
+
+  // A dispute window leaves the result pending until nowTs + disputeWindowSecs.
+  const pending = executeResolveMarket({ ...podium, nowTs: 1_800_000_000, disputeWindowSecs: 3600 });
+  assert.equal(pending.market.status, 'PendingResolution');
+  assert.equal(pending.market.disputeDeadline, 1_800_003_600);
+  assert.equal(pending.event.dispute_deadline, 1_800_003_600);
+
+  // Past the deadline the pending result finalizes to Resolved, once.
+  assert.equal(finalizeResolution('MarketA', pending.market, 1_800_003_600), null);
+  const final = finalizeResolution('MarketA', pending.market, 1_800_003_601);
+  assert.equal(final.market.status, 'Resolved');
+  assert.deepEqual(final.event.winning_outcomes, pending.market.winningOutcomes);
+  assert.equal(final.event.dispute_deadline, 1_800_003_600);
+  assert.equal(finalizeResolution('MarketA', final.market, 1_800_003_601), null);
+
+  assert.equal(validateResolveMarketInput({ ...podium, additionalWinningOutcomeIds: [3] }), 'InvalidOutcomeId');
+  assert.equal(validateResolveMarketInput({ ...podium, additionalWinningOutcomeIds: [3, 1] }), 'InvalidOutcomeId');
+  assert.equal(validateResolveMarketInput({ ...podium, additionalWinningOutcomeIds: [3, 4] }), 'InvalidOutcomeId');
+  assert.equal(
+    validateResolveMarketInput({ ...podium, additionalWinningPoolStates: [pool(0, 300), pool(3, 400)] }),
+    'OutcomeMismatch'
+  );
+  assert.equal(
+    validateResolveMarketInput({
+      ...podium,
+      marketState: { ...podium.marketState, outcomeCount: 2 },
+      additionalWinningOutcomeIds: [0],
+      additionalWinningPoolStates: [pool(0, 300)],
+    }),
+    null
+  );
+
+  // RSM-REJ-007..009: verified mode recomputes the payload hash and binds it to event_id.
+  const fields = Object.entries(vectors.vectorC.resultPayload).map(([key, value]) => ({ key, value }));
+  const verified = {
+    ...base,
+    marketState: { ...base.marketState, eventIdHex: vectors.vectorA.eventIdHex },
+    payloadHashHex: vectors.vectorC.payloadHashHex,
+    winningOutcomePoolState: { market: 'MarketA', outcomeId: 1, poolAmount: 0 },
+    resultPayload: fields.slice().reverse(),
+  };
+  assert.equal(validateResolveMarketInput(verified), null);
+  assert.equal(executeResolveMarket(verified).event.payload_verified, true);
+  assert.equal(executeResolveMarket({ ...verified, resultPayload: undefined }).event.payload_verified, false);
+  assert.equal(
+    validateResolveMarketInput({ ...verified, resultPayload: [...fields, { key: 'winner', value: 'car_1' }] }),
+    'InvalidResolutionPayload'
+  );
+  assert.equal(
+    validateResolveMarketInput({ ...verified, marketState: { ...verified.marketState, eventIdHex: 'cd'.repeat(32) } }),
+    'PayloadEventMismatch'
+  );
+  assert.equal(
+    validateResolveMarketInput({ ...verified, resultPayload: fields.filter((f) => f.key !== 'event_id') }),
+    'PayloadEventMismatch'
+  );
+  assert.equal(validateResolveMarketInput({ ...verified, payloadHashHex: 'ab'.repeat(32) }), 'PayloadHashMismatch');
+
+  // RSM-REJ-011: the payload's winner set must be the one being resolved.
+  const withWinners = (value) => {
+    const payload = { ...vectors.vectorC.resultPayload, winning_outcomes: value };
+    return {
+      ...verified,
+      payloadHashHex: computePayloadHashHex(payload),
+      resultPayload: Object.entries(payload).map(([key, value]) => ({ key, value })),
+    };
+  };
+  assert.equal(validateResolveMarketInput(withWinners('1')), null);
+  assert.equal(validateResolveMarketInput(withWinners('2')), 'PayloadOutcomeMismatch');
+  assert.equal(validateResolveMarketInput(withWinners('01')), 'PayloadOutcomeMismatch');
+  assert.equal(
+    validateResolveMarketInput({ ...verified, resultPayload: fields.filter((f) => f.key !== 'winning_outcomes') }),
+    'PayloadOutcomeMismatch'
+  );
 
   console.log('resolve_market spec tests ok');
 })();
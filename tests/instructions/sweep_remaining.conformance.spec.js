@@ -16,13 +16,16 @@ const { invokeSweepRemainingOnProgram } = require('../harness/sweep_remaining_ad
     vaultAmount: 123,
     treasuryAmount: 1000,
     treasury: 'TreasuryA',
-    configTreasury: 'TreasuryA',
     treasuryMint: 'MintA',
-    usdcMint: 'MintA',
     treasuryOwner: 'TreasuryAuthA',
     treasuryAuthority: 'TreasuryAuthA',
     tokenProgram: constants.REQUIRED_TOKEN_PROGRAM,
-    marketState: { status: 'Resolved', resolutionTimestamp: baseResolutionTs },
+    marketState: {
+      status: 'Resolved',
+      resolutionTimestamp: baseResolutionTs,
+      collateralMint: 'MintA',
+      treasury: 'TreasuryA',
+    },
   };
 
   // SWP-HP-001
@@ -49,7 +52,7 @@ const { invokeSweepRemainingOnProgram } = require('../harness/sweep_remaining_ad
     [{ authority: 'Other' }, 'Unauthorized'],
     [{ marketState: { ...base.marketState, status: 'Open' } }, 'MarketNotResolved'],
     [{ nowTs: base.marketState.resolutionTimestamp + claimWindowSecs }, 'ClaimWindowNotExpired'],
-    [{ configTreasury: 'OtherTreasury' }, 'InvalidTreasuryOwner'],
+    [{ marketState: { ...base.marketState, treasury: 'OtherTreasury' } }, 'InvalidTreasuryOwner'],
     [{ treasuryMint: 'OtherMint' }, 'InvalidTreasuryMint'],
     [{ treasuryOwner: 'OtherOwner' }, 'InvalidTreasuryOwner'],
   ];
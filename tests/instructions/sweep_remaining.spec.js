@@ -11,13 +11,16 @@ const { validateSweepRemainingInput } = require('../../packages/core/src/sweep_r
     vaultAmount: 123,
     treasuryAmount: 1000,
     treasury: 'TreasuryA',
-    configTreasury: 'TreasuryA',
     treasuryMint: 'MintA',
-    usdcMint: 'MintA',
     treasuryOwner: 'TreasuryAuthA',
     treasuryAuthority: 'TreasuryAuthA',
     tokenProgram: constants.REQUIRED_TOKEN_PROGRAM,
-    marketState: { status: 'Resolved', resolutionTimestamp: 1_800_000_000 },
+    marketState: {
+      status: 'Resolved',
+      resolutionTimestamp: 1_800_000_000,
+      collateralMint: 'MintA',
+      treasury: 'TreasuryA',
+    },
     market: 'MarketA',
   };
 
@@ -35,8 +38,13 @@ const { validateSweepRemainingInput } = require('../../packages/core/src/sweep_r
   const claimEnd = base.marketState.resolutionTimestamp + base.claimWindowSecs;
   assert.equal(validateSweepRemainingInput({ ...base, nowTs: claimEnd }), 'ClaimWindowNotExpired');
 
+  // SWP-WIN-002: after a dispute window the claim window starts at disputeDeadline.
+  const pending = { ...base.marketState, status: 'PendingResolution', disputeDeadline: base.marketState.resolutionTimestamp + 600 };
+  assert.equal(validateSweepRemainingInput({ ...base, marketState: pending, nowTs: claimEnd + 1 }), 'ClaimWindowNotExpired');
+  assert.equal(validateSweepRemainingInput({ ...base, marketState: pending, nowTs: claimEnd + 601 }), null);
+
   // SWP-REJ-004 treasury constraints
-  assert.equal(validateSweepRemainingInput({ ...base, configTreasury: 'OtherTreasury' }), 'InvalidTreasuryOwner');
+  assert.equal(validateSweepRemainingInput({ ...base, marketState: { ...base.marketState, treasury: 'OtherTreasury' } }), 'InvalidTreasuryOwner');
   assert.equal(validateSweepRemainingInput({ ...base, treasuryMint: 'OtherMint' }), 'InvalidTreasuryMint');
   assert.equal(validateSweepRemainingInput({ ...base, treasuryOwner: 'OtherOwner' }), 'InvalidTreasuryOwner');
 
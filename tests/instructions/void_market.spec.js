@@ -10,6 +10,16 @@ const { validateVoidMarketInput } = require('../../packages/core/src/void_market
 
   assert.equal(validateVoidMarketInput(base), null);
   assert.equal(validateVoidMarketInput({ ...base, oracle: 'Other' }), 'UnauthorizedOracle');
+  assert.equal(validateVoidMarketInput({ ...base, oracle: 'Keeper', attesters: ['OracleA'] }), null);
+  assert.equal(validateVoidMarketInput({ ...base, oracle: 'Keeper', attesters: [] }), 'UnauthorizedOracle');
+
+  const committee = { members: ['M1', 'M2', 'M3'], threshold: 2 };
+  const committeeInput = (votes) => ({ ...base, oracle: 'Keeper', configOracle: 'CommitteePda', payloadHash: 'h', committee, votes });
+  assert.equal(validateVoidMarketInput(committeeInput([{ member: 'M1', void: true, payloadHash: 'h' }, { member: 'M2', void: true, payloadHash: 'h' }])), null);
+  assert.equal(
+    validateVoidMarketInput(committeeInput([{ member: 'M1', void: true, payloadHash: 'h' }, { member: 'M2', winningOutcomes: [1], payloadHash: 'h' }])),
+    'QuorumNotReached'
+  );
   assert.equal(validateVoidMarketInput({ ...base, marketState: { ...base.marketState, status: 'Open' } }), 'MarketNotLocked');
   assert.equal(validateVoidMarketInput({ ...base, marketState: { ...base.marketState, status: 'Resolved' } }), 'MarketNotLocked');
 
@@ -4,6 +4,7 @@ const {
   canonicalJson,
   computeEventIdHex,
   computeMarketIdHex,
+  computePayloadHashHex,
 } = require('../../packages/core/src/protocol_primitives.cjs');
 
 (function run() {
@@ -15,11 +16,17 @@ const {
     vectors.vectorB.marketIdHex
   );
 
+  assert.equal(canonicalJson(vectors.vectorC.resultPayload), vectors.vectorC.canonicalJson);
+  assert.equal(computePayloadHashHex(vectors.vectorC.resultPayload), vectors.vectorC.payloadHashHex);
+  assert.equal(vectors.vectorC.resultPayload.event_id, vectors.vectorA.eventIdHex);
+
   const alt = { sport: 'f1', season: '2027', round: '01', session: 'race' };
   const altId = computeEventIdHex(alt);
   assert.equal(altId.length, 64);
   assert.notEqual(altId, vectors.vectorA.eventIdHex);
 
+  assert.equal(canonicalJson({ name: 'a"b\\c\n\u0001' }), '{"name":"a\\"b\\\\c\\n\\u0001"}');
+
   const m1 = computeMarketIdHex(altId, 0, 1);
   const m2 = computeMarketIdHex(altId, 0, 1);
   assert.equal(m1, m2, 'market id must be deterministic');
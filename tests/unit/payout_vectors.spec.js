@@ -0,0 +1,48 @@
+const assert = require('assert');
+const vectors = require('../../specs/vectors/payout_vectors.json');
+const {
+  computeFee,
+  computePrizePool,
+  computePayout,
+  computeDust,
+} = require('../../packages/core/src/protocol_primitives.cjs');
+
+(function run() {
+  for (const v of vectors.payout) {
+    const total = BigInt(v.totalPool);
+    const winnerPool = BigInt(v.winnerPool);
+    const position = BigInt(v.positionAmount);
+
+    if (v.error) {
+      assert.throws(
+        () => computePayout(position, computePrizePool(total, v.feeBps), winnerPool),
+        new RegExp(v.error),
+        v.name
+      );
+      continue;
+    }
+
+    assert.equal(computeFee(total, v.feeBps), BigInt(v.fee), `${v.name}: fee`);
+    const prize = computePrizePool(total, v.feeBps);
+    assert.equal(prize, BigInt(v.prizePool), `${v.name}: prizePool`);
+    assert.equal(computePayout(position, prize, winnerPool), BigInt(v.payout), `${v.name}: payout`);
+  }
+
+  for (const v of vectors.dust) {
+    const prize = BigInt(v.prizePool);
+    const winnerPool = BigInt(v.winnerPool);
+    const paid = v.positions
+      .map((p) => computePayout(BigInt(p), prize, winnerPool))
+      .reduce((a, b) => a + b, 0n);
+    assert.equal(paid, BigInt(v.totalPaid), `${v.name}: totalPaid`);
+    assert.equal(computeDust(prize, paid), BigInt(v.dust), `${v.name}: dust`);
+  }
+
+  // Number inputs: intermediate products beyond 2^53 are still exact.
+  assert.equal(computePayout(10_000_000_000_000, 19_600_000_000_000, 20_000_000_000_000), 9_800_000_000_000);
+  // A result that cannot be represented exactly as a Number must not be returned silently.
+  assert.throws(() => computePayout(10, 2_000_000_000_000_000, 1), /UnsafeInteger/);
+  assert.throws(() => computePayout(2n ** 32n, 2n ** 32n, 1n), /Overflow/);
+
+  console.log('payout vector tests ok');
+})();
@@ -0,0 +1,69 @@
+const assert = require('assert');
+const vectors = require('../../specs/vectors/podium_vectors.json');
+const { executeResolveMarket } = require('../../packages/core/src/resolve_market_instruction.cjs');
+const { executeClaimResolved } = require('../../packages/core/src/claim_resolved_instruction.cjs');
+const constants = require('../../specs/constants.json');
+
+(function run() {
+  for (const v of vectors.podium) {
+    const pools = v.outcomePools.map(Number);
+    const pool = (outcomeId) => ({ market: 'MarketA', outcomeId, poolAmount: pools[outcomeId] });
+    const [first, ...rest] = v.winningOutcomes;
+
+    const resolved = executeResolveMarket({
+      oracle: 'OracleA',
+      configOracle: 'OracleA',
+      market: 'MarketA',
+      marketState: {
+        status: 'Locked',
+        outcomeCount: pools.length,
+        marketType: 1,
+        totalPool: Number(v.totalPool),
+      },
+      winningOutcomeId: first,
+      payloadHashHex: 'ab'.repeat(32),
+      nowTs: 1_800_000_000,
+      winningOutcomePoolState: pool(first),
+      additionalWinningOutcomeIds: rest,
+      additionalWinningPoolStates: rest.map(pool),
+    });
+    assert.equal(resolved.ok, true, v.name);
+    assert.equal(resolved.market.winnerPool, Number(v.winnerPool), `${v.name}: winnerPool`);
+
+    let vaultAmount = Number(v.totalPool);
+    for (const p of v.positions) {
+      const claim = executeClaimResolved({
+        user: 'UserA',
+        market: 'MarketA',
+        outcomeId: p.outcomeId,
+        winningOutcomeId: first,
+        marketState: { ...resolved.market, status: 'Resolved' },
+        nowTs: 1_800_000_001,
+        resolutionTimestamp: 1_800_000_000,
+        claimWindowSecs: 3600,
+        feeBps: v.feeBps,
+        positionExists: true,
+        positionState: { amount: Number(p.amount), claimed: false, payout: 0 },
+        outcomePoolState: pool(p.outcomeId),
+        vaultAmount,
+        userUsdcAmount: 0,
+        tokenProgram: constants.REQUIRED_TOKEN_PROGRAM,
+      });
+
+      if (v.error) {
+        assert.deepEqual(claim, { ok: false, error: v.error }, v.name);
+        continue;
+      }
+      assert.equal(claim.ok, true, v.name);
+      assert.equal(claim.position.payout, Number(p.payout), `${v.name}: payout`);
+      vaultAmount = claim.vaultAmount;
+    }
+
+    if (!v.error) {
+      assert.equal(Number(v.totalPool) - vaultAmount, Number(v.totalPaid), `${v.name}: totalPaid`);
+      assert.equal(Number(v.prizePool) - Number(v.totalPaid), Number(v.dust), `${v.name}: dust`);
+    }
+  }
+
+  console.log('podium vector tests ok');
+})();